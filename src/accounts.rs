@@ -0,0 +1,176 @@
+use anyhow::Result;
+use std::process::Command;
+use crate::capabilities::{self, Tool};
+use crate::config::{Account, Config};
+
+// Read by the credential helper below, so tokens never appear in process arguments
+const USERNAME_ENV: &str = "CLAUDE_MD_SNIPPETS_GIT_USERNAME";
+const TOKEN_ENV: &str = "CLAUDE_MD_SNIPPETS_GIT_TOKEN";
+
+impl Account {
+    /// This account's token from its `token_env` variable, or from the GitHub CLI login.
+    pub fn token(&self) -> Option<String> {
+        if let Some(var) = &self.token_env {
+            if let Ok(token) = std::env::var(var) {
+                if !token.trim().is_empty() {
+                    return Some(token.trim().to_string());
+                }
+            }
+        }
+        
+        if !capabilities::has(Tool::Gh) {
+            return None;
+        }
+        let output = Command::new("gh")
+            .args(["auth", "token", "--hostname", "github.com", "--user", &self.username])
+            .output()
+            .ok()?;
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !token.is_empty() {
+            Some(token)
+        } else {
+            None
+        }
+    }
+    
+    fn email(&self) -> String {
+        self.email.clone().unwrap_or_else(|| format!("{}@users.noreply.github.com", self.username))
+    }
+}
+
+/// The account configured for `repo_name`, if any.
+pub fn for_repo(repo_name: &str) -> Result<Option<Account>> {
+    let config = Config::load()?;
+    Ok(config.get_repo_account(repo_name).cloned())
+}
+
+/// The account configured for the default repository, if any.
+pub fn for_default_repo() -> Result<Option<Account>> {
+    for_repo(&crate::config::get_default_repo_name()?)
+}
+
+/// Run git and gh commands as a specific account instead of the global login.
+pub trait WithAccount {
+    fn with_account(&mut self, account: Option<&Account>) -> &mut Self;
+    /// Authenticate git over HTTPS (and gh) with `token`, when there is one.
+    fn with_token(&mut self, username: &str, token: Option<&str>) -> &mut Self;
+}
+
+impl WithAccount for Command {
+    fn with_account(&mut self, account: Option<&Account>) -> &mut Self {
+        let Some(account) = account else { return self };
+        
+        self.env("GIT_AUTHOR_NAME", &account.username)
+            .env("GIT_COMMITTER_NAME", &account.username)
+            .env("GIT_AUTHOR_EMAIL", account.email())
+            .env("GIT_COMMITTER_EMAIL", account.email());
+        
+        self.with_token(&account.username, account.token().as_deref())
+    }
+    
+    fn with_token(&mut self, username: &str, token: Option<&str>) -> &mut Self {
+        let Some(token) = token else { return self };
+        
+        // Clear inherited credential helpers, then answer with this token
+        self.env("GIT_CONFIG_COUNT", "2")
+            .env("GIT_CONFIG_KEY_0", "credential.helper")
+            .env("GIT_CONFIG_VALUE_0", "")
+            .env("GIT_CONFIG_KEY_1", "credential.helper")
+            .env("GIT_CONFIG_VALUE_1", format!(
+                "!f() {{ test \"$1\" = get && echo username=\"${}\" && echo password=\"${}\"; }}; f",
+                USERNAME_ENV, TOKEN_ENV
+            ))
+            .env(USERNAME_ENV, username)
+            .env(TOKEN_ENV, token)
+            .env("GH_TOKEN", token)
+    }
+}
+
+pub fn add_account(name: &str, username: String, email: Option<String>, token_env: Option<String>) -> Result<()> {
+    let account = Account { username, email, token_env };
+    Config::update(|config| {
+        config.accounts.insert(name.to_string(), account);
+        Ok(())
+    })?;
+    println!("✅ Added account '{}'", name);
+    println!("💡 Assign it to a repository with 'claude-md-snippets account use {} --repo <repo>'", name);
+    Ok(())
+}
+
+pub fn remove_account(name: &str) -> Result<()> {
+    let mut found = false;
+    Config::update(|config| {
+        found = config.accounts.remove(name).is_some();
+        config.repo_accounts.retain(|_, account| account != name);
+        Ok(())
+    })?;
+    if found {
+        println!("✅ Removed account '{}'", name);
+    } else {
+        println!("❌ No account named '{}'", name);
+    }
+    Ok(())
+}
+
+pub fn list_accounts() -> Result<()> {
+    let config = Config::load()?;
+    println!("👤 Accounts:");
+    if config.accounts.is_empty() {
+        println!("  (none - the global git and gh logins are used)");
+        println!("💡 Add one with 'claude-md-snippets account add <name> --username <github-user>'");
+        return Ok(());
+    }
+    
+    for (name, account) in &config.accounts {
+        let token_source = match &account.token_env {
+            Some(var) => format!("token from ${}", var),
+            None => "token from gh login".to_string(),
+        };
+        println!("  • {} ({}, {})", name, account.username, token_source);
+        let repos: Vec<&str> = config.repo_accounts.iter()
+            .filter(|(_, account_name)| *account_name == name)
+            .map(|(repo, _)| repo.as_str())
+            .collect();
+        if !repos.is_empty() {
+            println!("      used by: {}", repos.join(", "));
+        }
+    }
+    Ok(())
+}
+
+/// Assign `name` to `repo_name` (or clear the assignment when `name` is None),
+/// and set the repository's local git identity to match.
+pub fn use_account(name: Option<&str>, repo_name: &str) -> Result<()> {
+    let config = Config::update(|config| {
+        match name {
+            Some(name) => {
+                if !config.accounts.contains_key(name) {
+                    anyhow::bail!("No account named '{}'. Add it with 'account add'", name);
+                }
+                config.repo_accounts.insert(repo_name.to_string(), name.to_string());
+            }
+            None => {
+                config.repo_accounts.remove(repo_name);
+            }
+        }
+        Ok(())
+    })?;
+    
+    let Some(account) = config.get_repo_account(repo_name) else {
+        println!("✅ Repository '{}' now uses the global git and gh logins", repo_name);
+        return Ok(());
+    };
+    
+    let repo_dir = crate::publish::get_repos_dir()?.join(repo_name);
+    if repo_dir.join(".git").exists() {
+        Command::new("git").current_dir(&repo_dir).args(["config", "user.name", &account.username]).output()?;
+        Command::new("git").current_dir(&repo_dir).args(["config", "user.email", &account.email()]).output()?;
+    }
+    
+    println!("✅ Repository '{}' now uses account '{}' ({})", repo_name, name.unwrap_or_default(), account.username);
+    if account.token().is_none() {
+        println!("⚠️  No token found for {}; set {} or run 'gh auth login' as that user",
+            account.username, account.token_env.as_deref().unwrap_or("token_env"));
+    }
+    Ok(())
+}
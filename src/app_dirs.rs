@@ -0,0 +1,165 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Environment variable that puts everything (config and data) in one directory.
+pub const HOME_ENV: &str = "CLAUDE_MD_SNIPPETS_HOME";
+const APP_NAME: &str = "claude-md-snippets";
+/// Where everything lived before the XDG split; still used while it exists.
+const LEGACY_DIR: &str = ".claude-md-snippets";
+
+static DATA_DIR_OVERRIDE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// `--data-dir`: keep everything in `dir` for the rest of this process.
+pub fn override_data_dir(dir: &Path) {
+    if let Ok(mut current) = DATA_DIR_OVERRIDE.lock() {
+        *current = Some(std::path::absolute(dir).unwrap_or(dir.to_path_buf()));
+    }
+}
+
+/// Where the app keeps its files: `config` holds config.json, `data` the
+/// repositories, install manifest, caches and logs.
+#[derive(Debug, PartialEq)]
+pub struct AppDirs {
+    pub config: PathBuf,
+    pub data: PathBuf,
+}
+
+impl AppDirs {
+    /// `--data-dir` or `$CLAUDE_MD_SNIPPETS_HOME` when given, else
+    /// `~/.claude-md-snippets` if it exists from an earlier version, else
+    /// the XDG base directories.
+    pub fn current() -> Result<Self> {
+        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+        let legacy = home.join(LEGACY_DIR);
+        if let Some(dir) = explicit_dir() {
+            return Ok(AppDirs::single(dir));
+        }
+        if legacy.is_dir() {
+            return Ok(AppDirs::single(legacy));
+        }
+        Ok(AppDirs::xdg(&home, xdg_env("XDG_CONFIG_HOME"), xdg_env("XDG_DATA_HOME")))
+    }
+
+    fn single(dir: PathBuf) -> Self {
+        AppDirs { config: dir.clone(), data: dir }
+    }
+
+    /// `$XDG_CONFIG_HOME/claude-md-snippets` and `$XDG_DATA_HOME/claude-md-snippets`,
+    /// with the spec's `~/.config` and `~/.local/share` when unset.
+    fn xdg(home: &Path, config_home: Option<PathBuf>, data_home: Option<PathBuf>) -> Self {
+        AppDirs {
+            config: config_home.unwrap_or_else(|| home.join(".config")).join(APP_NAME),
+            data: data_home.unwrap_or_else(|| home.join(".local").join("share")).join(APP_NAME),
+        }
+    }
+}
+
+/// The directory `--data-dir` or `$CLAUDE_MD_SNIPPETS_HOME` asked for, if any.
+pub fn explicit_dir() -> Option<PathBuf> {
+    if let Some(dir) = DATA_DIR_OVERRIDE.lock().ok().and_then(|current| current.clone()) {
+        return Some(dir);
+    }
+    std::env::var_os(HOME_ENV).filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+/// An XDG variable; relative paths are invalid per the spec and ignored.
+fn xdg_env(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name).map(PathBuf::from).filter(|dir| dir.is_absolute())
+}
+
+/// `state migrate`: move `~/.claude-md-snippets` to where this version keeps
+/// things (`--data-dir`, `$CLAUDE_MD_SNIPPETS_HOME` or the XDG directories),
+/// config files to the config directory and everything else to the data one.
+pub fn migrate() -> Result<()> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+    let legacy = home.join(LEGACY_DIR);
+    if !legacy.is_dir() {
+        println!("✅ Nothing to migrate: {} does not exist", legacy.display());
+        return Ok(());
+    }
+    let target = match explicit_dir() {
+        Some(dir) => AppDirs::single(dir),
+        None => AppDirs::xdg(&home, xdg_env("XDG_CONFIG_HOME"), xdg_env("XDG_DATA_HOME")),
+    };
+    if target.data == legacy {
+        println!("✅ {} is already the data directory", legacy.display());
+        return Ok(());
+    }
+    for dir in [&target.config, &target.data] {
+        if dir.join("config.json").exists() || dir.join("repos").exists() {
+            anyhow::bail!("{} already holds data; move or remove it before migrating", dir.display());
+        }
+    }
+
+    println!("📦 Moving {}:", legacy.display());
+    println!("  • config → {}", target.config.display());
+    println!("  • repositories, installs and caches → {}", target.data.display());
+    if !crate::prompt::confirm("Move it?", true)? {
+        println!("❌ Migration cancelled");
+        return Ok(());
+    }
+
+    let mut moved = 0;
+    for entry in fs::read_dir(&legacy)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let dir = match name.to_string_lossy().starts_with("config.") {
+            true => &target.config,
+            false => &target.data,
+        };
+        fs::create_dir_all(dir)?;
+        move_path(&entry.path(), &dir.join(&name))?;
+        moved += 1;
+    }
+    fs::remove_dir(&legacy).with_context(|| format!("Failed to remove {}", legacy.display()))?;
+    println!("✅ Moved {} item(s); {} is gone", moved, legacy.display());
+    if target.config == target.data {
+        println!("💡 Keep passing --data-dir or setting {} so later runs find it", HOME_ENV);
+    }
+    Ok(())
+}
+
+/// Rename `from` to `to`, copying and deleting instead across file systems.
+fn move_path(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    copy_all(from, to).with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    match from.is_dir() {
+        true => fs::remove_dir_all(from)?,
+        false => fs::remove_file(from)?,
+    }
+    Ok(())
+}
+
+fn copy_all(from: &Path, to: &Path) -> Result<()> {
+    if !from.is_dir() {
+        fs::copy(from, to)?;
+        return Ok(());
+    }
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        copy_all(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xdg_dirs() {
+        let home = Path::new("/home/me");
+        assert_eq!(AppDirs::xdg(home, None, None), AppDirs {
+            config: PathBuf::from("/home/me/.config/claude-md-snippets"),
+            data: PathBuf::from("/home/me/.local/share/claude-md-snippets"),
+        });
+        let custom = AppDirs::xdg(home, Some(PathBuf::from("/etc/me")), Some(PathBuf::from("/data")));
+        assert_eq!(custom.config, Path::new("/etc/me/claude-md-snippets"));
+        assert_eq!(custom.data, Path::new("/data/claude-md-snippets"));
+    }
+}
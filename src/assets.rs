@@ -0,0 +1,118 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::publish::Snippet;
+
+/// Where installed assets go, next to the target file: `snippet-assets/<id8>/`.
+pub const INSTALLED_ASSETS_DIR: &str = "snippet-assets";
+
+/// A snippet's companion files (diagrams, example configs) in its repository:
+/// `snippets/assets/<id>/`. Snippet content refers to them as `assets/<file>`.
+pub fn assets_dir(snippets_dir: &Path, id: &str) -> PathBuf {
+    snippets_dir.join("assets").join(id)
+}
+
+/// Copy `files` into the snippet's assets directory, returning their names.
+pub fn attach(snippets_dir: &Path, id: &str, files: &[PathBuf]) -> Result<Vec<String>> {
+    let dir = assets_dir(snippets_dir, id);
+    fs::create_dir_all(&dir)?;
+
+    let mut names = Vec::new();
+    for file in files {
+        let name = file
+            .file_name()
+            .and_then(|n| n.to_str())
+            .with_context(|| format!("Invalid attachment path: {}", file.display()))?;
+        fs::copy(file, dir.join(name))
+            .with_context(|| format!("Failed to attach {}", file.display()))?;
+        println!("📎 Attached {}", name);
+        names.push(name.to_string());
+    }
+    Ok(names)
+}
+
+/// The files attached to a snippet in the default repository.
+pub fn for_snippet(id: &str) -> Result<Vec<PathBuf>> {
+    let snippets_dir = crate::publish::get_snippets_dir()?.join("snippets");
+    let dir = assets_dir(&snippets_dir, id);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn installed_dir(target_path: &Path, short_id: &str) -> PathBuf {
+    target_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(INSTALLED_ASSETS_DIR)
+        .join(short_id)
+}
+
+/// Point `assets/<file>` references at where the files will be installed.
+pub fn rewrite_references(snippet: &Snippet, assets: &[PathBuf]) -> Snippet {
+    let short_id = crate::ids::short(&snippet.id);
+    let mut content = snippet.content.clone();
+    for asset in assets {
+        if let Some(name) = asset.file_name().and_then(|n| n.to_str()) {
+            content = content.replace(
+                &format!("assets/{}", name),
+                &format!("{}/{}/{}", INSTALLED_ASSETS_DIR, short_id, name),
+            );
+        }
+    }
+    Snippet { content, ..snippet.clone() }
+}
+
+/// Copy a snippet's assets next to the file it was installed into.
+pub fn install(short_id: &str, assets: &[PathBuf], target_path: &Path) -> Result<()> {
+    if assets.is_empty() {
+        return Ok(());
+    }
+    let dir = installed_dir(target_path, short_id);
+    fs::create_dir_all(&dir)?;
+    for asset in assets {
+        if let Some(name) = asset.file_name() {
+            fs::copy(asset, dir.join(name))?;
+        }
+    }
+    println!("📎 Copied {} asset(s) to {}", assets.len(), dir.display());
+    Ok(())
+}
+
+/// Remove the assets installed for a snippet, and the assets directory once empty.
+pub fn uninstall(short_id: &str, target_path: &Path) -> Result<()> {
+    let dir = installed_dir(target_path, short_id);
+    if !dir.exists() {
+        return Ok(());
+    }
+    fs::remove_dir_all(&dir)?;
+    println!("🧹 Removed assets in {}", dir.display());
+    if let Some(parent) = dir.parent() {
+        // Only succeeds when no other snippet's assets are left
+        fs::remove_dir(parent).ok();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_references() {
+        let snippet = Snippet {
+            id: "1234abcd-0000".to_string(),
+            name: "Architecture".to_string(),
+            content: "See ![diagram](assets/arch.png) and assets/other.txt".to_string(),
+            ..Default::default()
+        };
+        let rewritten = rewrite_references(&snippet, &[PathBuf::from("/repo/snippets/assets/1234abcd-0000/arch.png")]);
+        assert_eq!(rewritten.content, "See ![diagram](snippet-assets/1234abcd/arch.png) and assets/other.txt");
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::process::Command;
+use crate::capabilities::{self, Tool};
+use crate::perf::{Category, TimedCommand};
+use crate::publish::Snippet;
+
+/// `snippet` with its author, source and license filled in where unset, for
+/// publishing to the repository in `repo_dir`: the author from git's
+/// `user.name` (or the GitHub login), the source from the repository's
+/// remote, the license from the `license` setting. Anything that can't be
+/// found stays unset.
+pub fn fill(snippet: &Snippet, repo_dir: &Path) -> Snippet {
+    let mut snippet = snippet.clone();
+    if snippet.author.is_none() {
+        snippet.author = git(repo_dir, &["config", "user.name"]).or_else(github_login);
+    }
+    if snippet.source_url.is_none() {
+        snippet.source_url = git(repo_dir, &["remote", "get-url", "origin"])
+            .and_then(|url| crate::hosts::Remote::parse(&url))
+            .map(|remote| remote.web_url());
+    }
+    if snippet.license.is_none() {
+        snippet.license = crate::config::Config::load().ok().and_then(|config| config.license);
+    }
+    snippet
+}
+
+/// Whether `snippet` was written by `author` (case-insensitive, any part of the name).
+pub fn by(snippet: &Snippet, author: &str) -> bool {
+    snippet.author.as_deref().is_some_and(|name| name.to_lowercase().contains(&author.to_lowercase()))
+}
+
+fn git(repo_dir: &Path, args: &[&str]) -> Option<String> {
+    if !capabilities::has(Tool::Git) {
+        return None;
+    }
+    let output = Command::new("git").current_dir(repo_dir).args(args).timed_output(Category::Git).ok()?;
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+fn github_login() -> Option<String> {
+    if !capabilities::has(Tool::Gh) {
+        return None;
+    }
+    let output = Command::new("gh").args(["api", "user", "--jq", ".login"]).timed_output(Category::Git).ok()?;
+    let login = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !login.is_empty()).then_some(login)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribution_round_trip() {
+        let text = "---\nid: 7Hq2mXbK\nname: Tests\nauthor: Jo Dev\nsource_url: https://github.com/jo/snippets\nlicense: MIT\n---\n\nRun the tests\n";
+        let snippet = crate::publish::parse_markdown_frontmatter(text).unwrap();
+        assert_eq!(snippet.author.as_deref(), Some("Jo Dev"));
+        assert_eq!(snippet.source_url.as_deref(), Some("https://github.com/jo/snippets"));
+        let written = crate::publish::create_markdown_with_frontmatter(&snippet).unwrap();
+        assert!(written.contains("\nauthor: Jo Dev\nsource_url: https://github.com/jo/snippets\nlicense: MIT\n"));
+
+        assert!(by(&snippet, "jo") && by(&snippet, "DEV") && !by(&snippet, "sam"));
+    }
+}
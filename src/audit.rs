@@ -0,0 +1,176 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use crate::install::{marked_blocks, remove_snippet_from_content, resolve_target_path};
+use crate::manifest::{Manifest, content_hash, normalize_content};
+
+/// Something in the project CLAUDE.md that is also loaded from the user-level one.
+enum Overlap {
+    Snippet { id: String, label: String, size: usize },
+    Section { text: String, heading: String },
+}
+
+impl Overlap {
+    fn size(&self) -> usize {
+        match self {
+            Overlap::Snippet { size, .. } => *size,
+            Overlap::Section { text, .. } => text.len(),
+        }
+    }
+}
+
+/// Report snippets and sections present in both ~/.claude/CLAUDE.md and
+/// ./CLAUDE.md, and offer to remove the project-level copies.
+pub fn audit_overlap() -> Result<()> {
+    let user_path = resolve_target_path(false, true, None)?;
+    let project_path = resolve_target_path(true, false, None)?;
+
+    if !user_path.exists() || !project_path.exists() {
+        println!("ℹ️  Nothing to compare: both {} and {} must exist", user_path.display(), project_path.display());
+        return Ok(());
+    }
+
+    let user_content = fs::read_to_string(&user_path)?;
+    let project_content = fs::read_to_string(&project_path)?;
+    let overlaps = find_overlaps(&user_content, &project_content);
+
+    if overlaps.is_empty() {
+        println!("✅ No overlap between {} and {}", user_path.display(), project_path.display());
+        return Ok(());
+    }
+
+    println!("🔁 Loaded twice (in both user and project CLAUDE.md):");
+    for overlap in &overlaps {
+        match overlap {
+            Overlap::Snippet { label, size, .. } => println!("  • snippet {} ({} chars)", label, size),
+            Overlap::Section { heading, text } => println!("  • section \"{}\" ({} chars)", heading, text.len()),
+        }
+    }
+    let wasted: usize = overlaps.iter().map(|o| o.size()).sum();
+    println!();
+    println!("📏 ~{} chars (~{} tokens) of context are duplicated", wasted, wasted / 4);
+
+    if !crate::prompt::confirm(&format!("Remove the project-level copies from {}?", project_path.display()), false)? {
+        println!("❌ Left unchanged");
+        return Ok(());
+    }
+
+    remove_overlaps(&project_path, &project_content, &overlaps)?;
+    println!("✅ Removed {} duplicated item(s) from {}", overlaps.len(), project_path.display());
+    Ok(())
+}
+
+fn find_overlaps(user_content: &str, project_content: &str) -> Vec<Overlap> {
+    let mut overlaps = Vec::new();
+
+    // Snippet blocks match by ID, or by content when the same text was installed under another snippet
+    let user_blocks = marked_blocks(user_content);
+    for (id, body) in marked_blocks(project_content) {
+        let hash = content_hash(&body);
+        let same = user_blocks.iter().any(|(user_id, user_body)| *user_id == id || content_hash(user_body) == hash);
+        if same {
+            let label = body.lines()
+                .find(|line| !line.trim().is_empty())
+                .map(|line| format!("{} \"{}\"", id, line.trim_start_matches('#').trim()))
+                .unwrap_or(id.clone());
+            overlaps.push(Overlap::Snippet { id, label, size: body.len() });
+        }
+    }
+
+    // Hand-written sections outside snippet blocks match by normalized text
+    let user_sections: Vec<String> = sections(&strip_marked_blocks(user_content))
+        .iter()
+        .map(|s| normalize_content(s))
+        .collect();
+    for section in sections(&strip_marked_blocks(project_content)) {
+        if user_sections.contains(&normalize_content(&section)) {
+            let heading = section.lines().next().unwrap_or("").trim_start_matches('#').trim().to_string();
+            overlaps.push(Overlap::Section { text: section, heading });
+        }
+    }
+
+    overlaps
+}
+
+fn remove_overlaps(project_path: &Path, project_content: &str, overlaps: &[Overlap]) -> Result<()> {
+    let mut content = project_content.to_string();
+    let absolute_path = project_path.canonicalize().unwrap_or(project_path.to_path_buf());
+
+    for overlap in overlaps {
+        match overlap {
+            Overlap::Snippet { id, .. } => {
+                let start_marker = format!("<!-- SNIPPET_START:{} -->", id);
+                let end_marker = format!("<!-- SNIPPET_END:{} -->", id);
+                content = remove_snippet_from_content(&content, &start_marker, &end_marker)?;
+                Manifest::record_uninstall(id, &absolute_path)?;
+            }
+            Overlap::Section { text, .. } => {
+                content = content.replacen(text.as_str(), "", 1);
+            }
+        }
+    }
+
+    while content.contains("\n\n\n") {
+        content = content.replace("\n\n\n", "\n\n");
+    }
+    let cleaned = content.trim();
+    fs::write(project_path, if cleaned.is_empty() { String::new() } else { format!("{}\n", cleaned) })?;
+    Ok(())
+}
+
+pub fn strip_marked_blocks(content: &str) -> String {
+    let mut stripped = content.to_string();
+    for (id, _) in marked_blocks(content) {
+        let start_marker = format!("<!-- SNIPPET_START:{} -->", id);
+        let end_marker = format!("<!-- SNIPPET_END:{} -->", id);
+        if let (Some(start), Some(end)) = (stripped.find(&start_marker), stripped.find(&end_marker)) {
+            stripped.replace_range(start..end + end_marker.len(), "");
+        }
+    }
+    stripped
+}
+
+/// Split markdown into heading-led sections (text exactly as written), skipping
+/// headings inside code fences and sections with no body.
+pub fn sections(content: &str) -> Vec<String> {
+    let mut sections = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && line.starts_with('#') && !current.is_empty() {
+            sections.push(current.join("\n"));
+            current.clear();
+        }
+        if !current.is_empty() || line.starts_with('#') {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(current.join("\n"));
+    }
+
+    sections
+        .into_iter()
+        .map(|s| s.trim_end().to_string())
+        .filter(|s| s.lines().skip(1).any(|line| !line.trim().is_empty()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_overlaps() {
+        let user = "# Style\n\nUse tabs.\n\n<!-- SNIPPET_START:aaaa1111 -->\nRun tests\n<!-- SNIPPET_END:aaaa1111 -->\n";
+        let project = "# Project\n\nThis is acme.\n\n# Style\nUse  tabs.\n\n<!-- SNIPPET_START:aaaa1111 -->\nRun tests\n<!-- SNIPPET_END:aaaa1111 -->\n";
+        let overlaps = find_overlaps(user, project);
+        assert_eq!(overlaps.len(), 2);
+        assert!(matches!(&overlaps[0], Overlap::Snippet { id, .. } if id == "aaaa1111"));
+        assert!(matches!(&overlaps[1], Overlap::Section { heading, .. } if heading == "Style"));
+    }
+}
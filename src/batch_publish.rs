@@ -0,0 +1,144 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::github::ChangeKind;
+use crate::publish::{self, Snippet};
+use crate::store::{FsStore, SnippetStore};
+
+/// `publish --dir`: publish every markdown file in `dir` (and its subfolders
+/// with `recursive`) as a snippet, then sync once. Files that carry the ID of
+/// a snippet already in the repository, or the same content as one, are
+/// skipped, so running it again only adds what's new.
+pub async fn publish_dir(dir: &Path, recursive: bool) -> Result<()> {
+    if !dir.is_dir() {
+        anyhow::bail!("{} is not a directory", dir.display());
+    }
+    let files = markdown_files(dir, recursive)?;
+    if files.is_empty() {
+        println!("❌ No markdown files in {}{}", dir.display(), if recursive { "" } else { " (--recursive includes subfolders)" });
+        return Ok(());
+    }
+
+    let store = FsStore::default_repo()?;
+    let existing = store.load_all()?;
+    let known_ids: HashSet<&str> = existing.iter().map(|snippet| snippet.id.as_str()).collect();
+    let known_content: HashSet<&str> = existing.iter().map(|snippet| snippet.content.trim()).collect();
+
+    let mut new = Vec::new();
+    let mut skipped = Vec::new();
+    for path in &files {
+        let text = fs::read_to_string(path)?;
+        if text.trim().is_empty() {
+            continue;
+        }
+        let snippet = from_file(path, &text);
+        let relative = path.strip_prefix(dir).unwrap_or(path).display().to_string();
+        if known_ids.contains(snippet.id.as_str()) || known_content.contains(snippet.content.trim()) {
+            skipped.push(relative);
+        } else {
+            new.push((relative, snippet));
+        }
+    }
+
+    println!("📦 {} markdown file(s) in {}: {} new, {} already published", files.len(), dir.display(), new.len(), skipped.len());
+    for (relative, snippet) in &new {
+        println!("  • {} ← {}", snippet.name, relative);
+    }
+    for relative in &skipped {
+        println!("  ⏭️  {} (already in the repository)", relative);
+    }
+    if new.is_empty() {
+        println!("✅ Nothing to publish");
+        return Ok(());
+    }
+    let repo_name = crate::config::get_default_repo_name()?;
+    crate::subscriptions::ensure_writable(&repo_name)?;
+    if !crate::prompt::confirm(&format!("Publish these {} snippet(s) to '{}'?", new.len(), repo_name), true)? {
+        println!("❌ Publish cancelled");
+        return Ok(());
+    }
+
+    let repo_dir = publish::get_default_repo_dir()?;
+    for (_, snippet) in &new {
+        let snippet = crate::attribution::fill(&crate::limits::check_size(snippet)?, &repo_dir);
+        store.save(&snippet)?;
+    }
+    println!("✅ Published {} snippet(s) from {}", new.len(), dir.display());
+    crate::edit::finish(&repo_name, &format!("Publish {} snippet(s) from {}", new.len(), dir.display()), ChangeKind::Publish).await;
+    Ok(())
+}
+
+/// The snippet a file holds: as written when it has frontmatter (a file
+/// from `repo export` or another repository), else its whole text, named
+/// after its first heading or its file name, with a new ID.
+fn from_file(path: &Path, text: &str) -> Snippet {
+    if let Ok(snippet) = publish::parse_markdown_frontmatter(text) {
+        if crate::ids::is_valid(&snippet.id) && !snippet.name.is_empty() {
+            return snippet;
+        }
+    }
+    Snippet {
+        id: crate::ids::generate(),
+        name: infer_name(path, text),
+        content: text.trim().to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
+    }
+}
+
+/// The first markdown heading outside code blocks, else the file name made
+/// readable (`docker-compose_rules.md` -> "Docker compose rules").
+fn infer_name(path: &Path, text: &str) -> String {
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        let heading = line.trim_start_matches('#');
+        if !in_fence && heading.len() < line.len() && heading.starts_with(' ') && !heading.trim().is_empty() {
+            return heading.trim().to_string();
+        }
+    }
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().replace(['-', '_'], " ")).unwrap_or_default();
+    let mut chars = stem.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => "Untitled".to_string(),
+    }
+}
+
+/// Markdown files in `dir`, sorted, leaving out hidden ones and READMEs.
+fn markdown_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            if recursive {
+                files.extend(markdown_files(&path, true)?);
+            }
+        } else if (name.ends_with(".md") || name.ends_with(".markdown")) && !name.starts_with("readme") {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_name() {
+        let path = Path::new("notes/docker-compose_rules.md");
+        assert_eq!(infer_name(path, "```sh\n# not this\n```\n\n## Compose rules\n\nUse v2"), "Compose rules");
+        assert_eq!(infer_name(path, "Use compose v2 everywhere"), "Docker compose rules");
+        assert_eq!(infer_name(path, "#hashtag line\n"), "Docker compose rules");
+    }
+}
@@ -0,0 +1,200 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use crate::capabilities::{self, Tool};
+use crate::claude_client::ClaudeClient;
+use crate::github::ChangeKind;
+use crate::manifest::Manifest;
+use crate::publish::Snippet;
+use crate::store::{FsStore, SnippetStore};
+
+/// A part of the imported file that becomes one snippet.
+#[derive(Debug, PartialEq, Deserialize)]
+struct Section {
+    name: String,
+    content: String,
+}
+
+/// `repo import-claude-md`: split an existing CLAUDE.md into snippets (one
+/// per section, or as Claude Code suggests with `ai`), save them to the
+/// repository `repo_name` (created when missing), and with `rewrite` put
+/// each section of the file back between install markers so the file is
+/// managed from then on.
+pub async fn import_claude_md(path: &Path, repo_name: &str, ai: bool, rewrite: bool) -> Result<()> {
+    let Ok(content) = fs::read_to_string(path) else {
+        anyhow::bail!("{} not found", path.display());
+    };
+    // Blocks already between markers are installed snippets, not new content
+    let handwritten = crate::audit::strip_marked_blocks(&content);
+
+    let sections = match ai {
+        true => match refine_with_claude(&handwritten) {
+            Ok(sections) if !sections.is_empty() => sections,
+            Ok(_) => {
+                println!("⚠️  Claude Code suggested no snippets; splitting by heading instead");
+                split(&handwritten)
+            }
+            Err(e) => {
+                println!("⚠️  Could not split with Claude Code ({}); splitting by heading instead", e);
+                split(&handwritten)
+            }
+        },
+        false => split(&handwritten),
+    };
+    if sections.is_empty() {
+        println!("❌ No sections with content found in {}", path.display());
+        println!("💡 Sections start at markdown headings ('## Testing'); anything before the first one stays in the file");
+        return Ok(());
+    }
+
+    println!("📄 {} section(s) of {} become snippets:", sections.len(), path.display());
+    for section in &sections {
+        println!("  • {} ({} lines)", section.name, section.content.lines().count());
+    }
+    let repo_dir = crate::publish::get_repos_dir()?.join(repo_name);
+    let create = !repo_dir.exists();
+    let question = match create {
+        true => format!("Create repository '{}' with these {} snippet(s)?", repo_name, sections.len()),
+        false => format!("Add these {} snippet(s) to '{}'?", sections.len(), repo_name),
+    };
+    if !crate::prompt::confirm(&question, true)? {
+        println!("❌ Import cancelled");
+        return Ok(());
+    }
+    if create {
+        crate::github::create_local_repo(repo_name).await?;
+    }
+    crate::subscriptions::ensure_writable(repo_name)?;
+
+    let store = FsStore::new(repo_dir.join("snippets"));
+    let source = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let mut snippets = Vec::new();
+    for section in &sections {
+        let snippet = Snippet {
+            id: crate::ids::generate(),
+            name: section.name.clone(),
+            content: section.content.clone(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            description: Some(format!("Imported from {}", source)),
+            ..Default::default()
+        };
+        let snippet = crate::attribution::fill(&snippet, &repo_dir);
+        store.save(&snippet)?;
+        snippets.push(snippet);
+    }
+    println!("✅ Saved {} snippet(s) to '{}'", snippets.len(), repo_name);
+
+    let rewrite = rewrite || crate::prompt::offer(&format!("Manage these sections of {} as installed snippets from now on?", path.display()))?;
+    if rewrite {
+        rewrite_as_installs(path, &content, &snippets)?;
+    } else {
+        println!("💡 Install them anywhere with 'claude-md-snippets install <name>'");
+    }
+
+    crate::edit::finish(repo_name, &format!("Import {} snippet(s) from {}", snippets.len(), source), ChangeKind::Publish).await;
+    Ok(())
+}
+
+/// Sections of `content`, one per heading at the level the document is
+/// organised by: below a lone top-level title, at its subheadings. Deeper
+/// headings stay in their section, and text before the first section (the
+/// title and intro) is left out.
+fn split(content: &str) -> Vec<Section> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut headings = Vec::new();
+    let mut in_fence = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let level = line.chars().take_while(|&c| c == '#').count();
+        if !in_fence && (1..=6).contains(&level) && line[level..].starts_with(' ') {
+            headings.push((i, level));
+        }
+    }
+    let Some(top) = headings.iter().map(|&(_, level)| level).min() else {
+        return Vec::new();
+    };
+    let level = match headings.iter().filter(|&&(_, level)| level == top).count() {
+        1 => headings.iter().map(|&(_, level)| level).filter(|&level| level > top).min().unwrap_or(top),
+        _ => top,
+    };
+
+    let starts: Vec<usize> = headings.iter()
+        .filter(|&&(_, l)| l <= level && !(l == top && level > top))
+        .map(|&(i, _)| i)
+        .collect();
+    starts.iter().enumerate()
+        .filter_map(|(n, &start)| {
+            let end = starts.get(n + 1).copied().unwrap_or(lines.len());
+            let text = lines[start..end].join("\n").trim_end().to_string();
+            let name = lines[start].trim_start_matches('#').trim().to_string();
+            text.lines().skip(1).any(|line| !line.trim().is_empty()).then_some(Section { name, content: text })
+        })
+        .collect()
+}
+
+/// Let Claude Code decide where one instruction ends and the next begins.
+fn refine_with_claude(content: &str) -> Result<Vec<Section>> {
+    capabilities::require(Tool::Claude, "import-claude-md --ai")?;
+    println!("🤖 Asking Claude Code how to split the file...");
+    let prompt = format!(
+        "Split the following CLAUDE.md into self-contained, reusable snippets, one topic each. Keep the \
+        text of each snippet exactly as written, starting with a markdown heading. Skip the document \
+        title and anything specific to a single project that would make no sense elsewhere. Return \
+        only a JSON array of objects with \"name\" (a short title) and \"content\" fields.\n\n{}",
+        content
+    );
+    let response = ClaudeClient::from_config()?.prompt(&prompt)?;
+    let json = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => anyhow::bail!("the response had no JSON array"),
+    };
+    let sections: Vec<Section> = serde_json::from_str(json)?;
+    Ok(sections.into_iter().filter(|section| !section.content.trim().is_empty()).collect())
+}
+
+/// Put every section of `path` that was saved as a snippet between install
+/// markers, and record it as installed, like `promote` does for one.
+fn rewrite_as_installs(path: &Path, content: &str, snippets: &[Snippet]) -> Result<()> {
+    let absolute_path = path.canonicalize()?;
+    let mut rewritten = content.to_string();
+    let mut managed = 0;
+    for snippet in snippets {
+        let text = snippet.content.trim();
+        if !rewritten.contains(text) {
+            println!("⚠️  '{}' was reworded, so the file keeps its original text there", snippet.name);
+            continue;
+        }
+        let short_id = crate::ids::short(&snippet.id);
+        let block = format!("<!-- SNIPPET_START:{} -->\n{}\n<!-- SNIPPET_END:{} -->", short_id, text, short_id);
+        rewritten = rewritten.replacen(text, &block, 1);
+        Manifest::record_install(short_id, &snippet.name, &absolute_path, text)?;
+        managed += 1;
+    }
+    crate::lockfile::atomic_write(path, &rewritten)?;
+    println!("📝 {} now installs {} of them from the repository", path.display(), managed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_by_heading() {
+        let content = "# Project\n\nIntro text.\n\n## Testing\n\nRun cargo test.\n\n### GUI\n\nUse xvfb-run.\n\n## Empty\n\n## Style\n\n```sh\n# not a heading\ncargo fmt\n```\n";
+        let sections = split(content);
+        assert_eq!(sections.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), ["Testing", "Style"]);
+        assert_eq!(sections[0].content, "## Testing\n\nRun cargo test.\n\n### GUI\n\nUse xvfb-run.");
+        assert!(sections[1].content.ends_with("cargo fmt\n```"));
+
+        // Without a single title, every top-level heading starts a section
+        let flat = split("# One\nFirst\n# Two\nSecond\n");
+        assert_eq!(flat, [
+            Section { name: "One".to_string(), content: "# One\nFirst".to_string() },
+            Section { name: "Two".to_string(), content: "# Two\nSecond".to_string() },
+        ]);
+    }
+}
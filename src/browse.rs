@@ -0,0 +1,212 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use crate::capabilities::{self, Tool};
+use crate::publish::{self, Snippet};
+
+const API_BASE: &str = "https://api.github.com";
+
+/// A GitHub snippets repository read through the contents API, without
+/// cloning it. Downloaded files are cached under the app directory by their
+/// blob SHA, so only new or changed snippets are fetched again.
+pub struct RemoteRepo {
+    owner: String,
+    repo: String,
+    http: reqwest::Client,
+    /// Only raises the rate limit; public repositories are readable without one
+    token: Option<String>,
+}
+
+/// A snippet file as listed by the contents API.
+pub struct RemoteFile {
+    /// Path inside the repository, e.g. `snippets/rust/testing-7Hq2mXbK.md`
+    pub path: String,
+    sha: String,
+    download_url: String,
+}
+
+impl RemoteFile {
+    fn stem(&self) -> &str {
+        let name = self.path.rsplit('/').next().unwrap_or(&self.path);
+        name.strip_suffix(".md").unwrap_or(name)
+    }
+}
+
+impl RemoteRepo {
+    /// `owner/repo`, or the repository's GitHub URL.
+    pub fn parse(reference: &str) -> Result<Self> {
+        let (owner, repo) = parse_reference(reference)
+            .with_context(|| format!("'{}' is not a GitHub repository; use owner/repo", reference))?;
+        Ok(RemoteRepo { owner, repo, http: reqwest::Client::new(), token: crate::github::github_token(None) })
+    }
+
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    /// Every markdown file under `snippets/`, subfolders included.
+    pub async fn snippet_files(&self) -> Result<Vec<RemoteFile>> {
+        capabilities::require(Tool::Network, "browsing a remote repository")?;
+        let mut files = Vec::new();
+        let mut dirs = vec!["snippets".to_string()];
+        while let Some(dir) = dirs.pop() {
+            let url = format!("{}/repos/{}/{}/contents/{}", API_BASE, self.owner, self.repo, dir);
+            let Value::Array(entries) = self.get_json(&url).await? else {
+                anyhow::bail!("{} in {} is not a folder", dir, self.full_name());
+            };
+            for entry in entries {
+                let (Some(name), Some(path)) = (entry["name"].as_str(), entry["path"].as_str()) else { continue };
+                if name.starts_with('.') {
+                    continue;
+                }
+                match entry["type"].as_str() {
+                    Some("dir") => dirs.push(path.to_string()),
+                    Some("file") if name.ends_with(".md") && !name.to_lowercase().starts_with("readme") => files.push(RemoteFile {
+                        path: path.to_string(),
+                        sha: entry["sha"].as_str().unwrap_or_default().to_string(),
+                        download_url: entry["download_url"].as_str().unwrap_or_default().to_string(),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// The snippet in `file`, from the cache when this version was fetched before.
+    pub async fn fetch(&self, file: &RemoteFile) -> Result<Snippet> {
+        let cached = self.cache_dir()?.join(format!("{}.md", file.sha));
+        let content = match fs::read_to_string(&cached) {
+            Ok(content) if !file.sha.is_empty() => content,
+            _ => {
+                let response = crate::github_api::send(self.http.get(&file.download_url).header("User-Agent", "claude-md-snippets")).await?;
+                if !response.status().is_success() {
+                    anyhow::bail!("Could not download {}: {}", file.path, response.status());
+                }
+                let content = response.text().await?;
+                fs::create_dir_all(self.cache_dir()?)?;
+                fs::write(&cached, &content)?;
+                content
+            }
+        };
+        let snippet = publish::parse_markdown_frontmatter(&content)
+            .with_context(|| format!("{} in {} is not a snippet file", file.path, self.full_name()))?;
+        crate::ids::validate(&snippet.id, &snippet.name)?;
+        Ok(snippet)
+    }
+
+    /// Every snippet of the repository, skipping files that aren't snippets.
+    pub async fn fetch_all(&self, files: &[RemoteFile]) -> Result<Vec<Snippet>> {
+        let mut snippets = Vec::new();
+        for file in files {
+            match self.fetch(file).await {
+                Ok(snippet) => snippets.push(snippet),
+                Err(e) => println!("⚠️  Skipped {}: {}", file.path, e),
+            }
+        }
+        Ok(snippets)
+    }
+
+    fn cache_dir(&self) -> Result<PathBuf> {
+        Ok(publish::get_app_dir()?.join("cache").join("remote").join(&self.owner).join(&self.repo))
+    }
+
+    async fn get_json(&self, url: &str) -> Result<Value> {
+        let mut request = self.http.get(url)
+            .header("User-Agent", "claude-md-snippets")
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = crate::github_api::send(request).await?;
+        let status = response.status();
+        let json: Value = response.json().await.unwrap_or_default();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("{} has no snippets/ folder, or is private and no GitHub token is set", self.full_name());
+        }
+        if !status.is_success() {
+            anyhow::bail!("GitHub API returned {}: {}", status, json["message"].as_str().unwrap_or("unknown error"));
+        }
+        Ok(json)
+    }
+}
+
+/// `browse`: list the snippets of a GitHub repository without cloning it.
+pub async fn browse(reference: &str) -> Result<()> {
+    let remote = RemoteRepo::parse(reference)?;
+    println!("🔍 Listing snippets in {}...", remote.full_name());
+    let files = remote.snippet_files().await?;
+    if files.is_empty() {
+        println!("ℹ️  {} has no snippets yet", remote.full_name());
+        return Ok(());
+    }
+    let snippets = remote.fetch_all(&files).await?;
+
+    println!("📚 {} snippet(s) in {}:", snippets.len(), remote.full_name());
+    let short_ids = crate::ids::ShortIds::new(snippets.iter().map(|snippet| snippet.id.as_str()));
+    for snippet in &snippets {
+        println!("  📄 {} ({})", snippet.name, short_ids.get(&snippet.id));
+        if let Some(description) = &snippet.description {
+            println!("      {}", description);
+        }
+        if let Some(author) = &snippet.author {
+            println!("      👤 {}", author);
+        }
+    }
+    println!("💡 Install one with 'claude-md-snippets install --from {} <query>', or follow them all with 'repo subscribe'", remote.full_name());
+    Ok(())
+}
+
+/// The remote snippet `query` names: the one file whose name contains it (as
+/// a slug or ID prefix) is downloaded alone; otherwise all are matched.
+pub async fn find_remote(remote: &RemoteRepo, query: &str) -> Result<Option<Snippet>> {
+    let files = remote.snippet_files().await?;
+    let by_name = matching_files(&files, query);
+    if let [file] = by_name.as_slice() {
+        return Ok(Some(remote.fetch(file).await?));
+    }
+    let snippets = remote.fetch_all(&files).await?;
+    crate::install::find_best_match(&snippets, query).await
+}
+
+fn matching_files<'a>(files: &'a [RemoteFile], query: &str) -> Vec<&'a RemoteFile> {
+    let slug = crate::slug::slugify(query, '-');
+    files.iter()
+        .filter(|file| {
+            let stem = file.stem();
+            (slug != "snippet" && stem.contains(&slug))
+                || stem.rsplit('-').next().is_some_and(|id| crate::ids::matches_prefix(id, query))
+        })
+        .collect()
+}
+
+fn parse_reference(reference: &str) -> Option<(String, String)> {
+    if let Some(parsed) = crate::github::parse_github_remote(reference) {
+        return Some(parsed);
+    }
+    let (owner, repo) = reference.trim().trim_end_matches('/').split_once('/')?;
+    let valid = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    (valid(owner) && valid(repo)).then(|| (owner.to_string(), repo.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_references_and_file_matching() {
+        assert_eq!(parse_reference("eyalev/claude-md-snippets"), Some(("eyalev".to_string(), "claude-md-snippets".to_string())));
+        assert_eq!(parse_reference("https://github.com/eyalev/snippets.git"), Some(("eyalev".to_string(), "snippets".to_string())));
+        assert_eq!(parse_reference("just-a-name"), None);
+        assert_eq!(parse_reference("a/b/c"), None);
+
+        let file = |path: &str| RemoteFile { path: path.to_string(), sha: String::new(), download_url: String::new() };
+        let files = [file("snippets/rust/running-tests-7Hq2mXbK.md"), file("snippets/code-style-1a2b3c4d.md")];
+        let paths = |query| matching_files(&files, query).iter().map(|file| file.path.as_str()).collect::<Vec<_>>();
+        assert_eq!(paths("Running tests"), ["snippets/rust/running-tests-7Hq2mXbK.md"]);
+        assert_eq!(paths("1a2b"), ["snippets/code-style-1a2b3c4d.md"]);
+        assert!(paths("deployment").is_empty());
+    }
+}
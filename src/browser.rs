@@ -0,0 +1,46 @@
+use std::path::Path;
+
+/// Open `url` in the default browser, printing a manual fallback on failure.
+/// On WSL, `wslview` (from wslu) hands the URL to the Windows browser when it
+/// is installed; everywhere else the platform's opener is used.
+pub fn open(url: &str) -> bool {
+    let result = if is_wsl() && on_path("wslview") {
+        open::with(url, "wslview")
+    } else {
+        open::that(url)
+    };
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            println!("⚠️  Failed to open browser ({}). You can manually visit: {}", e, url);
+            false
+        }
+    }
+}
+
+fn is_wsl() -> bool {
+    std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::fs::read_to_string("/proc/sys/kernel/osrelease").is_ok_and(|release| is_wsl_kernel(&release))
+}
+
+/// WSL kernels identify themselves as Microsoft builds, e.g. "5.15.90.1-microsoft-standard-WSL2".
+fn is_wsl_kernel(release: &str) -> bool {
+    release.to_lowercase().contains("microsoft")
+}
+
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| Path::new(&dir).join(program).is_file()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_wsl_kernel() {
+        assert!(is_wsl_kernel("5.15.90.1-microsoft-standard-WSL2\n"));
+        assert!(is_wsl_kernel("4.4.0-19041-Microsoft"));
+        assert!(!is_wsl_kernel("6.8.0-45-generic"));
+    }
+}
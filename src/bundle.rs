@@ -0,0 +1,225 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use crate::github::ChangeKind;
+use crate::publish::{self, Snippet};
+use crate::store::{FsStore, SnippetStore};
+
+/// Separates snippets in a markdown bundle; invisible when the document is rendered.
+const MARKDOWN_SEPARATOR: &str = "<!-- claude-md-snippets:snippet -->";
+
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum BundleFormat {
+    /// One markdown document: every snippet with its frontmatter
+    #[value(alias = "markdown")]
+    Md,
+    /// A JSON array of snippets
+    Json,
+    /// The repository's snippets folder (variants and assets included)
+    #[value(name = "tar.gz", alias = "tgz")]
+    TarGz,
+}
+
+impl BundleFormat {
+    /// The format a file name implies, e.g. `backup.tar.gz`.
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(BundleFormat::TarGz)
+        } else if name.ends_with(".json") {
+            Some(BundleFormat::Json)
+        } else if name.ends_with(".md") || name.ends_with(".markdown") {
+            Some(BundleFormat::Md)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(format: Option<Self>, path: &Path) -> Result<Self> {
+        format.or_else(|| BundleFormat::from_path(path)).with_context(|| {
+            format!("Can't tell the format of {}; pass --format md, json or tar.gz", path.display())
+        })
+    }
+}
+
+/// `repo export`: write every snippet of the repository to one file, for
+/// backups or for moving snippets into other tools.
+pub fn export(repo_dir: &Path, repo_name: &str, output: &Path, format: Option<BundleFormat>) -> Result<()> {
+    let format = BundleFormat::resolve(format, output)?;
+    let snippets_dir = repo_dir.join("snippets");
+    let snippets = FsStore::new(snippets_dir.clone()).load_all()?;
+    if snippets.is_empty() {
+        anyhow::bail!("Repository '{}' has no snippets to export", repo_name);
+    }
+
+    match format {
+        BundleFormat::Md => fs::write(output, to_markdown(repo_name, &snippets)?)?,
+        BundleFormat::Json => fs::write(output, serde_json::to_string_pretty(&snippets)?)?,
+        BundleFormat::TarGz => {
+            let file = File::create(output).with_context(|| format!("Failed to create {}", output.display()))?;
+            let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+            builder.append_dir_all("snippets", &snippets_dir)?;
+            builder.into_inner()?.finish()?;
+        }
+    }
+    println!("✅ Exported {} snippet(s) from '{}' to {}", snippets.len(), repo_name, output.display());
+    Ok(())
+}
+
+/// `repo import`: add the snippets of a file written by `repo export` (or
+/// by hand in the same format) to the repository, then sync. Snippets whose
+/// ID is already there are skipped unless `overwrite`.
+pub async fn import(repo_dir: &Path, repo_name: &str, input: &Path, format: Option<BundleFormat>, overwrite: bool) -> Result<()> {
+    let format = BundleFormat::resolve(format, input)?;
+    let snippets_dir = repo_dir.join("snippets");
+    let store = FsStore::new(snippets_dir.clone());
+    let existing: Vec<String> = store.load_all()?.into_iter().map(|snippet| snippet.id).collect();
+
+    if format == BundleFormat::TarGz {
+        return import_archive(repo_name, &snippets_dir, input, &existing, overwrite).await;
+    }
+
+    let text = fs::read_to_string(input).with_context(|| format!("Failed to read {}", input.display()))?;
+    let snippets = match format {
+        BundleFormat::Json => serde_json::from_str::<Vec<Snippet>>(&text).context("Invalid snippet JSON (expected an array of snippets)")?,
+        _ => from_markdown(&text)?,
+    };
+    let (new, known): (Vec<&Snippet>, Vec<&Snippet>) = snippets.iter().partition(|snippet| !existing.contains(&snippet.id));
+    let to_write: Vec<&Snippet> = if overwrite { snippets.iter().collect() } else { new.clone() };
+    println!("📦 {} snippet(s) in {}: {} new, {} already in '{}'{}", snippets.len(), input.display(), new.len(), known.len(), repo_name,
+        if known.is_empty() { "" } else if overwrite { " (replaced)" } else { " (skipped; --overwrite replaces them)" });
+    if to_write.is_empty() {
+        println!("✅ Nothing to import");
+        return Ok(());
+    }
+    if !crate::prompt::confirm(&format!("Import {} snippet(s) into '{}'?", to_write.len(), repo_name), true)? {
+        println!("❌ Import cancelled");
+        return Ok(());
+    }
+
+    for snippet in &to_write {
+        store.save(snippet)?;
+    }
+    println!("✅ Imported {} snippet(s)", to_write.len());
+    crate::edit::finish(repo_name, &format!("Import {} snippet(s) from {}", to_write.len(), file_name(input)), ChangeKind::Publish).await;
+    Ok(())
+}
+
+/// Unpack an exported `snippets/` folder. Snippet files are matched to the
+/// repository by ID; other files (variants, assets) are added when missing.
+async fn import_archive(repo_name: &str, snippets_dir: &Path, input: &Path, existing: &[String], overwrite: bool) -> Result<()> {
+    let file = File::open(input).with_context(|| format!("Failed to open {}", input.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut files: Vec<(PathBuf, Vec<u8>)> = Vec::new();
+    let mut skipped = 0;
+    for entry in archive.entries()? {
+        let mut entry = entry.context("Not a snippets archive (expected a .tar.gz from 'repo export')")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.into_owned();
+        let Some(relative) = archived_snippet_path(&path) else {
+            anyhow::bail!("Refusing unexpected path in archive: {}", path.display());
+        };
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+
+        let target = snippets_dir.join(&relative);
+        let known = match publish::parse_markdown_frontmatter(&String::from_utf8_lossy(&bytes)) {
+            Ok(snippet) => existing.contains(&snippet.id),
+            Err(_) => target.exists(),
+        };
+        if known && !overwrite {
+            skipped += 1;
+            continue;
+        }
+        files.push((relative, bytes));
+    }
+
+    println!("📦 {} file(s) to import into '{}'{}", files.len(), repo_name,
+        if skipped > 0 { format!(", {} already there (skipped; --overwrite replaces them)", skipped) } else { String::new() });
+    if files.is_empty() {
+        println!("✅ Nothing to import");
+        return Ok(());
+    }
+    if !crate::prompt::confirm(&format!("Import {} file(s) into '{}'?", files.len(), repo_name), true)? {
+        println!("❌ Import cancelled");
+        return Ok(());
+    }
+    for (relative, bytes) in &files {
+        let target = snippets_dir.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, bytes)?;
+    }
+    println!("✅ Imported {} file(s)", files.len());
+    crate::edit::finish(repo_name, &format!("Import {} file(s) from {}", files.len(), file_name(input)), ChangeKind::Publish).await;
+    Ok(())
+}
+
+/// The path under `snippets/` an archive entry unpacks to, or `None` for
+/// anything outside it.
+fn archived_snippet_path(path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix("snippets").ok()?;
+    let normal = relative.components().all(|component| matches!(component, Component::Normal(_)));
+    (normal && relative.components().count() > 0).then(|| relative.to_path_buf())
+}
+
+fn to_markdown(repo_name: &str, snippets: &[Snippet]) -> Result<String> {
+    let mut out = format!("# Snippets from '{}'\n\nExported {}, {} snippet(s).\n", repo_name,
+        chrono::Local::now().format("%Y-%m-%d"), snippets.len());
+    for snippet in snippets {
+        out.push_str(&format!("\n{}\n", MARKDOWN_SEPARATOR));
+        out.push_str(&publish::create_markdown_with_frontmatter(snippet)?);
+    }
+    Ok(out)
+}
+
+/// Snippets of a markdown bundle: every section after a separator that
+/// starts with frontmatter (the heading before the first one is skipped).
+fn from_markdown(text: &str) -> Result<Vec<Snippet>> {
+    let snippets: Vec<Snippet> = text
+        .split(MARKDOWN_SEPARATOR)
+        .skip(1)
+        .map(|section| publish::parse_markdown_frontmatter(section.trim_start()))
+        .collect::<Result<_>>()
+        .context("Invalid snippet in the markdown bundle")?;
+    if snippets.is_empty() {
+        anyhow::bail!("No snippets found; a markdown bundle separates snippets with '{}'", MARKDOWN_SEPARATOR);
+    }
+    Ok(snippets)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_bundle_round_trip() {
+        let snippet = |id: &str, name: &str, content: &str| Snippet {
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            description: Some("From the team".to_string()),
+            tags: vec!["rust".to_string()],
+            ..crate::publish::test_snippet(id, name, content)
+        };
+        let snippets = vec![snippet("1a2b3c4d", "Formatting", "Run cargo fmt\n\n---\n\nThen clippy"), snippet("5e6f7a8b", "Tests", "Run the tests")];
+
+        let parsed = from_markdown(&to_markdown("team", &snippets).unwrap()).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].id, "1a2b3c4d");
+        assert_eq!(parsed[0].content.trim(), "Run cargo fmt\n\n---\n\nThen clippy");
+        assert_eq!(parsed[1].tags, ["rust"]);
+        assert_eq!(BundleFormat::from_path(Path::new("backup.tar.gz")), Some(BundleFormat::TarGz));
+        assert_eq!(archived_snippet_path(Path::new("snippets/../x")), None);
+    }
+}
@@ -0,0 +1,239 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use crate::lockfile::atomic_write;
+use crate::publish::get_app_dir;
+
+// Probe results are reused across back-to-back invocations for this long
+const CACHE_TTL_SECS: i64 = 300;
+// Reached for network use that isn't tied to a git remote (gists, the GitHub API)
+const NETWORK_PROBE_HOST: &str = "github.com:443";
+
+// Keyed by tool, and for the network by the host probed, so one remote's
+// result never answers for another
+static CACHE: Mutex<Option<HashMap<String, Probe>>> = Mutex::new(None);
+static FORCED_OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// External tools and resources that commands can degrade without.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Tool {
+    Git,
+    Gh,
+    Fzf,
+    Claude,
+    Network,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 5] = [Tool::Git, Tool::Gh, Tool::Fzf, Tool::Claude, Tool::Network];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tool::Git => "git",
+            Tool::Gh => "GitHub CLI (gh)",
+            Tool::Fzf => "fzf",
+            Tool::Claude => "Claude Code CLI",
+            Tool::Network => "network",
+        }
+    }
+
+    pub fn hint(self) -> &'static str {
+        match self {
+            Tool::Git => "Install git to version and sync snippets",
+            Tool::Gh => "Install the GitHub CLI from https://cli.github.com for automatic repository setup",
+            Tool::Fzf => "Install fzf (Ubuntu/Debian: sudo apt install fzf, macOS: brew install fzf)",
+            Tool::Claude => "Install Claude Code, or check 'config set-claude binary/enabled'",
+            Tool::Network => "Check your internet connection; local commands keep working offline",
+        }
+    }
+
+    /// Features that are switched off or degraded when this tool is missing.
+    pub fn affects(self) -> &'static str {
+        match self {
+            Tool::Git => "sync, pull, setup",
+            Tool::Gh => "automatic repository creation in setup",
+            Tool::Fzf => "interactive search",
+            Tool::Claude => "AI matching and extract (falls back to keyword matching)",
+            Tool::Network => "pull/push to remotes",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Probe {
+    available: bool,
+    checked_at: i64,
+}
+
+/// Whether `tool` is usable, probing at most once per invocation (and reusing
+/// a recent result from disk when available).
+pub fn has(tool: Tool) -> bool {
+    if FORCED_OFFLINE.load(Ordering::Relaxed) && matches!(tool, Tool::Network | Tool::Claude) {
+        return false;
+    }
+    match tool {
+        Tool::Network => cached(network_key(NETWORK_PROBE_HOST), tool.label(), || network_reachable(NETWORK_PROBE_HOST)),
+        _ => cached(tool_key(tool), tool.label(), || probe(tool)),
+    }
+}
+
+/// Whether the git remote at `url` can be reached, probing its own host (a
+/// self-hosted Gitea on the LAN needs no internet). URLs without a network
+/// host are not probed; git reports whatever goes wrong with them.
+pub fn reachable(url: &str) -> bool {
+    if FORCED_OFFLINE.load(Ordering::Relaxed) {
+        return false;
+    }
+    match crate::hosts::probe_address(url) {
+        Some(address) => cached(network_key(&address), &address, || network_reachable(&address)),
+        None => true,
+    }
+}
+
+fn cached(key: String, label: &str, probe: impl FnOnce() -> bool) -> bool {
+    let now = chrono::Utc::now().timestamp();
+    let mut guard = match CACHE.lock() {
+        Ok(guard) => guard,
+        Err(_) => return probe(),
+    };
+    let cache = guard.get_or_insert_with(load_cache);
+
+    if let Some(entry) = cache.get(&key) {
+        if now - entry.checked_at < CACHE_TTL_SECS {
+            return entry.available;
+        }
+    }
+
+    let available = crate::perf::timed(crate::perf::Category::Probe, &format!("probe {}", label), probe);
+    tracing::debug!("Probed {}: {}", label, if available { "available" } else { "not available" });
+    cache.insert(key, Probe { available, checked_at: now });
+    if let Err(e) = save_cache(cache) {
+        eprintln!("⚠️  Could not save capability cache: {}", e);
+    }
+    available
+}
+
+fn tool_key(tool: Tool) -> String {
+    serde_json::to_value(tool).ok().and_then(|value| value.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+fn network_key(address: &str) -> String {
+    format!("network:{}", address)
+}
+
+/// `--offline`: treat the network (and Claude, which needs it) as unavailable
+/// without probing.
+pub fn force_offline() {
+    FORCED_OFFLINE.store(true, Ordering::Relaxed);
+}
+
+pub fn is_forced_offline() -> bool {
+    FORCED_OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail with a consistent message when a required tool is missing.
+pub fn require(tool: Tool, feature: &str) -> Result<()> {
+    if has(tool) {
+        Ok(())
+    } else {
+        anyhow::bail!("{} requires {}, which is not available. {}", feature, tool.label(), tool.hint())
+    }
+}
+
+/// Print a consistent notice that `feature` is degraded because `tool` is missing.
+pub fn note_unavailable(tool: Tool, feature: &str) {
+    if FORCED_OFFLINE.load(Ordering::Relaxed) && matches!(tool, Tool::Network | Tool::Claude) {
+        println!("ℹ️  {} skipped (--offline)", feature);
+        return;
+    }
+    println!("ℹ️  {} unavailable: {} not detected. {}", feature, tool.label(), tool.hint());
+}
+
+/// Forget cached results so the next check probes again.
+pub fn clear_cache() -> Result<()> {
+    if let Ok(mut guard) = CACHE.lock() {
+        *guard = Some(HashMap::new());
+    }
+    let path = get_cache_path()?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn probe(tool: Tool) -> bool {
+    match tool {
+        Tool::Git => command_succeeds("git", &["--version"]),
+        Tool::Gh => command_succeeds("gh", &["--version"]),
+        Tool::Fzf => command_succeeds("fzf", &["--version"]),
+        Tool::Claude => match crate::config::Config::load() {
+            Ok(config) => config.claude.enabled && command_succeeds(&config.claude.binary, &["--version"]),
+            Err(_) => false,
+        },
+        Tool::Network => network_reachable(NETWORK_PROBE_HOST),
+    }
+}
+
+fn command_succeeds(program: &str, args: &[&str]) -> bool {
+    Command::new(program)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn network_reachable(address: &str) -> bool {
+    let Ok(mut addrs) = address.to_socket_addrs() else { return false };
+    addrs.next()
+        .map(|addr| TcpStream::connect_timeout(&addr, Duration::from_secs(2)).is_ok())
+        .unwrap_or(false)
+}
+
+fn get_cache_path() -> Result<std::path::PathBuf> {
+    Ok(get_app_dir()?.join("capabilities.json"))
+}
+
+fn load_cache() -> HashMap<String, Probe> {
+    get_cache_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(cache: &HashMap<String, Probe>) -> Result<()> {
+    atomic_write(&get_cache_path()?, &serde_json::to_string_pretty(cache)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probes_the_remote_host() {
+        // A server on the LAN, not github.com, answers for its own remote
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = crate::hosts::probe_address(&format!("http://{}/me/snips.git", listener.local_addr().unwrap())).unwrap();
+        assert!(address.starts_with("127.0.0.1:"));
+        assert!(network_reachable(&address));
+        drop(listener);
+        assert!(!network_reachable(&address));
+        assert!(reachable("/srv/git/snips.git"));
+    }
+
+    #[test]
+    fn test_network_cache_keys() {
+        assert_eq!(tool_key(Tool::Claude), "claude");
+        assert_eq!(network_key("gitea.lan:3000"), "network:gitea.lan:3000");
+        assert_ne!(network_key("gitea.lan:3000"), network_key(NETWORK_PROBE_HOST));
+    }
+}
@@ -0,0 +1,91 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::publish::{self, Snippet};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+// Shorter copies are usually a word or a path, not an instruction
+const MIN_CHARS: usize = 20;
+
+/// `capture`: watch the clipboard for `duration` and offer to save each newly
+/// copied block as a draft in `dir` (`./.claude.local/snippets` by default),
+/// where `publish --file` picks it up. Text already on the clipboard when the
+/// watch starts is left alone.
+pub async fn capture_clipboard(duration: chrono::Duration, dir: Option<PathBuf>) -> Result<()> {
+    let dir = dir.unwrap_or_else(|| PathBuf::from(crate::extract::DEFAULT_EXTRACT_DIR));
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| anyhow::anyhow!("Could not access the clipboard: {}", e))?;
+    let mut last = clipboard.get_text().unwrap_or_default();
+
+    let deadline = tokio::time::Instant::now() + duration.to_std().context("The duration must be positive")?;
+    let until = chrono::Local::now() + duration;
+    println!("📋 Watching the clipboard until {}; copy instructions to capture them (Ctrl-C to stop)", until.format("%H:%M"));
+
+    let mut saved = Vec::new();
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        // Images and other non-text contents are not for us
+        let Ok(text) = clipboard.get_text() else { continue };
+        if text == last {
+            continue;
+        }
+        last = text.clone();
+        if text.trim().chars().count() < MIN_CHARS {
+            tracing::debug!("Ignoring a short clipboard copy ({} chars)", text.trim().chars().count());
+            continue;
+        }
+
+        println!();
+        println!("📋 Copied:");
+        for line in crate::prompt::preview_lines(&text, 5).lines() {
+            println!("   {}", line);
+        }
+        if crate::prompt::confirm("Save as a snippet draft?", true)? {
+            let path = save_draft(&dir, &text)?;
+            println!("✅ Saved {}", path.display());
+            saved.push(path);
+        }
+    }
+
+    println!();
+    if saved.is_empty() {
+        println!("ℹ️  Nothing captured");
+    } else {
+        println!("✅ Captured {} draft(s) in {}", saved.len(), dir.display());
+        println!("💡 Review them, then publish one with 'claude-md-snippets publish --file <name>'");
+    }
+    Ok(())
+}
+
+/// Write `text` as a draft snippet named after its first meaningful line.
+fn save_draft(dir: &Path, text: &str) -> Result<PathBuf> {
+    let snippet = Snippet {
+        id: crate::ids::generate(),
+        name: publish::generate_name_from_content(text),
+        content: text.trim().to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: Some("Captured from the clipboard".to_string()),
+        ..Default::default()
+    };
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(crate::slug::snippet_filename(dir, &snippet.name, &snippet.id));
+    fs::write(&path, publish::create_markdown_with_frontmatter(&snippet)?)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_draft() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = save_draft(dir.path(), "Always run cargo fmt before committing\n").unwrap();
+
+        let snippet = publish::parse_markdown_frontmatter(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(snippet.content, "Always run cargo fmt before committing");
+        assert_eq!(snippet.description.as_deref(), Some("Captured from the clipboard"));
+        assert!(path.file_name().unwrap().to_str().unwrap().contains(crate::ids::short(&snippet.id)));
+    }
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use crate::perf::Category;
+use crate::process::{ProcessRunner, RunWith};
+
+/// Commit message used when the changes can't be described.
+pub const FALLBACK_MESSAGE: &str = "Sync snippets: add/modify/remove files";
+/// Longer summaries fall back to counts, to keep `git log --oneline` readable.
+const MAX_SUBJECT: usize = 72;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Add,
+    Update,
+    Rename,
+    Remove,
+}
+
+impl Kind {
+    fn verb(self) -> &'static str {
+        match self {
+            Kind::Add => "Add",
+            Kind::Update => "Update",
+            Kind::Rename => "Rename",
+            Kind::Remove => "Remove",
+        }
+    }
+}
+
+/// A commit message naming what changed in `repo_dir`, staged or not, e.g.
+/// "Add snippet: docker compose rules; Update: rust testing".
+pub fn describe(runner: &dyn ProcessRunner, repo_dir: &Path) -> String {
+    let Ok(output) = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["status", "--porcelain", "--untracked-files=all"])
+        .run_with(runner, Category::Git)
+    else {
+        return FALLBACK_MESSAGE.to_string();
+    };
+    let status = String::from_utf8_lossy(&output.stdout);
+    let (mut snippets, mut others) = (Vec::new(), 0);
+    for line in status.lines().filter(|line| line.len() > 3) {
+        let (code, path) = line.split_at(3);
+        let path = path.rsplit(" -> ").next().unwrap_or(path).trim_matches('"');
+        let kind = match code.chars().find(|&c| c != ' ') {
+            Some('D') => Kind::Remove,
+            Some('R') => Kind::Rename,
+            Some('A' | '?') => Kind::Add,
+            _ => Kind::Update,
+        };
+        match path.starts_with("snippets/") && path.ends_with(".md") && !path.contains("/assets/") {
+            true => snippets.push((kind, snippet_name(runner, repo_dir, path, kind))),
+            false => others += 1,
+        }
+    }
+    summarize(&snippets, others)
+}
+
+/// The snippet's name from its frontmatter (the last committed version for a
+/// deleted file), else the file name without its ID.
+fn snippet_name(runner: &dyn ProcessRunner, repo_dir: &Path, path: &str, kind: Kind) -> String {
+    let content = match kind {
+        Kind::Remove => Command::new("git")
+            .current_dir(repo_dir)
+            .args(["show", &format!("HEAD:{}", path)])
+            .run_with(runner, Category::Git)
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned()),
+        _ => fs::read_to_string(repo_dir.join(path)).ok(),
+    };
+    if let Some(snippet) = content.and_then(|content| crate::publish::parse_markdown_frontmatter(&content).ok()) {
+        return snippet.name;
+    }
+    let stem = Path::new(path).file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+    match stem.rsplit_once('-') {
+        Some((name, _id)) => name.replace('-', " "),
+        None => stem,
+    }
+}
+
+fn summarize(snippets: &[(Kind, String)], others: usize) -> String {
+    let groups: Vec<(Kind, Vec<&str>)> = [Kind::Add, Kind::Update, Kind::Rename, Kind::Remove].into_iter()
+        .map(|kind| (kind, snippets.iter().filter(|(k, _)| *k == kind).map(|(_, name)| name.as_str()).collect::<Vec<_>>()))
+        .filter(|(_, names)| !names.is_empty())
+        .collect();
+    if groups.is_empty() {
+        return match others {
+            0 => FALLBACK_MESSAGE.to_string(),
+            1 => "Update 1 file".to_string(),
+            n => format!("Update {} files", n),
+        };
+    }
+
+    // The first group says what the names are, the rest only what happened
+    let named = groups.iter().enumerate()
+        .map(|(i, (kind, names))| match (i, names.len()) {
+            (0, 1) => format!("{} snippet: {}", kind.verb(), names[0]),
+            (0, _) => format!("{} snippets: {}", kind.verb(), names.join(", ")),
+            _ => format!("{}: {}", kind.verb(), names.join(", ")),
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    if named.chars().count() <= MAX_SUBJECT {
+        return named;
+    }
+    groups.iter()
+        .map(|(kind, names)| match names.len() {
+            1 => format!("{} 1 snippet", kind.verb()),
+            n => format!("{} {} snippets", kind.verb(), n),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summarize_changes() {
+        let changes = [(Kind::Add, "docker compose rules".to_string()), (Kind::Update, "rust testing".to_string())];
+        assert_eq!(summarize(&changes, 0), "Add snippet: docker compose rules; Update: rust testing");
+        assert_eq!(summarize(&[], 2), "Update 2 files");
+        assert_eq!(summarize(&[], 0), FALLBACK_MESSAGE);
+
+        let many: Vec<(Kind, String)> = (0..8).map(|i| (Kind::Remove, format!("old snippet number {}", i))).collect();
+        assert_eq!(summarize(&many, 0), "Remove 8 snippets");
+    }
+}
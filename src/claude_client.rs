@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use crate::config::{ClaudeSettings, Config};
+use crate::perf::{Category, timed};
+
+/// Runs prompts through the Claude Code CLI using the `claude.*` config settings.
+pub struct ClaudeClient {
+    settings: ClaudeSettings,
+}
+
+impl ClaudeClient {
+    pub fn from_config() -> Result<Self> {
+        let config = Config::load()?;
+        Ok(ClaudeClient { settings: config.claude })
+    }
+
+    fn args(&self, prompt: &str) -> Vec<String> {
+        let mut args = vec!["--dangerously-skip-permissions".to_string(), "--print".to_string()];
+        if let Some(model) = &self.settings.model {
+            args.push("--model".to_string());
+            args.push(model.clone());
+        }
+        args.extend(self.settings.extra_args.iter().cloned());
+        args.push(prompt.to_string());
+        args
+    }
+
+    /// Send `prompt` and return Claude's trimmed response. Errors if AI matching
+    /// is disabled, the binary can't be run, it fails, or it exceeds the timeout.
+    pub fn prompt(&self, prompt: &str) -> Result<String> {
+        if !self.settings.enabled {
+            anyhow::bail!("Claude Code integration is disabled (claude.enabled = false)");
+        }
+
+        let args = self.args(prompt);
+        let shown: Vec<&str> = args[..args.len() - 1].iter().map(|a| a.as_str()).collect();
+        tracing::debug!("Calling Claude Code CLI: {} {} <prompt of {} characters>", self.settings.binary, shown.join(" "), prompt.len());
+        tracing::trace!("Prompt: {}", prompt);
+
+        let timeout = Duration::from_secs(self.settings.timeout_secs);
+        let (status, stdout, stderr) = timed(Category::Ai, "claude", || {
+            run_with_timeout(Command::new(&self.settings.binary).args(&args), timeout)
+        })
+        .with_context(|| format!("Failed to execute Claude Code CLI '{}'", self.settings.binary))?;
+
+        tracing::debug!("Claude Code CLI returned {}", status);
+        tracing::trace!("stdout: {}", stdout.trim());
+        if !stderr.is_empty() {
+            tracing::debug!("stderr: {}", stderr.trim());
+        }
+
+        if !status.success() {
+            anyhow::bail!("Claude Code failed ({}): {}", status, stderr.trim());
+        }
+
+        Ok(stdout.trim().to_string())
+    }
+}
+
+fn run_with_timeout(command: &mut Command, timeout: Duration) -> Result<(std::process::ExitStatus, String, String)> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain the pipes on separate threads so a chatty process can't block on a full buffer
+    let mut stdout_pipe = child.stdout.take().context("Missing stdout pipe")?;
+    let mut stderr_pipe = child.stderr.take().context("Missing stderr pipe")?;
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        stdout_pipe.read_to_end(&mut buf).ok();
+        buf
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        stderr_pipe.read_to_end(&mut buf).ok();
+        buf
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            child.kill().ok();
+            child.wait().ok();
+            anyhow::bail!("Claude Code timed out after {}s (raise claude.timeout_secs)", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    let stdout = String::from_utf8_lossy(&stdout_reader.join().unwrap_or_default()).to_string();
+    let stderr = String::from_utf8_lossy(&stderr_reader.join().unwrap_or_default()).to_string();
+    Ok((status, stdout, stderr))
+}
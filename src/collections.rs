@@ -0,0 +1,84 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::publish::Snippet;
+
+/// Groups of snippets that are installed and uninstalled together, defined
+/// in the repository's `collection.yaml`:
+///
+/// ```yaml
+/// rust-project-defaults:
+///   description: What every Rust repo gets
+///   snippets: [1a2b3c4d, 5e6f7a8b]
+/// ```
+pub const COLLECTIONS_FILE: &str = "collection.yaml";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Collection {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Member snippet IDs (or unique ID prefixes), in install order
+    pub snippets: Vec<String>,
+}
+
+/// Every collection defined in `repo_dir`; none if there is no collection file.
+pub fn load(repo_dir: &Path) -> Result<BTreeMap<String, Collection>> {
+    let path = repo_dir.join(COLLECTIONS_FILE);
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    if content.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    serde_yaml::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))
+}
+
+/// The collection called `name` in the default repository.
+pub fn find(name: &str) -> Result<Collection> {
+    let repo_dir = crate::publish::get_snippets_dir()?;
+    let mut collections = load(&repo_dir)?;
+    if let Some(collection) = collections.remove(name) {
+        return Ok(collection);
+    }
+    if collections.is_empty() {
+        anyhow::bail!("No collections defined; add them to {}", repo_dir.join(COLLECTIONS_FILE).display());
+    }
+    let names: Vec<&str> = collections.keys().map(|name| name.as_str()).collect();
+    anyhow::bail!("No collection named '{}'. Available: {}", name, names.join(", "))
+}
+
+/// The collection's snippets in order. Every member must match exactly one snippet.
+pub fn members(collection: &Collection, snippets: &[Snippet]) -> Result<Vec<Snippet>> {
+    collection.snippets.iter().map(|id| {
+        let matches: Vec<&Snippet> = snippets.iter().filter(|s| s.id.starts_with(id.as_str())).collect();
+        match matches.as_slice() {
+            [snippet] => Ok((*snippet).clone()),
+            [] => anyhow::bail!("Collection member '{}' doesn't match any snippet", id),
+            _ => anyhow::bail!("Collection member '{}' matches {} snippets; use a longer ID", id, matches.len()),
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_members() {
+        let collections: BTreeMap<String, Collection> = serde_yaml::from_str(
+            "rust-defaults:\n  description: Rust\n  snippets: [bbbb, aaaa1111]\n",
+        ).unwrap();
+        let snippets = ["aaaa1111-x", "aaaa2222-y", "bbbb3333-z"].map(|id| crate::publish::test_snippet(id, id, ""));
+
+        let members = members(&collections["rust-defaults"], &snippets).unwrap();
+        let ids: Vec<&str> = members.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, ["bbbb3333-z", "aaaa1111-x"]);
+
+        let ambiguous = Collection { description: None, snippets: vec!["aaaa".to_string()] };
+        assert!(super::members(&ambiguous, &snippets).is_err());
+    }
+}
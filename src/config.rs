@@ -1,46 +1,409 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use crate::publish::get_app_dir;
+use std::sync::Mutex;
+use crate::lockfile::{FileLock, atomic_write};
+use crate::project::ProjectConfig;
+use crate::visibility::Visibility;
+
+/// Version of the config.json layout. Older files are migrated on load, see [`migrate`].
+pub const SCHEMA_VERSION: u32 = 4;
 
 #[derive(Serialize, Deserialize, Default)]
 pub struct Config {
+    /// Layout version this file was written with
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Bumped on every locked write; lets readers tell whether their copy is stale
+    #[serde(default)]
+    pub revision: u64,
     pub default_repo: Option<String>,
-    pub default_install_location: Option<String>, // "local" or "user"
+    #[serde(default)]
+    pub install: InstallSettings,
+    #[serde(default)]
+    pub sync: SyncSettings,
+    #[serde(default)]
+    pub output: OutputSettings,
+    #[serde(default)]
+    pub claude: ClaudeSettings,
+    #[serde(default)]
+    pub limits: SizeLimits,
+    /// Named install targets for `install --target`, e.g. "rules" -> ".cursor/rules/main.md"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, String>,
+    /// Git repository with `<template>/*.md` starter snippets for `setup --template`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub templates_source: Option<String>,
+    /// GitHub identities by name, e.g. "work" and "personal"
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub accounts: BTreeMap<String, Account>,
+    /// Which account each repository uses (repo name -> account name)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repo_accounts: BTreeMap<String, String>,
+    /// Language whose snippet variants install picks, e.g. "es" (the original when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// License recorded in snippets published from here, e.g. "MIT"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Who each repository is shared with (repo name -> level); public when unset
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repo_visibility: BTreeMap<String, Visibility>,
+    /// Branch each repository syncs (repo name -> branch); detected from the remote when unset
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repo_branches: BTreeMap<String, String>,
+    /// Last `origin` URL seen for each repository, to restore it if it goes missing
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub repo_remotes: BTreeMap<String, String>,
+    /// Read-only upstream repositories by local name (see `repo subscribe`): searched and
+    /// installed from alongside your own, never published or pushed to
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sources: BTreeMap<String, Source>,
+}
+
+/// A read-only upstream repository.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Source {
+    /// Where it is cloned from, so `pull --all` can fetch it on a new machine; empty if
+    /// it was followed before sources recorded their URL (filled in on the next pull)
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub url: String,
+}
+
+/// A GitHub identity used for a repository's pushes, pulls and API calls.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Account {
+    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Environment variable holding this account's token (otherwise `gh auth token --user` is used)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+/// Agent instruction files that `--target` understands without any configuration.
+pub const BUILTIN_TARGETS: &[(&str, &str)] = &[
+    ("claude", "CLAUDE.md"),
+    ("agents", "AGENTS.md"),
+    ("cursor", ".cursorrules"),
+    ("copilot", ".github/copilot-instructions.md"),
+];
+
+/// Where `install` writes when neither `--local`, `--user` nor `--target` is given.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallLocation {
+    /// ./CLAUDE.md in the current directory
+    #[default]
+    Local,
+    /// ~/.claude/CLAUDE.md
+    User,
+}
+
+impl InstallLocation {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "local" => Ok(InstallLocation::Local),
+            "user" => Ok(InstallLocation::User),
+            _ => anyhow::bail!("Install location must be 'local' or 'user'"),
+        }
+    }
+    
+    pub fn as_str(self) -> &'static str {
+        match self {
+            InstallLocation::Local => "local",
+            InstallLocation::User => "user",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct InstallSettings {
+    pub location: InstallLocation,
+    /// Also ask Claude Code whether a snippet contradicts what's already installed
+    pub conflict_check_ai: bool,
+}
+
+/// Which changes are synced as soon as they are made; the rest are committed
+/// and queued for the next `sync`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncMode {
+    /// After publish, delete and every other change
+    #[default]
+    Auto,
+    /// Only when running `sync`
+    Manual,
+    /// After publishing, but not after deletes and edits
+    OnPublishOnly,
+}
+
+impl SyncMode {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "auto" => Ok(SyncMode::Auto),
+            "manual" => Ok(SyncMode::Manual),
+            "on-publish-only" => Ok(SyncMode::OnPublishOnly),
+            _ => anyhow::bail!("sync.mode must be 'auto', 'manual' or 'on-publish-only'"),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SyncMode::Auto => "auto",
+            SyncMode::Manual => "manual",
+            SyncMode::OnPublishOnly => "on-publish-only",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SyncSettings {
+    pub mode: SyncMode,
+    /// Pull remote changes before pushing; off, a push the remote is ahead of fails instead
+    pub pull_before_push: bool,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        SyncSettings { mode: SyncMode::Auto, pull_before_push: true }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct OutputSettings {
+    /// Lines of a snippet shown before install asks for confirmation
+    pub preview_lines: usize,
+}
+
+impl Default for OutputSettings {
+    fn default() -> Self {
+        OutputSettings { preview_lines: 30 }
+    }
+}
+
+/// Every setting `config get`/`config set` understand, with what it does.
+pub const SETTINGS: &[(&str, &str)] = &[
+    ("default_repo", "Repository used when no --name is given"),
+    ("install.location", "Where install writes by default: local (./CLAUDE.md) or user (~/.claude/CLAUDE.md)"),
+    ("install.conflict_check_ai", "Ask Claude Code about contradicting instructions before installing (true/false)"),
+    ("sync.mode", "When changes are synced: auto (after every change), manual (only on 'sync') or on-publish-only"),
+    ("sync.pull_before_push", "Pull remote changes before pushing (true/false)"),
+    ("output.preview_lines", "Lines of a snippet previewed before installing"),
+    ("claude.binary", "Claude Code CLI executable"),
+    ("claude.model", "Model passed to the CLI (empty for the CLI default)"),
+    ("claude.extra_args", "Extra CLI arguments, space-separated"),
+    ("claude.timeout_secs", "Seconds before a Claude Code call is abandoned"),
+    ("claude.enabled", "Use Claude Code for matching and extraction (true/false)"),
+    ("limits.max_tokens", "Warn about snippets over this many tokens (0 = off)"),
+    ("limits.max_bytes", "Warn about snippets over this many bytes (0 = off)"),
+    ("language", "Install snippet variants in this language, e.g. es (empty for the originals)"),
+    ("license", "SPDX license recorded in newly published snippets, e.g. MIT (empty for none)"),
+    ("templates_source", "Git repository with starter snippets for setup --template"),
+];
+
+/// How the Claude Code CLI is invoked for matching and extraction.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ClaudeSettings {
+    pub binary: String,
+    pub model: Option<String>,
+    pub extra_args: Vec<String>,
+    pub timeout_secs: u64,
+    pub enabled: bool,
+}
+
+impl Default for ClaudeSettings {
+    fn default() -> Self {
+        ClaudeSettings {
+            binary: "claude".to_string(),
+            model: None,
+            extra_args: Vec::new(),
+            timeout_secs: 120,
+            enabled: true,
+        }
+    }
+}
+
+impl ClaudeSettings {
+    fn get(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "binary" => self.binary.clone(),
+            "model" => self.model.clone().unwrap_or_default(),
+            "extra_args" => self.extra_args.join(" "),
+            "timeout_secs" => self.timeout_secs.to_string(),
+            "enabled" => self.enabled.to_string(),
+            _ => return None,
+        })
+    }
+    
+    /// Set a setting from its string form, e.g. `timeout_secs` = "60".
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        const KEYS: &[&str] = &["binary", "model", "extra_args", "timeout_secs", "enabled"];
+        match key {
+            "binary" => self.binary = value.to_string(),
+            "model" => self.model = if value.is_empty() { None } else { Some(value.to_string()) },
+            "extra_args" => self.extra_args = value.split_whitespace().map(|a| a.to_string()).collect(),
+            "timeout_secs" => {
+                self.timeout_secs = value.parse()
+                    .map_err(|_| anyhow::anyhow!("timeout_secs must be a whole number of seconds"))?;
+            }
+            "enabled" => {
+                self.enabled = value.parse()
+                    .map_err(|_| anyhow::anyhow!("enabled must be 'true' or 'false'"))?;
+            }
+            _ => anyhow::bail!("Unknown claude setting '{}'. Valid keys: {}", key, KEYS.join(", ")),
+        }
+        Ok(())
+    }
+}
+
+/// Advisory snippet sizes; publish and install warn above them. 0 turns a limit off.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SizeLimits {
+    pub max_tokens: usize,
+    pub max_bytes: usize,
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        SizeLimits { max_tokens: 1500, max_bytes: 8000 }
+    }
+}
+
+impl SizeLimits {
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let value: usize = value.parse()
+            .map_err(|_| anyhow::anyhow!("{} must be a whole number (0 turns it off)", key))?;
+        match key {
+            "max_tokens" => self.max_tokens = value,
+            "max_bytes" => self.max_bytes = value,
+            _ => anyhow::bail!("Unknown limit '{}'. Valid keys: max_tokens, max_bytes", key),
+        }
+        Ok(())
+    }
 }
 
 impl Config {
+    /// A setting's current value in its string form, e.g. `claude.timeout_secs` -> "120".
+    pub fn get(&self, key: &str) -> Result<String> {
+        let value = match key {
+            "default_repo" => Some(self.default_repo.clone().unwrap_or_default()),
+            "install.location" => Some(self.install.location.as_str().to_string()),
+            "install.conflict_check_ai" => Some(self.install.conflict_check_ai.to_string()),
+            "sync.mode" => Some(self.sync.mode.as_str().to_string()),
+            "sync.pull_before_push" => Some(self.sync.pull_before_push.to_string()),
+            "output.preview_lines" => Some(self.output.preview_lines.to_string()),
+            "limits.max_tokens" => Some(self.limits.max_tokens.to_string()),
+            "limits.max_bytes" => Some(self.limits.max_bytes.to_string()),
+            "language" => Some(self.language.clone().unwrap_or_default()),
+            "license" => Some(self.license.clone().unwrap_or_default()),
+            "templates_source" => Some(self.get_templates_source().to_string()),
+            _ => key.strip_prefix("claude.").and_then(|key| self.claude.get(key)),
+        };
+        value.ok_or_else(|| unknown_setting(key))
+    }
+    
+    /// Change a setting from its string form, validating the value. Only this
+    /// copy changes; [`Config::set`] also saves it.
+    pub fn apply(&mut self, key: &str, value: &str) -> Result<()> {
+        let optional = |value: &str| if value.is_empty() { None } else { Some(value.to_string()) };
+        let flag = |value: &str| value.parse::<bool>().map_err(|_| anyhow::anyhow!("{} must be 'true' or 'false'", key));
+        match key {
+            "default_repo" => self.default_repo = optional(value),
+            "install.location" => self.install.location = InstallLocation::parse(value)?,
+            "install.conflict_check_ai" => self.install.conflict_check_ai = flag(value)?,
+            "sync.mode" => self.sync.mode = SyncMode::parse(value)?,
+            "sync.pull_before_push" => self.sync.pull_before_push = flag(value)?,
+            "output.preview_lines" => {
+                self.output.preview_lines = value.parse()
+                    .map_err(|_| anyhow::anyhow!("output.preview_lines must be a whole number"))?;
+            }
+            "language" => self.language = optional(value),
+            "license" => self.license = optional(value),
+            "templates_source" => self.templates_source = optional(value),
+            _ => match key.split_once('.') {
+                Some(("claude", key)) => self.claude.set(key, value)?,
+                Some(("limits", key)) => self.limits.set(key, value)?,
+                _ => return Err(unknown_setting(key)),
+            },
+        }
+        Ok(())
+    }
+    
+    /// Change a setting and save it.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        *self = Config::update(|config| config.apply(key, value))?;
+        Ok(())
+    }
+    
+    /// The global config with the current project's `.claude-md-snippets.toml` merged over it,
+    /// and any `--repo` given to this command over both.
     pub fn load() -> Result<Self> {
+        let mut config = Config::load_global()?;
+        if let Some((_, project)) = ProjectConfig::find()? {
+            project.apply(&mut config);
+        }
+        if let Some(repo) = REPO_OVERRIDE.lock().ok().and_then(|repo| repo.clone()) {
+            config.default_repo = Some(repo);
+        }
+        Ok(config)
+    }
+    
+    pub fn load_global() -> Result<Self> {
         let config_path = get_config_path()?;
         
         if !config_path.exists() {
             // Create default config if it doesn't exist
-            let config = Config::default();
-            config.save()?;
-            return Ok(config);
+            return Config::update(|_| Ok(()));
         }
         
-        let content = fs::read_to_string(config_path)?;
-        let config: Config = serde_json::from_str(&content)?;
-        Ok(config)
+        match read_config(&config_path)? {
+            // Save the migrated layout (update migrates again under the lock)
+            (_, Some(_)) => Config::update(|_| Ok(())),
+            (config, None) => Ok(config),
+        }
     }
     
-    pub fn save(&self) -> Result<()> {
+    /// Apply `change` to the latest on-disk config while holding the config lock,
+    /// so edits from concurrent processes are merged rather than overwritten.
+    pub fn update<F>(change: F) -> Result<Config>
+    where
+        F: FnOnce(&mut Config) -> Result<()>,
+    {
         let config_path = get_config_path()?;
+        let _lock = FileLock::acquire(&config_path)?;
         
-        // Ensure parent directory exists
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
+        let mut config = if config_path.exists() {
+            let (config, migrated_from) = read_config(&config_path)?;
+            if let Some(old_version) = migrated_from {
+                let backup = config_path.with_extension(format!("v{}.json.bak", old_version));
+                fs::copy(&config_path, &backup)?;
+                println!("ℹ️  Upgraded config.json to schema v{} (previous file saved as {})", SCHEMA_VERSION, backup.display());
+            }
+            config
+        } else {
+            Config::default()
+        };
         
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(config_path, content)?;
-        Ok(())
+        change(&mut config)?;
+        config.schema_version = SCHEMA_VERSION;
+        config.revision += 1;
+        atomic_write(&config_path, &serde_json::to_string_pretty(&config)?)?;
+        Ok(config)
     }
     
     pub fn set_default_repo(&mut self, repo_name: String) -> Result<()> {
-        self.default_repo = Some(repo_name);
-        self.save()
+        *self = Config::update(|config| {
+            config.default_repo = Some(repo_name);
+            Ok(())
+        })?;
+        Ok(())
     }
     
     pub fn get_default_repo(&self) -> Option<&str> {
@@ -48,22 +411,181 @@ impl Config {
     }
     
     pub fn set_default_install_location(&mut self, location: String) -> Result<()> {
-        if location == "local" || location == "user" {
-            self.default_install_location = Some(location);
-            self.save()
-        } else {
-            anyhow::bail!("Install location must be 'local' or 'user'")
+        self.set("install.location", &location)
+    }
+    
+    /// Set the preferred snippet language; an empty string goes back to the originals.
+    pub fn set_language(&mut self, language: &str) -> Result<()> {
+        self.set("language", language)
+    }
+    
+    pub fn set_claude_setting(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set(&format!("claude.{}", key), value)
+    }
+    
+    pub fn set_limit(&mut self, key: &str, value: &str) -> Result<()> {
+        self.set(&format!("limits.{}", key), value)
+    }
+    
+    /// Add or replace a named target; an empty path removes it.
+    pub fn set_target(&mut self, name: &str, path: &str) -> Result<()> {
+        *self = Config::update(|config| {
+            if path.is_empty() {
+                config.targets.remove(name);
+            } else {
+                config.targets.insert(name.to_string(), path.to_string());
+            }
+            Ok(())
+        })?;
+        Ok(())
+    }
+    
+    /// The path for a named target, checking configured targets before the built-ins.
+    pub fn get_target(&self, name: &str) -> Option<&str> {
+        self.targets.get(name).map(|path| path.as_str()).or_else(|| {
+            BUILTIN_TARGETS.iter().find(|(builtin, _)| *builtin == name).map(|(_, path)| *path)
+        })
+    }
+    
+    /// Update every reference to a repository after its local directory was renamed.
+    pub fn rename_repo(&mut self, old_name: &str, new_name: &str) {
+        if self.default_repo.as_deref() == Some(old_name) {
+            self.default_repo = Some(new_name.to_string());
+        }
+        if let Some(account) = self.repo_accounts.remove(old_name) {
+            self.repo_accounts.insert(new_name.to_string(), account);
+        }
+        if let Some(visibility) = self.repo_visibility.remove(old_name) {
+            self.repo_visibility.insert(new_name.to_string(), visibility);
+        }
+        if let Some(branch) = self.repo_branches.remove(old_name) {
+            self.repo_branches.insert(new_name.to_string(), branch);
         }
+        if let Some(remote) = self.repo_remotes.remove(old_name) {
+            self.repo_remotes.insert(new_name.to_string(), remote);
+        }
+        if let Some(source) = self.sources.remove(old_name) {
+            self.sources.insert(new_name.to_string(), source);
+        }
+    }
+
+    /// Whether `repo_name` is a read-only source followed with `repo subscribe`.
+    pub fn is_subscribed(&self, repo_name: &str) -> bool {
+        self.sources.contains_key(repo_name)
+    }
+    
+    pub fn set_repo_visibility(&mut self, repo_name: &str, visibility: Visibility) -> Result<()> {
+        *self = Config::update(|config| {
+            config.repo_visibility.insert(repo_name.to_string(), visibility);
+            Ok(())
+        })?;
+        Ok(())
+    }
+    
+    /// Who `repo_name` is shared with. Unknown repositories count as public,
+    /// so snippets marked private or team never reach them by accident.
+    pub fn get_repo_visibility(&self, repo_name: &str) -> Visibility {
+        self.repo_visibility.get(repo_name).copied().unwrap_or(Visibility::Public)
+    }
+    
+    pub fn set_repo_branch(&mut self, repo_name: &str, branch: &str) -> Result<()> {
+        *self = Config::update(|config| {
+            config.repo_branches.insert(repo_name.to_string(), branch.to_string());
+            Ok(())
+        })?;
+        Ok(())
+    }
+    
+    /// The branch recorded for `repo_name`, if it was detected or set.
+    pub fn get_repo_branch(&self, repo_name: &str) -> Option<&str> {
+        self.repo_branches.get(repo_name).map(|branch| branch.as_str())
+    }
+    
+    pub fn set_repo_remote(&mut self, repo_name: &str, url: &str) -> Result<()> {
+        *self = Config::update(|config| {
+            config.repo_remotes.insert(repo_name.to_string(), url.to_string());
+            Ok(())
+        })?;
+        Ok(())
+    }
+    
+    /// The account assigned to `repo_name`, if any.
+    pub fn get_repo_account(&self, repo_name: &str) -> Option<&Account> {
+        self.repo_accounts.get(repo_name).and_then(|name| self.accounts.get(name))
+    }
+    
+    pub fn get_templates_source(&self) -> &str {
+        self.templates_source.as_deref().unwrap_or(crate::starters::DEFAULT_TEMPLATES_SOURCE)
     }
     
     pub fn get_default_install_location(&self) -> &str {
-        self.default_install_location.as_deref().unwrap_or("local")
+        self.install.location.as_str()
+    }
+}
+
+fn unknown_setting(key: &str) -> anyhow::Error {
+    anyhow::anyhow!("Unknown setting '{}'. Run 'claude-md-snippets config list-keys' to see them all", key)
+}
+
+/// Parse config.json, migrating older layouts. Also returns the version it
+/// was migrated from, if it needed migrating.
+fn read_config(config_path: &std::path::Path) -> Result<(Config, Option<u32>)> {
+    let content = fs::read_to_string(config_path)?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)?;
+    let old_version = migrate(&mut value);
+    let config: Config = serde_json::from_value(value)?;
+    Ok((config, old_version.filter(|&version| version < SCHEMA_VERSION)))
+}
+
+/// Bring a config.json document up to [`SCHEMA_VERSION`] in place, returning
+/// the version it started at. Files from before versioning count as v1.
+fn migrate(value: &mut serde_json::Value) -> Option<u32> {
+    let object = value.as_object_mut()?;
+    let version = object.get("schema_version").and_then(|v| v.as_u64()).unwrap_or(1) as u32;
+    
+    if version < 2 {
+        // v2 grouped settings: default_install_location became install.location
+        if let Some(location) = object.remove("default_install_location") {
+            if location.is_string() {
+                object.insert("install".to_string(), serde_json::json!({ "location": location }));
+            }
+        }
+    }
+    if version < 3 {
+        // v3 kept a URL per followed repository: the subscriptions list became sources
+        if let Some(serde_json::Value::Array(names)) = object.remove("subscriptions") {
+            let sources: serde_json::Map<String, serde_json::Value> = names.iter()
+                .filter_map(|name| name.as_str())
+                .map(|name| (name.to_string(), serde_json::json!({})))
+                .collect();
+            object.insert("sources".to_string(), serde_json::Value::Object(sources));
+        }
     }
+    if version < 4 {
+        // v4 replaced the sync.auto switch with sync.mode
+        if let Some(sync) = object.get_mut("sync").and_then(|sync| sync.as_object_mut()) {
+            if let Some(serde_json::Value::Bool(auto)) = sync.remove("auto") {
+                let mode = if auto { SyncMode::Auto } else { SyncMode::Manual };
+                sync.insert("mode".to_string(), mode.as_str().into());
+            }
+        }
+    }
+    
+    Some(version)
+}
+
+pub fn get_config_path() -> Result<std::path::PathBuf> {
+    Ok(crate::app_dirs::AppDirs::current()?.config.join("config.json"))
 }
 
-fn get_config_path() -> Result<std::path::PathBuf> {
-    let app_dir = get_app_dir()?;
-    Ok(app_dir.join("config.json"))
+static REPO_OVERRIDE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Treat `repo` as the default repository for the rest of this process, so a
+/// command's `--repo` reaches publishing and syncing.
+pub fn override_default_repo(repo: &str) {
+    if let Ok(mut current) = REPO_OVERRIDE.lock() {
+        *current = Some(repo.to_string());
+    }
 }
 
 pub fn get_default_repo_name() -> Result<String> {
@@ -81,7 +603,7 @@ pub fn get_default_repo_name() -> Result<String> {
             let path = entry.path();
             
             if path.is_dir() {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()).filter(|name| !config.is_subscribed(name)) {
                     // Auto-set this as default and save
                     let mut config = Config::load()?;
                     config.set_default_repo(name.to_string())?;
@@ -93,4 +615,53 @@ pub fn get_default_repo_name() -> Result<String> {
     
     // Ultimate fallback
     Ok("default".to_string())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migrate_v1_config() {
+        let mut value = serde_json::json!({
+            "revision": 7,
+            "default_repo": "team",
+            "default_install_location": "user",
+            "claude": { "timeout_secs": 60 }
+        });
+        assert_eq!(migrate(&mut value), Some(1));
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert_eq!(config.install.location, InstallLocation::User);
+        assert_eq!(config.get("claude.timeout_secs").unwrap(), "60");
+        assert_eq!(config.get("sync.mode").unwrap(), "auto");
+
+        let mut v2 = serde_json::json!({ "schema_version": 2, "subscriptions": ["community", "company"] });
+        assert_eq!(migrate(&mut v2), Some(2));
+        let config: Config = serde_json::from_value(v2).unwrap();
+        assert!(config.is_subscribed("company") && config.sources["community"].url.is_empty());
+
+        let mut v3 = serde_json::json!({ "schema_version": 3, "sync": { "auto": false } });
+        assert_eq!(migrate(&mut v3), Some(3));
+        let config: Config = serde_json::from_value(v3).unwrap();
+        assert_eq!(config.sync.mode, SyncMode::Manual);
+        assert!(config.sync.pull_before_push);
+    }
+
+    #[test]
+    fn test_typed_settings() {
+        let mut config = Config::default();
+        config.apply("output.preview_lines", "10").unwrap();
+        config.apply("limits.max_bytes", "0").unwrap();
+        config.apply("claude.model", "opus").unwrap();
+        assert_eq!(config.get("output.preview_lines").unwrap(), "10");
+        assert_eq!(config.get("limits.max_bytes").unwrap(), "0");
+        assert_eq!(config.get("claude.model").unwrap(), "opus");
+
+        assert!(config.apply("install.location", "elsewhere").is_err());
+        assert!(config.apply("sync.mode", "sometimes").is_err());
+        assert!(config.get("no.such.key").is_err());
+        for (key, _) in SETTINGS {
+            assert!(config.get(key).is_ok(), "{} has no getter", key);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Result;
+use serde::Deserialize;
+use std::path::Path;
+use crate::capabilities::{self, Tool};
+use crate::claude_client::ClaudeClient;
+use crate::install::InstalledSnippet;
+use crate::publish::Snippet;
+
+/// Two instructions that can't both be followed: one in the snippet being
+/// installed, one in a section already in the file.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Conflict {
+    /// Name of the installed section
+    pub section: String,
+    /// What the new snippet says, or why they conflict when only declared
+    pub new: String,
+    /// What the installed section says
+    pub existing: String,
+}
+
+/// Before `snippet` is written into `target_path`: the conflicts with
+/// snippets already there, declared in either one's `conflicts_with`, and
+/// (with `install.conflict_check_ai` on) those Claude Code finds in the text.
+/// `true` when there are none or the user installs anyway.
+pub fn check(snippet: &Snippet, target_path: &Path, existing_content: &str, force: bool) -> Result<bool> {
+    let own_id = crate::ids::short(&snippet.id);
+    let installed: Vec<InstalledSnippet> = crate::install::installed_snippets(target_path, existing_content)?
+        .into_iter()
+        .filter(|installed| installed.id != own_id)
+        .collect();
+    if installed.is_empty() {
+        return Ok(true);
+    }
+
+    let library = crate::store::load_snippets().unwrap_or_default();
+    let mut conflicts = declared(snippet, &installed, &library);
+    if crate::config::Config::load()?.install.conflict_check_ai {
+        match ask_claude(snippet, &installed) {
+            Ok(found) => {
+                for conflict in found {
+                    if !conflicts.contains(&conflict) {
+                        conflicts.push(conflict);
+                    }
+                }
+            }
+            Err(e) => println!("⚠️  Could not check for conflicts with Claude Code: {}", e),
+        }
+    }
+    if conflicts.is_empty() {
+        return Ok(true);
+    }
+
+    println!("⚠️  '{}' contradicts {} section(s) already in {}:", snippet.name, conflicts.len(), target_path.display());
+    for conflict in &conflicts {
+        println!("  • {}", conflict.section);
+        println!("      new:       {}", conflict.new);
+        println!("      installed: {}", conflict.existing);
+    }
+    if force {
+        println!("⚠️  Installing anyway (--force)");
+        return Ok(true);
+    }
+    if !crate::prompt::confirm("Install it anyway?", false)? {
+        println!("❌ Installation skipped; uninstall the conflicting section first, or use --force");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+/// Installed snippets that `snippet` lists in `conflicts_with`, or that list
+/// it in theirs (looked up in `library`).
+fn declared(snippet: &Snippet, installed: &[InstalledSnippet], library: &[Snippet]) -> Vec<Conflict> {
+    let names = |ids: &[String], id: &str| ids.iter().any(|listed| crate::ids::short(listed) == crate::ids::short(id));
+    installed.iter()
+        .filter(|installed| {
+            let theirs = library.iter().find(|other| crate::ids::short(&other.id) == installed.id);
+            names(&snippet.conflicts_with, &installed.id) || theirs.is_some_and(|other| names(&other.conflicts_with, &snippet.id))
+        })
+        .map(|installed| Conflict {
+            section: installed.name.clone(),
+            new: "declared incompatible in conflicts_with".to_string(),
+            existing: first_line(&installed.content),
+        })
+        .collect()
+}
+
+fn first_line(content: &str) -> String {
+    content.lines()
+        .map(|line| line.trim().trim_start_matches('#').trim())
+        .find(|line| !line.is_empty())
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Ask Claude Code which installed sections give instructions the new snippet contradicts.
+fn ask_claude(snippet: &Snippet, installed: &[InstalledSnippet]) -> Result<Vec<Conflict>> {
+    capabilities::require(Tool::Claude, "Checking for conflicting instructions")?;
+    println!("🤖 Checking for contradicting instructions with Claude Code...");
+    let sections: String = installed.iter()
+        .map(|installed| format!("### Section: {}\n{}\n", installed.name, installed.content.trim()))
+        .collect();
+    let prompt = format!(
+        "A CLAUDE.md file already contains the sections below. A new snippet is about to be added. \
+        List only direct contradictions, where following one instruction means breaking the other \
+        (e.g. \"use tabs\" vs \"use spaces\"); overlaps and differences in detail are fine. Return only \
+        a JSON array of objects with \"section\" (the existing section's name), \"new\" (the new \
+        snippet's instruction, quoted) and \"existing\" (the contradicting instruction, quoted); an \
+        empty array when there are none.\n\n## New snippet: {}\n{}\n\n## Existing sections\n{}",
+        snippet.name, snippet.content.trim(), sections
+    );
+    let response = ClaudeClient::from_config()?.prompt(&prompt)?;
+    let json = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => anyhow::bail!("the response had no JSON array"),
+    };
+    Ok(serde_json::from_str(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_conflicts() {
+        let parse = |text: &str| crate::publish::parse_markdown_frontmatter(text).unwrap();
+        let tabs = parse("---\nid: TabsAbCd\nname: Tabs\nconflicts_with: [SpacesXy]\n---\n\nIndent with tabs\n");
+        let spaces = parse("---\nid: SpacesXy\nname: Spaces\n---\n\n# Indentation\nIndent with spaces\n");
+        assert_eq!(tabs.conflicts_with, ["SpacesXy"]);
+
+        let installed = |snippet: &Snippet| InstalledSnippet { id: crate::ids::short(&snippet.id).to_string(), name: snippet.name.clone(), content: snippet.content.clone() };
+        let library = [tabs.clone(), spaces.clone()];
+        let found = declared(&tabs, &[installed(&spaces)], &library);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].existing, "Indentation");
+        // Declared on one side is enough, whichever is installed first
+        assert_eq!(declared(&spaces, &[installed(&tabs)], &library).len(), 1);
+        assert!(declared(&spaces, &[installed(&spaces)], &library).is_empty());
+    }
+}
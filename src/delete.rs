@@ -0,0 +1,146 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::assets;
+use crate::publish::{self, Snippet};
+
+/// Which snippets `repo delete --id/--tag/--older-than` removes. Every given
+/// criterion must match.
+pub struct DeleteFilter {
+    pub id: Option<String>,
+    pub tag: Option<String>,
+    pub older_than: Option<chrono::Duration>,
+}
+
+impl DeleteFilter {
+    fn matches(&self, snippet: &Snippet, now: chrono::DateTime<chrono::Utc>) -> bool {
+        if let Some(prefix) = &self.id {
+            if !snippet.id.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+        if let Some(tag) = &self.tag {
+            if !snippet.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                return false;
+            }
+        }
+        if let Some(age) = self.older_than {
+            // Snippets without a readable date are never considered old
+            match chrono::DateTime::parse_from_rfc3339(&snippet.created_at) {
+                Ok(created) if now.signed_duration_since(created) > age => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+/// Parse an age such as `90d`, `12w`, `1y` or `36h`.
+pub fn parse_age(age: &str) -> Result<chrono::Duration> {
+    let age = age.trim();
+    let split = age.find(|c: char| !c.is_ascii_digit()).unwrap_or(age.len());
+    let (number, unit) = age.split_at(split);
+    let number: i64 = number.parse().with_context(|| format!("Invalid age '{}' (e.g. 90d, 12w, 1y)", age))?;
+    match unit {
+        "m" => Ok(chrono::Duration::minutes(number)),
+        "h" => Ok(chrono::Duration::hours(number)),
+        "d" | "" => Ok(chrono::Duration::days(number)),
+        "w" => Ok(chrono::Duration::weeks(number)),
+        "y" => Ok(chrono::Duration::days(number * 365)),
+        _ => anyhow::bail!("Unknown unit '{}' in '{}' (use m, h, d, w or y)", unit, age),
+    }
+}
+
+/// Move every snippet in `repo_dir` matching `filter` to the trash, after
+/// listing the files and asking once, then sync them away in a single commit.
+pub async fn delete_matching(repo_dir: &Path, repo_name: &str, filter: DeleteFilter) -> Result<()> {
+    if let Some(prefix) = &filter.id {
+        if prefix.len() < 4 {
+            anyhow::bail!("--id needs at least 4 characters of the snippet ID");
+        }
+    }
+
+    let snippets_dir = repo_dir.join("snippets");
+    let now = chrono::Utc::now();
+    let all = snippet_files(&snippets_dir)?;
+    let short_ids = crate::ids::ShortIds::new(all.iter().map(|(_, snippet)| snippet.id.as_str()));
+    let mut doomed: Vec<(PathBuf, Snippet)> = all
+        .into_iter()
+        .filter(|(_, snippet)| filter.matches(snippet, now))
+        .collect();
+    doomed.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if doomed.is_empty() {
+        println!("ℹ️  No snippets in '{}' match", repo_name);
+        return Ok(());
+    }
+
+    println!("🗑️  Will delete from repository '{}':", repo_name);
+    for (path, snippet) in &doomed {
+        let created = snippet.created_at.get(..10).unwrap_or(&snippet.created_at);
+        println!("  • {} ({}, created {})", snippet.name, short_ids.get(&snippet.id), created);
+        println!("    📁 {}", path.display());
+        let assets_dir = assets::assets_dir(&snippets_dir, &snippet.id);
+        if assets_dir.exists() {
+            println!("    📎 {}/", assets_dir.display());
+        }
+    }
+    if !crate::prompt::confirm(&format!("❓ Delete these {} snippet(s)?", doomed.len()), false)? {
+        println!("❌ Deletion cancelled");
+        return Ok(());
+    }
+
+    for (path, snippet) in &doomed {
+        crate::trash::move_to_trash(&snippets_dir, path, Some(snippet))?;
+    }
+    println!("✅ Deleted {} snippet(s); 'repo restore' brings one back from the trash", doomed.len());
+
+    let message = match doomed.as_slice() {
+        [(_, snippet)] => format!("Delete snippet '{}' (ID: {})", snippet.name, short_ids.get(&snippet.id)),
+        _ => format!("Delete {} snippets", doomed.len()),
+    };
+    crate::github::sync_after_change(&message, crate::github::ChangeKind::Edit).await;
+
+    Ok(())
+}
+
+/// Every parseable snippet file in `snippets_dir` with its path.
+fn snippet_files(snippets_dir: &Path) -> Result<Vec<(PathBuf, Snippet)>> {
+    let mut files = Vec::new();
+    for path in crate::scan::snippet_files(snippets_dir) {
+        if let Ok(snippet) = publish::parse_markdown_frontmatter(&fs::read_to_string(&path)?) {
+            files.push((path, snippet));
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_filter() {
+        assert_eq!(parse_age("90d").unwrap(), chrono::Duration::days(90));
+        assert_eq!(parse_age("2w").unwrap(), chrono::Duration::days(14));
+        assert!(parse_age("3 months").is_err());
+
+        let now = chrono::DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+        let snippet = Snippet {
+            id: "1a2b3c4d-0000".to_string(),
+            name: "Old rule".to_string(),
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            tags: vec!["Legacy".to_string()],
+            ..Default::default()
+        };
+        let filter = |id: Option<&str>, tag: Option<&str>, days: Option<i64>| DeleteFilter {
+            id: id.map(String::from),
+            tag: tag.map(String::from),
+            older_than: days.map(chrono::Duration::days),
+        };
+        assert!(filter(Some("1a2b"), None, None).matches(&snippet, now));
+        assert!(filter(None, Some("legacy"), Some(90)).matches(&snippet, now));
+        assert!(!filter(None, Some("legacy"), Some(200)).matches(&snippet, now));
+        assert!(!filter(Some("ffff"), Some("legacy"), None).matches(&snippet, now));
+    }
+}
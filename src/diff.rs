@@ -0,0 +1,367 @@
+use anyhow::Result;
+use std::fs;
+use std::io::IsTerminal;
+use crate::install::{self, Destination};
+
+/// Unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+/// Terminal width assumed for side-by-side output when `COLUMNS` isn't set.
+const DEFAULT_WIDTH: usize = 120;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+// Words that changed within a changed line
+const RED_WORD: &str = "\x1b[1;30;41m";
+const GREEN_WORD: &str = "\x1b[1;30;42m";
+
+/// One line of a line-by-line diff.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Change<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// How `render` lays out a diff.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Layout {
+    /// `-`/`+` lines, like `git diff`
+    Unified,
+    /// Old on the left, new on the right, like `diff -y`
+    SideBySide,
+}
+
+/// `diff`: show how the installed copy of a snippet differs from the one in
+/// the repository, to decide between updating it and keeping local edits.
+pub async fn diff(query: &str, destination: Destination, layout: Layout) -> Result<()> {
+    let target_path = destination.resolve()?;
+    let target_name = target_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    let Ok(content) = fs::read_to_string(&target_path) else {
+        anyhow::bail!("{} not found", target_path.display());
+    };
+    let installed = install::installed_snippets(&target_path, &content)?;
+    let snippets = crate::store::load_snippets()?;
+
+    // An installed ID is taken as-is, anything else is matched against the repository
+    let block = match installed.iter().find(|i| query.len() >= 4 && i.id.starts_with(query)) {
+        Some(block) => block,
+        None => {
+            let Some(snippet) = install::find_best_match(&snippets, query).await? else {
+                anyhow::bail!("No snippet matches '{}'", query);
+            };
+            let id = crate::ids::short(&snippet.id);
+            installed.iter().find(|i| i.id == id)
+                .ok_or_else(|| anyhow::anyhow!("'{}' is not installed in {}", snippet.name, target_name))?
+        }
+    };
+    let Some(snippet) = snippets.iter().find(|s| s.id.starts_with(&block.id)) else {
+        anyhow::bail!("'{}' ({}) is not in the default repository; nothing to compare with", block.name, block.id);
+    };
+
+    let changes = lines(&block.content, snippet.content.trim());
+    if changes.iter().all(|change| matches!(change, Change::Same(_))) {
+        println!("✅ '{}' in {} matches the repository", snippet.name, target_name);
+        return Ok(());
+    }
+
+    let color = std::io::stdout().is_terminal();
+    println!("{}", paint(color, RED, &format!("--- {} ({})", target_name, block.id)));
+    println!("{}", paint(color, GREEN, &format!("+++ repository: {}", snippet.name)));
+    for line in render(&changes, layout, color, terminal_width()) {
+        println!("{}", line);
+    }
+
+    println!();
+    if !snippet.variables.is_empty() {
+        println!("ℹ️  The snippet has variables; placeholders filled in at install time show up as changes");
+    }
+    println!("💡 Take the repository version: claude-md-snippets install \"{}\" --force", snippet.name);
+    println!("💡 Or carry your edits over to the repository: claude-md-snippets repo edit \"{}\"", snippet.name);
+    Ok(())
+}
+
+/// Print the changes from `old` to `new`, in color when stdout is a terminal.
+/// Prints nothing when they are the same.
+pub fn print(old: &str, new: &str, layout: Layout) {
+    for line in render(&lines(old, new), layout, std::io::stdout().is_terminal(), terminal_width()) {
+        println!("{}", line);
+    }
+}
+
+/// `changes` as hunks (without the `---`/`+++` header) laid out per `layout`, `width` columns wide for a side
+/// by side diff. With `color`, changed lines are red and green and the words
+/// that changed within them are highlighted.
+pub fn render(changes: &[Change], layout: Layout, color: bool, width: usize) -> Vec<String> {
+    let column = (width.saturating_sub(3) / 2).max(20);
+    let mut out = Vec::new();
+    for (start, end) in hunks(changes) {
+        out.push(paint(color, CYAN, &hunk_header(changes, start, end)));
+        let rows = rows(&changes[start..end]);
+        match layout {
+            Layout::Unified => {
+                // Like git, each run of changed lines lists the old lines, then the new ones
+                let mut i = 0;
+                while i < rows.len() {
+                    if let Row::Same(line) = rows[i] {
+                        out.push(format!(" {}", line));
+                        i += 1;
+                        continue;
+                    }
+                    let run: Vec<&Row> = rows[i..].iter().take_while(|row| matches!(row, Row::Changed(..))).collect();
+                    i += run.len();
+                    for row in &run {
+                        if let Row::Changed(Some(old), _) = row {
+                            out.push(paint(color, RED, &format!("-{}", styled(old, RED, RED_WORD, color, None))));
+                        }
+                    }
+                    for row in &run {
+                        if let Row::Changed(_, Some(new)) = row {
+                            out.push(paint(color, GREEN, &format!("+{}", styled(new, GREEN, GREEN_WORD, color, None))));
+                        }
+                    }
+                }
+            }
+            Layout::SideBySide => {
+                for row in rows {
+                    out.push(match row {
+                        Row::Same(line) => {
+                            let cell = styled(&[(line, false)], "", "", false, Some(column));
+                            format!("{}   {}", cell, cell).trim_end().to_string()
+                        }
+                        Row::Changed(old, new) => {
+                            let mark = match (&old, &new) {
+                                (Some(_), Some(_)) => "|",
+                                (Some(_), None) => "<",
+                                _ => ">",
+                            };
+                            let left = match &old {
+                                Some(old) => paint(color, RED, &styled(old, RED, RED_WORD, color, Some(column))),
+                                None => " ".repeat(column),
+                            };
+                            let right = new.map(|new| paint(color, GREEN, &styled(&new, GREEN, GREEN_WORD, color, Some(column))));
+                            format!("{} {} {}", left, mark, right.unwrap_or_default()).trim_end().to_string()
+                        }
+                    });
+                }
+            }
+        }
+    }
+    out
+}
+
+/// A line split into words, each flagged `true` when it changed.
+type Words<'a> = Vec<(&'a str, bool)>;
+
+/// A line of output: unchanged, or an old and/or new line. A removed line is
+/// paired with the added line in the same position of the change.
+enum Row<'a> {
+    Same(&'a str),
+    Changed(Option<Words<'a>>, Option<Words<'a>>),
+}
+
+fn rows<'a>(changes: &[Change<'a>]) -> Vec<Row<'a>> {
+    let mut rows = Vec::new();
+    let mut i = 0;
+    while i < changes.len() {
+        if let Change::Same(line) = changes[i] {
+            rows.push(Row::Same(line));
+            i += 1;
+            continue;
+        }
+        let removed: Vec<&str> = changes[i..].iter().map_while(|c| match c { Change::Removed(l) => Some(*l), _ => None }).collect();
+        i += removed.len();
+        let added: Vec<&str> = changes[i..].iter().map_while(|c| match c { Change::Added(l) => Some(*l), _ => None }).collect();
+        i += added.len();
+        for n in 0..removed.len().max(added.len()) {
+            rows.push(match (removed.get(n), added.get(n)) {
+                (Some(old), Some(new)) => {
+                    let (old, new) = changed_words(old, new);
+                    Row::Changed(Some(old), Some(new))
+                }
+                (old, new) => Row::Changed(old.map(|old| vec![(*old, false)]), new.map(|new| vec![(*new, false)])),
+            });
+        }
+    }
+    rows
+}
+
+/// The words of `old` and of `new`, each flagged when the other line doesn't share it.
+fn changed_words<'a>(old: &'a str, new: &'a str) -> (Words<'a>, Words<'a>) {
+    let (mut old_words, mut new_words) = (Vec::new(), Vec::new());
+    for change in sequence(&words(old), &words(new)) {
+        match change {
+            Change::Same(word) => {
+                old_words.push((word, false));
+                new_words.push((word, false));
+            }
+            Change::Removed(word) => old_words.push((word, true)),
+            Change::Added(word) => new_words.push((word, true)),
+        }
+    }
+    (old_words, new_words)
+}
+
+/// `line` split into runs of word characters, runs of whitespace and single punctuation marks.
+fn words(line: &str) -> Vec<&str> {
+    let kind = |c: char| if c.is_alphanumeric() || c == '_' { 0 } else if c.is_whitespace() { 1 } else { 2 };
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut previous = None;
+    for (i, c) in line.char_indices() {
+        if previous.is_some_and(|previous| previous != kind(c) || kind(c) == 2) {
+            words.push(&line[start..i]);
+            start = i;
+        }
+        previous = Some(kind(c));
+    }
+    if start < line.len() {
+        words.push(&line[start..]);
+    }
+    words
+}
+
+/// `words` joined back into a line, the flagged ones in `highlight` and the
+/// rest in `base`. Cut to `width` characters (ending in `…`) and padded to
+/// it when a width is given.
+fn styled(words: &[(&str, bool)], base: &str, highlight: &str, color: bool, width: Option<usize>) -> String {
+    let length: usize = words.iter().map(|(word, _)| word.chars().count()).sum();
+    let cut = width.filter(|&width| length > width);
+    let mut room = cut.map_or(length, |width| width.saturating_sub(1));
+    let mut out = String::new();
+    let mut highlighted = false;
+    for (word, changed) in words {
+        // Neighbouring changed words share one highlight
+        if color && *changed != highlighted {
+            out.push_str(if *changed { highlight } else { RESET });
+            if !*changed {
+                out.push_str(base);
+            }
+            highlighted = *changed;
+        }
+        let text: String = word.chars().take(room).collect();
+        room -= text.chars().count();
+        out.push_str(&text);
+    }
+    if highlighted {
+        out.push_str(RESET);
+        out.push_str(base);
+    }
+    if cut.is_some() {
+        out.push('…');
+    }
+    if let Some(width) = width {
+        out.push_str(&" ".repeat(width.saturating_sub(length)));
+    }
+    out
+}
+
+fn paint(color: bool, code: &str, text: &str) -> String {
+    if color { format!("{}{}{}", code, text, RESET) } else { text.to_string() }
+}
+
+fn terminal_width() -> usize {
+    std::env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()).unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Line-by-line diff of `old` and `new`, from their longest common subsequence.
+pub fn lines<'a>(old: &'a str, new: &'a str) -> Vec<Change<'a>> {
+    sequence(&old.lines().collect::<Vec<_>>(), &new.lines().collect::<Vec<_>>())
+}
+
+/// Diff of two sequences of lines (or words).
+fn sequence<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Change<'a>> {
+    // common[i][j]: length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut changes = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            changes.push(Change::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            changes.push(Change::Removed(old[i]));
+            i += 1;
+        } else {
+            changes.push(Change::Added(new[j]));
+            j += 1;
+        }
+    }
+    changes
+}
+
+/// Ranges of `changes` to show: each change with `CONTEXT` lines around it,
+/// merged where they overlap.
+fn hunks(changes: &[Change]) -> Vec<(usize, usize)> {
+    let changed: Vec<usize> = changes.iter().enumerate()
+        .filter(|(_, change)| !matches!(change, Change::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    // Group changes whose context overlaps into one hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &i in &changed {
+        let start = i.saturating_sub(CONTEXT);
+        let end = (i + CONTEXT + 1).min(changes.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+    hunks
+}
+
+fn hunk_header(changes: &[Change], start: usize, end: usize) -> String {
+    // Line numbers where the hunk starts, on each side
+    let old_start = changes[..start].iter().filter(|c| !matches!(c, Change::Added(_))).count();
+    let new_start = changes[..start].iter().filter(|c| !matches!(c, Change::Removed(_))).count();
+    let hunk = &changes[start..end];
+    let old_len = hunk.iter().filter(|c| !matches!(c, Change::Added(_))).count();
+    let new_len = hunk.iter().filter(|c| !matches!(c, Change::Removed(_))).count();
+    format!("@@ -{},{} +{},{} @@", old_start + 1, old_len, new_start + 1, new_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj";
+        let new = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk";
+        let changes = lines(old, new);
+        assert_eq!(changes[1..3], [Change::Removed("b"), Change::Added("B")]);
+        assert_eq!(render(&changes, Layout::Unified, false, 80), [
+            "@@ -1,5 +1,5 @@", " a", "-b", "+B", " c", " d", " e",
+            "@@ -8,3 +8,4 @@", " h", " i", " j", "+k",
+        ]);
+        assert!(render(&lines("same", "same"), Layout::Unified, false, 80).is_empty());
+    }
+
+    #[test]
+    fn test_side_by_side_and_changed_words() {
+        let changes = lines("keep\nuse tabs\ngone", "keep\nuse spaces\n");
+        assert_eq!(render(&changes, Layout::SideBySide, false, 43), [
+            "@@ -1,3 +1,2 @@",
+            "keep                   keep",
+            "use tabs             | use spaces",
+            "gone                 <",
+        ]);
+        assert_eq!(changed_words("use tabs, always", "use spaces, always").1, [
+            ("use", false), (" ", false), ("spaces", true), (",", false), (" ", false), ("always", false),
+        ]);
+        assert_eq!(styled(&[("a long line", false)], "", "", false, Some(6)), "a lon…");
+    }
+}
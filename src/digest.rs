@@ -0,0 +1,226 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::journal::Journal;
+use crate::manifest::Manifest;
+use crate::perf::{Category, TimedCommand};
+use crate::publish::get_repos_dir;
+use crate::report::ReportFormat;
+
+/// What happened to one snippet file over the digest period.
+#[derive(Debug, PartialEq)]
+enum Change {
+    Added,
+    Updated,
+    Removed,
+}
+
+/// A snippet file changed in the period, with who changed it and when.
+#[derive(Debug, PartialEq)]
+struct FileChange {
+    file: String,
+    change: Change,
+    commits: usize,
+    authors: Vec<String>,
+    /// Date of the latest change, `YYYY-MM-DD`
+    last: String,
+}
+
+/// `digest`: new, updated and removed snippets in every repository over the
+/// last `since`, from their git history, plus what was installed on this
+/// machine (from the install manifest) and changes not synced yet. Written to
+/// `file`, or printed.
+pub fn generate(since: chrono::Duration, format: ReportFormat, file: Option<PathBuf>) -> Result<()> {
+    let start = chrono::Utc::now() - since;
+    let markdown = build_markdown(start)?;
+    let digest = match format {
+        ReportFormat::Markdown => markdown,
+        ReportFormat::Html => crate::report::to_html(&markdown),
+    };
+
+    match file {
+        Some(path) => {
+            fs::write(&path, digest)?;
+            println!("✅ Digest written to {}", path.display());
+        }
+        None => print!("{}", digest),
+    }
+    Ok(())
+}
+
+fn build_markdown(start: chrono::DateTime<chrono::Utc>) -> Result<String> {
+    let mut out = String::new();
+    out.push_str(&format!("# Snippet digest {} to {}\n\n",
+        start.with_timezone(&chrono::Local).format("%Y-%m-%d"), chrono::Local::now().format("%Y-%m-%d")));
+
+    let mut repos: Vec<PathBuf> = match fs::read_dir(get_repos_dir()?) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.join(".git").exists()).collect(),
+        Err(_) => Vec::new(),
+    };
+    repos.sort();
+    if repos.is_empty() {
+        out.push_str("No repositories set up yet.\n\n");
+    }
+    for repo in &repos {
+        let name = repo.file_name().unwrap_or_default().to_string_lossy();
+        out.push_str(&format!("## {}\n\n", name));
+        let changes = snippet_changes(repo, start)?;
+        if changes.is_empty() {
+            out.push_str("No snippet changes.\n\n");
+            continue;
+        }
+        for (change, heading) in [(Change::Added, "New"), (Change::Updated, "Updated"), (Change::Removed, "Removed")] {
+            let matching: Vec<&FileChange> = changes.iter().filter(|c| c.change == change).collect();
+            if matching.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("### {}\n\n", heading));
+            for file_change in matching {
+                out.push_str(&format!("- {}\n", describe(repo, file_change)));
+            }
+            out.push('\n');
+        }
+    }
+
+    out.push_str("## Installed on this machine\n\n");
+    let installs: Vec<_> = Manifest::load()?
+        .installs
+        .into_iter()
+        .filter(|record| chrono::DateTime::parse_from_rfc3339(&record.installed_at).is_ok_and(|at| at >= start))
+        .collect();
+    if installs.is_empty() {
+        out.push_str("Nothing installed in this period.\n\n");
+    } else {
+        for record in &installs {
+            out.push_str(&format!("- {} into `{}` ({})\n",
+                record.name, record.target, record.installed_at.get(..10).unwrap_or(&record.installed_at)));
+        }
+        out.push('\n');
+    }
+
+    let queued = Journal::load()?.pending;
+    if !queued.is_empty() {
+        out.push_str("## Not synced yet\n\n");
+        for change in &queued {
+            out.push_str(&format!("- [{}] {}\n", change.repo, change.description));
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// One digest line: the snippet's name and description where the file still
+/// exists, with who changed it.
+fn describe(repo_dir: &Path, change: &FileChange) -> String {
+    let snippet = fs::read_to_string(repo_dir.join(&change.file))
+        .ok()
+        .and_then(|content| crate::publish::parse_markdown_frontmatter(&content).ok());
+    let title = match &snippet {
+        Some(snippet) => format!("**{}**", snippet.name),
+        None => format!("`{}`", change.file),
+    };
+    let description = snippet
+        .and_then(|snippet| snippet.description)
+        .map(|description| format!(": {}", description))
+        .unwrap_or_default();
+    let edits = if change.change == Change::Updated && change.commits > 1 {
+        format!(", {} commits", change.commits)
+    } else {
+        String::new()
+    };
+    format!("{}{} ({}, {}{})", title, description, change.authors.join(", "), change.last, edits)
+}
+
+fn snippet_changes(repo_dir: &Path, start: chrono::DateTime<chrono::Utc>) -> Result<Vec<FileChange>> {
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["log", "--reverse", &format!("--since={}", start.to_rfc3339()), "--format=%x1e%an%x1f%aI", "--name-status", "--", "snippets/"])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        // A repository without commits yet has no history
+        return Ok(Vec::new());
+    }
+    Ok(parse_changes(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Fold `git log --reverse --format=%x1e%an%x1f%aI --name-status` into one
+/// change per snippet file: added in the period (even if edited since),
+/// updated, or removed. A renamed file counts as updated under its new name.
+fn parse_changes(log: &str) -> Vec<FileChange> {
+    let mut changes: BTreeMap<String, FileChange> = BTreeMap::new();
+    for entry in log.split('\x1e') {
+        let mut lines = entry.lines();
+        let Some((author, date)) = lines.next().and_then(|line| line.split_once('\x1f')) else { continue };
+        for line in lines {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let (status, file) = match fields.as_slice() {
+                [status, file] => (*status, *file),
+                [status, old, new] if status.starts_with('R') => {
+                    let earlier = changes.remove(*old);
+                    changes.insert(new.to_string(), FileChange {
+                        file: new.to_string(),
+                        change: match earlier.as_ref().map(|c| &c.change) { Some(Change::Added) => Change::Added, _ => Change::Updated },
+                        commits: earlier.as_ref().map_or(0, |c| c.commits),
+                        authors: earlier.map(|c| c.authors).unwrap_or_default(),
+                        last: String::new(),
+                    });
+                    ("M", *new)
+                }
+                _ => continue,
+            };
+            if !file.ends_with(".md") {
+                continue;
+            }
+
+            let next = match (status, changes.get(file).map(|c| &c.change)) {
+                ("D", Some(Change::Added)) => {
+                    // Added and removed within the period: nothing to report
+                    changes.remove(file);
+                    continue;
+                }
+                ("D", _) => Change::Removed,
+                ("A", None) | (_, Some(Change::Added)) => Change::Added,
+                _ => Change::Updated,
+            };
+            let change = changes.entry(file.to_string()).or_insert_with(|| FileChange {
+                file: file.to_string(),
+                change: Change::Updated,
+                commits: 0,
+                authors: Vec::new(),
+                last: String::new(),
+            });
+            change.change = next;
+            change.commits += 1;
+            if !change.authors.iter().any(|a| a == author) {
+                change.authors.push(author.to_string());
+            }
+            change.last = date.get(..10).unwrap_or(date).to_string();
+        }
+    }
+    changes.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_changes() {
+        let log = "\x1eAda\x1f2024-03-01T10:00:00+00:00\n\nA\tsnippets/new.md\nM\tsnippets/old.md\nD\tsnippets/gone.md\nA\tsnippets/brief.md\n\
+                   \x1eBob\x1f2024-03-02T10:00:00+00:00\n\nM\tsnippets/new.md\nM\tsnippets/old.md\nD\tsnippets/brief.md\n\
+                   \x1eBob\x1f2024-03-03T10:00:00+00:00\n\nR100\tsnippets/old.md\tsnippets/go/old.md\nM\tcollection.yaml\n";
+        let changes = parse_changes(log);
+        let summary: Vec<(&str, &Change, usize, String)> = changes
+            .iter()
+            .map(|c| (c.file.as_str(), &c.change, c.commits, c.authors.join(",")))
+            .collect();
+        assert_eq!(summary, vec![
+            ("snippets/go/old.md", &Change::Updated, 3, "Ada,Bob".to_string()),
+            ("snippets/gone.md", &Change::Removed, 1, "Ada".to_string()),
+            ("snippets/new.md", &Change::Added, 2, "Ada,Bob".to_string()),
+        ]);
+        assert_eq!(changes[0].last, "2024-03-03");
+    }
+}
@@ -0,0 +1,238 @@
+use anyhow::Result;
+use std::collections::HashSet;
+use std::fs;
+use crate::install::Destination;
+use crate::manifest::{Manifest, content_hash};
+
+const START_PREFIX: &str = "<!-- SNIPPET_START:";
+const END_PREFIX: &str = "<!-- SNIPPET_END:";
+const INSTALL_HEADER_SUFFIX: &str = " (installed snippet)";
+
+/// Something wrong in a target file, located by its (0-based) lines.
+#[derive(Debug, PartialEq)]
+enum Problem {
+    /// A second copy of a block: the same ID, or the same content as `of`
+    Duplicate { id: String, of: String, first: usize, last: usize },
+    /// A start marker without its end marker, or the other way round
+    OrphanedMarker { line: usize, text: String },
+    /// `#Heading` (no space), or a heading with no text; `fixed` is `None` to drop the line
+    BrokenHeading { line: usize, text: String, fixed: Option<String> },
+    /// The header `install` adds above a block, left behind after the block was removed
+    LeftoverHeader { line: usize, text: String },
+    /// Runs of more than one blank line
+    BlankLines { runs: usize },
+}
+
+impl Problem {
+    fn describe(&self) -> String {
+        match self {
+            Problem::Duplicate { id, of, first, .. } if id == of => format!("line {}: snippet {} is installed twice", first + 1, id),
+            Problem::Duplicate { id, of, first, .. } => format!("line {}: snippet {} has the same content as snippet {}", first + 1, id, of),
+            Problem::OrphanedMarker { line, text } => format!("line {}: orphaned marker {}", line + 1, text),
+            Problem::BrokenHeading { line, text, fixed: Some(fixed) } => format!("line {}: broken heading '{}' (should be '{}')", line + 1, text, fixed),
+            Problem::BrokenHeading { line, text, fixed: None } => format!("line {}: empty heading '{}'", line + 1, text),
+            Problem::LeftoverHeader { line, text } => format!("line {}: '{}' has no snippet below it", line + 1, text),
+            Problem::BlankLines { runs } => format!("{} place(s) with more than one blank line in a row", runs),
+        }
+    }
+}
+
+/// `doctor`: check the target file for duplicate snippet blocks, orphaned
+/// markers, broken headings and runs of blank lines, and fix the ones the
+/// user agrees to (all of them with `fix`).
+pub fn doctor(destination: Destination, fix: bool) -> Result<()> {
+    let path = destination.resolve()?;
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => {
+            println!("ℹ️  {} does not exist; nothing to check", path.display());
+            return Ok(());
+        }
+    };
+
+    println!("🩺 Checking {}", path.display());
+    let problems = diagnose(&content);
+    if problems.is_empty() {
+        println!("✅ No problems found");
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("  • {}", problem.describe());
+    }
+
+    let mut chosen = Vec::new();
+    for problem in &problems {
+        if fix || crate::prompt::confirm(&format!("Fix: {}?", problem.describe()), true)? {
+            chosen.push(problem);
+        }
+    }
+    if chosen.is_empty() {
+        println!("❌ Left unchanged");
+        return Ok(());
+    }
+
+    fs::write(&path, apply(&content, &chosen))?;
+    let absolute_path = path.canonicalize().unwrap_or(path.clone());
+    for problem in &chosen {
+        // A copy installed under another ID is gone, so is its install record
+        if let Problem::Duplicate { id, of, .. } = problem {
+            if id != of {
+                Manifest::record_uninstall(id, &absolute_path)?;
+            }
+        }
+    }
+    println!("✅ Fixed {} of {} problem(s) in {}", chosen.len(), problems.len(), path.display());
+    Ok(())
+}
+
+fn diagnose(content: &str) -> Vec<Problem> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut problems = Vec::new();
+
+    // Snippet blocks, in order, and markers that don't pair up
+    let mut in_block = vec![false; lines.len()];
+    let mut seen: Vec<(String, String)> = Vec::new();
+    let mut paired: HashSet<usize> = HashSet::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(id) = marker_id(lines[i], START_PREFIX) else {
+            i += 1;
+            continue;
+        };
+        let end = (i + 1..lines.len())
+            .take_while(|&j| marker_id(lines[j], START_PREFIX).is_none_or(|other| other != id))
+            .find(|&j| marker_id(lines[j], END_PREFIX) == Some(id));
+        let Some(end) = end else {
+            i += 1;
+            continue;
+        };
+        paired.extend([i, end]);
+        in_block[i..=end].iter_mut().for_each(|line| *line = true);
+
+        let hash = content_hash(&lines[i + 1..end].join("\n"));
+        match seen.iter().find(|(other, other_hash)| other == id || *other_hash == hash) {
+            Some((of, _)) => problems.push(Problem::Duplicate { id: id.to_string(), of: of.clone(), first: i, last: end }),
+            None => seen.push((id.to_string(), hash)),
+        }
+        i = end + 1;
+    }
+    for (line, text) in lines.iter().enumerate() {
+        if !paired.contains(&line) && (marker_id(text, START_PREFIX).is_some() || marker_id(text, END_PREFIX).is_some()) {
+            problems.push(Problem::OrphanedMarker { line, text: text.trim().to_string() });
+        }
+    }
+
+    // Headings and blank lines outside code fences and snippet blocks
+    let mut in_fence = false;
+    let mut blank_run = 0;
+    let mut runs = 0;
+    for (line, text) in lines.iter().enumerate() {
+        if text.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if in_fence || in_block[line] {
+            blank_run = 0;
+            continue;
+        }
+        if text.trim().is_empty() {
+            blank_run += 1;
+            if blank_run == 2 {
+                runs += 1;
+            }
+            continue;
+        }
+        blank_run = 0;
+
+        if let Some(fixed) = broken_heading(text) {
+            problems.push(Problem::BrokenHeading { line, text: text.to_string(), fixed });
+        } else if text.starts_with("# ") && text.ends_with(INSTALL_HEADER_SUFFIX) {
+            let next = lines[line + 1..].iter().find(|next| !next.trim().is_empty());
+            if next.is_none_or(|next| marker_id(next, START_PREFIX).is_none()) {
+                problems.push(Problem::LeftoverHeader { line, text: text.to_string() });
+            }
+        }
+    }
+    if runs > 0 {
+        problems.push(Problem::BlankLines { runs });
+    }
+    problems
+}
+
+/// `content` with the chosen problems fixed. Blank lines are collapsed
+/// whenever something is removed, so removals don't leave gaps behind.
+fn apply(content: &str, problems: &[&Problem]) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut replaced: Vec<Option<String>> = lines.iter().map(|line| Some(line.to_string())).collect();
+    for problem in problems {
+        match problem {
+            Problem::Duplicate { first, last, .. } => replaced[*first..=*last].iter_mut().for_each(|line| *line = None),
+            Problem::OrphanedMarker { line, .. } | Problem::LeftoverHeader { line, .. } => replaced[*line] = None,
+            Problem::BrokenHeading { line, fixed, .. } => replaced[*line] = fixed.clone(),
+            Problem::BlankLines { .. } => {}
+        }
+    }
+    collapse_blank_lines(&replaced.into_iter().flatten().collect::<Vec<_>>())
+}
+
+fn collapse_blank_lines(lines: &[String]) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        let blank = line.trim().is_empty();
+        if !in_fence && blank && out.last().is_none_or(|last| last.trim().is_empty()) {
+            continue;
+        }
+        out.push(line);
+    }
+    while out.last().is_some_and(|last| last.trim().is_empty()) {
+        out.pop();
+    }
+    if out.is_empty() { String::new() } else { format!("{}\n", out.join("\n")) }
+}
+
+fn marker_id<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.trim().strip_prefix(prefix)?.strip_suffix("-->").map(str::trim)
+}
+
+/// The fixed form of a broken heading line (`None` to drop an empty one), or
+/// `None` when `line` is fine or not a heading.
+fn broken_heading(line: &str) -> Option<Option<String>> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    if hashes == 0 || hashes > 6 {
+        return None;
+    }
+    let rest = &line[hashes..];
+    if rest.trim().is_empty() {
+        return Some(None);
+    }
+    // "#!" shebangs and "#123"-style references aren't meant as headings
+    if rest.starts_with([' ', '\t', '!']) || rest.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(Some(format!("{} {}", &line[..hashes], rest.trim())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_and_fix() {
+        let content = "# Project\n\n\n\n##Testing\n\n<!-- SNIPPET_START:1a2b -->\nRun tests\n<!-- SNIPPET_END:1a2b -->\n\n<!-- SNIPPET_START:9f8e -->\nrun  TESTS\n<!-- SNIPPET_END:9f8e -->\n\n# Old (installed snippet)\n\n<!-- SNIPPET_END:5e6f -->\n";
+        let problems = diagnose(content);
+        assert_eq!(problems, vec![
+            Problem::Duplicate { id: "9f8e".to_string(), of: "1a2b".to_string(), first: 10, last: 12 },
+            Problem::OrphanedMarker { line: 16, text: "<!-- SNIPPET_END:5e6f -->".to_string() },
+            Problem::BrokenHeading { line: 4, text: "##Testing".to_string(), fixed: Some("## Testing".to_string()) },
+            Problem::LeftoverHeader { line: 14, text: "# Old (installed snippet)".to_string() },
+            Problem::BlankLines { runs: 1 },
+        ]);
+
+        let fixed = apply(content, &problems.iter().collect::<Vec<_>>());
+        assert_eq!(fixed, "# Project\n\n## Testing\n\n<!-- SNIPPET_START:1a2b -->\nRun tests\n<!-- SNIPPET_END:1a2b -->\n");
+        assert!(diagnose(&fixed).is_empty());
+    }
+}
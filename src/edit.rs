@@ -0,0 +1,175 @@
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::editor;
+use crate::github::ChangeKind;
+use crate::publish::{self, Snippet};
+use crate::visibility::Visibility;
+
+/// Frontmatter changes requested with `repo edit --description/--add-tag/--remove-tag/--visibility`.
+#[derive(Default)]
+pub struct MetadataChanges {
+    /// New description; an empty one removes it
+    pub description: Option<String>,
+    pub add_tags: Vec<String>,
+    pub remove_tags: Vec<String>,
+    pub visibility: Option<Visibility>,
+}
+
+impl MetadataChanges {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none() && self.add_tags.is_empty() && self.remove_tags.is_empty() && self.visibility.is_none()
+    }
+
+    fn apply(&self, snippet: &mut Snippet) {
+        if let Some(description) = &self.description {
+            let description = description.trim();
+            snippet.description = (!description.is_empty()).then(|| description.to_string());
+        }
+        for tag in &self.add_tags {
+            let tag = tag.trim().to_lowercase();
+            if !tag.is_empty() && !snippet.tags.contains(&tag) {
+                snippet.tags.push(tag);
+            }
+        }
+        snippet.tags.retain(|tag| !self.remove_tags.iter().any(|removed| removed.trim().eq_ignore_ascii_case(tag)));
+        if self.visibility.is_some() {
+            snippet.visibility = self.visibility;
+        }
+    }
+}
+
+/// `repo rename`: give the snippet in `file` a new name and move the file to match.
+pub async fn rename_snippet(file: &Path, repo_name: &str, new_name: &str) -> Result<()> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        anyhow::bail!("The new name can't be empty");
+    }
+    let mut snippet = publish::parse_markdown_frontmatter(&fs::read_to_string(file)?)?;
+    if snippet.name == new_name {
+        println!("ℹ️  The snippet is already called '{}'", new_name);
+        return Ok(());
+    }
+
+    let old_name = std::mem::replace(&mut snippet.name, new_name.to_string());
+    fs::write(file, publish::create_markdown_with_frontmatter(&snippet)?)?;
+    let path = match_filename(file, &snippet)?;
+    println!("✅ Renamed '{}' to '{}'", old_name, snippet.name);
+    println!("📁 {}", path.display());
+
+    finish(repo_name, &format!("Rename snippet '{}' to '{}'", old_name, snippet.name), ChangeKind::Edit).await;
+    Ok(())
+}
+
+/// `repo edit`: apply `changes` to the snippet in `file`, or open the file in
+/// the editor when there are none. The file follows a changed name.
+pub async fn edit_snippet(file: &Path, repo_name: &str, changes: MetadataChanges) -> Result<()> {
+    let original = fs::read_to_string(file)?;
+    let before = publish::parse_markdown_frontmatter(&original)?;
+
+    let snippet = if changes.is_empty() {
+        match edit_in_editor(file, &original, &before)? {
+            Some(snippet) => snippet,
+            None => return Ok(()),
+        }
+    } else {
+        let mut snippet = before.clone();
+        changes.apply(&mut snippet);
+        if snippet.description == before.description && snippet.tags == before.tags && snippet.visibility == before.visibility {
+            println!("ℹ️  Nothing to change");
+            return Ok(());
+        }
+        fs::write(file, publish::create_markdown_with_frontmatter(&snippet)?)?;
+        snippet
+    };
+
+    let path = match_filename(file, &snippet)?;
+    println!("✅ Updated '{}'", snippet.name);
+    if let Some(description) = &snippet.description {
+        println!("   📝 {}", description);
+    }
+    if !snippet.tags.is_empty() {
+        println!("   🏷️  {}", snippet.tags.join(", "));
+    }
+    if let Some(visibility) = snippet.visibility {
+        println!("   🔒 {}", visibility.as_str());
+    }
+    println!("📁 {}", path.display());
+
+    finish(repo_name, &format!("Edit snippet '{}' (ID: {})", snippet.name, crate::ids::short(&snippet.id)), ChangeKind::Edit).await;
+    Ok(())
+}
+
+/// Let the user edit the file in place until it parses, keeping the snippet's
+/// ID. Returns `None` (with the file restored) when they give up or change nothing.
+fn edit_in_editor(file: &Path, original: &str, before: &Snippet) -> Result<Option<Snippet>> {
+    loop {
+        println!("📝 Opening {} in your editor...", editor::editor_command());
+        editor::open_file(file)?;
+        let text = fs::read_to_string(file)?;
+        if text == original {
+            println!("ℹ️  Snippet left unchanged");
+            return Ok(None);
+        }
+
+        let problem = match publish::parse_markdown_frontmatter(&text) {
+            Ok(snippet) if snippet.name.trim().is_empty() => "The snippet needs a name in the frontmatter".to_string(),
+            Ok(snippet) if snippet.id != before.id => format!("The ID can't change (it was {})", before.id),
+            Ok(snippet) => return Ok(Some(snippet)),
+            Err(e) => format!("Invalid frontmatter: {}", e),
+        };
+        println!("❌ {}", problem);
+        if !crate::prompt::confirm("Re-open the editor to fix it?", true)? {
+            fs::write(file, original)?;
+            println!("❌ Changes discarded");
+            return Ok(None);
+        }
+    }
+}
+
+/// Move `file` to the `<slug>-<id8>.md` name its snippet should have, in the
+/// same folder, returning where it now is.
+fn match_filename(file: &Path, snippet: &Snippet) -> Result<PathBuf> {
+    let dir = file.parent().unwrap_or(Path::new("."));
+    let target = dir.join(crate::slug::snippet_filename(dir, &snippet.name, &snippet.id));
+    if target != file {
+        fs::rename(file, &target)?;
+    }
+    Ok(target)
+}
+
+/// Refresh the search index (it covers the default repository only) and sync
+/// the repository the snippet lives in, as `sync.mode` says for a change of `kind`.
+pub async fn finish(repo_name: &str, message: &str, kind: ChangeKind) {
+    if crate::config::get_default_repo_name().is_ok_and(|default| default == repo_name) {
+        crate::index::refresh_index_quietly();
+    }
+    crate::config::override_default_repo(repo_name);
+    crate::github::sync_after_change(message, kind).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_metadata_changes() {
+        let mut snippet = Snippet {
+            id: "1a2b3c4d".to_string(),
+            name: "Rule".to_string(),
+            content: "Body".to_string(),
+            description: Some("Old".to_string()),
+            tags: vec!["rust".to_string(), "legacy".to_string()],
+            ..Default::default()
+        };
+        MetadataChanges {
+            description: Some("  ".to_string()),
+            add_tags: vec!["Testing".to_string(), "rust".to_string()],
+            remove_tags: vec!["LEGACY".to_string()],
+            visibility: Some(Visibility::Team),
+        }.apply(&mut snippet);
+        assert_eq!(snippet.description, None);
+        assert_eq!(snippet.tags, vec!["rust", "testing"]);
+        assert_eq!(snippet.visibility, Some(Visibility::Team));
+    }
+}
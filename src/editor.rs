@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// The user's preferred editor: `$VISUAL`, then `$EDITOR`, then `vi`.
+pub fn editor_command() -> String {
+    std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string())
+}
+
+/// Open `initial` in the user's editor and return the saved text.
+///
+/// The editor value may include arguments (e.g. `code --wait`).
+pub fn edit_text(initial: &str, file_stem: &str) -> Result<String> {
+    let dir = std::env::temp_dir().join(format!("claude-md-snippets-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.md", file_stem));
+    fs::write(&path, initial)?;
+
+    let status = open_file(&path);
+
+    let edited = fs::read_to_string(&path);
+    fs::remove_dir_all(&dir).ok();
+
+    status?;
+    Ok(edited?)
+}
+
+/// Open `path` in the user's editor and wait for it to exit.
+pub fn open_file(path: &Path) -> Result<()> {
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to launch editor '{}' (set $EDITOR)", editor))?;
+
+    if !status.success() {
+        anyhow::bail!("Editor '{}' exited with {}", editor, status);
+    }
+    Ok(())
+}
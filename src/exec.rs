@@ -0,0 +1,92 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use crate::install::{self, Destination};
+use crate::project::{PROJECT_CONFIG_FILE, ProjectConfig};
+use crate::publish::Snippet;
+
+/// `exec -- <command>`: make sure every snippet the project's
+/// `.claude-md-snippets.toml` lists under `required` is installed in the
+/// target file (installing missing ones once confirmed), then run `command`
+/// and exit with its status. Meant for CI bots and scripts that run Claude.
+pub async fn exec(command: &[String], destination: Destination) -> Result<()> {
+    let (program, args) = command.split_first().context("No command given; usage: claude-md-snippets exec -- claude ...")?;
+    ensure_required(&destination).await?;
+
+    let status = std::process::Command::new(program)
+        .args(args)
+        .status()
+        .with_context(|| format!("Could not run '{}'", program))?;
+    // Pass the command's exit status through, so callers see its result
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+async fn ensure_required(destination: &Destination) -> Result<()> {
+    let Some((project_path, project)) = ProjectConfig::find()? else {
+        tracing::info!("No {} found; nothing required", PROJECT_CONFIG_FILE);
+        return Ok(());
+    };
+    if project.required.is_empty() {
+        return Ok(());
+    }
+
+    let target = destination.resolve()?;
+    let installed: Vec<String> = match fs::read_to_string(&target) {
+        Ok(content) => install::installed_snippets(&target, &content)?.into_iter().map(|snippet| snippet.id).collect(),
+        Err(_) => Vec::new(),
+    };
+    let missing = missing_ids(&project.required, &installed);
+    if missing.is_empty() {
+        println!("✅ All {} required snippet(s) are installed in {}", project.required.len(), target.display());
+        return Ok(());
+    }
+
+    let snippets = crate::store::load_snippets()?;
+    let mut to_install: Vec<&Snippet> = Vec::new();
+    for prefix in &missing {
+        let matching: Vec<&Snippet> = snippets.iter().filter(|snippet| snippet.id.starts_with(prefix.as_str())).collect();
+        match matching.as_slice() {
+            [snippet] => to_install.push(snippet),
+            [] => anyhow::bail!("Required snippet '{}' (from {}) is not in the repository; try 'claude-md-snippets pull'", prefix, project_path.display()),
+            _ => anyhow::bail!("Required snippet '{}' (from {}) matches {} snippets; use a longer ID", prefix, project_path.display(), matching.len()),
+        }
+    }
+
+    println!("📋 {} required snippet(s) missing from {}:", to_install.len(), target.display());
+    for snippet in &to_install {
+        println!("  • {} ({})", snippet.name, crate::ids::short(&snippet.id));
+    }
+    if !crate::prompt::confirm("Install them before running the command?", true)? {
+        anyhow::bail!("Not running the command without the required snippets");
+    }
+    for snippet in to_install {
+        install::install_confirmed(snippet, &target, false, &HashMap::new(), &destination.position).await?;
+    }
+    println!("✅ Required snippets installed");
+    Ok(())
+}
+
+/// Required IDs (or prefixes) without an installed snippet. Markers carry the
+/// short ID, so a full required ID matches on its first eight characters.
+fn missing_ids(required: &[String], installed: &[String]) -> Vec<String> {
+    required
+        .iter()
+        .filter(|prefix| {
+            !prefix.is_empty() && !installed.iter().any(|id| id.starts_with(prefix.as_str()) || prefix.starts_with(id.as_str()))
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_ids() {
+        let required = vec!["1a2b3c4d".to_string(), "5e6f".to_string(), String::new()];
+        let installed = vec!["1a2b3c4d".to_string()];
+        assert_eq!(missing_ids(&required, &installed), ["5e6f"]);
+        assert!(missing_ids(&["1a2b3c4d-0000-4000-8000-000000000000".to_string()], &installed).is_empty());
+    }
+}
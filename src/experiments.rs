@@ -0,0 +1,143 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::lockfile::{FileLock, atomic_write};
+use crate::publish::{Snippet, get_app_dir};
+
+/// Label recorded when a snippet with variants is installed as written.
+pub const ORIGINAL: &str = "original";
+
+/// Which variant of each snippet was installed into which target file, and
+/// when, so phrasings can be compared against how Claude behaved meanwhile.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ExperimentLog {
+    #[serde(default)]
+    pub entries: Vec<ExperimentEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ExperimentEntry {
+    pub target: String,
+    /// Short (8 char) ID, as in the install markers
+    pub id: String,
+    pub name: String,
+    pub variant: String,
+    pub activated_at: String,
+}
+
+impl ExperimentLog {
+    pub fn load() -> Result<Self> {
+        let path = get_log_path()?;
+        if !path.exists() {
+            return Ok(ExperimentLog::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Note that `variant` of `snippet` is now active in `target`. Installing
+    /// the same variant again doesn't start a new period.
+    pub fn record(target: &Path, snippet: &Snippet, variant: &str) -> Result<()> {
+        let path = get_log_path()?;
+        let _lock = FileLock::acquire(&path)?;
+        let mut log = ExperimentLog::load()?;
+
+        let target = target.canonicalize().unwrap_or(target.to_path_buf()).display().to_string();
+        let id = crate::ids::short(&snippet.id).to_string();
+        if log.active(&target, &id).is_some_and(|entry| entry.variant == variant) {
+            return Ok(());
+        }
+        log.entries.push(ExperimentEntry {
+            target,
+            id,
+            name: snippet.name.clone(),
+            variant: variant.to_string(),
+            activated_at: chrono::Utc::now().to_rfc3339(),
+        });
+        atomic_write(&path, &serde_json::to_string_pretty(&log)?)
+    }
+
+    /// Drop the history of snippet `id` in `target`, for `uninstall --purge`.
+    /// Returns how many entries were removed.
+    pub fn forget(id: &str, target: &Path) -> Result<usize> {
+        let path = get_log_path()?;
+        let _lock = FileLock::acquire(&path)?;
+        let mut log = ExperimentLog::load()?;
+        let target = target.display().to_string();
+        let before = log.entries.len();
+        log.entries.retain(|entry| !(entry.target == target && entry.id == id));
+        let removed = before - log.entries.len();
+        if removed > 0 {
+            atomic_write(&path, &serde_json::to_string_pretty(&log)?)?;
+        }
+        Ok(removed)
+    }
+
+    /// The variant currently active for snippet `id` in `target`.
+    pub fn active(&self, target: &str, id: &str) -> Option<&ExperimentEntry> {
+        self.entries.iter().rev().find(|entry| entry.target == target && entry.id == id)
+    }
+
+    /// Entries grouped by target, then snippet, oldest first.
+    fn grouped(&self) -> BTreeMap<&str, BTreeMap<&str, Vec<&ExperimentEntry>>> {
+        let mut grouped: BTreeMap<&str, BTreeMap<&str, Vec<&ExperimentEntry>>> = BTreeMap::new();
+        for entry in &self.entries {
+            grouped.entry(&entry.target).or_default().entry(&entry.id).or_default().push(entry);
+        }
+        grouped
+    }
+}
+
+/// `experiments`: the active variant of each snippet per target file, with
+/// the earlier variants and the dates each one was in use.
+pub fn show() -> Result<()> {
+    let log = ExperimentLog::load()?;
+    if log.entries.is_empty() {
+        println!("ℹ️  No experiments yet");
+        println!("💡 Install a snippet that has variants with 'install <query> --variant <label>'");
+        return Ok(());
+    }
+
+    for (target, snippets) in log.grouped() {
+        println!("📄 {}", target);
+        for entries in snippets.values() {
+            let Some(current) = entries.last() else { continue };
+            println!("  🧪 {} ({}): '{}' since {}", current.name, current.id, current.variant, date(&current.activated_at));
+            for pair in entries.windows(2).rev() {
+                println!("     '{}' {} to {}", pair[0].variant, date(&pair[0].activated_at), date(&pair[1].activated_at));
+            }
+        }
+        println!();
+    }
+    Ok(())
+}
+
+fn date(timestamp: &str) -> &str {
+    timestamp.get(..10).unwrap_or(timestamp)
+}
+
+fn get_log_path() -> Result<std::path::PathBuf> {
+    Ok(get_app_dir()?.join("experiments.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_variant() {
+        let entry = |id: &str, variant: &str| ExperimentEntry {
+            target: "/work/app/CLAUDE.md".to_string(),
+            id: id.to_string(),
+            name: String::new(),
+            variant: variant.to_string(),
+            activated_at: String::new(),
+        };
+        let log = ExperimentLog { entries: vec![entry("1a2b3c4d", "a"), entry("5e6f7a8b", "original"), entry("1a2b3c4d", "b")] };
+
+        assert_eq!(log.active("/work/app/CLAUDE.md", "1a2b3c4d").unwrap().variant, "b");
+        assert!(log.active("/work/other/CLAUDE.md", "1a2b3c4d").is_none());
+        assert_eq!(log.grouped()["/work/app/CLAUDE.md"]["1a2b3c4d"].len(), 2);
+    }
+}
@@ -1,33 +1,94 @@
 use anyhow::{Result, Context};
 use std::fs;
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
 use tokio::fs as async_fs;
-use uuid::Uuid;
+use crate::claude_client::ClaudeClient;
 
-pub async fn extract_snippet(query: String) -> Result<()> {
+/// Where extracted (and captured) snippets are written unless `--dir` or `--output` say otherwise.
+pub const DEFAULT_EXTRACT_DIR: &str = "./.claude.local/snippets";
+
+/// How `extract` reads its source and what it does with the result.
+#[derive(Default)]
+pub struct ExtractOptions {
+    /// Instruction file to read instead of `~/.claude/CLAUDE.md`
+    pub from: Option<PathBuf>,
+    /// Read `./CLAUDE.md`
+    pub local: bool,
+    pub no_ai: bool,
+    /// Publish straight to a repository instead of writing `./.claude.local/snippets`
+    pub publish: bool,
+    /// Repository to publish to (the default repository otherwise)
+    pub repo: Option<String>,
+    /// Snippet name, instead of the query
+    pub name: Option<String>,
+    /// Exact file to write
+    pub output: Option<PathBuf>,
+    /// Directory to write into, instead of `./.claude.local/snippets`
+    pub dir: Option<PathBuf>,
+}
+
+/// Extract what an instruction file says about `query` into a local snippet,
+/// or publish it directly.
+pub async fn extract_snippet(query: String, options: ExtractOptions) -> Result<()> {
     println!("Extracting information about: {}", query);
     
-    // Check if ~/.claude/CLAUDE.md exists
-    let home_dir = dirs::home_dir()
-        .context("Could not find home directory")?;
-    let claude_md_path = home_dir.join(".claude/CLAUDE.md");
+    let claude_md_path = match options.from {
+        Some(path) => path,
+        None if options.local => PathBuf::from("CLAUDE.md"),
+        None => dirs::home_dir()
+            .context("Could not find home directory")?
+            .join(".claude/CLAUDE.md"),
+    };
+    let source = display_source(&claude_md_path);
+    
+    if !claude_md_path.is_file() {
+        anyhow::bail!("{} not found", source);
+    }
+    if let Some(output) = options.output.as_ref().filter(|output| output.exists()) {
+        anyhow::bail!("{} already exists; pick another --output", output.display());
+    }
+    if let Some(repo) = &options.repo {
+        if !crate::publish::get_repos_dir()?.join(repo).exists() {
+            anyhow::bail!("Repository '{}' not found; run 'claude-md-snippets setup {}' first", repo, repo);
+        }
+        crate::config::override_default_repo(repo);
+    }
     
-    if !claude_md_path.exists() {
-        anyhow::bail!("~/.claude/CLAUDE.md not found");
+    // Use Claude Code to extract relevant information, or fall back to matching sections by heading
+    let no_ai = options.no_ai;
+    let use_claude = !no_ai && crate::capabilities::has(crate::capabilities::Tool::Claude);
+    if !no_ai && !use_claude {
+        crate::capabilities::note_unavailable(crate::capabilities::Tool::Claude, "AI extraction");
     }
+    let extracted = if use_claude {
+        extract_with_claude_code(&query, &claude_md_path).await?
+    } else {
+        match extract_by_heading(&query, &fs::read_to_string(&claude_md_path)?, &source) {
+            Some(extracted) => extracted,
+            None => return Ok(()),
+        }
+    };
     
-    // Create local .claude.local/snippets directory
-    let local_snippets_dir = Path::new("./.claude.local/snippets");
-    fs::create_dir_all(local_snippets_dir)
-        .context("Failed to create ./.claude.local/snippets directory")?;
+    let name = options.name.as_deref().unwrap_or(&query);
+    if options.publish {
+        return publish_extracted(name, &extracted, &source).await;
+    }
     
-    // Use Claude Code to extract relevant information
-    let (extracted_content, snippet_id) = extract_with_claude_code(&query, &claude_md_path).await?;
+    let (extracted_content, snippet_id) = with_frontmatter(name, &query, &extracted, &source)?;
     
-    // Generate filename from query (sanitized) with ID
-    let filename = format!("{}-{}.md", sanitize_filename(&query), &snippet_id[..8]);
-    let output_path = local_snippets_dir.join(&filename);
+    let output_path = match options.output {
+        Some(path) => path,
+        None => {
+            // Named like published snippets, so the name only matters to the frontmatter
+            let dir = options.dir.unwrap_or_else(|| PathBuf::from(DEFAULT_EXTRACT_DIR));
+            let filename = crate::slug::snippet_filename(&dir, name, &snippet_id);
+            dir.join(filename)
+        }
+    };
+    if let Some(parent) = output_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
     
     // Write extracted content to file
     async_fs::write(&output_path, extracted_content)
@@ -39,62 +100,168 @@ pub async fn extract_snippet(query: String) -> Result<()> {
     Ok(())
 }
 
-async fn extract_with_claude_code(query: &str, claude_md_path: &Path) -> Result<(String, String)> {
+/// `extract --publish`: preview the extracted snippet and publish it once confirmed.
+async fn publish_extracted(name: &str, extracted: &str, source: &str) -> Result<()> {
+    let snippet = extracted_snippet(name, extracted, source);
+    
+    println!("📋 Extracted snippet '{}':", snippet.name);
+    println!("{}", snippet.content);
+    if !crate::prompt::confirm(&format!("Publish it to '{}'?", crate::config::get_default_repo_name()?), true)? {
+        println!("❌ Publishing cancelled");
+        return Ok(());
+    }
+    
+    crate::publish::save_and_sync_snippet(&snippet).await
+}
+
+async fn extract_with_claude_code(query: &str, claude_md_path: &Path) -> Result<String> {
     println!("Using Claude Code to extract relevant information...");
     
     // Prepare the prompt for Claude Code
     let prompt = format!(
-        "Please extract all relevant information about '{}' from the CLAUDE.md file. \
+        "Please extract all relevant information about '{}' from this instruction file. \
         Include any related sections, instructions, code examples, or configuration details. \
         Format the output as a clean markdown snippet that can be used independently.",
         query
     );
     
     // Run Claude Code with the prompt
-    let output = Command::new("claude")
-        .arg("--dangerously-skip-permissions")
-        .arg("--print")
-        .arg(&format!("Read the file {} and {}", claude_md_path.display(), prompt))
-        .output()
-        .context("Failed to execute Claude Code CLI")?;
+    let client = ClaudeClient::from_config()?;
+    client
+        .prompt(&format!("Read the file {} and {}", claude_md_path.display(), prompt))
+        .context("Failed to extract with Claude Code")
+}
+
+/// Deterministic extraction: the sections of `content` whose heading or body
+/// match the query words, in document order. Prints what was picked, or the
+/// available headings when nothing matched.
+fn extract_by_heading(query: &str, content: &str, source: &str) -> Option<String> {
+    println!("Extracting sections whose heading or body match the query...");
+    let sections = crate::audit::sections(content);
+    let matched: Vec<&String> = sections.iter().filter(|section| section_matches(section, query)).collect();
+    
+    if matched.is_empty() {
+        println!("❌ No section of {} matches '{}'", source, query);
+        println!("💡 Headings found:");
+        for section in &sections {
+            println!("  - {}", section.lines().next().unwrap_or("").trim());
+        }
+        return None;
+    }
     
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Claude Code failed: {}", stderr);
+    for section in &matched {
+        println!("  📎 {}", section.lines().next().unwrap_or("").trim());
+    }
+    Some(matched.iter().map(|s| s.as_str()).collect::<Vec<_>>().join("\n\n"))
+}
+
+/// A section matches when any query word is in its heading, or every query
+/// word is somewhere in its body. Words of two letters or fewer are ignored.
+pub fn section_matches(section: &str, query: &str) -> bool {
+    let query = query.to_lowercase();
+    let words: Vec<&str> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .collect();
+    if words.is_empty() {
+        return false;
     }
     
-    let extracted = String::from_utf8_lossy(&output.stdout);
+    let (heading, body) = section.split_once('\n').unwrap_or((section, ""));
+    let (heading, body) = (heading.to_lowercase(), body.to_lowercase());
+    words.iter().any(|word| heading.contains(word)) || words.iter().all(|word| body.contains(word))
+}
+
+/// A new snippet holding the extracted markdown.
+fn extracted_snippet(name: &str, extracted: &str, source: &str) -> crate::publish::Snippet {
+    crate::publish::Snippet {
+        id: crate::ids::generate(),
+        name: name.to_string(),
+        content: extracted.trim().to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: Some(format!("Extracted from {}", source)),
+        ..Default::default()
+    }
+}
+
+/// Wrap extracted markdown in snippet frontmatter, returning it with the new ID.
+fn with_frontmatter(name: &str, query: &str, extracted: &str, source: &str) -> Result<(String, String)> {
+    let snippet = extracted_snippet(name, extracted, source);
+    // Where it came from, for reference; publishing doesn't keep these
+    let origin = serde_yaml::to_string(&serde_yaml::Mapping::from_iter([
+        (serde_yaml::Value::from("source"), serde_yaml::Value::from("extract")),
+        (serde_yaml::Value::from("query"), serde_yaml::Value::from(query)),
+    ]))?;
+    let content = crate::publish::create_markdown_with_frontmatter(&snippet)?
+        .replacen("\n---\n\n", &format!("\n{}---\n\n", origin), 1);
+    Ok((content, snippet.id))
+}
+
+/// `extract gc`: delete extracted files in `dir` (`./.claude.local/snippets`
+/// by default) that were already published to a repository (same ID) or are
+/// older than `stale_after`.
+pub fn collect_garbage(dir: Option<PathBuf>, stale_after: chrono::Duration) -> Result<()> {
+    let local_snippets_dir = &dir.unwrap_or_else(|| PathBuf::from(DEFAULT_EXTRACT_DIR));
+    if !local_snippets_dir.exists() {
+        println!("ℹ️  No extracted snippets in {}", local_snippets_dir.display());
+        return Ok(());
+    }
     
-    // Create content with YAML frontmatter
-    let id = Uuid::new_v4().to_string();
-    let timestamp = chrono::Utc::now().to_rfc3339();
+    let published: Vec<String> = match crate::publish::get_repos_dir() {
+        Ok(repos_dir) if repos_dir.exists() => fs::read_dir(repos_dir)?
+            .filter_map(|entry| entry.ok())
+            .flat_map(|entry| crate::store::load_snippets_from(&entry.path().join("snippets")).unwrap_or_default())
+            .map(|snippet| snippet.id)
+            .collect(),
+        _ => Vec::new(),
+    };
     
-    let frontmatter = format!(
-        "---\nid: {}\nname: {}\ncreated_at: {}\ndescription: Extracted from ~/.claude/CLAUDE.md\nsource: extract\nquery: {}\n---\n\n",
-        id,
-        query,
-        timestamp,
-        query
-    );
+    let now = chrono::Utc::now();
+    let mut garbage: Vec<(PathBuf, &str)> = Vec::new();
+    for entry in fs::read_dir(local_snippets_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("md") {
+            continue;
+        }
+        let Ok(snippet) = crate::publish::parse_markdown_frontmatter(&fs::read_to_string(&path)?) else {
+            continue;
+        };
+        if published.contains(&snippet.id) {
+            garbage.push((path, "published"));
+        } else if chrono::DateTime::parse_from_rfc3339(&snippet.created_at)
+            .is_ok_and(|created| now.signed_duration_since(created) > stale_after)
+        {
+            garbage.push((path, "stale"));
+        }
+    }
+    garbage.sort();
     
-    let content = format!("{}{}", frontmatter, extracted.trim());
+    if garbage.is_empty() {
+        println!("✅ Nothing to clean up in {}", local_snippets_dir.display());
+        return Ok(());
+    }
+    println!("🧹 Extracted snippets that can go:");
+    for (path, reason) in &garbage {
+        println!("  • {} ({})", path.display(), reason);
+    }
+    if !crate::prompt::confirm(&format!("Delete these {} file(s)?", garbage.len()), false)? {
+        println!("❌ Cleanup cancelled");
+        return Ok(());
+    }
+    for (path, _) in &garbage {
+        fs::remove_file(path)?;
+    }
+    println!("✅ Removed {} extracted snippet(s)", garbage.len());
     
-    Ok((content, id))
+    Ok(())
 }
 
-fn sanitize_filename(input: &str) -> String {
-    input
-        .chars()
-        .map(|c| {
-            if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' {
-                c
-            } else {
-                '_'
-            }
-        })
-        .collect::<String>()
-        .replace(' ', "_")
-        .to_lowercase()
+/// The source path as the user would write it, with `~` for the home directory.
+fn display_source(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(relative) => format!("~/{}", relative.display()),
+        None => path.display().to_string(),
+    }
 }
 
 #[cfg(test)]
@@ -102,9 +269,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_sanitize_filename() {
-        assert_eq!(sanitize_filename("Running GUI Applications"), "running_gui_applications");
-        assert_eq!(sanitize_filename("Test/Path\\Name"), "test_path_name");
-        assert_eq!(sanitize_filename("Special-Characters!@#"), "special-characters___");
+    fn test_extract_by_heading() {
+        let content = "# Testing\n\nRun cargo test.\n\n## GUI apps\n\nUse xvfb-run for GUI tests.\n\n# Style\n\nUse tabs.\n";
+        assert_eq!(extract_by_heading("testing", content, "CLAUDE.md").unwrap(), "# Testing\n\nRun cargo test.");
+        assert_eq!(extract_by_heading("gui tests", content, "CLAUDE.md").unwrap(), "## GUI apps\n\nUse xvfb-run for GUI tests.");
+        assert!(extract_by_heading("deployment", content, "CLAUDE.md").is_none());
     }
 }
\ No newline at end of file
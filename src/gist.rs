@@ -0,0 +1,134 @@
+use anyhow::{Context, Result};
+use crate::capabilities::{self, Tool};
+use crate::publish::{self, Snippet};
+
+const GISTS_API: &str = "https://api.github.com/gists";
+
+/// Share a single snippet as a secret gist, outside any snippets repository.
+pub async fn publish_gist(snippet: &Snippet) -> Result<()> {
+    capabilities::require(Tool::Network, "publish --gist")?;
+    let token = crate::github::github_token(None)
+        .context("publish --gist needs a GitHub token: run 'gh auth login' or set GITHUB_TOKEN")?;
+
+    let filename = format!("{}.md", crate::slug::slugify(&snippet.name, '-'));
+    let description = snippet.description.clone().unwrap_or_else(|| snippet.name.clone());
+    println!("📤 Creating secret gist for '{}'...", snippet.name);
+
+    let request = reqwest::Client::new()
+        .post(GISTS_API)
+        .header("User-Agent", "claude-md-snippets")
+        .header("Accept", "application/vnd.github+json")
+        .bearer_auth(token)
+        .json(&serde_json::json!({
+            "description": description,
+            "public": false,
+            "files": { filename: { "content": publish::create_markdown_with_frontmatter(snippet)? } },
+        }));
+    let response = crate::github_api::send(request).await?;
+
+    let status = response.status();
+    let json: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = json["message"].as_str().unwrap_or("unknown error");
+        anyhow::bail!("GitHub API returned {}: {}", status, message);
+    }
+
+    println!("✅ Shared '{}' as a secret gist", snippet.name);
+    println!("🔗 {}", json["html_url"].as_str().unwrap_or_default());
+    println!("💡 Anyone with the link can install it: claude-md-snippets install --from-gist {}",
+        json["id"].as_str().unwrap_or_default());
+    Ok(())
+}
+
+/// Fetch the snippet stored in a gist, given its URL or ID. Gists that weren't
+/// created by `publish --gist` are read as plain snippet content.
+pub async fn fetch_gist(reference: &str) -> Result<Snippet> {
+    let id = parse_gist_id(reference)
+        .with_context(|| format!("'{}' is not a gist URL or ID", reference))?;
+    capabilities::require(Tool::Network, "install --from-gist")?;
+
+    let client = reqwest::Client::new();
+    let mut request = client
+        .get(format!("{}/{}", GISTS_API, id))
+        .header("User-Agent", "claude-md-snippets")
+        .header("Accept", "application/vnd.github+json");
+    // Secret gists are readable by ID alone; a token only raises the rate limit
+    if let Some(token) = crate::github::github_token(None) {
+        request = request.bearer_auth(token);
+    }
+    let response = crate::github_api::send(request).await?;
+
+    let status = response.status();
+    let json: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = json["message"].as_str().unwrap_or("unknown error");
+        anyhow::bail!("Could not fetch gist {}: GitHub API returned {}: {}", id, status, message);
+    }
+
+    let files = json["files"].as_object().context("Gist has no files")?;
+    let file = files
+        .values()
+        .find(|file| file["filename"].as_str().is_some_and(|name| name.ends_with(".md")))
+        .or_else(|| files.values().next())
+        .context("Gist has no files")?;
+
+    // Large files are truncated in the API response and must be fetched raw
+    let content = if file["truncated"] == true {
+        let raw_url = file["raw_url"].as_str().context("Gist file has no raw URL")?;
+        crate::github_api::send(client.get(raw_url).header("User-Agent", "claude-md-snippets")).await?.text().await?
+    } else {
+        file["content"].as_str().unwrap_or_default().to_string()
+    };
+
+    if let Ok(snippet) = publish::parse_markdown_frontmatter(&content) {
+        if !snippet.id.is_empty() && !snippet.content.trim().is_empty() {
+            return Ok(snippet);
+        }
+    }
+
+    let name = json["description"]
+        .as_str()
+        .filter(|description| !description.trim().is_empty())
+        .map(|description| description.to_string())
+        .unwrap_or_else(|| publish::generate_name_from_content(&content));
+    Ok(Snippet {
+        id: crate::ids::generate(),
+        name,
+        content,
+        created_at: json["created_at"].as_str().unwrap_or_default().to_string(),
+        description: Some(format!("From gist {}", id)),
+        ..Default::default()
+    })
+}
+
+/// The gist ID from `<id>`, `https://gist.github.com/<user>/<id>` or a git URL
+/// ending in `<id>.git`.
+fn parse_gist_id(reference: &str) -> Option<String> {
+    let reference = reference.trim().trim_end_matches('/');
+    let last = reference
+        .split(['#', '?'])
+        .next()?
+        .rsplit('/')
+        .next()?
+        .trim_end_matches(".git");
+    if !last.is_empty() && last.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(last.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gist_id() {
+        let id = "aa5a315d61ae9438b18d";
+        assert_eq!(parse_gist_id(id).as_deref(), Some(id));
+        assert_eq!(parse_gist_id(&format!("https://gist.github.com/octocat/{}", id)).as_deref(), Some(id));
+        assert_eq!(parse_gist_id(&format!("https://gist.github.com/{}.git", id)).as_deref(), Some(id));
+        assert_eq!(parse_gist_id(&format!("https://gist.github.com/octocat/{}#file-a-md", id)).as_deref(), Some(id));
+        assert_eq!(parse_gist_id("https://example.com/not-a-gist"), None);
+    }
+}
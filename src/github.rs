@@ -1,16 +1,70 @@
 use anyhow::Result;
-use serde_json::json;
 use std::fs;
 use std::process::Command;
-use std::io::{self, Write};
-use crate::publish::{Snippet, get_snippets_dir, get_repos_dir};
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::publish::{get_snippets_dir, get_repos_dir};
+use crate::capabilities::{self, Tool};
+use crate::visibility::Visibility;
+use crate::perf::{Category, TimedCommand};
+use crate::starters::StarterTemplate;
+use crate::accounts::{self, WithAccount};
+use crate::config::{Account, SyncMode};
+use crate::journal::Journal;
+use crate::process::{ProcessRunner, RunWith, SystemRunner};
+use crate::github_api::{CreatedRepo, GitHubClient};
+use crate::hosts::{self, HostKind, HostOptions};
 
-const DEFAULT_REPO: &str = "claude-md-snippets/community-snippets";
+pub const DEFAULT_REPO: &str = "claude-md-snippets/community-snippets";
 
-pub async fn sync_snippets() -> Result<()> {
+/// First run: with no repository at all, offer to create a local-only one
+/// right away (private, named `default`). Connecting it to GitHub is left to
+/// the first `sync`.
+pub async fn ensure_repository() -> Result<()> {
+    let repos_dir = get_repos_dir()?;
+    // Followed read-only repositories can't take the user's own snippets
+    let config = crate::config::Config::load()?;
+    let has_repository = fs::read_dir(&repos_dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).any(|e| e.path().is_dir() && !config.is_subscribed(&e.file_name().to_string_lossy())))
+        .unwrap_or(false);
+    if has_repository {
+        return Ok(());
+    }
+
+    println!("👋 No snippet repository yet.");
+    if !crate::prompt::confirm("Create a local one now? (GitHub can be connected later, on the first sync)", true)? {
+        println!("💡 Run 'claude-md-snippets setup' to create one on GitHub (or GitLab, Gitea, plain git)");
+        anyhow::bail!("No snippet repository to work with");
+    }
+    capabilities::require(Tool::Git, "a local repository")?;
+    let name = "default";
+    init_snippets_repo(&repos_dir.join(name)).await?;
+    let mut config = crate::config::Config::load()?;
+    config.set_default_repo(name.to_string())?;
+    config.set_repo_visibility(name, Visibility::Private)?;
+    println!("🎯 '{}' is your default repository; 'claude-md-snippets sync' offers to put it on GitHub", name);
+    crate::subscriptions::offer_community()?;
+    Ok(())
+}
+
+/// How `sync` commits: `message` replaces the one generated from the
+/// changes, and `squash` folds every unpushed commit into one before pushing.
+#[derive(Default)]
+pub struct SyncOptions {
+    pub message: Option<String>,
+    pub squash: bool,
+}
+
+pub async fn sync_snippets(options: SyncOptions) -> Result<()> {
+    sync_snippets_with(&SystemRunner, options).await
+}
+
+async fn sync_snippets_with(runner: &dyn ProcessRunner, options: SyncOptions) -> Result<()> {
     println!("🔄 Syncing snippets with GitHub repository...");
+    capabilities::require(Tool::Git, "sync")?;
+    crate::subscriptions::ensure_writable(&crate::config::get_default_repo_name()?)?;
     
     let snippets_dir = get_snippets_dir()?;
+    let account = accounts::for_default_repo()?;
     
     // Initialize git repository if it doesn't exist
     let git_dir = snippets_dir.join(".git");
@@ -18,29 +72,56 @@ pub async fn sync_snippets() -> Result<()> {
         println!("📦 Initializing snippet repository...");
         init_snippets_repo(&snippets_dir).await?;
     }
+    let repo_name = crate::config::get_default_repo_name()?;
+    if !crate::health::ensure_origin(runner, &repo_name, &snippets_dir)? {
+        return connect_local_repo(runner, &snippets_dir, &repo_name, options.message).await;
+    }
+    let online = remote_reachable(runner, &snippets_dir);
+    let branch = sync_branch(runner, &repo_name, &snippets_dir, online);
+    crate::health::check_and_heal(runner, &snippets_dir, &branch, online, account.as_ref())?;
     
-    // First, pull any remote changes
-    println!("📥 Pulling latest changes from remote...");
-    let pull_output = Command::new("git")
-        .current_dir(&snippets_dir)
-        .args(&["pull", "origin", "main"])
-        .output()?;
+    let queued = Journal::load()?.pending_for(&repo_name).len();
+    if queued > 0 {
+        if online {
+            println!("📬 Replaying {} change(s) queued while offline", queued);
+        } else {
+            println!("📴 {} change(s) queued while offline are still waiting for a connection", queued);
+        }
+    }
     
-    if !pull_output.status.success() {
-        println!("⚠️  Warning: Could not pull from remote - continuing with local sync");
-        let stderr = String::from_utf8_lossy(&pull_output.stderr);
-        if !stderr.is_empty() && !stderr.contains("no such ref") {
-            println!("⚠️  Git pull error: {}", stderr);
+    // First, pull any remote changes
+    let pull_first = crate::config::Config::load()?.sync.pull_before_push;
+    if online && !pull_first {
+        println!("⏭️  Not pulling first (sync.pull_before_push is off)");
+    } else if online {
+        println!("📥 Pulling latest changes from remote...");
+        let pull_output = Command::new("git")
+            .current_dir(&snippets_dir)
+            .args(["pull", "origin", &branch])
+            .with_account(account.as_ref())
+            .run_with(runner, Category::Git)?;
+        
+        if !pull_output.status.success() {
+            println!("⚠️  Warning: Could not pull from remote - continuing with local sync");
+            let stderr = String::from_utf8_lossy(&pull_output.stderr);
+            if !stderr.is_empty() && !stderr.contains("no such ref") {
+                println!("⚠️  Git pull error: {}", stderr);
+            }
+        } else {
+            println!("✅ Successfully pulled remote changes");
+            crate::index::refresh_index_quietly();
         }
     } else {
-        println!("✅ Successfully pulled remote changes");
+        capabilities::note_unavailable(Tool::Network, "Pulling remote changes");
     }
     
+    crate::visibility::check_before_push(&repo_name, &snippets_dir)?;
+    
     // Add all changes (including deletions)
     let output = Command::new("git")
         .current_dir(&snippets_dir)
-        .args(&["add", "-A"])
-        .output()?;
+        .args(["add", "-A"])
+        .run_with(runner, Category::Git)?;
     
     if !output.status.success() {
         println!("⚠️  Warning: Could not stage changes");
@@ -53,36 +134,58 @@ pub async fn sync_snippets() -> Result<()> {
     // Check if there are changes to commit
     let status_output = Command::new("git")
         .current_dir(&snippets_dir)
-        .args(&["status", "--porcelain"])
-        .output()?;
+        .args(["status", "--porcelain"])
+        .run_with(runner, Category::Git)?;
     
     if status_output.stdout.is_empty() {
-        println!("✅ Sync complete - no local changes to push");
-        return Ok(());
+        // Commits made while offline (or before a remote existed) still need pushing
+        if !(online && has_unpushed_commits(runner, &snippets_dir, &branch)) {
+            if online {
+                Journal::mark_synced(&repo_name)?;
+            }
+            println!("✅ Sync complete - no local changes to push");
+            return Ok(());
+        }
+    } else {
+        // Commit changes
+        let message = options.message.clone().unwrap_or_else(|| crate::changeset::describe(runner, &snippets_dir));
+        let commit_output = Command::new("git")
+            .current_dir(&snippets_dir)
+            .args(["commit", "-m", &message])
+            .with_account(account.as_ref())
+            .run_with(runner, Category::Git)?;
+        
+        if !commit_output.status.success() {
+            println!("⚠️  Warning: Could not create commit");
+            return Ok(());
+        }
     }
     
-    // Commit changes
-    let commit_output = Command::new("git")
-        .current_dir(&snippets_dir)
-        .args(&["commit", "-m", "Sync snippets: add/modify/remove files"])
-        .output()?;
-    
-    if !commit_output.status.success() {
-        println!("⚠️  Warning: Could not create commit");
+    // Push to remote (if configured)
+    if !online {
+        capabilities::note_unavailable(Tool::Network, "Pushing to remote");
+        println!("✅ Changes committed locally - run 'claude-md-snippets sync' again when online");
         return Ok(());
     }
-    
-    // Push to remote (if configured)
+    if options.squash {
+        squash_unpushed(runner, &snippets_dir, &branch, options.message.as_deref(), account.as_ref())?;
+    }
     println!("📤 Pushing to remote repository...");
     let push_output = Command::new("git")
         .current_dir(&snippets_dir)
-        .args(&["push", "origin", "main"])
-        .output();
+        .args(["push", "origin", &branch])
+        .with_account(account.as_ref())
+        .run_with(runner, Category::Git);
     
     match push_output {
         Ok(output) if output.status.success() => {
+            Journal::mark_synced(&repo_name)?;
             println!("✅ Successfully synced snippets! (pulled remote changes + pushed local changes)");
         }
+        Ok(output) if String::from_utf8_lossy(&output.stderr).contains("Repository not found") => {
+            println!("⚠️  Could not push: the remote repository was not found.");
+            println!("💡 If it was renamed or transferred on GitHub, run 'claude-md-snippets repo web-sync'");
+        }
         _ => {
             println!("⚠️  Could not push to remote. Make sure you have push access and the remote is configured.");
             println!("💡 To setup remote: cd {} && git remote add origin <your-repo-url>", snippets_dir.display());
@@ -92,50 +195,336 @@ pub async fn sync_snippets() -> Result<()> {
     Ok(())
 }
 
-pub async fn pull_snippets() -> Result<()> {
+/// `sync` of a repository without a remote (one created on first run):
+/// commit locally, then offer to create it on GitHub and push.
+async fn connect_local_repo(runner: &dyn ProcessRunner, snippets_dir: &std::path::Path, repo_name: &str, message: Option<String>) -> Result<()> {
+    let message = message.unwrap_or_else(|| crate::changeset::describe(runner, snippets_dir));
+    commit_locally(runner, snippets_dir, &message).await?;
+    println!("📁 '{}' is local-only: it has no remote to sync with", repo_name);
+    if crate::prompt::offer("Connect it to GitHub now?")? {
+        let host = HostOptions { kind: HostKind::Github, base_url: None, remote: None };
+        return setup_repository(Some(repo_name.to_string()), None, host, None).await;
+    }
+    println!("✅ Changes committed locally");
+    println!("💡 Run 'claude-md-snippets setup --repo {}' (or 'sync' again) to put it on GitHub", repo_name);
+    Ok(())
+}
+
+static AUTO_SYNC_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// `--no-sync`: commit and queue changes instead of syncing them, whatever
+/// `sync.mode` says.
+pub fn disable_auto_sync() {
+    AUTO_SYNC_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// What made a change, which decides whether `sync.mode` syncs it right away.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    /// New snippets: publish, promote, import
+    Publish,
+    /// Deletes, edits and status changes
+    Edit,
+    /// Files changed under `watch`, which is itself a request to sync
+    Watched,
+}
+
+/// Why a change of `kind` is not synced right away, if it isn't.
+fn deferred_because(mode: SyncMode, kind: ChangeKind) -> Option<&'static str> {
+    if AUTO_SYNC_DISABLED.load(Ordering::Relaxed) {
+        return Some("--no-sync");
+    }
+    match (mode, kind) {
+        (_, ChangeKind::Watched) | (SyncMode::Auto, _) => None,
+        (SyncMode::Manual, _) => Some("sync.mode is manual"),
+        (SyncMode::OnPublishOnly, ChangeKind::Publish) => None,
+        (SyncMode::OnPublishOnly, _) => Some("sync.mode is on-publish-only"),
+    }
+}
+
+/// Sync after a change made by publish, delete or an edit. When offline (or
+/// when `sync.mode` or `--no-sync` defer it) the change is committed locally
+/// and queued in the journal for the next `sync`, without the usual stream of
+/// warnings.
+pub async fn sync_after_change(description: &str, kind: ChangeKind) {
+    let runner = &SystemRunner;
+    let result = async {
+        let snippets_dir = get_snippets_dir()?;
+        let repo_name = crate::config::get_default_repo_name()?;
+        crate::subscriptions::ensure_writable(&repo_name)?;
+        if !snippets_dir.join(".git").exists() || !crate::health::ensure_origin(runner, &repo_name, &snippets_dir)? {
+            commit_locally(runner, &snippets_dir, description).await?;
+            println!("📝 Change committed locally; 'claude-md-snippets sync' offers to connect the repository to GitHub");
+            return Ok(());
+        }
+        if let Some(reason) = deferred_because(crate::config::Config::load()?.sync.mode, kind) {
+            commit_locally(runner, &snippets_dir, description).await?;
+            Journal::record(&repo_name, description)?;
+            println!("📝 Change committed locally ({}); run 'claude-md-snippets sync' to push it", reason);
+            return Ok(());
+        }
+        if capabilities::is_forced_offline() || !remote_reachable(runner, &snippets_dir) {
+            commit_locally(runner, &snippets_dir, description).await?;
+            Journal::record(&repo_name, description)?;
+            println!("📴 Offline: change committed locally and queued; 'claude-md-snippets sync' pushes it once you're back online");
+            return Ok(());
+        }
+
+        println!("🔄 Syncing with repository...");
+        if let Err(e) = sync_snippets_with(runner, SyncOptions::default()).await {
+            println!("⚠️  Sync failed: {}", e);
+            Journal::record(&repo_name, description)?;
+            println!("💡 Change queued; run 'claude-md-snippets sync' to retry");
+        } else {
+            println!("✅ Successfully synced to repository!");
+        }
+        Ok::<(), anyhow::Error>(())
+    }.await;
+
+    if let Err(e) = result {
+        println!("⚠️  Could not sync or queue the change: {}", e);
+        println!("💡 You can manually sync later with 'claude-md-snippets sync'");
+    }
+}
+
+/// Commit everything in the repository without touching the remote.
+async fn commit_locally(runner: &dyn ProcessRunner, snippets_dir: &std::path::Path, message: &str) -> Result<()> {
+    if !snippets_dir.join(".git").exists() {
+        init_snippets_repo(snippets_dir).await?;
+    }
+    let account = accounts::for_default_repo()?;
+    Command::new("git")
+        .current_dir(snippets_dir)
+        .args(["add", "-A"])
+        .run_with(runner, Category::Git)?;
+    let output = Command::new("git")
+        .current_dir(snippets_dir)
+        .args(["commit", "--quiet", "-m", message])
+        .with_account(account.as_ref())
+        .run_with(runner, Category::Git)?;
+    // Exit status 1 with nothing staged just means there was nothing to commit
+    if !output.status.success() && !String::from_utf8_lossy(&output.stdout).contains("nothing to commit") {
+        anyhow::bail!("git commit failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+pub async fn pull_snippets(all: bool) -> Result<()> {
+    pull_snippets_with(&SystemRunner, all).await
+}
+
+async fn pull_snippets_with(runner: &dyn ProcessRunner, all: bool) -> Result<()> {
     println!("📥 Pulling latest snippets from repository...");
+    capabilities::require(Tool::Git, "pull")?;
     
     let snippets_dir = get_snippets_dir()?;
-    
     if !snippets_dir.join(".git").exists() {
+        capabilities::require(Tool::Network, "pull")?;
         println!("📦 Repository not initialized. Cloning default repository...");
         clone_default_repo().await?;
         return Ok(());
     }
     
+    if !remote_reachable(runner, &snippets_dir) {
+        anyhow::bail!("pull requires the remote repository, which can't be reached. {}", Tool::Network.hint());
+    }
+    
     // Pull latest changes
+    let repo_name = crate::config::get_default_repo_name()?;
+    let branch = sync_branch(runner, &repo_name, &snippets_dir, true);
     let output = Command::new("git")
         .current_dir(&snippets_dir)
-        .args(&["pull", "origin", "main"])
-        .output()?;
+        .args(["pull", "origin", &branch])
+        .with_account(accounts::for_default_repo()?.as_ref())
+        .run_with(runner, Category::Git)?;
     
     if output.status.success() {
         println!("✅ Successfully pulled latest snippets!");
+        crate::index::refresh_index_quietly();
         
         // Show count of available snippets
-        let snippets = load_snippets().await?;
+        let snippets = crate::store::load_snippets_async().await?;
         println!("📚 {} snippets available locally", snippets.len());
     } else {
         println!("⚠️  Could not pull from remote. Check your internet connection and repository configuration.");
     }
+    crate::subscriptions::pull_sources(all)?;
     
     Ok(())
 }
 
+/// The branch `repo_name` syncs: the one recorded in config, else the
+/// remote's default branch (recorded once found), else the checked-out one.
+pub fn sync_branch(runner: &dyn ProcessRunner, repo_name: &str, repo_dir: &std::path::Path, online: bool) -> String {
+    let config = crate::config::Config::load().ok();
+    if let Some(branch) = config.as_ref().and_then(|config| config.get_repo_branch(repo_name)) {
+        return branch.to_string();
+    }
+    if let Some(branch) = remote_default_branch(runner, repo_dir, online) {
+        tracing::info!("'{}' syncs branch '{}' (the remote's default)", repo_name, branch);
+        if let Some(mut config) = config {
+            if let Err(e) = config.set_repo_branch(repo_name, &branch) {
+                tracing::warn!("Could not record the branch of '{}': {}", repo_name, e);
+            }
+        }
+        return branch;
+    }
+    checked_out_branch(runner, repo_dir).unwrap_or_else(|| "main".to_string())
+}
+
+/// The remote's default branch: from `origin/HEAD` as recorded by clone, or
+/// by asking the remote when `online`.
+fn remote_default_branch(runner: &dyn ProcessRunner, repo_dir: &std::path::Path, online: bool) -> Option<String> {
+    if let Some(head) = git_line(runner, repo_dir, &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+        return head.strip_prefix("origin/").map(|branch| branch.to_string());
+    }
+    if !online {
+        return None;
+    }
+    // "ref: refs/heads/<branch>\tHEAD"; an empty remote has no HEAD yet
+    let head = git_line(runner, repo_dir, &["ls-remote", "--symref", "origin", "HEAD"])?;
+    head.strip_prefix("ref: refs/heads/")?.split('\t').next().map(|branch| branch.to_string())
+}
+
+fn checked_out_branch(runner: &dyn ProcessRunner, repo_dir: &std::path::Path) -> Option<String> {
+    git_line(runner, repo_dir, &["symbolic-ref", "--short", "HEAD"])
+}
+
+/// The branch checked out in `repo_dir`, which setup pushes (main when unknown).
+pub fn local_branch(repo_dir: &std::path::Path) -> String {
+    checked_out_branch(&SystemRunner, repo_dir).unwrap_or_else(|| "main".to_string())
+}
+
+/// First line of a successful git command's output, if it printed anything.
+fn git_line(runner: &dyn ProcessRunner, repo_dir: &std::path::Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").current_dir(repo_dir).args(args).run_with(runner, Category::Git).ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout.lines().next().map(|line| line.trim().to_string()).filter(|line| !line.is_empty())
+}
+
+/// Whether `origin` (after any `insteadOf` rewrites) is a path on this machine,
+/// which needs no network to reach.
+pub fn remote_is_local(runner: &dyn ProcessRunner, repo_dir: &std::path::Path) -> bool {
+    if !repo_dir.join(".git").exists() {
+        return false;
+    }
+    Command::new("git")
+        .current_dir(repo_dir)
+        .args(["ls-remote", "--get-url", "origin"])
+        .run_with(runner, Category::Git)
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| {
+            let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            url.starts_with('/') || url.starts_with("file://") || url.starts_with("./") || url.starts_with("../")
+        })
+        .unwrap_or(false)
+}
+
+/// Whether `repo_dir`'s origin can be reached: always when it is a local
+/// path, otherwise by probing the remote's own host, so a self-hosted server
+/// on the LAN counts as online without internet access.
+pub fn remote_reachable(runner: &dyn ProcessRunner, repo_dir: &std::path::Path) -> bool {
+    if remote_is_local(runner, repo_dir) {
+        return true;
+    }
+    match git_line(runner, repo_dir, &["remote", "get-url", "origin"]) {
+        Some(url) => capabilities::reachable(&url),
+        None => capabilities::has(Tool::Network),
+    }
+}
+
+/// `sync --squash`: fold the commits not on `origin/<branch>` yet (changes
+/// queued while offline or with `sync.mode` manual) into one.
+fn squash_unpushed(runner: &dyn ProcessRunner, repo_dir: &std::path::Path, branch: &str, message: Option<&str>, account: Option<&Account>) -> Result<()> {
+    let remote = format!("origin/{}", branch);
+    let count = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["rev-list", "--count", "--no-merges", &format!("{}..HEAD", remote)])
+        .run_with(runner, Category::Git)?;
+    let count: usize = String::from_utf8_lossy(&count.stdout).trim().parse().unwrap_or(0);
+    if count < 2 {
+        return Ok(());
+    }
+    let reset = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["reset", "--soft", &remote])
+        .run_with(runner, Category::Git)?;
+    if !reset.status.success() {
+        println!("⚠️  Could not squash: {}", String::from_utf8_lossy(&reset.stderr).trim());
+        return Ok(());
+    }
+    let message = message.map(str::to_string).unwrap_or_else(|| crate::changeset::describe(runner, repo_dir));
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["commit", "--quiet", "-m", &message])
+        .with_account(account)
+        .run_with(runner, Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("git commit failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    println!("🗜️  Squashed {} commits into one: {}", count, message);
+    Ok(())
+}
+
+/// Whether `branch` has commits that `origin` hasn't seen, including when the
+/// remote branch doesn't exist yet.
+fn has_unpushed_commits(runner: &dyn ProcessRunner, repo_dir: &std::path::Path, branch: &str) -> bool {
+    let has_remote = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["remote", "get-url", "origin"])
+        .run_with(runner, Category::Git)
+        .map(|output| output.status.success())
+        .unwrap_or(false);
+    if !has_remote {
+        return false;
+    }
+    match Command::new("git")
+        .current_dir(repo_dir)
+        .args(["rev-list", "--count", &format!("origin/{0}..{0}", branch)])
+        .run_with(runner, Category::Git)
+    {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim() != "0"
+        }
+        _ => true,
+    }
+}
+
+/// Create the local repository `name`, without a remote until the first
+/// `sync` offers one. It becomes the default when there is none yet.
+pub async fn create_local_repo(name: &str) -> Result<()> {
+    capabilities::require(Tool::Git, "a local repository")?;
+    let mut config = crate::config::Config::load()?;
+    let first = config.get_default_repo().is_none();
+    init_snippets_repo(&get_repos_dir()?.join(name)).await?;
+    config.set_repo_visibility(name, Visibility::Private)?;
+    if first {
+        config.set_default_repo(name.to_string())?;
+        println!("🎯 '{}' is your default repository", name);
+    }
+    Ok(())
+}
+
 async fn init_snippets_repo(snippets_dir: &std::path::Path) -> Result<()> {
     fs::create_dir_all(snippets_dir)?;
     
     // Initialize git repository
     Command::new("git")
         .current_dir(snippets_dir)
-        .args(&["init"])
-        .output()?;
+        .args(["init"])
+        .timed_output(Category::Git)?;
     
-    // Set default branch to main
+    // Start on the user's preferred initial branch (init.defaultBranch), else main
+    let branch = git_line(&SystemRunner, snippets_dir, &["config", "--get", "init.defaultBranch"])
+        .unwrap_or_else(|| "main".to_string());
     Command::new("git")
         .current_dir(snippets_dir)
-        .args(&["branch", "-M", "main"])
-        .output()?;
+        .args(["branch", "-M", &branch])
+        .timed_output(Category::Git)?;
     
     // Create .gitignore
     let gitignore_content = "# Temp files\n*.tmp\n*.swp\n*~\n\n# OS files\n.DS_Store\nThumbs.db\n";
@@ -149,18 +538,18 @@ async fn init_snippets_repo(snippets_dir: &std::path::Path) -> Result<()> {
     fs::write(snippets_dir.join("README.md"), readme_content)?;
     
     // Configure git user for this repository
-    configure_git_user(snippets_dir)?;
+    configure_git_user(snippets_dir).await?;
     
     // Initial commit
     Command::new("git")
         .current_dir(snippets_dir)
-        .args(&["add", "."])
-        .output()?;
+        .args(["add", "."])
+        .timed_output(Category::Git)?;
     
     let commit_output = Command::new("git")
         .current_dir(snippets_dir)
-        .args(&["commit", "-m", "Initial commit"])
-        .output()?;
+        .args(["commit", "-m", "Initial commit"])
+        .timed_output(Category::Git)?;
     
     if !commit_output.status.success() {
         let stderr = String::from_utf8_lossy(&commit_output.stderr);
@@ -186,15 +575,16 @@ async fn clone_default_repo() -> Result<()> {
     // Clone the repository
     let output = Command::new("git")
         .current_dir(parent_dir)
-        .args(&[
+        .args([
             "clone", 
             &format!("https://github.com/{}", DEFAULT_REPO),
             snippets_dir.file_name().unwrap().to_str().unwrap()
         ])
-        .output()?;
+        .timed_output(Category::Git)?;
     
     if output.status.success() {
         println!("✅ Cloned community snippets repository");
+        crate::index::refresh_index_quietly();
     } else {
         println!("⚠️  Could not clone default repository. Creating local repository instead.");
         init_snippets_repo(&snippets_dir).await?;
@@ -203,48 +593,26 @@ async fn clone_default_repo() -> Result<()> {
     Ok(())
 }
 
-async fn load_snippets() -> Result<Vec<Snippet>> {
-    let repo_dir = get_snippets_dir()?;
-    let snippets_dir = repo_dir.join("snippets");
-    
-    if !snippets_dir.exists() {
-        return Ok(Vec::new());
-    }
-    
-    let mut snippets = Vec::new();
-    
-    for entry in fs::read_dir(snippets_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(snippet) = crate::publish::parse_markdown_frontmatter(&content) {
-                    snippets.push(snippet);
-                }
-            }
-        }
-    }
-    
-    Ok(snippets)
+/// `setup --org`: create the repository under a GitHub organization, for a
+/// company-wide snippet library.
+pub struct OrgOptions {
+    pub name: String,
+    /// Slug of the team to give read access
+    pub team: Option<String>,
 }
 
-pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
-    println!("🔧 Setting up GitHub repository for claude-md-snippets...");
+pub async fn setup_repository(repo_name_option: Option<String>, template: Option<StarterTemplate>, host: HostOptions, org: Option<OrgOptions>) -> Result<()> {
+    println!("🔧 Setting up {} repository for claude-md-snippets...", host.kind.label());
     
     // Check if gh CLI is available
-    let gh_check = Command::new("gh").arg("--version").output();
-    
-    let use_gh_cli = match gh_check {
-        Ok(output) if output.status.success() => {
-            println!("✅ GitHub CLI detected");
-            true
-        }
-        _ => {
-            println!("⚠️  GitHub CLI not found. You'll need to create the repository manually.");
-            false
-        }
-    };
+    capabilities::require(Tool::Git, "setup")?;
+    if host.kind == HostKind::Gitea && host.base_url.is_none() {
+        anyhow::bail!("--host gitea needs --host-url, e.g. https://codeberg.org");
+    }
+    if org.is_some() && host.kind != HostKind::Github {
+        anyhow::bail!("--org is only supported with --host github");
+    }
+    let gh_available = host.kind == HostKind::Github && capabilities::has(Tool::Gh);
     
     // Get repository visibility and name from user
     let (is_private, github_repo_name) = if let Some(provided_name) = &repo_name_option {
@@ -252,11 +620,7 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
         (true, provided_name.clone())
     } else {
         // Ask for visibility
-        print!("Create repository as private or public? (p/P for public, default: private): ");
-        io::stdout().flush()?;
-        let mut visibility = String::new();
-        io::stdin().read_line(&mut visibility)?;
-        let visibility = visibility.trim().to_lowercase();
+        let visibility = crate::prompt::input("Create repository as private or public? (p for public)", Some("private"))?.to_lowercase();
         
         let is_private = !matches!(visibility.as_str(), "p" | "public");
         
@@ -268,17 +632,7 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
         };
         
         let repo_type = if is_private { "private" } else { "public" };
-        print!("Enter GitHub repository name (default: {}): ", default_name);
-        io::stdout().flush()?;
-        let mut repo_name = String::new();
-        io::stdin().read_line(&mut repo_name)?;
-        let repo_name = repo_name.trim();
-        
-        let final_name = if repo_name.is_empty() { 
-            default_name.to_string()
-        } else { 
-            repo_name.to_string()
-        };
+        let final_name = crate::prompt::input(&format!("Enter {} repository name", host.kind.label()), Some(default_name))?;
         
         println!("Creating {} repository '{}'", repo_type, final_name);
         
@@ -289,42 +643,89 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
     let repos_dir = get_repos_dir()?;
     let snippets_dir = repos_dir.join(&github_repo_name);
     
-    if use_gh_cli {
-        // Create repository using gh CLI
-        let visibility_flag = if is_private { "--private" } else { "--public" };
-        let visibility_text = if is_private { "private" } else { "public" };
-        println!("📦 Creating {} repository '{}'...", visibility_text, github_repo_name);
+    if host.kind != HostKind::Github {
+        if !snippets_dir.join(".git").exists() {
+            init_snippets_repo(&snippets_dir).await?;
+        }
+        seed_template(&snippets_dir, template)?;
+        hosts::setup_on_host(&host, &github_repo_name, is_private, &snippets_dir).await?;
         
-        let create_output = Command::new("gh")
-            .args(&["repo", "create", &github_repo_name, visibility_flag, "--description", "Personal CLAUDE.md snippets"])
-            .output()?;
+        let mut config = crate::config::Config::load()?;
+        config.set_default_repo(github_repo_name.clone())?;
+        config.set_repo_visibility(&github_repo_name, if is_private { Visibility::Private } else { Visibility::Public })?;
+        println!("🎯 Set '{}' as your default repository", github_repo_name);
+        return Ok(());
+    }
+    
+    let account = accounts::for_repo(&github_repo_name)?;
+    if let Some(account) = &account {
+        println!("👤 Using account {}", account.username);
+    }
+    
+    // Prefer the API with a token; the gh CLI is the fallback
+    let api = GitHubClient::new(account.as_ref());
+    if api.is_some() {
+        println!("✅ GitHub token found, using the GitHub API");
+    } else if gh_available {
+        println!("✅ GitHub CLI detected");
+    } else {
+        println!("⚠️  No GitHub token or GitHub CLI found. You'll need to create the repository manually.");
+        println!("💡 Set GITHUB_TOKEN (or install gh) to let setup create it for you");
+    }
+    
+    if api.is_some() || gh_available {
+        let visibility_text = if is_private { "private" } else { "public" };
+        let org_name = org.as_ref().map(|org| org.name.as_str());
+        match org_name {
+            Some(org) => println!("📦 Creating {} repository '{}' in organization '{}'...", visibility_text, github_repo_name, org),
+            None => println!("📦 Creating {} repository '{}'...", visibility_text, github_repo_name),
+        }
         
-        if !create_output.status.success() {
-            let stderr = String::from_utf8_lossy(&create_output.stderr);
-            if stderr.contains("already exists") {
-                println!("ℹ️  Repository '{}' already exists", github_repo_name);
-            } else {
-                println!("⚠️  Failed to create repository: {}", stderr);
-                return manual_setup_instructions(&github_repo_name, &snippets_dir, is_private);
+        let description = if org.is_some() { "Shared CLAUDE.md snippets" } else { "Personal CLAUDE.md snippets" };
+        let created = match &api {
+            Some(api) => api.create_repo(org_name, &github_repo_name, is_private, description).await,
+            None => {
+                let full_name = org_name.map(|org| format!("{}/{}", org, github_repo_name)).unwrap_or(github_repo_name.clone());
+                create_repo_with_gh(&full_name, is_private, description, account.as_ref())
+            }
+        };
+        match created {
+            Ok(CreatedRepo::Created) => println!("✅ Repository created successfully!"),
+            Ok(CreatedRepo::AlreadyExists) => println!("ℹ️  Repository '{}' already exists", github_repo_name),
+            Err(e) => {
+                println!("⚠️  Failed to create repository: {}", e);
+                return manual_setup_instructions(&github_repo_name, &snippets_dir, is_private, org_name);
             }
-        } else {
-            println!("✅ Repository created successfully!");
         }
         
         // Initialize local repository if needed
         if !snippets_dir.join(".git").exists() {
             init_snippets_repo(&snippets_dir).await?;
         }
+        seed_template(&snippets_dir, template)?;
+        if let Some(org) = &org {
+            add_codeowners(&snippets_dir, org)?;
+        }
         
         // Add remote
-        let username = get_github_username()?;
-        let remote_url = format!("https://github.com/{}/{}.git", username, github_repo_name);
+        let username = match (&account, &api) {
+            (Some(account), _) => account.username.clone(),
+            (None, Some(api)) => match api.authenticated_user().await {
+                Ok(user) => user.login,
+                Err(_) => get_github_username()?,
+            },
+            (None, None) => get_github_username()?,
+        };
+        // Accounts bring their own credentials; otherwise push with the API token
+        let push_token = if account.is_none() { api.as_ref().map(|api| api.token()) } else { None };
+        let owner = org_name.unwrap_or(&username);
+        let remote_url = format!("https://github.com/{}/{}.git", owner, github_repo_name);
         
         println!("🔗 Adding remote origin...");
         let remote_output = Command::new("git")
             .current_dir(&snippets_dir)
-            .args(&["remote", "add", "origin", &remote_url])
-            .output();
+            .args(["remote", "add", "origin", &remote_url])
+            .timed_output(Category::Git);
         
         match remote_output {
             Ok(output) if output.status.success() => {
@@ -336,8 +737,8 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
                     // Update existing remote
                     Command::new("git")
                         .current_dir(&snippets_dir)
-                        .args(&["remote", "set-url", "origin", &remote_url])
-                        .output()?;
+                        .args(["remote", "set-url", "origin", &remote_url])
+                        .timed_output(Category::Git)?;
                     println!("✅ Remote origin updated: {}", remote_url);
                 } else {
                     println!("⚠️  Could not add remote: {}", stderr);
@@ -349,15 +750,18 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
         }
         
         // Try initial push, but handle existing repository case
+        let branch = local_branch(&snippets_dir);
         println!("📤 Pushing to remote repository...");
         let push_output = Command::new("git")
             .current_dir(&snippets_dir)
-            .args(&["push", "-u", "origin", "main"])
-            .output()?;
+            .args(["push", "-u", "origin", &branch])
+            .with_account(account.as_ref())
+            .with_token(&username, push_token)
+            .timed_output(Category::Git)?;
         
         if push_output.status.success() {
             println!("✅ Setup complete! Your snippets repository is ready.");
-            println!("🌐 Repository: https://github.com/{}/{}", username, github_repo_name);
+            println!("🌐 Repository: https://github.com/{}/{}", owner, github_repo_name);
             println!("📁 Local directory: {}", snippets_dir.display());
         } else {
             let stderr = String::from_utf8_lossy(&push_output.stderr);
@@ -369,8 +773,10 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
                 // Try to pull and merge with explicit merge strategy
                 let pull_output = Command::new("git")
                     .current_dir(&snippets_dir)
-                    .args(&["pull", "origin", "main", "--allow-unrelated-histories", "--no-rebase"])
-                    .output()?;
+                    .args(["pull", "origin", &branch, "--allow-unrelated-histories", "--no-rebase"])
+                    .with_account(account.as_ref())
+                    .with_token(&username, push_token)
+                    .timed_output(Category::Git)?;
                 
                 if pull_output.status.success() {
                     println!("✅ Successfully synced with existing repository content.");
@@ -378,12 +784,14 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
                     // Try push again
                     let retry_push = Command::new("git")
                         .current_dir(&snippets_dir)
-                        .args(&["push", "-u", "origin", "main"])
-                        .output()?;
+                        .args(["push", "-u", "origin", &branch])
+                        .with_account(account.as_ref())
+                        .with_token(&username, push_token)
+                        .timed_output(Category::Git)?;
                     
                     if retry_push.status.success() {
                         println!("✅ Setup complete! Your snippets repository is ready.");
-                        println!("🌐 Repository: https://github.com/{}/{}", username, github_repo_name);
+                        println!("🌐 Repository: https://github.com/{}/{}", owner, github_repo_name);
                         println!("📁 Local directory: {}", snippets_dir.display());
                     } else {
                         println!("⚠️  Could not push after sync. Manual intervention may be needed.");
@@ -400,49 +808,106 @@ pub async fn setup_repository(repo_name_option: Option<String>) -> Result<()> {
             }
         }
         
+        if let Some(org) = &org {
+            org_defaults(api.as_ref(), org, &github_repo_name).await;
+        }
+        
         // Set as default repository regardless of push success
         let mut config = crate::config::Config::load()?;
         config.set_default_repo(github_repo_name.clone())?;
+        config.set_repo_visibility(&github_repo_name, if is_private { Visibility::Private } else { Visibility::Public })?;
         println!("🎯 Set '{}' as your default repository", github_repo_name);
         
     } else {
-        manual_setup_instructions(&github_repo_name, &snippets_dir, is_private)?;
+        manual_setup_instructions(&github_repo_name, &snippets_dir, is_private, org.as_ref().map(|org| org.name.as_str()))?;
         
         // Initialize local repository for manual setup too
         if !snippets_dir.join(".git").exists() {
             init_snippets_repo(&snippets_dir).await?;
         }
+        seed_template(&snippets_dir, template)?;
         
         // Set as default repository
         let mut config = crate::config::Config::load()?;
         config.set_default_repo(github_repo_name.clone())?;
+        config.set_repo_visibility(&github_repo_name, if is_private { Visibility::Private } else { Visibility::Public })?;
         println!("🎯 Set '{}' as your default repository", github_repo_name);
     }
     
     Ok(())
 }
 
-fn manual_setup_instructions(repo_name: &str, snippets_dir: &std::path::Path, is_private: bool) -> Result<()> {
+fn seed_template(snippets_dir: &std::path::Path, template: Option<StarterTemplate>) -> Result<()> {
+    if let Some(template) = template {
+        let config = crate::config::Config::load()?;
+        crate::starters::seed_starter_snippets(snippets_dir, template, config.get_templates_source())?;
+    }
+    Ok(())
+}
+
+/// Commit a `.github/CODEOWNERS` stub so changes to an organization's
+/// snippets get reviewed by the team (or, until it's filled in, the org admins).
+fn add_codeowners(snippets_dir: &std::path::Path, org: &OrgOptions) -> Result<()> {
+    let path = snippets_dir.join(".github").join("CODEOWNERS");
+    if path.exists() {
+        return Ok(());
+    }
+    let owners = match &org.team {
+        Some(team) => format!("* @{}/{}\n", org.name, team),
+        None => format!("# Replace with the team that reviews snippet changes:\n# * @{}/<team>\n", org.name),
+    };
+    fs::create_dir_all(path.parent().unwrap_or(snippets_dir))?;
+    fs::write(&path, format!("# Reviewers for changes to the shared snippets\n{}", owners))?;
+    Command::new("git")
+        .current_dir(snippets_dir)
+        .args(["add", ".github/CODEOWNERS"])
+        .timed_output(Category::Git)?;
+    Command::new("git")
+        .current_dir(snippets_dir)
+        .args(["commit", "-m", "Add CODEOWNERS"])
+        .timed_output(Category::Git)?;
+    println!("👥 Added .github/CODEOWNERS");
+    Ok(())
+}
+
+/// Give the team read access and point at branch protection, which needs
+/// an org admin's decision on required reviews.
+async fn org_defaults(api: Option<&GitHubClient>, org: &OrgOptions, repo_name: &str) {
+    match (&org.team, api) {
+        (Some(team), Some(api)) => match api.grant_team_read(&org.name, team, repo_name).await {
+            Ok(()) => println!("✅ Team '{}/{}' can read the repository", org.name, team),
+            Err(e) => println!("⚠️  Could not give team '{}' read access: {}", team, e),
+        },
+        (Some(team), None) => println!("💡 Give team '{}' read access at https://github.com/{}/{}/settings/access", team, org.name, repo_name),
+        (None, _) => println!("💡 Pass --team <slug> to give a team read access, or add it at https://github.com/{}/{}/settings/access", org.name, repo_name),
+    }
+    println!("💡 Protect the default branch (require pull request reviews from CODEOWNERS) at https://github.com/{}/{}/settings/branches", org.name, repo_name);
+}
+
+fn manual_setup_instructions(repo_name: &str, snippets_dir: &std::path::Path, is_private: bool, org: Option<&str>) -> Result<()> {
     let visibility = if is_private { "private" } else { "public" };
     println!("\n📝 Manual Setup Instructions:");
-    println!("1. Create a new {} repository on GitHub named '{}'", visibility, repo_name);
+    match org {
+        Some(org) => println!("1. Create a new {} repository in the GitHub organization '{}' named '{}'", visibility, org, repo_name),
+        None => println!("1. Create a new {} repository on GitHub named '{}'", visibility, repo_name),
+    }
     println!("2. Run the following commands:");
     println!("   cd {}", snippets_dir.display());
-    println!("   git remote add origin https://github.com/YOUR_USERNAME/{}.git", repo_name);
-    println!("   git push -u origin main");
+    println!("   git remote add origin https://github.com/{}/{}.git", org.unwrap_or("YOUR_USERNAME"), repo_name);
+    println!("   git push -u origin {}", local_branch(snippets_dir));
     println!("\n💡 After setup, use 'claude-md-snippets sync' to upload snippets");
     Ok(())
 }
 
-fn configure_git_user(snippets_dir: &std::path::Path) -> Result<()> {
+async fn configure_git_user(snippets_dir: &std::path::Path) -> Result<()> {
     // Check if git is already configured globally
     let global_name = Command::new("git")
-        .args(&["config", "--global", "user.name"])
-        .output();
+        .args(["config", "--global", "user.name"])
+        .timed_output(Category::Git);
     
     let global_email = Command::new("git")
-        .args(&["config", "--global", "user.email"])
-        .output();
+        .args(["config", "--global", "user.email"])
+        .timed_output(Category::Git);
     
     // If global config exists, use it
     if let (Ok(name_output), Ok(email_output)) = (&global_name, &global_email) {
@@ -451,12 +916,19 @@ fn configure_git_user(snippets_dir: &std::path::Path) -> Result<()> {
         }
     }
     
-    // Try to get info from GitHub CLI
+    // Try the GitHub API, then the GitHub CLI
+    let api_user = match GitHubClient::new(None) {
+        Some(api) => api.authenticated_user().await.ok(),
+        None => None,
+    };
     let gh_user = Command::new("gh")
-        .args(&["api", "user"])
+        .args(["api", "user"])
         .output();
     
-    let (username, email) = if let Ok(output) = gh_user {
+    let (username, email) = if let Some(user) = api_user {
+        let email = user.email.unwrap_or_else(|| format!("{}@users.noreply.github.com", user.login));
+        (user.login, email)
+    } else if let Ok(output) = gh_user {
         if output.status.success() {
             let user_info = String::from_utf8_lossy(&output.stdout);
             if let Ok(json) = serde_json::from_str::<serde_json::Value>(&user_info) {
@@ -478,21 +950,39 @@ fn configure_git_user(snippets_dir: &std::path::Path) -> Result<()> {
     // Configure for this repository only
     Command::new("git")
         .current_dir(snippets_dir)
-        .args(&["config", "user.name", &username])
-        .output()?;
+        .args(["config", "user.name", &username])
+        .timed_output(Category::Git)?;
     
     Command::new("git")
         .current_dir(snippets_dir)
-        .args(&["config", "user.email", &email])
-        .output()?;
+        .args(["config", "user.email", &email])
+        .timed_output(Category::Git)?;
     
     Ok(())
 }
 
+fn create_repo_with_gh(name: &str, is_private: bool, description: &str, account: Option<&Account>) -> Result<CreatedRepo> {
+    let visibility_flag = if is_private { "--private" } else { "--public" };
+    let output = Command::new("gh")
+        .args(["repo", "create", name, visibility_flag, "--description", description])
+        .with_account(account)
+        .output()?;
+    
+    if output.status.success() {
+        return Ok(CreatedRepo::Created);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("already exists") {
+        Ok(CreatedRepo::AlreadyExists)
+    } else {
+        anyhow::bail!("{}", stderr.trim())
+    }
+}
+
 fn get_github_username() -> Result<String> {
     // Try to get username from gh CLI
     let output = Command::new("gh")
-        .args(&["api", "user", "--jq", ".login"])
+        .args(["api", "user", "--jq", ".login"])
         .output()?;
     
     if output.status.success() {
@@ -501,9 +991,188 @@ fn get_github_username() -> Result<String> {
     }
     
     // Fallback: ask user
-    print!("Enter your GitHub username: ");
-    io::stdout().flush()?;
-    let mut username = String::new();
-    io::stdin().read_line(&mut username)?;
-    Ok(username.trim().to_string())
-}
\ No newline at end of file
+    crate::prompt::input("Enter your GitHub username", None)
+}
+
+/// A token for the GitHub API: the repository's account if one is configured,
+/// otherwise GITHUB_TOKEN/GH_TOKEN or the GitHub CLI login.
+pub fn github_token(account: Option<&Account>) -> Option<String> {
+    if let Some(account) = account {
+        return account.token();
+    }
+    
+    for var in ["GITHUB_TOKEN", "GH_TOKEN"] {
+        if let Ok(token) = std::env::var(var) {
+            if !token.trim().is_empty() {
+                return Some(token.trim().to_string());
+            }
+        }
+    }
+    
+    if !capabilities::has(Tool::Gh) {
+        return None;
+    }
+    let output = Command::new("gh")
+        .args(["auth", "token"])
+        .output()
+        .ok()?;
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !token.is_empty() {
+        Some(token)
+    } else {
+        None
+    }
+}
+
+/// The (owner, repo) of a GitHub remote URL, in either SSH or HTTPS form.
+pub fn parse_github_remote(remote_url: &str) -> Option<(String, String)> {
+    let path = remote_url
+        .strip_prefix("git@github.com:")
+        .or_else(|| remote_url.strip_prefix("https://github.com/"))
+        .or_else(|| remote_url.strip_prefix("ssh://git@github.com/"))?;
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, repo) = path.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// Follow GitHub's redirect for a renamed or transferred repository and point
+/// the local clone (and, optionally, its directory name) at the new location.
+pub async fn web_sync(repo_name: &str) -> Result<()> {
+    capabilities::require(Tool::Git, "repo web-sync")?;
+    capabilities::require(Tool::Network, "repo web-sync")?;
+    
+    let repo_dir = get_repos_dir()?.join(repo_name);
+    if !repo_dir.join(".git").exists() {
+        anyhow::bail!("Repository '{}' is not a git repository at {}", repo_name, repo_dir.display());
+    }
+    
+    let output = Command::new("git")
+        .current_dir(&repo_dir)
+        .args(["remote", "get-url", "origin"])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("No git remote 'origin' found for repository '{}'", repo_name);
+    }
+    let remote_url = String::from_utf8(output.stdout)?.trim().to_string();
+    let Some((owner, repo)) = parse_github_remote(&remote_url) else {
+        anyhow::bail!("'{}' is not a GitHub remote; web-sync only understands GitHub redirects", remote_url);
+    };
+    
+    println!("🔍 Checking github.com/{}/{}...", owner, repo);
+    let account = accounts::for_repo(repo_name)?;
+    let mut request = reqwest::Client::new()
+        .get(format!("https://api.github.com/repos/{}/{}", owner, repo))
+        .header("User-Agent", "claude-md-snippets")
+        .header("Accept", "application/vnd.github+json");
+    if let Some(token) = github_token(account.as_ref()) {
+        request = request.bearer_auth(token);
+    }
+    let response = crate::github_api::send(request).await?;
+    
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        anyhow::bail!("github.com/{}/{} was not found: it may have been deleted, or it is private and no token is available", owner, repo);
+    }
+    if !response.status().is_success() {
+        anyhow::bail!("GitHub API returned {}", response.status());
+    }
+    let json: serde_json::Value = response.json().await?;
+    let full_name = json["full_name"].as_str().unwrap_or_default().to_string();
+    
+    if full_name.is_empty() || full_name.eq_ignore_ascii_case(&format!("{}/{}", owner, repo)) {
+        println!("✅ Remote is up to date: {}", remote_url);
+        return Ok(());
+    }
+    
+    // Keep the remote's protocol
+    let new_url = if remote_url.starts_with("git@") || remote_url.starts_with("ssh://") {
+        format!("git@github.com:{}.git", full_name)
+    } else {
+        format!("https://github.com/{}.git", full_name)
+    };
+    println!("🔀 Repository moved: {}/{} -> {}", owner, repo, full_name);
+    
+    let output = Command::new("git")
+        .current_dir(&repo_dir)
+        .args(["remote", "set-url", "origin", &new_url])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("Could not update remote: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    println!("✅ Remote origin updated: {}", new_url);
+    
+    // Local directories are named after the GitHub repository by setup
+    let new_repo_name = full_name.rsplit('/').next().unwrap_or(&full_name).to_string();
+    if repo_name == repo && new_repo_name != repo_name && !get_repos_dir()?.join(&new_repo_name).exists() {
+        if crate::prompt::confirm(&format!("Rename the local repository '{}' to '{}' as well?", repo_name, new_repo_name), true)? {
+            fs::rename(&repo_dir, get_repos_dir()?.join(&new_repo_name))?;
+            crate::config::Config::update(|config| {
+                config.rename_repo(repo_name, &new_repo_name);
+                Ok(())
+            })?;
+            println!("✅ Renamed local repository to '{}'", new_repo_name);
+        }
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_mode_defers_changes() {
+        assert_eq!(deferred_because(SyncMode::Auto, ChangeKind::Edit), None);
+        assert_eq!(deferred_because(SyncMode::OnPublishOnly, ChangeKind::Publish), None);
+        assert_eq!(deferred_because(SyncMode::OnPublishOnly, ChangeKind::Edit), Some("sync.mode is on-publish-only"));
+        assert_eq!(deferred_because(SyncMode::Manual, ChangeKind::Publish), Some("sync.mode is manual"));
+        assert_eq!(deferred_because(SyncMode::Manual, ChangeKind::Watched), None);
+    }
+
+    #[test]
+    fn test_parse_github_remote() {
+        let expected = Some(("me".to_string(), "snips".to_string()));
+        assert_eq!(parse_github_remote("git@github.com:me/snips.git"), expected);
+        assert_eq!(parse_github_remote("https://github.com/me/snips"), expected);
+        assert_eq!(parse_github_remote("https://gitlab.com/me/snips.git"), None);
+    }
+
+    #[test]
+    fn test_has_unpushed_commits() {
+        use crate::process::fake::FakeRunner;
+        let dir = std::path::Path::new("/repo");
+
+        let ahead = FakeRunner::new().respond("git rev-list", "2\n", true);
+        assert!(has_unpushed_commits(&ahead, dir, "main"));
+        assert_eq!(ahead.calls(), ["git remote get-url origin", "git rev-list --count origin/main..main"]);
+
+        let up_to_date = FakeRunner::new().respond("git rev-list", "0\n", true);
+        assert!(!has_unpushed_commits(&up_to_date, dir, "main"));
+
+        // Never pushed: the remote branch doesn't exist yet
+        let never_pushed = FakeRunner::new().respond("git rev-list", "", false);
+        assert!(has_unpushed_commits(&never_pushed, dir, "main"));
+
+        let no_remote = FakeRunner::new().respond("git remote get-url", "", false);
+        assert!(!has_unpushed_commits(&no_remote, dir, "master"));
+    }
+
+    #[test]
+    fn test_remote_default_branch() {
+        use crate::process::fake::FakeRunner;
+        let dir = std::path::Path::new("/repo");
+
+        let cloned = FakeRunner::new().respond("git symbolic-ref", "origin/master\n", true);
+        assert_eq!(remote_default_branch(&cloned, dir, false).as_deref(), Some("master"));
+
+        let asked = FakeRunner::new()
+            .respond("git symbolic-ref", "", false)
+            .respond("git ls-remote", "ref: refs/heads/trunk\tHEAD\n0123abcd\tHEAD\n", true);
+        assert_eq!(remote_default_branch(&asked, dir, true).as_deref(), Some("trunk"));
+        assert_eq!(remote_default_branch(&asked, dir, false), None);
+    }
+}
@@ -0,0 +1,203 @@
+use anyhow::Result;
+use reqwest::header::HeaderMap;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde_json::Value;
+use std::time::Duration;
+use crate::config::Account;
+
+const API_BASE: &str = "https://api.github.com";
+
+/// Tries per request, including the first one.
+const MAX_ATTEMPTS: u32 = 4;
+/// Wait before the first retry; doubled for each retry after it.
+const BASE_DELAY: Duration = Duration::from_secs(1);
+/// Longest wait for a rate limit to reset before giving up on the request.
+const MAX_WAIT: Duration = Duration::from_secs(60);
+
+/// A token-authenticated client for the GitHub REST API. Setup uses it to
+/// read the user and create repositories, so the `gh` CLI is only needed as
+/// a fallback when no token can be found.
+///
+/// Built on reqwest rather than octocrab: only a handful of endpoints are
+/// needed, reqwest is already a dependency (the GitLab and Gitea clients in
+/// `hosts` use it the same way), and octocrab would pull in a second HTTP stack.
+pub struct GitHubClient {
+    http: reqwest::Client,
+    token: String,
+}
+
+pub struct GitHubUser {
+    pub login: String,
+    pub email: Option<String>,
+}
+
+pub enum CreatedRepo {
+    Created,
+    AlreadyExists,
+}
+
+impl GitHubClient {
+    /// A client using the account's token, else GITHUB_TOKEN/GH_TOKEN, else
+    /// the token stored by `gh auth login`. `None` if there is no token.
+    pub fn new(account: Option<&Account>) -> Option<Self> {
+        let token = crate::github::github_token(account)?;
+        Some(GitHubClient { http: reqwest::Client::new(), token })
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    async fn request(&self, method: Method, path: &str, body: Option<Value>) -> Result<(StatusCode, Value)> {
+        let mut request = self.http
+            .request(method, format!("{}{}", API_BASE, path))
+            .header("User-Agent", "claude-md-snippets")
+            .header("Accept", "application/vnd.github+json")
+            .bearer_auth(&self.token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = send(request).await?;
+        let status = response.status();
+        let json = response.json().await.unwrap_or_default();
+        Ok((status, json))
+    }
+
+    pub async fn authenticated_user(&self) -> Result<GitHubUser> {
+        let (status, json) = self.request(Method::GET, "/user", None).await?;
+        if !status.is_success() {
+            anyhow::bail!("GitHub API returned {}: {}", status, api_message(&json));
+        }
+        Ok(GitHubUser {
+            login: json["login"].as_str().unwrap_or_default().to_string(),
+            email: json["email"].as_str().map(|email| email.to_string()),
+        })
+    }
+
+    /// Create `name` under the authenticated user, or under `org` when given.
+    pub async fn create_repo(&self, org: Option<&str>, name: &str, private: bool, description: &str) -> Result<CreatedRepo> {
+        let body = serde_json::json!({ "name": name, "private": private, "description": description });
+        let path = match org {
+            Some(org) => format!("/orgs/{}/repos", org),
+            None => "/user/repos".to_string(),
+        };
+        let (status, json) = self.request(Method::POST, &path, Some(body)).await?;
+        if status.is_success() {
+            return Ok(CreatedRepo::Created);
+        }
+        let already_exists = json["errors"]
+            .as_array()
+            .is_some_and(|errors| errors.iter().any(|e| e["message"].as_str().is_some_and(|m| m.contains("already exists"))));
+        if status == StatusCode::UNPROCESSABLE_ENTITY && already_exists {
+            return Ok(CreatedRepo::AlreadyExists);
+        }
+        anyhow::bail!("GitHub API returned {}: {}", status, api_message(&json))
+    }
+
+    /// Give the organization team `team` (its slug) read access to `org/repo`.
+    pub async fn grant_team_read(&self, org: &str, team: &str, repo: &str) -> Result<()> {
+        let path = format!("/orgs/{}/teams/{}/repos/{}/{}", org, team, org, repo);
+        let (status, json) = self.request(Method::PUT, &path, Some(serde_json::json!({ "permission": "pull" }))).await?;
+        if !status.is_success() {
+            anyhow::bail!("GitHub API returned {}: {}", status, api_message(&json));
+        }
+        Ok(())
+    }
+
+    /// Open an issue and return its URL.
+    pub async fn create_issue(&self, owner: &str, repo: &str, title: &str, body: &str) -> Result<String> {
+        let path = format!("/repos/{}/{}/issues", owner, repo);
+        let (status, json) = self.request(Method::POST, &path, Some(serde_json::json!({ "title": title, "body": body }))).await?;
+        if !status.is_success() {
+            anyhow::bail!("GitHub API returned {}: {}", status, api_message(&json));
+        }
+        Ok(json["html_url"].as_str().unwrap_or_default().to_string())
+    }
+}
+
+/// Send a GitHub API request, retrying with exponential backoff on server
+/// errors and dropped connections, and waiting out rate limits as the
+/// `Retry-After` / `X-RateLimit-Reset` headers say. A rate limit that resets
+/// later than `MAX_WAIT` fails right away with the time it resets, so bulk
+/// operations stop cleanly instead of hanging.
+pub async fn send(request: RequestBuilder) -> Result<Response> {
+    let mut attempt = 1;
+    loop {
+        // Requests with a streamed body can't be cloned, so they get one try
+        let Some(this_try) = request.try_clone().filter(|_| attempt < MAX_ATTEMPTS) else {
+            return Ok(request.send().await?);
+        };
+        let delay = match this_try.send().await {
+            Ok(response) => {
+                let Some(delay) = retry_delay(response.status(), response.headers(), attempt, chrono::Utc::now().timestamp()) else {
+                    return Ok(response);
+                };
+                if delay > MAX_WAIT {
+                    let resets = chrono::Local::now() + delay;
+                    anyhow::bail!("GitHub API rate limit exceeded; it resets at {}. Try again then, or set GITHUB_TOKEN for a higher limit", resets.format("%H:%M"));
+                }
+                println!("⏳ GitHub API returned {}; retrying in {}s (attempt {}/{})", response.status(), delay.as_secs().max(1), attempt + 1, MAX_ATTEMPTS);
+                delay
+            }
+            Err(e) if e.is_connect() || e.is_timeout() => {
+                let delay = backoff(attempt);
+                println!("⏳ Could not reach GitHub ({}); retrying in {}s (attempt {}/{})", e, delay.as_secs(), attempt + 1, MAX_ATTEMPTS);
+                delay
+            }
+            Err(e) => return Err(e.into()),
+        };
+        tokio::time::sleep(delay).await;
+        attempt += 1;
+    }
+}
+
+/// How long to wait before retrying a response, or `None` if it shouldn't be
+/// retried. `now` is the current Unix time, to compare `X-RateLimit-Reset` with.
+fn retry_delay(status: StatusCode, headers: &HeaderMap, attempt: u32, now: i64) -> Option<Duration> {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<i64>().ok();
+    let rate_limited = status == StatusCode::TOO_MANY_REQUESTS
+        || (status == StatusCode::FORBIDDEN && (header("x-ratelimit-remaining") == Some(0) || header("retry-after").is_some()));
+    if rate_limited {
+        // Secondary limits send Retry-After; the primary one sends the reset time
+        let seconds = header("retry-after").or_else(|| header("x-ratelimit-reset").map(|reset| reset - now));
+        return Some(match seconds {
+            Some(seconds) => Duration::from_secs(seconds.max(1) as u64),
+            None => backoff(attempt),
+        });
+    }
+    status.is_server_error().then(|| backoff(attempt))
+}
+
+fn backoff(attempt: u32) -> Duration {
+    BASE_DELAY * 2u32.pow(attempt - 1)
+}
+
+fn api_message(json: &Value) -> &str {
+    json["message"].as_str().unwrap_or("unknown error")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        let headers = |pairs: &[(&'static str, &str)]| {
+            let mut map = HeaderMap::new();
+            for (name, value) in pairs {
+                map.insert(*name, value.parse().unwrap());
+            }
+            map
+        };
+        let none = HeaderMap::new();
+        assert_eq!(retry_delay(StatusCode::OK, &none, 1, 0), None);
+        assert_eq!(retry_delay(StatusCode::NOT_FOUND, &none, 1, 0), None);
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &none, 1, 0), None);
+        assert_eq!(retry_delay(StatusCode::BAD_GATEWAY, &none, 3, 0), Some(Duration::from_secs(4)));
+
+        let exhausted = headers(&[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1030")]);
+        assert_eq!(retry_delay(StatusCode::FORBIDDEN, &exhausted, 1, 1000), Some(Duration::from_secs(30)));
+        assert_eq!(retry_delay(StatusCode::TOO_MANY_REQUESTS, &headers(&[("retry-after", "5")]), 1, 0), Some(Duration::from_secs(5)));
+        assert_eq!(retry_delay(StatusCode::TOO_MANY_REQUESTS, &none, 2, 0), Some(Duration::from_secs(2)));
+    }
+}
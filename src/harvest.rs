@@ -0,0 +1,238 @@
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use crate::perf::{Category, timed};
+use crate::publish::{self, Snippet};
+
+// Phrases that usually mark a correction or standing instruction rather than a task
+const INSTRUCTION_CUES: &[&str] = &[
+    "always ", "never ", "don't ", "do not ", "dont ", "stop ", "instead of ", "make sure ",
+    "remember to ", "please use ", "you should ", "you must ", "avoid ", "prefer ",
+];
+// Longer messages are usually task descriptions, not corrections
+const MAX_SENTENCE_LEN: usize = 240;
+const SIMILARITY_THRESHOLD: f64 = 0.6;
+
+/// An instruction given in one or more sessions, grouped with its near-duplicates.
+struct Candidate {
+    text: String,
+    words: HashSet<String>,
+    occurrences: usize,
+    sessions: HashSet<PathBuf>,
+}
+
+/// Scan Claude Code session transcripts for instructions that were repeated
+/// across sessions and offer to publish them as snippets.
+pub async fn harvest_snippets(days: u64, min_count: usize) -> Result<()> {
+    let home_dir = dirs::home_dir().context("Could not find home directory")?;
+    let projects_dir = home_dir.join(".claude/projects");
+    if !projects_dir.exists() {
+        println!("❌ No Claude Code sessions found at {}", projects_dir.display());
+        return Ok(());
+    }
+
+    let session_files = timed(Category::FsScan, "find sessions", || find_session_files(&projects_dir, days))?;
+    if session_files.is_empty() {
+        println!("❌ No sessions from the last {} days in {}", days, projects_dir.display());
+        return Ok(());
+    }
+
+    println!("🔒 harvest reads your own messages from {} session logs in {}", session_files.len(), projects_dir.display());
+    println!("   Nothing is sent anywhere; candidates are only published if you pick them.");
+    if !crate::prompt::confirm("Continue?", false)? {
+        println!("❌ Harvest cancelled");
+        return Ok(());
+    }
+
+    let mut candidates: Vec<Candidate> = Vec::new();
+    timed(Category::FsScan, "scan sessions", || {
+        for path in &session_files {
+            for message in user_messages(path) {
+                for sentence in instruction_sentences(&message) {
+                    add_candidate(&mut candidates, sentence, path);
+                }
+            }
+        }
+    });
+
+    let mut repeated: Vec<Candidate> = candidates
+        .into_iter()
+        .filter(|c| c.occurrences >= min_count)
+        .collect();
+    repeated.sort_by_key(|c| std::cmp::Reverse((c.sessions.len(), c.occurrences)));
+
+    if repeated.is_empty() {
+        println!("✅ No instructions repeated at least {} times in the last {} days", min_count, days);
+        return Ok(());
+    }
+
+    println!("🔁 Instructions you gave Claude repeatedly:");
+    println!();
+    for (i, candidate) in repeated.iter().enumerate() {
+        println!("  {}. {}", i + 1, candidate.text);
+        println!("     ({} times across {} sessions)", candidate.occurrences, candidate.sessions.len());
+    }
+    println!();
+
+    let selected = crate::prompt::select("Publish which as snippets?", repeated.len())?;
+    if selected.is_empty() {
+        println!("ℹ️  Nothing published");
+        return Ok(());
+    }
+
+    for index in selected {
+        let candidate = &repeated[index];
+        let content = format!("- {}\n", candidate.text);
+        let snippet = Snippet {
+            id: crate::ids::generate(),
+            name: publish::generate_name_from_content(&candidate.text),
+            content,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            description: Some("Harvested from Claude Code sessions".to_string()),
+            ..Default::default()
+        };
+        publish::save_and_sync_snippet(&snippet).await?;
+    }
+
+    Ok(())
+}
+
+fn find_session_files(projects_dir: &Path, days: u64) -> Result<Vec<PathBuf>> {
+    let cutoff = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+    let mut files = Vec::new();
+
+    for project in fs::read_dir(projects_dir)? {
+        let project = project?.path();
+        if !project.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&project)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+            if modified >= cutoff {
+                files.push(path);
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Text the user typed in a session, skipping tool results, sidechains and
+/// injected system content.
+fn user_messages(path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path) else { return Vec::new() };
+    let mut messages = Vec::new();
+
+    for line in content.lines() {
+        let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else { continue };
+        if entry["type"] != "user" || entry["isSidechain"] == true || entry["isMeta"] == true {
+            continue;
+        }
+        match &entry["message"]["content"] {
+            serde_json::Value::String(text) => messages.push(text.clone()),
+            serde_json::Value::Array(parts) => {
+                for part in parts {
+                    if part["type"] == "text" {
+                        if let Some(text) = part["text"].as_str() {
+                            messages.push(text.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    messages
+        .into_iter()
+        .filter(|m| !m.trim_start().starts_with('<') && !m.starts_with("Caveat:"))
+        .collect()
+}
+
+/// Sentences in `message` that read like standing instructions, trimmed to
+/// start at the instruction itself ("ok, and never X" becomes "Never X").
+fn instruction_sentences(message: &str) -> Vec<String> {
+    message
+        .split(['.', '!', '\n'])
+        .filter_map(|sentence| {
+            let sentence = sentence.trim().trim_start_matches(['-', '*', ' ']);
+            let lower = format!("{} ", sentence.to_ascii_lowercase());
+            let start = INSTRUCTION_CUES
+                .iter()
+                .filter_map(|cue| {
+                    if lower.starts_with(cue) {
+                        Some(0)
+                    } else {
+                        lower.find(&format!(" {}", cue)).map(|pos| pos + 1)
+                    }
+                })
+                .min()?;
+            let instruction = sentence[start..].trim();
+            if instruction.len() < 12 || instruction.len() > MAX_SENTENCE_LEN {
+                return None;
+            }
+            let mut chars = instruction.chars();
+            let first = chars.next()?;
+            Some(first.to_uppercase().chain(chars).collect())
+        })
+        .collect()
+}
+
+fn word_set(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() > 2)
+        .map(|w| w.to_string())
+        .collect()
+}
+
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+fn add_candidate(candidates: &mut Vec<Candidate>, sentence: String, session: &Path) {
+    let words = word_set(&sentence);
+    if let Some(existing) = candidates.iter_mut().find(|c| similarity(&c.words, &words) >= SIMILARITY_THRESHOLD) {
+        existing.occurrences += 1;
+        existing.sessions.insert(session.to_path_buf());
+        return;
+    }
+    candidates.push(Candidate {
+        text: sentence,
+        words,
+        occurrences: 1,
+        sessions: HashSet::from([session.to_path_buf()]),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_instructions_are_grouped() {
+        let mut candidates = Vec::new();
+        let messages = [
+            "Looks good. Don't use unwrap in library code!",
+            "please fix the build, and don't use unwrap in the library code",
+            "Add a new subcommand for listing",
+        ];
+        for message in messages {
+            for sentence in instruction_sentences(message) {
+                add_candidate(&mut candidates, sentence, Path::new("session.jsonl"));
+            }
+        }
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].occurrences, 2);
+    }
+}
@@ -0,0 +1,285 @@
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+use crate::accounts::WithAccount;
+use crate::config::{Account, Config};
+use crate::perf::Category;
+use crate::process::{ProcessRunner, RunWith};
+
+/// Something about a repository's git state that would derail a sync.
+#[derive(Debug, PartialEq)]
+enum Problem {
+    /// A merge or rebase that was started and never finished
+    UnfinishedMerge { rebase: bool, conflicts: Vec<String> },
+    /// HEAD points at a commit rather than a branch
+    DetachedHead,
+    /// Local and remote both have commits the other lacks
+    Diverged { ahead: usize, behind: usize },
+    /// The remote has commits the local branch lacks, and nothing more
+    Behind(usize),
+}
+
+/// Make sure the repository has its `origin`: record its URL while it's there,
+/// and put it back from the recorded one if it went missing. `false` when
+/// there is no remote and none was ever recorded.
+pub fn ensure_origin(runner: &dyn ProcessRunner, repo_name: &str, repo_dir: &Path) -> Result<bool> {
+    let mut config = Config::load()?;
+    if let Some(url) = git_line(runner, repo_dir, &["remote", "get-url", "origin"]) {
+        if config.repo_remotes.get(repo_name) != Some(&url) {
+            config.set_repo_remote(repo_name, &url)?;
+        }
+        return Ok(true);
+    }
+    let Some(url) = config.repo_remotes.get(repo_name).cloned() else { return Ok(false) };
+    git(runner, repo_dir, &["remote", "add", "origin", &url])?;
+    println!("🩹 Restored the missing 'origin' remote ({})", url);
+    Ok(true)
+}
+
+/// Check the repository before a sync and fix what can be fixed safely:
+/// finish a resolved merge, return to `branch` from a detached HEAD, and
+/// (when `online`) catch up with or merge the remote. What needs a decision
+/// goes through a guided prompt; an error means the sync should stop.
+pub fn check_and_heal(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str, online: bool, account: Option<&Account>) -> Result<()> {
+    for problem in local_problems(runner, repo_dir) {
+        heal(runner, repo_dir, branch, account, problem)?;
+    }
+    if !online {
+        return Ok(());
+    }
+
+    let fetched = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["fetch", "--quiet", "origin", branch])
+        .with_account(account)
+        .run_with(runner, Category::Git)?;
+    // An empty remote has no branch to compare with yet
+    if !fetched.status.success() {
+        return Ok(());
+    }
+    // Snippets edited on both sides are merged hunk by hunk, before git would conflict
+    let overlapping = crate::resolve::overlapping_edits(runner, repo_dir, branch);
+    if !overlapping.is_empty() {
+        return crate::resolve::merge_interactively(runner, repo_dir, branch, account, &overlapping);
+    }
+    if let Some(problem) = remote_problem(runner, repo_dir, branch) {
+        heal(runner, repo_dir, branch, account, problem)?;
+    }
+    Ok(())
+}
+
+fn local_problems(runner: &dyn ProcessRunner, repo_dir: &Path) -> Vec<Problem> {
+    let git_dir = repo_dir.join(".git");
+    let mut problems = Vec::new();
+    let rebase = git_dir.join("rebase-merge").exists() || git_dir.join("rebase-apply").exists();
+    if rebase || git_dir.join("MERGE_HEAD").exists() {
+        let conflicts = conflicted_files(runner, repo_dir);
+        problems.push(Problem::UnfinishedMerge { rebase, conflicts });
+    }
+    // A rebase in progress detaches HEAD by design; finishing it reattaches
+    if !rebase && git_line(runner, repo_dir, &["symbolic-ref", "-q", "HEAD"]).is_none() {
+        problems.push(Problem::DetachedHead);
+    }
+    problems
+}
+
+fn remote_problem(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str) -> Option<Problem> {
+    let counts = git_line(runner, repo_dir, &["rev-list", "--left-right", "--count", &format!("{0}...origin/{0}", branch)])?;
+    let mut numbers = counts.split_whitespace().filter_map(|n| n.parse::<usize>().ok());
+    match (numbers.next()?, numbers.next()?) {
+        (_, 0) => None,
+        (0, behind) => Some(Problem::Behind(behind)),
+        (ahead, behind) => Some(Problem::Diverged { ahead, behind }),
+    }
+}
+
+fn heal(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str, account: Option<&Account>, problem: Problem) -> Result<()> {
+    match problem {
+        Problem::UnfinishedMerge { rebase, conflicts } if conflicts.is_empty() => {
+            let finished = if rebase {
+                Command::new("git")
+                    .current_dir(repo_dir)
+                    .args(["rebase", "--continue"])
+                    .env("GIT_EDITOR", "true")
+                    .with_account(account)
+                    .run_with(runner, Category::Git)?
+            } else {
+                Command::new("git")
+                    .current_dir(repo_dir)
+                    .args(["commit", "--quiet", "--no-edit"])
+                    .with_account(account)
+                    .run_with(runner, Category::Git)?
+            };
+            if !finished.status.success() {
+                anyhow::bail!("Could not finish the interrupted {}: {}",
+                    if rebase { "rebase" } else { "merge" }, String::from_utf8_lossy(&finished.stderr).trim());
+            }
+            println!("🩹 Finished an interrupted {} (its conflicts were already resolved)", if rebase { "rebase" } else { "merge" });
+        }
+        Problem::UnfinishedMerge { rebase, conflicts } => resolve_conflicts(runner, repo_dir, account, rebase, &conflicts)?,
+        Problem::DetachedHead => reattach(runner, repo_dir, branch, account)?,
+        Problem::Behind(behind) => {
+            git(runner, repo_dir, &["merge", "--quiet", "--ff-only", &format!("origin/{}", branch)])?;
+            println!("🩹 Fast-forwarded '{}' by {} remote commit(s)", branch, behind);
+        }
+        Problem::Diverged { ahead, behind } => {
+            println!("🔀 '{}' has diverged: {} local and {} remote commit(s)", branch, ahead, behind);
+            let merged = Command::new("git")
+                .current_dir(repo_dir)
+                .args(["merge", "--quiet", "--no-edit", &format!("origin/{}", branch)])
+                .with_account(account)
+                .run_with(runner, Category::Git)?;
+            if merged.status.success() {
+                println!("🩹 Merged the remote commits");
+            } else {
+                let conflicts = conflicted_files(runner, repo_dir);
+                if conflicts.is_empty() {
+                    anyhow::bail!("Could not merge the remote commits: {}", String::from_utf8_lossy(&merged.stderr).trim());
+                }
+                resolve_conflicts(runner, repo_dir, account, false, &conflicts)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Back onto `branch` from a detached HEAD. Commits made while detached are
+/// merged into the branch once confirmed, so nothing is left behind.
+fn reattach(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str, account: Option<&Account>) -> Result<()> {
+    let head = git_line(runner, repo_dir, &["rev-parse", "HEAD"]).unwrap_or_default();
+    let short = head.get(..8).unwrap_or(&head).to_string();
+    if git_line(runner, repo_dir, &["rev-parse", "--verify", "--quiet", &format!("refs/heads/{}", branch)]).is_none() {
+        git(runner, repo_dir, &["checkout", "--quiet", "-b", branch])?;
+        println!("🩹 HEAD was detached at {}; created branch '{}' there", short, branch);
+        return Ok(());
+    }
+
+    let contained = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["merge-base", "--is-ancestor", "HEAD", branch])
+        .run_with(runner, Category::Git)?
+        .status
+        .success();
+    if contained {
+        git(runner, repo_dir, &["checkout", "--quiet", branch])?;
+        println!("🩹 HEAD was detached at {}; switched back to '{}'", short, branch);
+        return Ok(());
+    }
+
+    println!("⚠️  HEAD is detached at {}, with commits that are not on '{}'", short, branch);
+    if !crate::prompt::confirm(&format!("Switch back to '{}' and merge those commits into it?", branch), true)? {
+        println!("💡 To do it by hand: cd {} && git checkout {} && git merge {}", repo_dir.display(), branch, short);
+        anyhow::bail!("Sync stopped: HEAD is detached");
+    }
+    git(runner, repo_dir, &["checkout", "--quiet", branch])?;
+    let merged = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["merge", "--quiet", "--no-edit", &head])
+        .with_account(account)
+        .run_with(runner, Category::Git)?;
+    if !merged.status.success() {
+        let conflicts = conflicted_files(runner, repo_dir);
+        return resolve_conflicts(runner, repo_dir, account, false, &conflicts);
+    }
+    println!("🩹 Switched back to '{}' and merged the detached commits", branch);
+    Ok(())
+}
+
+/// Guided fix for a merge (or rebase) stopped on conflicts: keep one side of
+/// every conflicting file, abort, or stop to resolve them by hand.
+fn resolve_conflicts(runner: &dyn ProcessRunner, repo_dir: &Path, account: Option<&Account>, rebase: bool, conflicts: &[String]) -> Result<()> {
+    let operation = if rebase { "rebase" } else { "merge" };
+    println!("⚠️  The {} stopped on conflicts in {} file(s):", operation, conflicts.len());
+    for file in conflicts {
+        println!("   • {}", file);
+    }
+
+    let mut options = vec![(format!("Abort the {} (the sync stops)", operation), String::new())];
+    if !rebase {
+        options.push(("Keep the local version of each conflicting file".to_string(), String::new()));
+        options.push(("Take the remote version of each conflicting file".to_string(), String::new()));
+    }
+    let choice = crate::prompt::choose("How should the conflicts be handled?", &options)?;
+    match choice {
+        Some(0) => {
+            git(runner, repo_dir, &[operation, "--abort"])?;
+            println!("🩹 Aborted the {}", operation);
+            anyhow::bail!("Sync stopped after aborting the {}; resolve the differences, then sync again", operation);
+        }
+        Some(side) => {
+            let side = if side == 1 { "--ours" } else { "--theirs" };
+            let mut checkout = vec!["checkout", side, "--"];
+            checkout.extend(conflicts.iter().map(|file| file.as_str()));
+            git(runner, repo_dir, &checkout)?;
+            let mut add = vec!["add", "--"];
+            add.extend(conflicts.iter().map(|file| file.as_str()));
+            git(runner, repo_dir, &add)?;
+            let committed = Command::new("git")
+                .current_dir(repo_dir)
+                .args(["commit", "--quiet", "--no-edit"])
+                .with_account(account)
+                .run_with(runner, Category::Git)?;
+            if !committed.status.success() {
+                anyhow::bail!("Could not complete the merge: {}", String::from_utf8_lossy(&committed.stderr).trim());
+            }
+            println!("🩹 Resolved {} conflict(s) with the {} version", conflicts.len(), if side == "--ours" { "local" } else { "remote" });
+            Ok(())
+        }
+        None => {
+            println!("💡 Resolve the conflicts in {}, commit, then run 'claude-md-snippets sync' again", repo_dir.display());
+            anyhow::bail!("Sync stopped: unresolved {} conflicts", operation)
+        }
+    }
+}
+
+fn conflicted_files(runner: &dyn ProcessRunner, repo_dir: &Path) -> Vec<String> {
+    git_output(runner, repo_dir, &["diff", "--name-only", "--diff-filter=U"])
+        .map(|out| out.lines().map(|line| line.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Run a git command that must succeed.
+fn git(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git").current_dir(repo_dir).args(args).run_with(runner, Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("git {} failed: {}", args.first().unwrap_or(&""), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+fn git_output(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").current_dir(repo_dir).args(args).run_with(runner, Category::Git).ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn git_line(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str]) -> Option<String> {
+    git_output(runner, repo_dir, args)?.lines().next().map(|line| line.trim().to_string()).filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::fake::FakeRunner;
+
+    #[test]
+    fn test_detect_problems() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/MERGE_HEAD"), "abc\n").unwrap();
+        let runner = FakeRunner::new()
+            .respond("git diff --name-only", "snippets/a.md\n", true)
+            .respond("git symbolic-ref", "", false)
+            .respond("git rev-list --left-right", "2\t3\n", true);
+
+        assert_eq!(local_problems(&runner, dir.path()), vec![
+            Problem::UnfinishedMerge { rebase: false, conflicts: vec!["snippets/a.md".to_string()] },
+            Problem::DetachedHead,
+        ]);
+        assert_eq!(remote_problem(&runner, dir.path(), "main"), Some(Problem::Diverged { ahead: 2, behind: 3 }));
+
+        let behind = FakeRunner::new().respond("git rev-list", "0\t4\n", true);
+        assert_eq!(remote_problem(&behind, dir.path(), "main"), Some(Problem::Behind(4)));
+        let ahead = FakeRunner::new().respond("git rev-list", "1\t0\n", true);
+        assert_eq!(remote_problem(&ahead, dir.path(), "main"), None);
+    }
+}
@@ -0,0 +1,328 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::path::Path;
+use std::process::Command;
+use crate::accounts::WithAccount;
+use crate::github_api::CreatedRepo;
+use crate::perf::{Category, TimedCommand};
+
+/// Kinds of git hosting that setup can create repositories on and `open`
+/// knows the web URL layout of.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum HostKind {
+    Github,
+    Gitlab,
+    /// Gitea and Forgejo (e.g. Codeberg)
+    Gitea,
+    /// Any other git remote, set up by URL
+    Git,
+}
+
+impl HostKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            HostKind::Github => "GitHub",
+            HostKind::Gitlab => "GitLab",
+            HostKind::Gitea => "Gitea",
+            HostKind::Git => "git",
+        }
+    }
+
+    /// Guess the host software from its domain.
+    fn detect(host: &str) -> Self {
+        let host = host.to_lowercase();
+        if host == "github.com" || host.ends_with(".github.com") {
+            HostKind::Github
+        } else if host.contains("gitlab") {
+            HostKind::Gitlab
+        } else if host.contains("gitea") || host.contains("forgejo") || host == "codeberg.org" {
+            HostKind::Gitea
+        } else {
+            HostKind::Git
+        }
+    }
+}
+
+/// A remote URL split into host and repository path, whatever its form.
+#[derive(Debug, PartialEq)]
+pub struct Remote {
+    pub kind: HostKind,
+    pub host: String,
+    /// `owner/repo` (or `group/subgroup/repo` on GitLab), without `.git`
+    pub path: String,
+}
+
+impl Remote {
+    /// Parse `git@host:path.git`, `ssh://git@host[:port]/path.git` or `https://host/path[.git]`.
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        let (host, path) = if let Some(rest) = url.strip_prefix("ssh://") {
+            let rest = rest.split_once('@').map(|(_, rest)| rest).unwrap_or(rest);
+            let (host, path) = rest.split_once('/')?;
+            // The SSH port says nothing about the web URL
+            (host.split(':').next()?, path)
+        } else if let Some(rest) = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+            let rest = rest.split_once('@').map(|(_, rest)| rest).unwrap_or(rest);
+            rest.split_once('/')?
+        } else if let Some((user_host, path)) = url.split_once(':') {
+            // scp-like syntax: [user@]host:path
+            if user_host.contains('/') || path.starts_with("//") {
+                return None;
+            }
+            (user_host.rsplit('@').next()?, path)
+        } else {
+            return None;
+        };
+
+        let path = path.trim_matches('/').trim_end_matches(".git").to_string();
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+        Some(Remote { kind: HostKind::detect(host), host: host.to_string(), path })
+    }
+
+    /// The repository's web page.
+    pub fn web_url(&self) -> String {
+        format!("https://{}/{}", self.host, self.path)
+    }
+
+    /// The web page of `file` on `branch`, for hosts whose layout is known.
+    pub fn blob_url(&self, branch: &str, file: &str) -> Option<String> {
+        let web_url = self.web_url();
+        match self.kind {
+            HostKind::Github => Some(format!("{}/blob/{}/{}", web_url, branch, file)),
+            HostKind::Gitlab => Some(format!("{}/-/blob/{}/{}", web_url, branch, file)),
+            HostKind::Gitea => Some(format!("{}/src/branch/{}/{}", web_url, branch, file)),
+            HostKind::Git => None,
+        }
+    }
+}
+
+/// `host:port` that the remote at `url` is reached on, for checking it is
+/// online: the port in the URL, or the scheme's (22 for SSH). `None` for local
+/// paths and anything else without a network host.
+pub fn probe_address(url: &str) -> Option<String> {
+    let remote = Remote::parse(url)?;
+    let url = url.trim();
+    if let Some(rest) = url.strip_prefix("ssh://") {
+        let authority = rest.split('/').next()?;
+        let authority = authority.rsplit('@').next()?;
+        return Some(if authority.contains(':') { authority.to_string() } else { format!("{}:22", remote.host) });
+    }
+    if remote.host.contains(':') {
+        return Some(remote.host);
+    }
+    let port = if url.starts_with("https://") {
+        443
+    } else if url.starts_with("http://") {
+        80
+    } else {
+        22
+    };
+    Some(format!("{}:{}", remote.host, port))
+}
+
+/// `setup` options for hosts other than GitHub.
+pub struct HostOptions {
+    pub kind: HostKind,
+    /// Base URL of a self-hosted instance, e.g. https://gitlab.example.com
+    pub base_url: Option<String>,
+    /// Existing remote to use with `--host git`
+    pub remote: Option<String>,
+}
+
+/// REST access to GitLab or Gitea for creating the snippets repository.
+struct HostApi {
+    kind: HostKind,
+    base_url: String,
+    token: String,
+    http: reqwest::Client,
+}
+
+impl HostApi {
+    /// Uses GITLAB_TOKEN or GITEA_TOKEN; `None` when it isn't set.
+    fn new(kind: HostKind, base_url: &str) -> Option<Self> {
+        let var = match kind {
+            HostKind::Gitlab => "GITLAB_TOKEN",
+            HostKind::Gitea => "GITEA_TOKEN",
+            _ => return None,
+        };
+        let token = std::env::var(var).ok().filter(|token| !token.trim().is_empty())?;
+        Some(HostApi {
+            kind,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token: token.trim().to_string(),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let (prefix, auth) = match self.kind {
+            HostKind::Gitlab => ("/api/v4", format!("Bearer {}", self.token)),
+            _ => ("/api/v1", format!("token {}", self.token)),
+        };
+        self.http
+            .request(method, format!("{}{}{}", self.base_url, prefix, path))
+            .header("User-Agent", "claude-md-snippets")
+            .header("Authorization", auth)
+    }
+
+    async fn current_user(&self) -> Result<String> {
+        let response = self.request(reqwest::Method::GET, "/user").send().await?;
+        let status = response.status();
+        let json: serde_json::Value = response.json().await.unwrap_or_default();
+        if !status.is_success() {
+            anyhow::bail!("{} API returned {}", self.kind.label(), status);
+        }
+        let field = if self.kind == HostKind::Gitlab { "username" } else { "login" };
+        json[field].as_str().map(|user| user.to_string()).context("No username in API response")
+    }
+
+    async fn create_repo(&self, name: &str, is_private: bool) -> Result<CreatedRepo> {
+        let request = match self.kind {
+            HostKind::Gitlab => self.request(reqwest::Method::POST, "/projects").json(&serde_json::json!({
+                "name": name,
+                "visibility": if is_private { "private" } else { "public" },
+                "description": "Personal CLAUDE.md snippets",
+            })),
+            _ => self.request(reqwest::Method::POST, "/user/repos").json(&serde_json::json!({
+                "name": name,
+                "private": is_private,
+                "description": "Personal CLAUDE.md snippets",
+            })),
+        };
+        let response = request.send().await?;
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        if status.is_success() {
+            Ok(CreatedRepo::Created)
+        } else if status == reqwest::StatusCode::CONFLICT || body.contains("already") {
+            Ok(CreatedRepo::AlreadyExists)
+        } else {
+            anyhow::bail!("{} API returned {}: {}", self.kind.label(), status, body.trim())
+        }
+    }
+
+    /// GitLab accepts personal access tokens with any username; Gitea wants the owner.
+    fn push_username(&self, user: &str) -> String {
+        if self.kind == HostKind::Gitlab { "oauth2".to_string() } else { user.to_string() }
+    }
+}
+
+/// Set up the snippets repository on GitLab, Gitea or a plain git remote.
+/// The local repository is initialized and pushed; the caller records it as default.
+pub async fn setup_on_host(options: &HostOptions, repo_name: &str, is_private: bool, snippets_dir: &Path) -> Result<()> {
+    let mut push_credentials = None;
+    let remote_url = match options.kind {
+        HostKind::Git => match &options.remote {
+            Some(remote) => remote.clone(),
+            None => {
+                let remote = crate::prompt::input(&format!("Enter the git remote URL for '{}'", repo_name), None)?;
+                if remote.is_empty() {
+                    anyhow::bail!("A remote URL is required with --host git (or pass --remote <url>)");
+                }
+                remote
+            }
+        },
+        HostKind::Gitlab | HostKind::Gitea => {
+            let base_url = match (&options.base_url, options.kind) {
+                (Some(url), _) => url.trim_end_matches('/').to_string(),
+                (None, HostKind::Gitlab) => "https://gitlab.com".to_string(),
+                (None, _) => anyhow::bail!("--host gitea needs --host-url, e.g. https://codeberg.org"),
+            };
+            let Some(api) = HostApi::new(options.kind, &base_url) else {
+                let var = if options.kind == HostKind::Gitlab { "GITLAB_TOKEN" } else { "GITEA_TOKEN" };
+                println!("⚠️  {} is not set, so the repository can't be created for you.", var);
+                println!("📝 Create a {} repository named '{}' on {}, then:",
+                    if is_private { "private" } else { "public" }, repo_name, base_url);
+                println!("   cd {}", snippets_dir.display());
+                println!("   git remote add origin {}/YOUR_USERNAME/{}.git", base_url, repo_name);
+                println!("   git push -u origin main");
+                return Ok(());
+            };
+
+            println!("📦 Creating repository '{}' on {}...", repo_name, base_url);
+            match api.create_repo(repo_name, is_private).await? {
+                CreatedRepo::Created => println!("✅ Repository created successfully!"),
+                CreatedRepo::AlreadyExists => println!("ℹ️  Repository '{}' already exists", repo_name),
+            }
+            let user = api.current_user().await?;
+            push_credentials = Some((api.push_username(&user), api.token.clone()));
+            format!("{}/{}/{}.git", base_url, user, repo_name)
+        }
+        HostKind::Github => unreachable!("GitHub setup is handled by github::setup_repository"),
+    };
+
+    let output = Command::new("git")
+        .current_dir(snippets_dir)
+        .args(["remote", "add", "origin", &remote_url])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        Command::new("git")
+            .current_dir(snippets_dir)
+            .args(["remote", "set-url", "origin", &remote_url])
+            .timed_output(Category::Git)?;
+    }
+    println!("✅ Remote origin: {}", remote_url);
+
+    println!("📤 Pushing to remote repository...");
+    let (username, token) = match &push_credentials {
+        Some((username, token)) => (username.as_str(), Some(token.as_str())),
+        None => ("", None),
+    };
+    let output = Command::new("git")
+        .current_dir(snippets_dir)
+        .args(["push", "-u", "origin", &crate::github::local_branch(snippets_dir)])
+        .with_token(username, token)
+        .timed_output(Category::Git)?;
+    if output.status.success() {
+        println!("✅ Setup complete! Your snippets repository is ready.");
+        if let Some(remote) = Remote::parse(&remote_url) {
+            println!("🌐 Repository: {}", remote.web_url());
+        }
+    } else {
+        println!("⚠️  Push failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        println!("💡 Try running 'claude-md-snippets sync' once the remote is reachable");
+    }
+    println!("📁 Local directory: {}", snippets_dir.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_parsing_and_urls() {
+        let github = Remote::parse("git@github.com:me/snips.git").unwrap();
+        assert_eq!(github.web_url(), "https://github.com/me/snips");
+        assert_eq!(github.blob_url("main", "snippets/a.md").unwrap(), "https://github.com/me/snips/blob/main/snippets/a.md");
+
+        let gitlab = Remote::parse("ssh://git@gitlab.example.com:2222/team/sub/snips.git").unwrap();
+        assert_eq!(gitlab.kind, HostKind::Gitlab);
+        assert_eq!(gitlab.web_url(), "https://gitlab.example.com/team/sub/snips");
+        assert_eq!(gitlab.blob_url("main", "a.md").unwrap(), "https://gitlab.example.com/team/sub/snips/-/blob/main/a.md");
+
+        let gitea = Remote::parse("https://user@codeberg.org/me/snips").unwrap();
+        assert_eq!(gitea.kind, HostKind::Gitea);
+        assert_eq!(gitea.blob_url("main", "a.md").unwrap(), "https://codeberg.org/me/snips/src/branch/main/a.md");
+
+        let plain = Remote::parse("git@git.example.org:me/snips.git").unwrap();
+        assert_eq!(plain.kind, HostKind::Git);
+        assert!(plain.blob_url("main", "a.md").is_none());
+
+        assert!(Remote::parse("/srv/git/snips.git").is_none());
+        assert!(Remote::parse("file:///srv/git/snips.git").is_none());
+    }
+
+    #[test]
+    fn test_probe_address() {
+        assert_eq!(probe_address("https://gitea.lan:3000/me/snips.git").as_deref(), Some("gitea.lan:3000"));
+        assert_eq!(probe_address("http://192.168.1.20/me/snips").as_deref(), Some("192.168.1.20:80"));
+        assert_eq!(probe_address("ssh://git@gitlab.example.com:2222/team/snips.git").as_deref(), Some("gitlab.example.com:2222"));
+        assert_eq!(probe_address("git@git.example.org:me/snips.git").as_deref(), Some("git.example.org:22"));
+        assert_eq!(probe_address("https://github.com/me/snips").as_deref(), Some("github.com:443"));
+        assert!(probe_address("/srv/git/snips.git").is_none());
+        assert!(probe_address("file:///srv/git/snips.git").is_none());
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+use crate::publish::Snippet;
+
+/// Characters of a snippet ID used in install markers, file names and output.
+pub const SHORT_ID_LEN: usize = 8;
+
+/// Fewest characters of an ID accepted as a reference to it (`install 7Hq2`).
+pub const MIN_PREFIX_LEN: usize = 4;
+
+/// Base58: no 0/O or I/l to confuse when typing an ID.
+const BASE58: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Shown in place of a missing ID (a file without `id:` in its frontmatter).
+const NO_ID: &str = "(no id)";
+
+/// The first `SHORT_ID_LEN` characters of `id`, cut on a character boundary
+/// so short, empty or non-ASCII IDs never panic. This is the ID written into
+/// install markers, so it doesn't depend on the other snippets.
+pub fn short(id: &str) -> &str {
+    id.char_indices().nth(SHORT_ID_LEN).map_or(id, |(end, _)| &id[..end])
+}
+
+/// A new snippet ID: `SHORT_ID_LEN` random base58 characters, so the ID in
+/// markers and file names is the whole ID. One already in use by the default
+/// or a followed repository (as a short ID, old UUIDs included) is drawn again.
+pub fn generate() -> String {
+    let taken: HashSet<String> = crate::subscriptions::Followed::load()
+        .and_then(|followed| crate::store::SnippetStore::load_all(&followed))
+        .unwrap_or_default()
+        .iter()
+        .map(|snippet| short(&snippet.id).to_string())
+        .collect();
+    generate_avoiding(&taken)
+}
+
+fn generate_avoiding(taken: &HashSet<String>) -> String {
+    loop {
+        // The low 64 bits of a v4 UUID are random apart from the two variant bits at the top
+        let mut bits = uuid::Uuid::new_v4().as_u128() as u64;
+        let id: String = (0..SHORT_ID_LEN)
+            .map(|_| {
+                let c = BASE58[(bits % 58) as usize] as char;
+                bits /= 58;
+                c
+            })
+            .collect();
+        if !taken.contains(&id) {
+            return id;
+        }
+    }
+}
+
+/// Whether `query` refers to `id`: at least `MIN_PREFIX_LEN` of its first
+/// characters, which works for old UUID IDs and new short ones alike.
+pub fn matches_prefix(id: &str, query: &str) -> bool {
+    query.chars().count() >= MIN_PREFIX_LEN && id.starts_with(query)
+}
+
+/// The snippet `query` is an ID prefix of, if it is one. Several matches
+/// fail with their IDs, so a longer prefix can be given.
+pub fn find_by_prefix<'a>(snippets: &'a [Snippet], query: &str) -> anyhow::Result<Option<&'a Snippet>> {
+    let matching: Vec<&Snippet> = snippets.iter().filter(|snippet| matches_prefix(&snippet.id, query)).collect();
+    match matching.as_slice() {
+        [] => Ok(None),
+        [snippet] => Ok(Some(snippet)),
+        several => {
+            let ids = ShortIds::new(several.iter().map(|snippet| snippet.id.as_str()));
+            let listed: Vec<String> = several.iter().map(|snippet| format!("{} ({})", ids.get(&snippet.id), snippet.name)).collect();
+            anyhow::bail!("'{}' is the start of several IDs: {}; give more characters", query, listed.join(", "))
+        }
+    }
+}
+
+/// Whether `id` can go into install markers and file names: not empty, and
+/// no whitespace or characters that would end an HTML comment or a path.
+pub fn is_valid(id: &str) -> bool {
+    !id.is_empty() && !id.chars().any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | '/' | '\\' | ':'))
+}
+
+/// Fail with a pointer to `repo lint` when `id` can't be used in markers.
+pub fn validate(id: &str, name: &str) -> anyhow::Result<()> {
+    if !is_valid(id) {
+        anyhow::bail!("Snippet '{}' has no usable ID ('{}'); fix its frontmatter (see 'claude-md-snippets repo lint')", name, id);
+    }
+    Ok(())
+}
+
+/// Short IDs for everything in one listing: `SHORT_ID_LEN` characters, or
+/// more where two IDs share that prefix, so every ID shown tells its
+/// snippet apart from the others.
+pub struct ShortIds {
+    lengths: HashMap<String, usize>,
+}
+
+impl ShortIds {
+    pub fn new<'a>(ids: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut sorted: Vec<Vec<char>> = ids.into_iter().map(|id| id.chars().collect()).collect();
+        sorted.sort();
+        sorted.dedup();
+
+        let mut lengths = HashMap::new();
+        for (i, id) in sorted.iter().enumerate() {
+            // Neighbours in sorted order share the longest prefixes
+            let shared = [i.checked_sub(1), Some(i + 1)]
+                .into_iter()
+                .flatten()
+                .filter_map(|j| sorted.get(j))
+                .map(|other| id.iter().zip(other).take_while(|(a, b)| a == b).count())
+                .max()
+                .unwrap_or(0);
+            lengths.insert(id.iter().collect(), (shared + 1).max(SHORT_ID_LEN).min(id.len()));
+        }
+        ShortIds { lengths }
+    }
+
+    /// The unique short form of `id`; IDs this wasn't built from get the
+    /// plain `short` form.
+    pub fn get<'a>(&self, id: &'a str) -> &'a str {
+        if id.is_empty() {
+            return NO_ID;
+        }
+        match self.lengths.get(id) {
+            Some(&length) => id.char_indices().nth(length).map_or(id, |(end, _)| &id[..end]),
+            None => short(id),
+        }
+    }
+
+    /// `get`, padded to `SHORT_ID_LEN` so short IDs keep columns aligned.
+    pub fn padded(&self, id: &str) -> String {
+        format!("{:<width$}", self.get(id), width = SHORT_ID_LEN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_ids() {
+        assert_eq!(short("1a2b3c4d-5e6f"), "1a2b3c4d");
+        assert_eq!(short("1a2b"), "1a2b");
+        assert_eq!(short("ünïcödé-ïd"), "ünïcödé-");
+        assert_eq!(short(""), "");
+        assert!(!is_valid("") && !is_valid("a b") && !is_valid("x-->") && is_valid("1a2b3c4d"));
+
+        let ids = ShortIds::new(["1a2b3c4d-0001", "1a2b3c4d-0002", "5e6f7a8b-0003", "9f", ""]);
+        assert_eq!(ids.get("1a2b3c4d-0001"), "1a2b3c4d-0001");
+        assert_eq!(ids.get("5e6f7a8b-0003"), "5e6f7a8b");
+        assert_eq!(ids.padded("9f"), "9f      ");
+        assert_eq!(ids.get(""), "(no id)");
+    }
+
+    #[test]
+    fn test_generated_ids() {
+        let id = generate_avoiding(&HashSet::new());
+        assert_eq!(id.len(), SHORT_ID_LEN);
+        assert!(is_valid(&id) && id.bytes().all(|c| BASE58.contains(&c)));
+        assert_eq!(short(&id), id);
+
+        let snippets = ["1a2b3c4d-5e6f", "1a2b9f8e-7d6c", "7Hq2mXbK"].map(|id| crate::publish::test_snippet(id, id, "Body\n"));
+        assert!(matches_prefix("7Hq2mXbK", "7Hq2") && !matches_prefix("7Hq2mXbK", "7Hq"));
+        assert_eq!(find_by_prefix(&snippets, "7Hq2").unwrap().map(|s| s.id.as_str()), Some("7Hq2mXbK"));
+        assert_eq!(find_by_prefix(&snippets, "1a2b3").unwrap().map(|s| s.id.as_str()), Some("1a2b3c4d-5e6f"));
+        assert!(find_by_prefix(&snippets, "1a2b").is_err());
+        assert!(find_by_prefix(&snippets, "Testing").unwrap().is_none());
+    }
+}
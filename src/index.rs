@@ -0,0 +1,240 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::lockfile::atomic_write;
+use crate::publish::{get_app_dir, get_snippets_dir, parse_markdown_frontmatter};
+
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "how", "in", "is", "it",
+    "of", "on", "or", "that", "the", "this", "to", "use", "when", "with", "you", "your",
+];
+
+// Snippet names are short, so weight their terms above body text
+const NAME_WEIGHT: u32 = 3;
+
+/// On-disk TF-IDF index of the default repository's snippets.
+#[derive(Serialize, Deserialize)]
+pub struct SearchIndex {
+    pub fingerprint: String,
+    pub built_at: String,
+    pub document_frequency: HashMap<String, usize>,
+    pub documents: Vec<IndexedSnippet>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct IndexedSnippet {
+    pub id: String,
+    pub name: String,
+    pub file: String,
+    pub terms: HashMap<String, u32>,
+    pub norm: f64,
+}
+
+pub struct SearchHit<'a> {
+    pub snippet: &'a IndexedSnippet,
+    pub score: f64,
+}
+
+impl SearchIndex {
+    pub fn build(snippets_dir: &Path) -> Result<Self> {
+        let mut documents = Vec::new();
+
+        for path in crate::scan::snippet_files(snippets_dir) {
+            let Some(filename) = path.file_name().and_then(|n| n.to_str()) else { continue };
+            let file = path.strip_prefix(snippets_dir).unwrap_or(&path).display().to_string();
+            if filename.to_lowercase().contains("readme") {
+                continue;
+            }
+
+            if let Ok(content) = fs::read_to_string(&path) {
+                if let Ok(snippet) = parse_markdown_frontmatter(&content) {
+                    if snippet.status == crate::status::Status::Archived {
+                        continue;
+                    }
+                    let mut terms = HashMap::new();
+                    for _ in 0..NAME_WEIGHT {
+                        count_terms(&snippet.name, &mut terms);
+                    }
+                    if let Some(desc) = &snippet.description {
+                        count_terms(desc, &mut terms);
+                    }
+                    count_terms(&snippet.content, &mut terms);
+
+                    documents.push(IndexedSnippet {
+                        id: snippet.id,
+                        name: snippet.name,
+                        file,
+                        terms,
+                        norm: 0.0,
+                    });
+                }
+            }
+        }
+
+        Ok(SearchIndex::from_documents(documents, fingerprint(snippets_dir)?))
+    }
+
+    fn from_documents(documents: Vec<IndexedSnippet>, fingerprint: String) -> Self {
+        let mut document_frequency: HashMap<String, usize> = HashMap::new();
+        for doc in &documents {
+            for term in doc.terms.keys() {
+                *document_frequency.entry(term.clone()).or_default() += 1;
+            }
+        }
+
+        let mut index = SearchIndex {
+            fingerprint,
+            built_at: chrono::Utc::now().to_rfc3339(),
+            document_frequency,
+            documents,
+        };
+
+        let norms: Vec<f64> = index.documents.iter()
+            .map(|doc| {
+                doc.terms.iter()
+                    .map(|(term, tf)| (term_weight(*tf) * index.idf(term)).powi(2))
+                    .sum::<f64>()
+                    .sqrt()
+            })
+            .collect();
+        for (doc, norm) in index.documents.iter_mut().zip(norms) {
+            doc.norm = norm;
+        }
+
+        index
+    }
+
+    fn idf(&self, term: &str) -> f64 {
+        let n = self.documents.len() as f64;
+        let df = self.document_frequency.get(term).copied().unwrap_or(0) as f64;
+        (1.0 + n / (1.0 + df)).ln()
+    }
+
+    /// Rank documents by cosine similarity between the query and each snippet.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'_>> {
+        let mut query_terms = HashMap::new();
+        count_terms(query, &mut query_terms);
+
+        let mut hits: Vec<SearchHit> = self.documents.iter()
+            .filter_map(|doc| {
+                if doc.norm == 0.0 {
+                    return None;
+                }
+                let dot: f64 = query_terms.iter()
+                    .filter_map(|(term, qtf)| {
+                        doc.terms.get(term).map(|tf| {
+                            let idf = self.idf(term);
+                            term_weight(*tf) * idf * term_weight(*qtf) * idf
+                        })
+                    })
+                    .sum();
+                (dot > 0.0).then(|| SearchHit { snippet: doc, score: dot / doc.norm })
+            })
+            .collect();
+
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits
+    }
+}
+
+fn term_weight(tf: u32) -> f64 {
+    1.0 + (tf as f64).ln()
+}
+
+fn count_terms(text: &str, terms: &mut HashMap<String, u32>) {
+    for token in text.split(|c: char| !c.is_alphanumeric()) {
+        let token = token.to_lowercase();
+        if token.chars().count() < 2 || STOP_WORDS.contains(&token.as_str()) {
+            continue;
+        }
+        *terms.entry(token).or_default() += 1;
+    }
+}
+
+/// Cheap signature of the snippet directory (names, sizes, mtimes) used to detect a stale index.
+fn fingerprint(snippets_dir: &Path) -> Result<String> {
+    let mut entries = Vec::new();
+
+    for path in crate::scan::snippet_files(snippets_dir) {
+        let metadata = fs::metadata(&path)?;
+        let modified = metadata.modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let name = path.strip_prefix(snippets_dir).unwrap_or(&path).display().to_string();
+        entries.push(format!("{}:{}:{}", name, metadata.len(), modified));
+    }
+
+    entries.sort();
+    Ok(crate::manifest::content_hash(&entries.join("\n")))
+}
+
+fn get_index_path() -> Result<std::path::PathBuf> {
+    let repo_name = crate::config::get_default_repo_name()?;
+    Ok(get_app_dir()?.join("index").join(format!("{}.json", repo_name)))
+}
+
+/// Rebuild the index for the default repository and save it to disk.
+pub fn rebuild_index() -> Result<SearchIndex> {
+    let snippets_dir = get_snippets_dir()?.join("snippets");
+    let index = SearchIndex::build(&snippets_dir)?;
+    atomic_write(&get_index_path()?, &serde_json::to_string(&index)?)?;
+    Ok(index)
+}
+
+/// Load the saved index, rebuilding it if snippets changed since it was written.
+pub fn load_index() -> Result<SearchIndex> {
+    let index_path = get_index_path()?;
+    let snippets_dir = get_snippets_dir()?.join("snippets");
+
+    if index_path.exists() {
+        if let Ok(content) = fs::read_to_string(&index_path) {
+            if let Ok(index) = serde_json::from_str::<SearchIndex>(&content) {
+                if index.fingerprint == fingerprint(&snippets_dir)? {
+                    return Ok(index);
+                }
+            }
+        }
+    }
+
+    rebuild_index()
+}
+
+/// Refresh the index after repository content changed, without failing the caller.
+pub fn refresh_index_quietly() {
+    if let Err(e) = crate::perf::timed(crate::perf::Category::FsScan, "rebuild search index", rebuild_index) {
+        println!("⚠️  Could not update search index: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(id: &str, name: &str, body: &str) -> IndexedSnippet {
+        let mut terms = HashMap::new();
+        for _ in 0..NAME_WEIGHT {
+            count_terms(name, &mut terms);
+        }
+        count_terms(body, &mut terms);
+        IndexedSnippet { id: id.to_string(), name: name.to_string(), file: String::new(), terms, norm: 0.0 }
+    }
+
+    #[test]
+    fn test_search_ranks_name_matches_first() {
+        let documents = vec![
+            doc("1", "Running GUI applications", "Use nohup to detach chrome and firefox"),
+            doc("2", "Rust testing", "Run cargo test before committing, mention chrome only in passing"),
+            doc("3", "Git commits", "Write short commit subjects"),
+        ];
+        let index = SearchIndex::from_documents(documents, String::new());
+
+        let hits = index.search("gui chrome");
+        assert_eq!(hits[0].snippet.id, "1");
+        assert_eq!(hits.len(), 2);
+        assert!(index.search("the and").is_empty());
+    }
+}
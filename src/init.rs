@@ -0,0 +1,136 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use crate::collections::Collection;
+use crate::placement::Placement;
+use crate::publish::Snippet;
+
+/// What `init` was told on the command line; everything missing is asked.
+pub struct InitOptions {
+    pub name: Option<String>,
+    pub language: Option<String>,
+    pub collections: Vec<String>,
+    pub snippets: Vec<String>,
+    pub force: bool,
+}
+
+/// `init`: ask for the project's name, language and the collections and
+/// snippets it should start with, then write a fresh ./CLAUDE.md: a short
+/// header about the project followed by the chosen snippets, installed
+/// between markers so `update` and `uninstall` manage them from then on.
+pub async fn init(options: InitOptions) -> Result<()> {
+    let project_dir = std::env::current_dir()?;
+    let target_path = crate::install::resolve_target_path(true, false, None)?;
+    if fs::read_to_string(&target_path).is_ok_and(|content| !content.trim().is_empty()) && !options.force {
+        println!("⚠️  {} already exists", target_path.display());
+        if !crate::prompt::confirm("Replace it with a new one?", false)? {
+            println!("❌ Init cancelled; 'install' and 'suggest' add snippets to the existing file");
+            return Ok(());
+        }
+    }
+
+    let default_name = project_dir.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match options.name {
+        Some(name) => name,
+        None => crate::prompt::input("Project name", Some(&default_name))?,
+    };
+    let language = match options.language {
+        Some(language) => language,
+        None => crate::prompt::input("Main language (empty for none)", crate::suggest::language(&project_dir))?,
+    };
+
+    let snippets = crate::store::load_snippets()?;
+    let collections = crate::collections::load(&crate::publish::get_snippets_dir()?)?;
+    let chosen = match options.collections.is_empty() && options.snippets.is_empty() {
+        true => choose(&snippets, &collections, &project_dir, &language)?,
+        false => {
+            let mut chosen = Vec::new();
+            for collection in &options.collections {
+                chosen.extend(crate::collections::members(&crate::collections::find(collection)?, &snippets)?);
+            }
+            for query in &options.snippets {
+                match crate::install::find_best_match(&snippets, query).await? {
+                    Some(snippet) => chosen.push(snippet),
+                    None => anyhow::bail!("No snippet found for '{}'", query),
+                }
+            }
+            chosen
+        }
+    };
+    let mut seen = BTreeSet::new();
+    let chosen: Vec<Snippet> = chosen.into_iter().filter(|snippet| seen.insert(snippet.id.clone())).collect();
+
+    fs::write(&target_path, header(&name, &language))?;
+    println!("📝 Wrote the project header to {}", target_path.display());
+    let mut installed = 0;
+    for snippet in &chosen {
+        println!("📦 {}", snippet.name);
+        if crate::install::install_confirmed(snippet, &target_path, options.force, &HashMap::new(), &Placement::End).await? {
+            installed += 1;
+        }
+    }
+    println!("✅ Created {} with {} snippet(s)", target_path.display(), installed);
+    println!("💡 Add project-specific notes under the header; 'claude-md-snippets update' keeps the snippets current");
+    Ok(())
+}
+
+/// Ask which collections and snippets to start with: collections first, then
+/// the remaining snippets, those suggested for this project listed first.
+fn choose(snippets: &[Snippet], collections: &BTreeMap<String, Collection>, project_dir: &Path, language: &str) -> Result<Vec<Snippet>> {
+    let mut chosen = Vec::new();
+    if !collections.is_empty() {
+        println!("📚 Collections:");
+        let names: Vec<&String> = collections.keys().collect();
+        for (i, name) in names.iter().enumerate() {
+            match &collections[*name].description {
+                Some(description) => println!("  {}. {} - {}", i + 1, name, description),
+                None => println!("  {}. {}", i + 1, name),
+            }
+        }
+        for i in crate::prompt::select("Start with which collections?", names.len())? {
+            chosen.extend(crate::collections::members(&collections[names[i]], snippets)?);
+        }
+    }
+
+    let mut keywords: BTreeSet<String> = crate::suggest::detect(project_dir).into_values().flatten().collect();
+    if !language.is_empty() {
+        keywords.insert(language.to_lowercase());
+    }
+    let keywords: BTreeSet<&str> = keywords.iter().map(String::as_str).collect();
+    let mut remaining: Vec<(usize, &Snippet)> = snippets.iter()
+        .filter(|snippet| snippet.status.is_active() && !chosen.iter().any(|other: &Snippet| other.id == snippet.id))
+        .map(|snippet| (crate::suggest::score(snippet, &keywords), snippet))
+        .collect();
+    remaining.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    if !remaining.is_empty() {
+        println!("📄 Snippets (⭐ suggested for this project):");
+        for (i, (score, snippet)) in remaining.iter().enumerate() {
+            let star = if *score > 0 { " ⭐" } else { "" };
+            println!("  {}. {} ({}){}", i + 1, snippet.name, crate::ids::short(&snippet.id), star);
+        }
+        for i in crate::prompt::select("Add which snippets?", remaining.len())? {
+            chosen.push(remaining[i].1.clone());
+        }
+    }
+    Ok(chosen)
+}
+
+/// The hand-written top of the file, above the installed snippets.
+fn header(name: &str, language: &str) -> String {
+    match language.trim() {
+        "" => format!("# {}\n", name),
+        language => format!("# {}\n\nA {} project.\n", name, language),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header() {
+        assert_eq!(header("crate", "rust"), "# crate\n\nA rust project.\n");
+        assert_eq!(header("notes", " "), "# notes\n");
+    }
+}
@@ -1,12 +1,30 @@
 use anyhow::Result;
+use std::collections::HashMap;
 use std::fs;
-use std::process::Command;
-use std::io::Write;
-use crate::publish::{Snippet, get_snippets_dir};
+use std::path::{Path, PathBuf};
+use crate::manifest::{Manifest, content_hash, normalize_content};
+use crate::publish::Snippet;
+use crate::template;
+use crate::assets;
+use crate::collections;
+use crate::limits;
+use crate::variants;
+use crate::capabilities::{self, Tool};
+use crate::claude_client::ClaudeClient;
+use crate::perf::{Category, timed};
+use crate::store::SnippetStore;
+use crate::placement::Placement;
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
 
-pub async fn install_snippet(query: String, force_local: bool, force_user: bool) -> Result<()> {
+pub async fn install_snippet(query: String, destination: Destination, force: bool, vars: HashMap<String, String>, variant: Option<String>) -> Result<()> {
+    install_snippet_from(&crate::subscriptions::Followed::load()?, query, &destination, force, vars, variant.as_deref()).await
+}
+
+async fn install_snippet_from(store: &dyn SnippetStore, query: String, destination: &Destination, force: bool, vars: HashMap<String, String>, variant: Option<&str>) -> Result<()> {
     // Load all available snippets
-    let snippets = load_snippets()?;
+    let mut snippets = timed(Category::FsScan, "load snippets", || store.load_all())?;
+    let archived = crate::status::hide_archived(&mut snippets);
     
     if snippets.is_empty() {
         println!("❌ No snippets found. Try publishing some first!");
@@ -20,37 +38,149 @@ pub async fn install_snippet(query: String, force_local: bool, force_user: bool)
     
     if let Some(snippet) = best_match {
         println!("✅ Found matching snippet: '{}'", snippet.name);
-        println!("📋 Content preview:");
-        println!("{}", preview_content(&snippet.content));
-        
-        // Confirm installation - show the exact path
-        let claude_md_path = get_claude_md_path(force_local, force_user)?;
-        let absolute_path = claude_md_path.canonicalize().unwrap_or(claude_md_path);
-        print!("Install this snippet to {}? [Y/n]: ", absolute_path.display());
-        std::io::stdout().flush()?;
-        
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        let input = input.trim().to_lowercase();
-        
-        if input.is_empty() || input == "y" || input == "yes" {
-            install_to_claude_md(&snippet, force_local, force_user).await?;
-            println!("✅ Snippet installed successfully!");
-        } else {
-            println!("❌ Installation cancelled");
-        }
+        confirm_and_install(&snippet, destination, force, &vars, variant).await?;
     } else {
         println!("❌ No suitable snippet found for query: '{}'", query);
         println!("💡 Available snippets:");
         for snippet in &snippets {
             println!("  - {}", snippet.name);
         }
+        if archived > 0 {
+            println!("💡 {} archived snippet(s) not shown; 'repo reactivate <query>' brings one back", archived);
+        }
+    }
+    
+    Ok(())
+}
+
+/// `install --from-gist`: install the snippet shared in a gist without adding
+/// it to any repository.
+pub async fn install_from_gist(reference: &str, destination: Destination, force: bool, vars: HashMap<String, String>) -> Result<()> {
+    println!("📥 Fetching gist {}...", reference);
+    let snippet = crate::gist::fetch_gist(reference).await?;
+    println!("✅ Found snippet: '{}'", snippet.name);
+    confirm_and_install(&snippet, &destination, force, &vars, None).await
+}
+
+/// `install --from owner/repo`: install one snippet of a GitHub repository
+/// without cloning or following it.
+pub async fn install_from_repo(reference: &str, query: &str, destination: Destination, force: bool, vars: HashMap<String, String>) -> Result<()> {
+    let remote = crate::browse::RemoteRepo::parse(reference)?;
+    println!("🔍 Finding '{}' in {}...", query, remote.full_name());
+    let Some(snippet) = crate::browse::find_remote(&remote, query).await? else {
+        println!("❌ No snippet in {} matches '{}'", remote.full_name(), query);
+        println!("💡 See what it has with 'claude-md-snippets browse {}'", remote.full_name());
+        return Ok(());
+    };
+    println!("✅ Found snippet: '{}'", snippet.name);
+    confirm_and_install(&snippet, &destination, force, &vars, None).await
+}
+
+async fn confirm_and_install(snippet: &Snippet, destination: &Destination, force: bool, vars: &HashMap<String, String>, variant: Option<&str>) -> Result<()> {
+    crate::status::warn_if_deprecated(snippet);
+    let (prepared, label) = localized(snippet, variant)?;
+    let prepared = &limits::check_size(&prepared)?;
+    println!("📋 Content preview:");
+    println!("{}", preview_content(&prepared.content, crate::config::Config::load()?.output.preview_lines));
+    
+    // Confirm installation - show the exact path
+    let claude_md_path = destination.resolve()?;
+    let absolute_path = claude_md_path.canonicalize().unwrap_or(claude_md_path.clone());
+    if crate::prompt::confirm(&format!("Install this snippet to {}?", absolute_path.display()), true)? {
+        if install_with_assets(prepared, &claude_md_path, force, vars, &destination.position).await? {
+            println!("✅ Snippet installed successfully!");
+            if destination.pin {
+                crate::update::pin(&snippet.id, &claude_md_path)?;
+            }
+            if !snippet.variants.is_empty() {
+                let label = label.as_deref().unwrap_or(crate::experiments::ORIGINAL);
+                crate::experiments::ExperimentLog::record(&claude_md_path, snippet, label)?;
+            }
+        }
+    } else {
+        println!("❌ Installation cancelled");
     }
     
     Ok(())
 }
 
-async fn find_best_match(snippets: &[Snippet], query: &str) -> Result<Option<Snippet>> {
+/// `install --collection`: install every member of a collection, in order, after one confirmation.
+pub async fn install_collection(name: &str, destination: Destination, force: bool, vars: HashMap<String, String>) -> Result<()> {
+    let collection = collections::find(name)?;
+    let members = collections::members(&collection, &crate::store::load_snippets()?)?;
+    if members.is_empty() {
+        println!("ℹ️  Collection '{}' has no snippets", name);
+        return Ok(());
+    }
+    
+    match &collection.description {
+        Some(description) => println!("📚 Collection '{}': {}", name, description),
+        None => println!("📚 Collection '{}'", name),
+    }
+    for (i, snippet) in members.iter().enumerate() {
+        println!("  {}. {} ({})", i + 1, snippet.name, crate::ids::short(&snippet.id));
+    }
+    
+    let claude_md_path = destination.resolve()?;
+    let absolute_path = claude_md_path.canonicalize().unwrap_or(claude_md_path.clone());
+    if !crate::prompt::confirm(&format!("Install {} snippet(s) to {}?", members.len(), absolute_path.display()), true)? {
+        println!("❌ Installation cancelled");
+        return Ok(());
+    }
+    
+    let mut installed = 0;
+    for snippet in &members {
+        println!("📦 {}", snippet.name);
+        if install_confirmed(snippet, &claude_md_path, force, &vars, &destination.position).await? {
+            installed += 1;
+            if destination.pin {
+                crate::update::pin(&snippet.id, &claude_md_path)?;
+            }
+        }
+    }
+    println!("✅ Installed {} of {} snippet(s) from collection '{}'", installed, members.len(), name);
+    
+    Ok(())
+}
+
+/// Install a snippet the user already agreed to: pinned, localized and
+/// size-checked like any install, without another confirmation.
+pub async fn install_confirmed(snippet: &Snippet, target_path: &Path, force: bool, vars: &HashMap<String, String>, placement: &Placement) -> Result<bool> {
+    crate::status::warn_if_deprecated(snippet);
+    let snippet = limits::check_size(&localized(snippet, None)?.0)?;
+    install_with_assets(&snippet, target_path, force, vars, placement).await
+}
+
+/// The snippet at the project's pinned revision, as the `variant` asked for
+/// or else in the configured language when it has that variant, with the
+/// label of the variant used.
+fn localized(snippet: &Snippet, variant: Option<&str>) -> Result<(Snippet, Option<String>)> {
+    let snippet = crate::project::pinned_version(snippet)?;
+    let snippets_dir = crate::publish::get_snippets_dir()?.join("snippets");
+    if let Some(label) = variant {
+        return Ok((variants::select(&snippet, &snippets_dir, label)?, Some(label.to_string())));
+    }
+    let language = crate::config::Config::load()?.language;
+    variants::localize(&snippet, &snippets_dir, language.as_deref())
+}
+
+/// Render the snippet's variables, install it and copy its assets alongside.
+async fn install_with_assets(snippet: &Snippet, target_path: &Path, force: bool, vars: &HashMap<String, String>, placement: &Placement) -> Result<bool> {
+    let snippet = template::render_snippet(snippet, vars)?;
+    let assets = assets::for_snippet(&snippet.id)?;
+    let snippet = assets::rewrite_references(&snippet, &assets);
+    if !install_to_target(&snippet, target_path, force, placement).await? {
+        return Ok(false);
+    }
+    assets::install(crate::ids::short(&snippet.id), &assets, target_path)?;
+    Ok(true)
+}
+
+pub async fn find_best_match(snippets: &[Snippet], query: &str) -> Result<Option<Snippet>> {
+    // An ID prefix names the snippet without any matching
+    if let Some(snippet) = crate::ids::find_by_prefix(snippets, query)? {
+        return Ok(Some(snippet.clone()));
+    }
     // Create a temporary file with snippet information for Claude Code to analyze
     let temp_dir = std::env::temp_dir();
     let snippets_file = temp_dir.join("claude_snippets_analysis.json");
@@ -60,7 +190,7 @@ async fn find_best_match(snippets: &[Snippet], query: &str) -> Result<Option<Sni
         "snippets": snippets.iter().map(|s| serde_json::json!({
             "id": s.id,
             "name": s.name,
-            "content_preview": preview_content(&s.content),
+            "content_preview": preview_content(&s.content, 30),
             "full_content": s.content
         })).collect::<Vec<_>>()
     });
@@ -78,79 +208,179 @@ async fn find_best_match(snippets: &[Snippet], query: &str) -> Result<Option<Sni
     );
     
     // Try to run Claude Code
-    let output = Command::new("claude")
-        .args(&["--dangerously-skip-permissions", "--non-interactive"])
-        .arg(&claude_prompt)
-        .output();
-    
-    match output {
-        Ok(result) => {
-            let response = String::from_utf8_lossy(&result.stdout).trim().to_string();
-            
+    if !capabilities::has(Tool::Claude) {
+        capabilities::note_unavailable(Tool::Claude, "AI matching");
+        return fuzzy_match(snippets, query);
+    }
+    let client = ClaudeClient::from_config()?;
+    
+    match client.prompt(&claude_prompt) {
+        Ok(response) => {
             if response == "NONE" {
                 return Ok(None);
             }
             
             // Find the snippet with the matching ID
-            for snippet in snippets {
-                if snippet.id.starts_with(&response) || response.contains(&snippet.id) {
-                    return Ok(Some(snippet.clone()));
+            if !response.is_empty() {
+                for snippet in snippets {
+                    if snippet.id.starts_with(&response) || response.contains(&snippet.id) {
+                        return Ok(Some(snippet.clone()));
+                    }
                 }
             }
             
             // Fallback: simple text matching
             fuzzy_match(snippets, query)
         }
-        Err(_) => {
-            println!("⚠️  Claude Code not available, using fuzzy matching...");
+        Err(e) => {
+            println!("⚠️  Claude Code not available ({}), using fuzzy matching...", e);
             fuzzy_match(snippets, query)
         }
     }
 }
 
+// A word found in the name counts most, then tags and description, then the body
+const NAME_WEIGHT: i64 = 4;
+const TAG_WEIGHT: i64 = 3;
+const DESCRIPTION_WEIGHT: i64 = 2;
+const CONTENT_WEIGHT: i64 = 1;
+// Skim scores below this per query character are letters scattered across unrelated words
+const MIN_SCORE_PER_CHAR: i64 = 12;
+
 fn fuzzy_match(snippets: &[Snippet], query: &str) -> Result<Option<Snippet>> {
-    let query_lower = query.to_lowercase();
-    let query_words: Vec<&str> = query_lower.split_whitespace().collect();
+    let scored_snippets = rank_matches(snippets, query);
+    
+    // Without a clear winner, let the user pick among the close matches
+    let close = close_matches(&scored_snippets.iter().map(|(score, _)| *score).collect::<Vec<_>>());
+    if close > 1 {
+        println!("🤔 Several snippets match '{}' about equally well:", query);
+        let candidates: Vec<(String, String)> = scored_snippets[..close].iter()
+            .map(|(_, s)| (format!("{} ({})", s.name, crate::ids::short(&s.id)), crate::prompt::preview_lines(&s.content, 2)))
+            .collect();
+        return Ok(crate::prompt::choose("Which one?", &candidates)?.map(|i| scored_snippets[i].1.clone()));
+    }
     
-    let mut scored_snippets: Vec<(usize, &Snippet)> = Vec::new();
+    Ok(scored_snippets.first().map(|(_, snippet)| (*snippet).clone()))
+}
+
+/// Snippets matching `query` with their fuzzy score, best first.
+pub fn rank_matches<'a>(snippets: &'a [Snippet], query: &str) -> Vec<(i64, &'a Snippet)> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let mut scored: Vec<(i64, &Snippet)> = snippets
+        .iter()
+        .map(|snippet| (match_score(&matcher, snippet, query), snippet))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored
+}
+
+/// Fuzzy (skim) score of `snippet` for `query`: each query word scores its
+/// best weighted hit among the name, tags, description and content.
+fn match_score(matcher: &SkimMatcherV2, snippet: &Snippet, query: &str) -> i64 {
+    let tags = snippet.tags.join(" ");
+    let fields = [
+        (snippet.name.as_str(), NAME_WEIGHT),
+        (tags.as_str(), TAG_WEIGHT),
+        (snippet.description.as_deref().unwrap_or(""), DESCRIPTION_WEIGHT),
+        (snippet.content.as_str(), CONTENT_WEIGHT),
+    ];
+    query.split_whitespace()
+        .map(|word| {
+            let min_score = MIN_SCORE_PER_CHAR * word.chars().count() as i64;
+            fields.iter()
+                .filter_map(|(text, weight)| matcher.fuzzy_match(text, word).filter(|&score| score >= min_score).map(|score| score * weight))
+                .max()
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+/// How many of the top `scores` (sorted, highest first) are within 20% of the
+/// best one, capped at five. 1 means there is a clear winner.
+fn close_matches(scores: &[i64]) -> usize {
+    let Some(&best) = scores.first() else { return 0 };
+    scores.iter().take(5).take_while(|&&score| score * 5 >= best * 4).count()
+}
+
+/// Install a snippet into the target instruction file (CLAUDE.md, AGENTS.md, ...).
+/// Returns `false` if the user declined because the snippet (or identical
+/// content) is already present, or because it contradicts an installed one.
+pub async fn install_to_target(snippet: &Snippet, target_path: &Path, force: bool, placement: &Placement) -> Result<bool> {
+    let existing_content = read_target(target_path)?;
+    let Some(new_content) = add_block(snippet, target_path, &existing_content, force, placement)? else {
+        return Ok(false);
+    };
+    write_target(target_path, &new_content)?;
     
+    // Show absolute path for clarity
+    let absolute_path = record_install(snippet, target_path)?;
+    println!("📝 Added to: {}", absolute_path.display());
+    
+    Ok(true)
+}
+
+/// Install several snippets the user already agreed to (prepared like
+/// [`install_confirmed`]) with a single write of the target file. Returns
+/// how many went in.
+pub async fn install_batch(snippets: &[Snippet], target_path: &Path, force: bool, placement: &Placement) -> Result<usize> {
+    let mut content = read_target(target_path)?;
+    let mut added = Vec::new();
     for snippet in snippets {
-        let content_lower = format!("{} {}", snippet.name, snippet.content).to_lowercase();
-        let mut score = 0;
-        
-        // Score based on word matches
-        for word in &query_words {
-            if content_lower.contains(word) {
-                score += word.len();
-            }
-        }
-        
-        // Bonus for name matches
-        if snippet.name.to_lowercase().contains(&query_lower) {
-            score += 50;
-        }
-        
-        if score > 0 {
-            scored_snippets.push((score, snippet));
+        crate::status::warn_if_deprecated(snippet);
+        let snippet = limits::check_size(&localized(snippet, None)?.0)?;
+        let snippet = template::render_snippet(&snippet, &HashMap::new())?;
+        let assets = assets::for_snippet(&snippet.id)?;
+        let snippet = assets::rewrite_references(&snippet, &assets);
+        println!("📦 {}", snippet.name);
+        if let Some(new_content) = add_block(&snippet, target_path, &content, force, placement)? {
+            content = new_content;
+            added.push((snippet, assets));
         }
     }
+    if added.is_empty() {
+        return Ok(0);
+    }
     
-    // Sort by score (highest first)
-    scored_snippets.sort_by(|a, b| b.0.cmp(&a.0));
-    
-    Ok(scored_snippets.first().map(|(_, snippet)| (*snippet).clone()))
+    write_target(target_path, &content)?;
+    let mut absolute_path = target_path.to_path_buf();
+    for (snippet, assets) in &added {
+        absolute_path = record_install(snippet, target_path)?;
+        assets::install(crate::ids::short(&snippet.id), assets, target_path)?;
+    }
+    println!("📝 Added {} snippet(s) to: {}", added.len(), absolute_path.display());
+    Ok(added.len())
 }
 
-pub async fn install_to_claude_md(snippet: &Snippet, force_local: bool, force_user: bool) -> Result<()> {
-    let claude_md_path = get_claude_md_path(force_local, force_user)?;
-    
-    // Read existing CLAUDE.md content
-    let existing_content = if claude_md_path.exists() {
-        fs::read_to_string(&claude_md_path)?
+fn read_target(target_path: &Path) -> Result<String> {
+    Ok(if target_path.exists() {
+        fs::read_to_string(target_path)?
     } else {
         String::new()
-    };
-    
+    })
+}
+
+fn write_target(target_path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = target_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(target_path, content)?;
+    Ok(())
+}
+
+/// Remember the install in the manifest and the usage log; returns the
+/// target's absolute path.
+fn record_install(snippet: &Snippet, target_path: &Path) -> Result<PathBuf> {
+    let absolute_path = target_path.canonicalize().unwrap_or(target_path.to_path_buf());
+    Manifest::record_install(crate::ids::short(&snippet.id), &snippet.name, &absolute_path, snippet.content.trim())?;
+    crate::usage::UsageLog::record(&absolute_path, snippet)?;
+    Ok(absolute_path)
+}
+
+/// `existing_content` with the snippet's block added (or replaced, when the
+/// same snippet is there), after the duplicate and conflict checks; `None`
+/// when the user declined.
+fn add_block(snippet: &Snippet, target_path: &Path, existing_content: &str, force: bool, placement: &Placement) -> Result<Option<String>> {
     // Check if snippet content already starts with a header
     let snippet_content = snippet.content.trim();
     let already_has_header = snippet_content.lines().next()
@@ -158,30 +388,214 @@ pub async fn install_to_claude_md(snippet: &Snippet, force_local: bool, force_us
         .unwrap_or(false);
     
     // Create snippet markers with ID for easy identification and removal
-    let snippet_id = &snippet.id[..8]; // Use first 8 chars of ID
+    crate::ids::validate(&snippet.id, &snippet.name)?;
+    let snippet_id = crate::ids::short(&snippet.id);
     let start_marker = format!("<!-- SNIPPET_START:{} -->", snippet_id);
     let end_marker = format!("<!-- SNIPPET_END:{} -->", snippet_id);
     
-    let new_content = if already_has_header {
-        // Just add the content with markers
-        format!("{}\n\n{}\n{}\n{}", existing_content, start_marker, snippet_content, end_marker)
+    let duplicate = find_duplicate(existing_content, snippet, target_path)?;
+    if let Some(duplicate) = &duplicate {
+        if force {
+            println!("⚠️  {} - installing anyway (--force)", duplicate.describe());
+        } else {
+            println!("⚠️  {}", duplicate.describe());
+            // The installed copy may have drifted from the repository version
+            if matches!(duplicate, Duplicate::SameId) {
+                if let Some((_, body)) = marked_blocks(existing_content).into_iter().find(|(id, _)| id == snippet_id) {
+                    crate::diff::print(&body, snippet_content, crate::diff::Layout::Unified);
+                }
+            }
+            if !crate::prompt::offer("Install it again anyway?")? {
+                println!("❌ Installation skipped (use --force to override)");
+                return Ok(None);
+            }
+        }
+    }
+    
+    if !matches!(duplicate, Some(Duplicate::SameId)) && !crate::conflicts::check(snippet, target_path, existing_content, force)? {
+        return Ok(None);
+    }
+    
+    let new_content = if matches!(duplicate, Some(Duplicate::SameId)) {
+        // Replace the existing block in place so the markers stay unique
+        let block = format!("{}\n{}\n{}", start_marker, snippet_content, end_marker);
+        replace_marked_block(existing_content, &start_marker, &end_marker, &block)?
     } else {
-        // Add header for content without one, plus markers
-        let snippet_header = format!("\n\n# {} (installed snippet)\n\n", snippet.name);
-        format!("{}{}{}\n{}\n{}", existing_content, snippet_header, start_marker, snippet_content, end_marker)
+        let block = format!("{}\n{}\n{}", start_marker, snippet_content, end_marker);
+        // Add a header for content without one, unless the block goes into an existing section
+        let block = if already_has_header || matches!(placement, Placement::AfterHeading(_) | Placement::Alphabetical) {
+            block
+        } else {
+            format!("# {} (installed snippet)\n\n{}", snippet.name, block)
+        };
+        let names: HashMap<String, String> = installed_snippets(target_path, existing_content)?
+            .into_iter()
+            .map(|installed| (installed.id, installed.name))
+            .collect();
+        placement.insert(existing_content, &block, &snippet.name, &names)?
     };
     
-    // Write back to CLAUDE.md
-    fs::write(&claude_md_path, new_content)?;
+    Ok(Some(new_content))
+}
+
+/// Append `block` after the file's existing content, separated by one blank
+/// line, so any markdown file (not just CLAUDE.md) stays tidy.
+pub fn append_block(existing_content: &str, block: &str) -> String {
+    let existing = existing_content.trim_end();
+    if existing.is_empty() {
+        format!("{}\n", block)
+    } else {
+        format!("{}\n\n{}\n", existing, block)
+    }
+}
+
+enum Duplicate {
+    SameId,
+    SameContent { existing: String },
+    UnmarkedCopy,
+}
+
+impl Duplicate {
+    fn describe(&self) -> String {
+        match self {
+            Duplicate::SameId => "This snippet is already installed in the target file".to_string(),
+            Duplicate::SameContent { existing } => {
+                format!("Identical content is already installed as snippet '{}'", existing)
+            }
+            Duplicate::UnmarkedCopy => "The target file already contains this snippet's content".to_string(),
+        }
+    }
+}
+
+fn find_duplicate(existing_content: &str, snippet: &Snippet, target: &Path) -> Result<Option<Duplicate>> {
+    let snippet_id = crate::ids::short(&snippet.id);
+    let blocks = marked_blocks(existing_content);
+    
+    if blocks.iter().any(|(id, _)| id == snippet_id) {
+        return Ok(Some(Duplicate::SameId));
+    }
     
-    // Show absolute path for clarity
-    let absolute_path = claude_md_path.canonicalize().unwrap_or(claude_md_path);
-    println!("📝 Added to: {}", absolute_path.display());
+    // Compare against other installed blocks by normalized content hash
+    let hash = content_hash(&snippet.content);
+    let absolute_target = target.canonicalize().unwrap_or_else(|_| target.to_path_buf());
+    let manifest = Manifest::load()?;
+    for (id, body) in &blocks {
+        if content_hash(body) == hash {
+            let existing = manifest.installs_in(&absolute_target)
+                .find(|record| &record.id == id)
+                .map(|record| record.name.clone())
+                .unwrap_or_else(|| id.clone());
+            return Ok(Some(Duplicate::SameContent { existing }));
+        }
+    }
     
-    Ok(())
+    // Content that was pasted in by hand, without markers
+    let normalized = normalize_content(&snippet.content);
+    if !normalized.is_empty() && normalize_content(existing_content).contains(&normalized) {
+        return Ok(Some(Duplicate::UnmarkedCopy));
+    }
+    
+    Ok(None)
+}
+
+/// All `SNIPPET_START`/`SNIPPET_END` blocks in a file as (short ID, inner content).
+pub fn marked_blocks(content: &str) -> Vec<(String, String)> {
+    const START_PREFIX: &str = "<!-- SNIPPET_START:";
+    let mut blocks = Vec::new();
+    let mut rest = content;
+    
+    while let Some(start) = rest.find(START_PREFIX) {
+        let after_prefix = &rest[start + START_PREFIX.len()..];
+        let Some(id_end) = after_prefix.find(" -->") else { break };
+        let id = after_prefix[..id_end].trim().to_string();
+        let body_start = &after_prefix[id_end + " -->".len()..];
+        let end_marker = format!("<!-- SNIPPET_END:{} -->", id);
+        
+        match body_start.find(&end_marker) {
+            Some(end) => {
+                blocks.push((id, body_start[..end].trim().to_string()));
+                rest = &body_start[end + end_marker.len()..];
+            }
+            None => rest = body_start,
+        }
+    }
+    
+    blocks
 }
 
-fn get_claude_md_path(force_local: bool, force_user: bool) -> Result<std::path::PathBuf> {
+fn replace_marked_block(content: &str, start_marker: &str, end_marker: &str, replacement: &str) -> Result<String> {
+    match (content.find(start_marker), content.find(end_marker)) {
+        (Some(start), Some(end)) if end > start => {
+            let end_with_marker = end + end_marker.len();
+            Ok(format!("{}{}{}", &content[..start], replacement, &content[end_with_marker..]))
+        }
+        _ => anyhow::bail!("Could not find both start and end markers for snippet"),
+    }
+}
+
+/// Where install, uninstall and installed operate, as given on the command line.
+#[derive(Default)]
+pub struct Destination {
+    pub local: bool,
+    pub user: bool,
+    pub target: Option<String>,
+    /// An arbitrary existing markdown file (CONTRIBUTING.md, a docs page, ...)
+    pub append_file: Option<PathBuf>,
+    /// Where in the file new blocks go
+    pub position: Placement,
+    /// Pin new installs to the repository's current commit (`install --pin`)
+    pub pin: bool,
+}
+
+impl Destination {
+    pub fn resolve(&self) -> Result<PathBuf> {
+        let Some(file) = &self.append_file else {
+            return resolve_target_path(self.local, self.user, self.target.as_deref());
+        };
+        let path = expand_target_path(&file.to_string_lossy())?;
+        if !path.is_file() {
+            anyhow::bail!("{} does not exist (--append-file only adds to existing files; use --target to create one)", path.display());
+        }
+        if path.extension().is_none_or(|ext| !ext.eq_ignore_ascii_case("md")) {
+            println!("⚠️  {} is not a markdown file; snippet markers are HTML comments", path.display());
+        }
+        Ok(path)
+    }
+}
+
+/// Resolve where to install: `--target` (a configured or built-in target name,
+/// or a path), then `--local`/`--user`, then the configured default location.
+pub fn resolve_target_path(force_local: bool, force_user: bool, target: Option<&str>) -> Result<PathBuf> {
+    // A project's default target applies when nothing more specific was asked for
+    let project_target = match (target, force_local || force_user) {
+        (None, false) => crate::project::ProjectConfig::find()?.and_then(|(_, project)| project.default_target),
+        _ => None,
+    };
+    if let Some(target) = target.or(project_target.as_deref()) {
+        let config = crate::config::Config::load()?;
+        let path = config.get_target(target).unwrap_or(target);
+        return expand_target_path(path);
+    }
+    
+    get_claude_md_path(force_local, force_user)
+}
+
+/// Relative target paths are resolved against the current directory; `~/` is expanded.
+fn expand_target_path(path: &str) -> Result<PathBuf> {
+    if let Some(rest) = path.strip_prefix("~/") {
+        let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+        return Ok(home.join(rest));
+    }
+    
+    let path = Path::new(path);
+    if path.is_absolute() {
+        Ok(path.to_path_buf())
+    } else {
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+fn get_claude_md_path(force_local: bool, force_user: bool) -> Result<PathBuf> {
     if force_local {
         // Force local installation
         let current_dir = std::env::current_dir()?;
@@ -219,93 +633,188 @@ fn get_claude_md_path(force_local: bool, force_user: bool) -> Result<std::path::
     }
 }
 
-fn load_snippets() -> Result<Vec<Snippet>> {
-    let repo_dir = get_snippets_dir()?;
-    let snippets_dir = repo_dir.join("snippets");
+/// A snippet block found in a target file, named from the manifest or the repository.
+pub struct InstalledSnippet {
+    pub id: String,
+    pub name: String,
+    pub content: String,
+}
+
+/// Snippets present in `target_path`, identified by their markers.
+pub fn installed_snippets(target_path: &Path, content: &str) -> Result<Vec<InstalledSnippet>> {
+    let absolute_path = target_path.canonicalize().unwrap_or(target_path.to_path_buf());
+    let manifest = Manifest::load()?;
+    let snippets = crate::store::load_snippets().unwrap_or_default();
+    
+    Ok(marked_blocks(content).into_iter().map(|(id, body)| {
+        let name = manifest.installs_in(&absolute_path)
+            .find(|record| record.id == id)
+            .map(|record| record.name.clone())
+            .or_else(|| snippets.iter().find(|s| s.id.starts_with(&id)).map(|s| s.name.clone()))
+            .unwrap_or_else(|| {
+                body.lines().find(|l| !l.trim().is_empty()).unwrap_or("").trim_start_matches('#').trim().to_string()
+            });
+        InstalledSnippet { id, name, content: body }
+    }).collect())
+}
+
+pub async fn list_installed(destination: Destination) -> Result<()> {
+    let target_path = destination.resolve()?;
     
-    if !snippets_dir.exists() {
-        return Ok(Vec::new());
+    if !target_path.exists() {
+        println!("❌ {} not found", target_path.display());
+        return Ok(());
     }
     
-    let mut snippets = Vec::new();
+    let content = fs::read_to_string(&target_path)?;
+    let installed = installed_snippets(&target_path, &content)?;
     
-    for entry in fs::read_dir(snippets_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(snippet) = crate::publish::parse_markdown_frontmatter(&content) {
-                    snippets.push(snippet);
-                }
-            }
-        }
+    println!("📦 Snippets installed in {}:", target_path.display());
+    if installed.is_empty() {
+        println!("  (none)");
+    }
+    let experiments = crate::experiments::ExperimentLog::load()?;
+    let manifest = Manifest::load()?;
+    let absolute_path = target_path.canonicalize().unwrap_or(target_path.clone());
+    let target_key = absolute_path.display().to_string();
+    for snippet in &installed {
+        let variant = experiments.active(&target_key, &snippet.id)
+            .map(|entry| format!(", variant '{}'", entry.variant))
+            .unwrap_or_default();
+        let pinned = manifest.installs_in(&absolute_path)
+            .find(|record| record.id == snippet.id)
+            .and_then(|record| record.pinned.as_deref())
+            .map(|commit| format!(", 📌 pinned at {}", commit))
+            .unwrap_or_default();
+        println!("  • {} ({}, {} lines{}{})", snippet.name, snippet.id, snippet.content.lines().count(), variant, pinned);
     }
     
-    // Sort by creation date (newest first)
-    snippets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    // Repository snippets pasted in by hand, without markers
+    let normalized = normalize_content(&content);
+    let unmarked: Vec<Snippet> = crate::store::load_snippets()?
+        .into_iter()
+        .filter(|s| !installed.iter().any(|i| s.id.starts_with(&i.id)))
+        .filter(|s| {
+            let snippet_content = normalize_content(&s.content);
+            !snippet_content.is_empty() && normalized.contains(&snippet_content)
+        })
+        .collect();
+    if !unmarked.is_empty() {
+        println!();
+        println!("🔎 Also present without markers (can't be uninstalled automatically):");
+        for snippet in unmarked {
+            println!("  • {} ({})", snippet.name, crate::ids::short(&snippet.id));
+        }
+    }
     
-    Ok(snippets)
+    Ok(())
 }
 
-pub async fn uninstall_snippet(query: String, force_local: bool, force_user: bool) -> Result<()> {
-    let snippets = load_snippets()?;
-    
-    if snippets.is_empty() {
-        println!("❌ No snippets found. Nothing to uninstall!");
+/// `uninstall`: remove snippet blocks (and their assets) from the target file
+/// and the manifest. `purge` also forgets their usage stats and experiment
+/// history for that target.
+pub async fn uninstall_snippet(queries: Vec<String>, all: bool, collection: Option<String>, destination: Destination, purge: bool) -> Result<()> {
+    let claude_md_path = destination.resolve()?;
+    let target_name = claude_md_path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "CLAUDE.md".to_string());
+    
+    if !claude_md_path.exists() {
+        println!("❌ {} not found at: {}", target_name, claude_md_path.display());
         return Ok(());
     }
-
-    println!("🔍 Finding snippet to uninstall: '{}'", query);
     
-    let best_match = find_best_match(&snippets, &query).await?;
+    let existing_content = fs::read_to_string(&claude_md_path)?;
+    let installed = installed_snippets(&claude_md_path, &existing_content)?;
     
-    if let Some(snippet) = best_match {
-        println!("✅ Found matching snippet: '{}'", snippet.name);
-        
-        let claude_md_path = get_claude_md_path(force_local, force_user)?;
-        
-        if !claude_md_path.exists() {
-            println!("❌ CLAUDE.md not found at: {}", claude_md_path.display());
-            return Ok(());
-        }
-        
-        let existing_content = fs::read_to_string(&claude_md_path)?;
-        let snippet_id = &snippet.id[..8];
-        let start_marker = format!("<!-- SNIPPET_START:{} -->", snippet_id);
-        let end_marker = format!("<!-- SNIPPET_END:{} -->", snippet_id);
-        
-        if !existing_content.contains(&start_marker) {
-            println!("❌ Snippet '{}' is not installed in CLAUDE.md", snippet.name);
-            return Ok(());
+    if installed.is_empty() {
+        println!("❌ No installed snippets found in {}", target_name);
+        return Ok(());
+    }
+    
+    let selected: Vec<&InstalledSnippet> = if all {
+        installed.iter().collect()
+    } else if let Some(name) = &collection {
+        let members = collections::members(&collections::find(name)?, &crate::store::load_snippets()?)?;
+        let selected: Vec<&InstalledSnippet> = installed.iter()
+            .filter(|i| members.iter().any(|m| m.id.starts_with(&i.id)))
+            .collect();
+        if selected.len() < members.len() {
+            println!("ℹ️  {} of the {} snippet(s) in '{}' are installed in {}", selected.len(), members.len(), name, target_name);
         }
-        
-        print!("Remove snippet '{}' from CLAUDE.md? [Y/n]: ", snippet.name);
-        std::io::stdout().flush()?;
-        
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input)?;
-        let input = input.trim().to_lowercase();
-        
-        if input.is_empty() || input == "y" || input == "yes" {
-            let updated_content = remove_snippet_from_content(&existing_content, &start_marker, &end_marker)?;
-            fs::write(&claude_md_path, updated_content)?;
-            println!("✅ Snippet '{}' removed successfully from {}", snippet.name, claude_md_path.display());
-        } else {
-            println!("❌ Uninstall cancelled");
+        selected
+    } else if queries.is_empty() {
+        // Interactive multi-select
+        println!("📦 Snippets installed in {}:", claude_md_path.display());
+        for (i, snippet) in installed.iter().enumerate() {
+            println!("  {}. {} ({})", i + 1, snippet.name, snippet.id);
         }
+        crate::prompt::select("Uninstall which?", installed.len())?
+            .into_iter()
+            .map(|i| &installed[i])
+            .collect()
     } else {
-        println!("❌ No suitable snippet found for query: '{}'", query);
-        println!("💡 Available snippets:");
-        for snippet in &snippets {
-            println!("  - {}", snippet.name);
+        let snippets = timed(Category::FsScan, "load snippets", crate::store::load_snippets)?;
+        let mut selected: Vec<&InstalledSnippet> = Vec::new();
+        for query in &queries {
+            println!("🔍 Finding snippet to uninstall: '{}'", query);
+            
+            // An installed ID is taken as-is, anything else is matched against the repository
+            let id = if let Some(by_id) = installed.iter().find(|i| crate::ids::matches_prefix(&i.id, query)) {
+                Some(by_id.id.clone())
+            } else {
+                find_best_match(&snippets, query).await?.map(|snippet| crate::ids::short(&snippet.id).to_string())
+            };
+            
+            match id.and_then(|id| installed.iter().find(|i| i.id == id)) {
+                Some(snippet) => {
+                    if !selected.iter().any(|s| s.id == snippet.id) {
+                        selected.push(snippet);
+                    }
+                }
+                None => println!("❌ No snippet installed in {} matches '{}'", target_name, query),
+            }
+        }
+        selected
+    };
+    
+    if selected.is_empty() {
+        println!("ℹ️  Nothing to uninstall");
+        return Ok(());
+    }
+    
+    println!("🗑️  Will remove from {}:", claude_md_path.display());
+    for snippet in &selected {
+        println!("  • {} ({})", snippet.name, snippet.id);
+    }
+    if !crate::prompt::confirm(&format!("Remove {} snippet(s)?", selected.len()), true)? {
+        println!("❌ Uninstall cancelled");
+        return Ok(());
+    }
+    
+    let mut updated_content = existing_content;
+    let absolute_path = claude_md_path.canonicalize().unwrap_or(claude_md_path.clone());
+    for snippet in &selected {
+        let start_marker = format!("<!-- SNIPPET_START:{} -->", snippet.id);
+        let end_marker = format!("<!-- SNIPPET_END:{} -->", snippet.id);
+        updated_content = remove_snippet_from_content(&updated_content, &start_marker, &end_marker)?;
+    }
+    fs::write(&claude_md_path, updated_content)?;
+    for snippet in &selected {
+        Manifest::record_uninstall(&snippet.id, &absolute_path)?;
+        assets::uninstall(&snippet.id, &claude_md_path)?;
+        println!("✅ Snippet '{}' removed successfully from {}", snippet.name, claude_md_path.display());
+        if purge {
+            let usage = crate::usage::UsageLog::forget(&snippet.id, &absolute_path)?;
+            let experiments = crate::experiments::ExperimentLog::forget(&snippet.id, &absolute_path)?;
+            println!("🧹 Purged {} usage record(s) and {} experiment record(s) for it", usage, experiments);
         }
     }
     
     Ok(())
 }
 
-fn remove_snippet_from_content(content: &str, start_marker: &str, end_marker: &str) -> Result<String> {
+pub fn remove_snippet_from_content(content: &str, start_marker: &str, end_marker: &str) -> Result<String> {
     let start_pos = content.find(start_marker);
     let end_pos = content.find(end_marker);
     
@@ -332,14 +841,62 @@ fn remove_snippet_from_content(content: &str, start_marker: &str, end_marker: &s
     }
 }
 
-fn preview_content(content: &str) -> String {
-    let lines: Vec<&str> = content.lines().take(30).collect();
+fn preview_content(content: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = content.lines().take(max_lines).collect();
     let total_lines = content.lines().count();
     let preview = lines.join("\n");
     
-    if total_lines > 30 {
-        format!("{}\n... (truncated, {} more lines)", preview, total_lines - 30)
+    if total_lines > max_lines {
+        format!("{}\n... (truncated, {} more lines)", preview, total_lines - max_lines)
     } else {
         preview
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marked_blocks() {
+        let content = "intro\n<!-- SNIPPET_START:aaaa1111 -->\nfirst\n<!-- SNIPPET_END:aaaa1111 -->\n\n<!-- SNIPPET_START:bbbb2222 -->\n# Second\nbody\n<!-- SNIPPET_END:bbbb2222 -->";
+        let blocks = marked_blocks(content);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0], ("aaaa1111".to_string(), "first".to_string()));
+        assert_eq!(blocks[1], ("bbbb2222".to_string(), "# Second\nbody".to_string()));
+        assert!(marked_blocks("<!-- SNIPPET_START:cccc3333 -->\nunterminated").is_empty());
+    }
+
+    #[test]
+    fn test_close_matches() {
+        assert_eq!(close_matches(&[]), 0);
+        assert_eq!(close_matches(&[60, 20]), 1);
+        assert_eq!(close_matches(&[60, 55, 50, 10]), 3);
+        assert_eq!(close_matches(&[9; 8]), 5);
+    }
+
+    #[test]
+    fn test_fuzzy_match_score() {
+        let snippet = |name: &str, tags: &[&str], content: &str| Snippet {
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            ..crate::publish::test_snippet(name, name, content)
+        };
+        let matcher = SkimMatcherV2::default().ignore_case();
+        let testing = snippet("Rust testing", &[], "Run the whole suite with cargo before pushing, keep fixtures small.");
+        let style = snippet("Code style", &["python"], "Format with black and keep lines short.");
+
+        // Abbreviations still find the name; the name outweighs the body
+        assert!(match_score(&matcher, &testing, "rst tst") > 0);
+        assert!(match_score(&matcher, &testing, "testing") > match_score(&matcher, &style, "keep"));
+        assert!(match_score(&matcher, &style, "python") > match_score(&matcher, &testing, "cargo"));
+        // Letters scattered through unrelated text don't count
+        assert_eq!(match_score(&matcher, &testing, "python"), 0);
+        assert_eq!(match_score(&matcher, &testing, "docker"), 0);
+    }
+
+    #[test]
+    fn test_append_block() {
+        assert_eq!(append_block("", "block"), "block\n");
+        assert_eq!(append_block("# Contributing\n\n\n", "block"), "# Contributing\n\nblock\n");
+    }
+}
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::lockfile::{FileLock, atomic_write};
+use crate::publish::get_app_dir;
+
+/// Changes committed locally while offline, waiting for `sync` to push them.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Journal {
+    #[serde(default)]
+    pub pending: Vec<PendingChange>,
+    /// When each repository last synced completely (repo name -> RFC 3339 time)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub last_synced: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingChange {
+    pub repo: String,
+    pub description: String,
+    pub queued_at: String,
+}
+
+impl Journal {
+    pub fn load() -> Result<Self> {
+        let journal_path = get_journal_path()?;
+        if !journal_path.exists() {
+            return Ok(Journal::default());
+        }
+        read_journal(&journal_path)
+    }
+
+    fn update<F>(change: F) -> Result<()>
+    where
+        F: FnOnce(&mut Journal),
+    {
+        let journal_path = get_journal_path()?;
+        let _lock = FileLock::acquire(&journal_path)?;
+
+        let mut journal = if journal_path.exists() {
+            read_journal(&journal_path)?
+        } else {
+            Journal::default()
+        };
+
+        change(&mut journal);
+        atomic_write(&journal_path, &serde_json::to_string_pretty(&journal)?)
+    }
+
+    pub fn record(repo: &str, description: &str) -> Result<()> {
+        let change = PendingChange {
+            repo: repo.to_string(),
+            description: description.to_string(),
+            queued_at: chrono::Utc::now().to_rfc3339(),
+        };
+        Journal::update(|journal| journal.pending.push(change))
+    }
+
+    pub fn pending_for(&self, repo: &str) -> Vec<&PendingChange> {
+        self.pending.iter().filter(|change| change.repo == repo).collect()
+    }
+
+    /// Forget a repository's queued changes once they have been pushed, and
+    /// note when that was.
+    pub fn mark_synced(repo: &str) -> Result<()> {
+        Journal::update(|journal| {
+            journal.pending.retain(|change| change.repo != repo);
+            journal.last_synced.insert(repo.to_string(), chrono::Utc::now().to_rfc3339());
+        })
+    }
+}
+
+fn get_journal_path() -> Result<std::path::PathBuf> {
+    let app_dir = get_app_dir()?;
+    Ok(app_dir.join("pending-sync.json"))
+}
+
+fn read_journal(journal_path: &Path) -> Result<Journal> {
+    let content = fs::read_to_string(journal_path)?;
+    Ok(serde_json::from_str(&content)?)
+}
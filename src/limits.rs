@@ -0,0 +1,93 @@
+use anyhow::Result;
+use crate::capabilities::{self, Tool};
+use crate::claude_client::ClaudeClient;
+use crate::config::{Config, SizeLimits};
+use crate::publish::Snippet;
+
+/// Rough token count for English markdown (about four characters per token).
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Which configured limits `content` goes over, e.g. "~2100 tokens (limit 1500)".
+pub fn exceeded(content: &str, limits: &SizeLimits) -> Vec<String> {
+    let mut over = Vec::new();
+    let tokens = estimate_tokens(content);
+    if limits.max_tokens > 0 && tokens > limits.max_tokens {
+        over.push(format!("~{} tokens (limit {})", tokens, limits.max_tokens));
+    }
+    if limits.max_bytes > 0 && content.len() > limits.max_bytes {
+        over.push(format!("{} bytes (limit {})", content.len(), limits.max_bytes));
+    }
+    over
+}
+
+/// Warn when a snippet is over the size limits at publish or install time, and
+/// offer to have Claude Code condense it. Returns the snippet to use.
+pub fn check_size(snippet: &Snippet) -> Result<Snippet> {
+    let limits = Config::load()?.limits;
+    let over = exceeded(&snippet.content, &limits);
+    if over.is_empty() {
+        return Ok(snippet.clone());
+    }
+
+    println!("⚠️  '{}' is large: {}", snippet.name, over.join(", "));
+    println!("💡 Every installed line is loaded into Claude's context; large snippets crowd out the conversation");
+    if !capabilities::has(Tool::Claude) {
+        capabilities::note_unavailable(Tool::Claude, "Summarizing to fit");
+        return Ok(snippet.clone());
+    }
+
+    if !crate::prompt::offer("Summarize it to fit with Claude Code?")? {
+        return Ok(snippet.clone());
+    }
+
+    let budget = match (limits.max_tokens, limits.max_bytes / 4) {
+        (0, bytes) => bytes,
+        (tokens, 0) => tokens,
+        (tokens, bytes) => tokens.min(bytes),
+    };
+    let prompt = format!(
+        "Condense the following CLAUDE.md instructions to at most {} tokens. Keep every rule and \
+        command that changes behaviour; drop repetition, examples that restate a rule, and filler. \
+        Return only the condensed markdown.\n\n{}",
+        budget, snippet.content
+    );
+    let condensed = match ClaudeClient::from_config()?.prompt(&prompt) {
+        Ok(condensed) if !condensed.is_empty() => condensed,
+        Ok(_) => {
+            println!("⚠️  Claude Code returned nothing; keeping the original");
+            return Ok(snippet.clone());
+        }
+        Err(e) => {
+            println!("⚠️  Could not summarize ({}); keeping the original", e);
+            return Ok(snippet.clone());
+        }
+    };
+
+    println!("📋 Summarized ({} → ~{} tokens):", estimate_tokens(&snippet.content), estimate_tokens(&condensed));
+    crate::diff::print(&snippet.content, &condensed, crate::diff::Layout::Unified);
+    let still_over = exceeded(&condensed, &limits);
+    if !still_over.is_empty() {
+        println!("⚠️  Still over the limit: {}", still_over.join(", "));
+    }
+    if crate::prompt::confirm("Use the summarized version?", true)? {
+        Ok(Snippet { content: condensed, ..snippet.clone() })
+    } else {
+        Ok(snippet.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exceeded_limits() {
+        let limits = SizeLimits { max_tokens: 10, max_bytes: 100 };
+        assert!(exceeded("short rule", &limits).is_empty());
+        assert_eq!(exceeded(&"x".repeat(60), &limits), ["~15 tokens (limit 10)"]);
+        assert_eq!(exceeded(&"x".repeat(120), &limits).len(), 2);
+        assert!(exceeded(&"x".repeat(120), &SizeLimits { max_tokens: 0, max_bytes: 0 }).is_empty());
+    }
+}
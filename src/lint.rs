@@ -0,0 +1,265 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+use crate::publish::{self, Snippet};
+
+/// `repo lint` exit status: no errors (warnings allowed).
+pub const EXIT_CLEAN: i32 = 0;
+/// `repo lint` exit status: at least one error-level finding.
+pub const EXIT_FINDINGS: i32 = 1;
+/// `repo lint --ci` exit status: the lint itself could not run.
+pub const EXIT_FAILED: i32 = 2;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum LintFormat {
+    Text,
+    Json,
+    /// SARIF 2.1.0, for code scanning annotations on pull requests
+    Sarif,
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Finding {
+    pub rule: &'static str,
+    pub severity: Severity,
+    /// Path relative to the repository root, with `/` separators
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Every rule with its description, as listed in SARIF output.
+const RULES: [(&str, &str); 9] = [
+    ("invalid-frontmatter", "The file has no frontmatter, or it is not valid YAML"),
+    ("missing-id", "The frontmatter has no id"),
+    ("missing-name", "The frontmatter has no name"),
+    ("duplicate-id", "Another snippet has the same id"),
+    ("empty-content", "The snippet has no instructions below its frontmatter"),
+    ("missing-variant", "A file listed under variants does not exist"),
+    ("oversized", "The snippet is over the configured size limits"),
+    ("secret", "The file contains what looks like a credential"),
+    ("possible-secret", "The file assigns what may be a credential"),
+];
+
+/// Credentials with a recognisable shape; any of these fails the lint.
+static SECRET_PATTERNS: LazyLock<Vec<(&str, Regex)>> = LazyLock::new(|| {
+    [
+        ("AWS access key", r"\b(AKIA|ASIA)[0-9A-Z]{16}\b"),
+        ("GitHub token", r"\b(gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})"),
+        ("Anthropic API key", r"\bsk-ant-[A-Za-z0-9_\-]{20,}"),
+        ("OpenAI API key", r"\bsk-(proj-)?[A-Za-z0-9]{20,}"),
+        ("Slack token", r"\bxox[abprs]-[A-Za-z0-9\-]{10,}"),
+        ("private key", r"-----BEGIN [A-Z ]*PRIVATE KEY-----"),
+    ]
+    .into_iter()
+    .map(|(kind, pattern)| (kind, Regex::new(pattern).expect("valid secret pattern")))
+    .collect()
+});
+
+/// `password = ...` style assignments of a long opaque value; placeholders
+/// such as `<token>` or `$TOKEN` don't match.
+static ASSIGNMENT_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(password|passwd|secret|api[_-]?key|access[_-]?token|auth[_-]?token)\b\s*[:=]\s*["']?([A-Za-z0-9/+_\-]{16,})"#)
+        .expect("valid assignment pattern")
+});
+
+/// Check every snippet under `repo_dir/snippets`: frontmatter, IDs, variant
+/// files, size limits and anything that looks like a credential.
+pub fn lint_repo(repo_dir: &Path) -> Result<(usize, Vec<Finding>)> {
+    let snippets_dir = repo_dir.join("snippets");
+    if !snippets_dir.is_dir() {
+        anyhow::bail!("{} has no snippets/ folder", repo_dir.display());
+    }
+    let limits = crate::config::Config::load().map(|config| config.limits).unwrap_or_default();
+
+    let files = crate::scan::snippet_files(&snippets_dir);
+    let mut findings = Vec::new();
+    let mut ids: HashMap<String, String> = HashMap::new();
+    for path in &files {
+        let file = relative(repo_dir, path);
+        let text = fs::read_to_string(path)?;
+        findings.extend(scan_secrets(&file, &text));
+        if path.file_name().is_some_and(|name| name.to_string_lossy().to_lowercase().starts_with("readme")) {
+            continue;
+        }
+
+        let snippet = match publish::parse_markdown_frontmatter(&text) {
+            Ok(snippet) => snippet,
+            Err(e) => {
+                findings.push(finding("invalid-frontmatter", Severity::Error, &file, Some(1), e.to_string()));
+                continue;
+            }
+        };
+        findings.extend(check_snippet(&file, &snippet));
+        if !snippet.id.is_empty() {
+            if let Some(first) = ids.insert(snippet.id.clone(), file.clone()) {
+                findings.push(finding("duplicate-id", Severity::Error, &file, Some(frontmatter_line(&text, "id")),
+                    format!("ID '{}' is also used by {}", snippet.id, first)));
+            }
+        }
+        for (label, variant) in &snippet.variants {
+            let variant_path = snippets_dir.join(variant);
+            match fs::read_to_string(&variant_path) {
+                Ok(variant_text) => findings.extend(scan_secrets(&relative(repo_dir, &variant_path), &variant_text)),
+                Err(_) => findings.push(finding("missing-variant", Severity::Error, &file, Some(frontmatter_line(&text, "variants")),
+                    format!("Variant '{}' points at {}, which does not exist", label, variant))),
+            }
+        }
+        let over = crate::limits::exceeded(&snippet.content, &limits);
+        if !over.is_empty() {
+            findings.push(finding("oversized", Severity::Warning, &file, None, format!("Over the size limits: {}", over.join(", "))));
+        }
+    }
+    Ok((files.len(), findings))
+}
+
+fn check_snippet(file: &str, snippet: &Snippet) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    if snippet.id.trim().is_empty() {
+        findings.push(finding("missing-id", Severity::Error, file, Some(1), "No 'id' in the frontmatter".to_string()));
+    }
+    if snippet.name.trim().is_empty() {
+        findings.push(finding("missing-name", Severity::Error, file, Some(1), "No 'name' in the frontmatter".to_string()));
+    }
+    if snippet.content.trim().is_empty() {
+        findings.push(finding("empty-content", Severity::Warning, file, None, "The snippet has no content".to_string()));
+    }
+    findings
+}
+
+/// Credential-like strings in `text`, one finding per line, with the value
+/// itself left out of the message.
+fn scan_secrets(file: &str, text: &str) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if let Some((kind, found)) = SECRET_PATTERNS.iter().find_map(|(kind, pattern)| pattern.find(line).map(|m| (kind, m))) {
+            findings.push(finding("secret", Severity::Error, file, Some(number + 1),
+                format!("Looks like a {} ({})", kind, redact(found.as_str()))));
+        } else if let Some(captures) = ASSIGNMENT_PATTERN.captures(line) {
+            findings.push(finding("possible-secret", Severity::Warning, file, Some(number + 1),
+                format!("'{}' is set to what may be a real credential ({})", &captures[1], redact(&captures[2]))));
+        }
+    }
+    findings
+}
+
+fn redact(value: &str) -> String {
+    format!("{}…", value.chars().take(4).collect::<String>())
+}
+
+/// Line of the `key:` entry in the frontmatter, or 1.
+fn frontmatter_line(text: &str, key: &str) -> usize {
+    text.lines().position(|line| line.starts_with(&format!("{}:", key))).map_or(1, |i| i + 1)
+}
+
+fn relative(repo_dir: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(repo_dir).unwrap_or(path);
+    relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+fn finding(rule: &'static str, severity: Severity, file: &str, line: Option<usize>, message: String) -> Finding {
+    Finding { rule, severity, file: file.to_string(), line, message }
+}
+
+/// Print the findings in `format` and return the exit status for them.
+pub fn report(files: usize, findings: &[Finding], format: LintFormat) -> Result<i32> {
+    let errors = findings.iter().filter(|f| f.severity == Severity::Error).count();
+    let warnings = findings.len() - errors;
+    match format {
+        LintFormat::Text => {
+            for f in findings {
+                let icon = if f.severity == Severity::Error { "❌" } else { "⚠️ " };
+                let location = f.line.map(|line| format!("{}:{}", f.file, line)).unwrap_or_else(|| f.file.clone());
+                println!("{} {} [{}] {}", icon, location, f.rule, f.message);
+            }
+            if findings.is_empty() {
+                println!("✅ {} snippet file(s) checked, no problems found", files);
+            } else {
+                println!("📋 {} snippet file(s) checked: {} error(s), {} warning(s)", files, errors, warnings);
+            }
+        }
+        LintFormat::Json => {
+            let report = serde_json::json!({ "files_checked": files, "errors": errors, "warnings": warnings, "findings": findings });
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        LintFormat::Sarif => println!("{}", serde_json::to_string_pretty(&to_sarif(findings))?),
+    }
+    Ok(if errors > 0 { EXIT_FINDINGS } else { EXIT_CLEAN })
+}
+
+fn to_sarif(findings: &[Finding]) -> serde_json::Value {
+    let rules: Vec<serde_json::Value> = RULES
+        .iter()
+        .map(|(id, description)| serde_json::json!({ "id": id, "shortDescription": { "text": description } }))
+        .collect();
+    let results: Vec<serde_json::Value> = findings
+        .iter()
+        .map(|f| {
+            let mut location = serde_json::json!({ "artifactLocation": { "uri": f.file } });
+            if let Some(line) = f.line {
+                location["region"] = serde_json::json!({ "startLine": line });
+            }
+            serde_json::json!({
+                "ruleId": f.rule,
+                "level": if f.severity == Severity::Error { "error" } else { "warning" },
+                "message": { "text": f.message },
+                "locations": [{ "physicalLocation": location }],
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": { "driver": {
+                "name": "claude-md-snippets",
+                "version": env!("CARGO_PKG_VERSION"),
+                "informationUri": "https://github.com/eyalev/claude-md-snippets-manager",
+                "rules": rules,
+            } },
+            "results": results,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lint_repo() {
+        let repo = tempfile::tempdir().unwrap();
+        let snippets = repo.path().join("snippets");
+        fs::create_dir_all(&snippets).unwrap();
+        let key = format!("AKIA{}", "ABCDEFGHIJKLMNOP");
+        fs::write(snippets.join("a.md"), format!("---\nid: 1a2b\nname: Deploy\n---\nUse key {} to deploy\n", key)).unwrap();
+        fs::write(snippets.join("b.md"), "---\nid: 1a2b\nname: Copy\nvariants:\n  b: variants/b.md\n---\nRun the tests\n").unwrap();
+        fs::write(snippets.join("c.md"), "No frontmatter here\n").unwrap();
+        fs::write(snippets.join("d.md"), "---\nid: 9f8e\nname: Tokens\n---\nSet API_KEY=<your key> in .env\n").unwrap();
+
+        let (files, findings) = lint_repo(repo.path()).unwrap();
+        let rules: Vec<(&str, &str, Option<usize>)> = findings.iter().map(|f| (f.file.as_str(), f.rule, f.line)).collect();
+        assert_eq!(files, 4);
+        assert_eq!(rules, vec![
+            ("snippets/a.md", "secret", Some(5)),
+            ("snippets/b.md", "duplicate-id", Some(2)),
+            ("snippets/b.md", "missing-variant", Some(4)),
+            ("snippets/c.md", "invalid-frontmatter", Some(1)),
+        ]);
+        assert!(!findings[0].message.contains(&key));
+        assert_eq!(to_sarif(&findings)["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"]["startLine"], 5);
+    }
+}
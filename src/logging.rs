@@ -0,0 +1,76 @@
+use anyhow::Result;
+use std::io::IsTerminal;
+use tracing::level_filters::LevelFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::prelude::*;
+
+/// Daily log files kept under `<app dir>/logs` before the oldest is removed.
+const KEPT_LOG_FILES: usize = 7;
+
+/// Diagnostics level on stderr: warnings by default, `-v` info, `-vv` debug
+/// (Claude CLI and git invocations), `-vvv` trace (their output). `--quiet`
+/// leaves only errors.
+pub fn level(verbosity: u8, quiet: bool) -> LevelFilter {
+    match (quiet, verbosity) {
+        (true, _) => LevelFilter::ERROR,
+        (false, 0) => LevelFilter::WARN,
+        (false, 1) => LevelFilter::INFO,
+        (false, 2) => LevelFilter::DEBUG,
+        (false, _) => LevelFilter::TRACE,
+    }
+}
+
+/// Set up logging to stderr at `level`, plus a rotating debug-level log file
+/// when `log_file` is set. Keep the returned guard alive until exit so the
+/// file is flushed.
+pub fn init(level: LevelFilter, log_file: bool) -> Result<Option<WorkerGuard>> {
+    let stderr_layer = tracing_subscriber::fmt::layer()
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .without_time()
+        .with_target(false)
+        .with_filter(level);
+
+    let (file_layer, guard) = if log_file {
+        let logs_dir = crate::publish::get_app_dir()?.join("logs");
+        std::fs::create_dir_all(&logs_dir)?;
+        let appender = Builder::new()
+            .rotation(Rotation::DAILY)
+            .filename_prefix("claude-md-snippets")
+            .filename_suffix("log")
+            .max_log_files(KEPT_LOG_FILES)
+            .build(&logs_dir)?;
+        let (writer, guard) = tracing_appender::non_blocking(appender);
+        let layer = tracing_subscriber::fmt::layer()
+            .with_writer(writer)
+            .with_ansi(false)
+            .with_filter(level.max(LevelFilter::DEBUG));
+        (Some(layer), Some(guard))
+    } else {
+        (None, None)
+    };
+
+    tracing_subscriber::registry()
+        .with(stderr_layer)
+        .with(file_layer)
+        .try_init()
+        .ok();
+    Ok(guard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_levels() {
+        assert_eq!(level(0, false), LevelFilter::WARN);
+        assert_eq!(level(2, false), LevelFilter::DEBUG);
+        assert_eq!(level(5, false), LevelFilter::TRACE);
+        assert_eq!(level(2, true), LevelFilter::ERROR);
+        // The log file records at least debug detail whatever the stderr level
+        assert_eq!(level(0, false).max(LevelFilter::DEBUG), LevelFilter::DEBUG);
+        assert_eq!(level(3, false).max(LevelFilter::DEBUG), LevelFilter::TRACE);
+    }
+}
@@ -1,22 +1,129 @@
+#![allow(clippy::collapsible_if)]
+
 use clap::{Parser, Subcommand};
 use anyhow::Result;
+use capabilities::Tool;
+use perf::{Category, TimedCommand};
 
 mod publish;
 mod install;
 mod search;
+mod template;
 mod github;
 mod extract;
+mod capabilities;
 mod config;
+mod claude_client;
+mod index;
+mod lockfile;
+mod manifest;
+mod perf;
+mod editor;
+mod scaffold;
+mod harvest;
+mod audit;
+mod starters;
+mod accounts;
+mod prompt;
+mod journal;
+mod gist;
+mod process;
+mod store;
+mod slug;
+mod github_api;
+mod assets;
+mod hosts;
+mod variants;
+mod collections;
+mod project;
+mod stats;
+mod limits;
+mod report;
+mod delete;
+mod promote;
+mod logging;
+mod scan;
+mod edit;
+mod visibility;
+mod show;
+mod capture;
+mod digest;
+mod state;
+mod watch;
+mod health;
+mod serve;
+mod exec;
+mod experiments;
+mod bundle;
+mod ids;
+mod status;
+mod usage;
+mod placement;
+mod doctor;
+mod diff;
+mod subscriptions;
+mod resolve;
+mod update;
+mod tokens;
+mod browser;
+mod bootstrap;
+mod app_dirs;
+mod attribution;
+mod browse;
+mod lint;
+mod changeset;
+mod sync_status;
+mod trash;
+mod batch_publish;
+mod conflicts;
+mod suggest;
+mod init;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 #[command(name = "claude-md-snippets")]
 #[command(about = "Manage and share CLAUDE.md snippets")]
 struct Cli {
-    /// Enable debug logging
+    /// More diagnostics on stderr: -v info, -vv Claude CLI and git calls, -vvv their output
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+    
+    /// Only log errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+    
+    /// Keep config and data in this directory instead of the XDG ones (or set CLAUDE_MD_SNIPPETS_HOME)
+    #[arg(long, global = true, value_name = "DIR")]
+    data_dir: Option<std::path::PathBuf>,
+    
+    /// Also write debug logs to a daily rotating file under the data directory's logs/
     #[arg(long, global = true)]
+    log_file: bool,
+    
+    /// Same as -vv
+    #[arg(long, global = true, hide = true)]
     debug: bool,
     
+    /// Answer yes to every confirmation, for scripts and CI
+    #[arg(short, long, global = true)]
+    yes: bool,
+    
+    /// Fail instead of asking anything on stdin
+    #[arg(long, global = true, conflicts_with = "yes")]
+    no_input: bool,
+    
+    /// Report where time was spent (fs scan, git, AI calls) after the command
+    #[arg(long, global = true)]
+    profile_perf: bool,
+    
+    /// Don't touch the network: changes are committed locally and queued for the next 'sync'
+    #[arg(long, global = true)]
+    offline: bool,
+    
+    /// Commit changes locally and queue them for the next 'sync' instead of syncing now
+    #[arg(long, global = true)]
+    no_sync: bool,
+    
     #[command(subcommand)]
     command: Commands,
 }
@@ -33,53 +140,474 @@ enum Commands {
         /// Publish from a saved snippet file
         #[arg(short, long)]
         file: Option<String>,
+        /// Read the snippet content from piped stdin
+        #[arg(long, conflicts_with_all = ["content", "file", "clipboard"])]
+        stdin: bool,
+        /// Read the snippet content from the clipboard
+        #[arg(long, conflicts_with_all = ["content", "file"])]
+        clipboard: bool,
+        /// Write or review the snippet in $EDITOR before publishing
+        #[arg(short, long, conflicts_with = "file")]
+        edit: bool,
+        /// Share the snippet as a secret GitHub gist instead of adding it to a repository
+        #[arg(long, conflicts_with = "edit")]
+        gist: bool,
+        /// Attach a companion file (diagram, example config), referenced in the content as assets/<name> (repeatable)
+        #[arg(long = "attach", value_name = "FILE", conflicts_with_all = ["gist", "edit"])]
+        attachments: Vec<std::path::PathBuf>,
+        /// Publish every markdown file in this directory as a snippet, syncing once at the end
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["content", "name", "file", "stdin", "clipboard", "edit", "gist", "attachments"])]
+        dir: Option<std::path::PathBuf>,
+        /// With --dir, include the markdown files in subfolders too
+        #[arg(short, long, requires = "dir")]
+        recursive: bool,
+    },
+    /// Write a new snippet from a template in your editor, then publish it
+    New {
+        /// Skeleton to start from
+        #[arg(short, long, value_enum, default_value = "basic")]
+        template: scaffold::SnippetTemplate,
+        /// Name for the snippet (defaults to one based on the template)
+        #[arg(short, long)]
+        name: Option<String>,
+    },
+    /// Print a snippet's metadata and content
+    Show {
+        /// Snippet name, description or ID prefix
+        #[arg(required_unless_present = "id")]
+        query: Option<String>,
+        /// Show the snippet with exactly this ID (or unique ID prefix) in the default or a followed repository
+        #[arg(long, conflicts_with = "query")]
+        id: Option<String>,
+        /// Print the snippet file as stored (frontmatter and markdown) without formatting
+        #[arg(long)]
+        raw: bool,
+    },
+    /// List the snippets of a GitHub repository (owner/repo) without cloning it
+    Browse {
+        /// The repository, e.g. eyalev/claude-md-snippets or its URL
+        repo: String,
     },
     /// Install a snippet to CLAUDE.md
     Install {
         /// Description to find the relevant snippet
-        query: String,
+        #[arg(required_unless_present_any = ["from_gist", "collection"], conflicts_with_all = ["from_gist", "collection"])]
+        query: Option<String>,
+        /// Install the snippet shared in a gist (URL or ID) instead of searching your repositories
+        #[arg(long, value_name = "URL|ID", conflicts_with = "collection")]
+        from_gist: Option<String>,
+        /// Find the snippet in a GitHub repository (owner/repo) and install it, without cloning the repository
+        #[arg(long, value_name = "OWNER/REPO", requires = "query", conflicts_with_all = ["collection", "variant", "pin"])]
+        from: Option<String>,
+        /// Install every snippet of a collection from the repository's collection.yaml
+        #[arg(long, value_name = "NAME")]
+        collection: Option<String>,
         /// Install to local CLAUDE.md in current directory
         #[arg(long, conflicts_with = "user")]
         local: bool,
         /// Install to user CLAUDE.md at ~/.claude/CLAUDE.md
         #[arg(long, conflicts_with = "local")]
         user: bool,
+        /// Install into another agent file: claude, agents, cursor, copilot, a configured target, or a path
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Append to an existing markdown file such as CONTRIBUTING.md or a docs page
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["local", "user", "target"])]
+        append_file: Option<std::path::PathBuf>,
+        /// Install even if the snippet or identical content is already present
+        #[arg(long)]
+        force: bool,
+        /// Value for a template variable, e.g. --var PROJECT_NAME=acme (repeatable)
+        #[arg(long = "var", value_name = "KEY=VALUE")]
+        vars: Vec<String>,
+        /// Install one of the snippet's variants by label (e.g. 'b', or 'original'); recorded in the experiments log
+        #[arg(long, value_name = "LABEL", conflicts_with_all = ["from_gist", "collection"])]
+        variant: Option<String>,
+        /// Where in the file: top, end (default), alphabetical (a managed "## Installed Snippets" section) or after-heading <name>
+        #[arg(long, num_args = 1..=2, value_names = ["PLACE", "HEADING"])]
+        position: Vec<String>,
+        /// Pin the install to the repository's current commit, so 'update' leaves it alone
+        #[arg(long, conflicts_with = "from_gist")]
+        pin: bool,
     },
-    /// Uninstall a snippet from CLAUDE.md
+    /// Uninstall snippets from CLAUDE.md (pick interactively when no query is given)
     Uninstall {
-        /// Description or ID to find the snippet to uninstall
-        query: String,
+        /// Descriptions or IDs of the snippets to uninstall
+        #[arg(value_name = "QUERY", conflicts_with_all = ["all", "collection"])]
+        queries: Vec<String>,
+        /// Uninstall every installed snippet
+        #[arg(long, conflicts_with = "collection")]
+        all: bool,
+        /// Uninstall the installed members of a collection
+        #[arg(long, value_name = "NAME")]
+        collection: Option<String>,
         /// Uninstall from local CLAUDE.md in current directory
         #[arg(long, conflicts_with = "user")]
         local: bool,
         /// Uninstall from user CLAUDE.md at ~/.claude/CLAUDE.md
         #[arg(long, conflicts_with = "local")]
         user: bool,
+        /// Uninstall from another agent file (same names as install --target)
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Uninstall from a markdown file the snippet was appended to with install --append-file
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["local", "user", "target"])]
+        append_file: Option<std::path::PathBuf>,
+        /// Also forget the usage stats and experiment history of the snippets in this file
+        #[arg(long)]
+        purge: bool,
+    },
+    /// List snippets installed in CLAUDE.md
+    Installed {
+        /// Check local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// Check user CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Check another agent file (same names as install --target)
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Check a markdown file snippets were appended to with install --append-file
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["local", "user", "target"])]
+        append_file: Option<std::path::PathBuf>,
+    },
+    /// Update installed snippets to their latest repository version (pinned ones are skipped)
+    Update {
+        /// Update local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// Update user CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Update another agent file (same names as install --target)
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Update a markdown file snippets were appended to with install --append-file
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["local", "user", "target"])]
+        append_file: Option<std::path::PathBuf>,
+        /// Also update pinned installs (they stay pinned, at the new version)
+        #[arg(long)]
+        include_pinned: bool,
+    },
+    /// Release a pinned install so 'update' changes it again
+    Unpin {
+        /// Snippet name, search query or installed ID
+        query: String,
+        /// Local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// User CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Another agent file (same names as install --target)
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// A markdown file the snippet was appended to with install --append-file
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["local", "user", "target"])]
+        append_file: Option<std::path::PathBuf>,
+    },
+    /// Show how an installed snippet differs from the repository version
+    Diff {
+        /// Snippet name, search query or installed ID
+        query: String,
+        /// Compare with local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// Compare with user CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Compare with another agent file (same names as install --target)
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Compare with a markdown file the snippet was appended to with install --append-file
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["local", "user", "target"])]
+        append_file: Option<std::path::PathBuf>,
+        /// Installed copy on the left, repository version on the right
+        #[arg(long)]
+        side_by_side: bool,
+    },
+    /// Show which variant of each snippet is active per target file, and since when
+    Experiments,
+    /// Check CLAUDE.md for duplicate snippets, orphaned markers, broken headings and extra blank lines
+    Doctor {
+        /// Check local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// Check user CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Check another agent file: claude, agents, cursor, copilot, a configured target, or a path
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Fix every problem found without asking
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Show the most installed, recently installed and never installed snippets
+    #[command(args_conflicts_with_subcommands = true)]
+    Stats {
+        #[command(subcommand)]
+        stats_command: Option<StatsCommand>,
+        /// How many snippets to list as most and recently installed
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Create ./CLAUDE.md for a new project from a header and the snippets you pick
+    Init {
+        /// Project name (asked, defaulting to the directory name)
+        #[arg(long)]
+        name: Option<String>,
+        /// Main language (asked, defaulting to the detected one)
+        #[arg(long)]
+        language: Option<String>,
+        /// Install this collection's snippets instead of picking interactively (repeatable)
+        #[arg(short, long = "collection", value_name = "NAME")]
+        collections: Vec<String>,
+        /// Install this snippet instead of picking interactively (repeatable)
+        #[arg(short, long = "snippet", value_name = "QUERY")]
+        snippets: Vec<String>,
+        /// Replace an existing CLAUDE.md without asking, and install despite duplicates or conflicts
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Suggest snippets for the project in the current directory and install the ones you pick
+    Suggest {
+        /// How many suggestions to list
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+        /// Install to local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// Install to user CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Install to another agent file (same names as install --target)
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// Install even if a snippet conflicts with one already there
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Search snippets with fuzzy finder, or rank them for a query
+    Search {
+        /// Query to rank snippets against (omit for the interactive fuzzy finder)
+        query: Option<String>,
+        /// Maximum number of results to show
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+        /// Ask Claude Code for the best match instead of using the local index
+        #[arg(long)]
+        ai: bool,
     },
-    /// Search snippets with fuzzy finder
-    Search,
     /// Sync snippets with GitHub repository
-    Sync,
+    Sync {
+        /// Commit message (default: one naming the snippets added, updated and removed)
+        #[arg(short, long)]
+        message: Option<String>,
+        /// Fold all unpushed commits (e.g. changes queued offline) into one before pushing
+        #[arg(long)]
+        squash: bool,
+        /// Only report each repository's state against its remote, without syncing
+        #[arg(long, conflicts_with_all = ["message", "squash"])]
+        status: bool,
+    },
     /// Pull latest snippets from repository
-    Pull,
+    Pull {
+        /// Also clone followed sources that aren't on this machine yet
+        #[arg(long)]
+        all: bool,
+    },
     /// Extract relevant information from ~/.claude/CLAUDE.md
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Extract {
+        #[command(subcommand)]
+        extract_command: Option<ExtractCommand>,
         /// Topic or query to extract information about
+        #[arg(required = true)]
+        query: Option<String>,
+        /// Instruction file to extract from (default: ~/.claude/CLAUDE.md)
+        #[arg(long, value_name = "PATH", conflicts_with = "local")]
+        from: Option<std::path::PathBuf>,
+        /// Extract from ./CLAUDE.md in the current directory
+        #[arg(long)]
+        local: bool,
+        /// Pick sections by matching headings instead of asking Claude Code
+        #[arg(long)]
+        no_ai: bool,
+        /// Publish the extracted snippet after a preview instead of saving it to ./.claude.local/snippets
+        #[arg(long, conflicts_with_all = ["output", "dir"])]
+        publish: bool,
+        /// Repository to publish to (default: the configured default)
+        #[arg(long, requires = "publish")]
+        repo: Option<String>,
+        /// Snippet name (default: the query)
+        #[arg(long)]
+        name: Option<String>,
+        /// File to write the snippet to
+        #[arg(short, long, value_name = "PATH", conflicts_with = "dir")]
+        output: Option<std::path::PathBuf>,
+        /// Directory to write the snippet into (default: ./.claude.local/snippets)
+        #[arg(long, value_name = "PATH")]
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Publish a hand-written section of ./CLAUDE.md and manage it as an installed snippet
+    Promote {
+        /// Heading or topic of the section to promote
         query: String,
     },
-    /// Setup GitHub repository for snippets
+    /// Find instructions you keep repeating in Claude Code sessions and publish them
+    Harvest {
+        /// Only scan sessions modified in the last N days
+        #[arg(long, default_value_t = 30)]
+        days: u64,
+        /// Minimum number of times an instruction must appear
+        #[arg(long, default_value_t = 2)]
+        min_count: usize,
+    },
+    /// Watch the clipboard and offer to save each copied block as a snippet draft
+    Capture {
+        /// How long to watch (e.g. 10m, 2h)
+        #[arg(long = "for", value_name = "DURATION", default_value = "10m")]
+        duration: String,
+        /// Directory to save drafts in (default: ./.claude.local/snippets)
+        #[arg(long, value_name = "PATH")]
+        dir: Option<std::path::PathBuf>,
+    },
+    /// Sync repositories automatically as their files change, and pull them periodically
+    Watch {
+        /// Seconds to wait for edits to settle before syncing
+        #[arg(long, value_name = "SECONDS", default_value_t = 5)]
+        debounce: u64,
+        /// How often to pull every repository (e.g. 10m, 1h)
+        #[arg(long, value_name = "DURATION", default_value = "10m")]
+        pull_every: String,
+        /// Run in the background, logging to logs/watch.log in the data directory
+        #[arg(long)]
+        daemon: bool,
+        /// Stop the background watcher
+        #[arg(long, conflicts_with_all = ["daemon", "debounce", "pull_every"])]
+        stop: bool,
+    },
+    /// Make sure the project's required snippets are installed, then run a command (e.g. exec -- claude -p "...")
+    Exec {
+        /// Check local CLAUDE.md in current directory
+        #[arg(long, conflicts_with = "user")]
+        local: bool,
+        /// Check user CLAUDE.md at ~/.claude/CLAUDE.md
+        #[arg(long, conflicts_with = "local")]
+        user: bool,
+        /// Check another agent file: claude, agents, cursor, copilot, a configured target, or a path
+        #[arg(long, conflicts_with_all = ["local", "user"])]
+        target: Option<String>,
+        /// The command to run, after --
+        #[arg(required = true, last = true, value_name = "COMMAND")]
+        command: Vec<String>,
+    },
+    /// Serve the local repositories over a token-protected REST API
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+        /// Address to bind; use 0.0.0.0 to accept connections from other machines
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+        /// Bearer token clients must send (default: $CLAUDE_MD_SNIPPETS_TOKEN, else a generated one)
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Setup a GitHub (or GitLab, Gitea, plain git) repository for snippets
     Setup {
         /// Repository name (defaults to 'default')
         #[arg(short, long)]
         repo: Option<String>,
+        /// Seed the new repository with a starter set of snippets
+        #[arg(short, long, value_enum)]
+        template: Option<starters::StarterTemplate>,
+        /// Where to host the repository
+        #[arg(long, value_enum, default_value = "github")]
+        host: hosts::HostKind,
+        /// Base URL of a self-hosted GitLab or Gitea instance
+        #[arg(long)]
+        host_url: Option<String>,
+        /// Existing remote URL, for --host git
+        #[arg(long)]
+        remote: Option<String>,
+        /// Create the repository under this GitHub organization, with a CODEOWNERS stub
+        #[arg(long)]
+        org: Option<String>,
+        /// Team (slug) in the organization to give read access
+        #[arg(long, requires = "org")]
+        team: Option<String>,
     },
     /// Show status of repositories and current default
-    Status,
+    Status {
+        /// Re-detect available tools instead of using cached results
+        #[arg(long)]
+        refresh: bool,
+    },
+    /// Write a markdown or HTML report of repositories, installed snippets and pending updates
+    Report {
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: report::ReportFormat,
+        /// Write the report to a file instead of printing it
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Digest of recent snippet activity: changes in every repository and your installs
+    Digest {
+        /// How far back to look (e.g. 7d, 2w)
+        #[arg(long, default_value = "7d")]
+        since: String,
+        /// Output format
+        #[arg(long, value_enum, default_value = "markdown")]
+        output: report::ReportFormat,
+        /// Write the digest to a file instead of printing it
+        #[arg(long, short, value_name = "PATH")]
+        file: Option<std::path::PathBuf>,
+    },
+    /// Check installed instructions for problems
+    Audit {
+        #[command(subcommand)]
+        audit_command: AuditCommand,
+    },
     /// Manage configuration
     Config {
         #[command(subcommand)]
         config_command: ConfigCommand,
     },
+    /// Back up or restore this machine's setup (config, installs, repositories)
+    State {
+        #[command(subcommand)]
+        state_command: StateCommand,
+    },
+    /// Open a snippet's file in your editor, or its page on the remote with --web
+    Open {
+        /// Description or filename of the snippet
+        query: String,
+        /// Open the snippet on the repository's remote (e.g. GitHub) instead
+        #[arg(long)]
+        web: bool,
+        /// Repository name (defaults to configured default)
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Open a GitHub issue with feedback on a snippet
+    Feedback {
+        /// Description or filename of the snippet
+        query: String,
+        /// Your feedback
+        message: String,
+        /// Repository name (defaults to configured default)
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Manage GitHub accounts used for each repository
+    Account {
+        #[command(subcommand)]
+        account_command: AccountCommand,
+    },
     /// Manage repository content
     Repo {
         /// Repository name (defaults to configured default)
@@ -93,8 +621,63 @@ enum Commands {
     },
 }
 
+impl Commands {
+    /// Commands that read or write snippets in the default repository, and
+    /// so offer to create one on first run.
+    fn needs_repository(&self) -> bool {
+        matches!(self,
+            Commands::Publish { gist: false, .. } | Commands::New { .. } | Commands::Show { .. } | Commands::Install { from: None, .. } | Commands::Update { .. } | Commands::Suggest { .. } | Commands::Init { .. }
+            | Commands::Search { .. } | Commands::Sync { .. } | Commands::Harvest { .. } | Commands::Promote { .. } | Commands::Open { .. })
+    }
+}
+
+#[derive(Subcommand)]
+enum TrashCommand {
+    /// List the deleted snippets that can still be restored
+    List,
+    /// Delete the snippets in the trash for good
+    Empty {
+        /// Only those deleted longer ago than this, e.g. 30d
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum StateCommand {
+    /// Write the config, install manifest and unsynced changes to a .tar.gz archive
+    Export {
+        /// Archive to create, e.g. snippets-state.tar.gz
+        archive: std::path::PathBuf,
+        /// Include every repository (with its git history)
+        #[arg(long)]
+        repos: bool,
+    },
+    /// Verify an archive from 'state export' and restore it over the local state
+    Import {
+        /// Archive written by 'state export'
+        archive: std::path::PathBuf,
+    },
+    /// Move ~/.claude-md-snippets from older versions to the XDG (or --data-dir) directories
+    Migrate,
+}
+
 #[derive(Subcommand)]
 enum ConfigCommand {
+    /// Change a setting, e.g. 'config set sync.mode manual'
+    Set {
+        /// Setting name (see 'config list-keys')
+        key: String,
+        /// New value (an empty string clears optional settings)
+        value: String,
+    },
+    /// Print a setting's current value
+    Get {
+        /// Setting name (see 'config list-keys')
+        key: String,
+    },
+    /// List the settings 'config get' and 'config set' accept
+    ListKeys,
     /// Set the default repository
     SetDefault {
         /// Repository name
@@ -107,55 +690,497 @@ enum ConfigCommand {
         /// Install location: 'local' or 'user'
         location: String,
     },
+    /// Configure how the Claude Code CLI is invoked
+    SetClaude {
+        /// Setting: binary, model, extra_args, timeout_secs or enabled
+        key: String,
+        /// New value (empty string clears the model; extra_args is space-separated)
+        value: String,
+    },
+    /// Set the snippet size above which publish and install warn
+    SetLimit {
+        /// Limit: max_tokens or max_bytes
+        key: String,
+        /// New limit (0 turns it off)
+        value: String,
+    },
+    /// Prefer snippet variants in a language when installing
+    SetLanguage {
+        /// Language code, e.g. 'es' or 'pt-BR' (empty string installs the originals)
+        language: String,
+    },
+    /// Name an install target for 'install --target <name>'
+    SetTarget {
+        /// Target name
+        name: String,
+        /// File path, relative to the project or starting with ~/ (empty string removes it)
+        path: String,
+    },
+    /// Record who a repository is shared with; sync refuses to push snippets more private than that
+    SetVisibility {
+        /// Repository name
+        repo: String,
+        /// Visibility level of the repository
+        #[arg(value_enum)]
+        level: visibility::Visibility,
+    },
+    /// Set the branch a repository syncs (detected from the remote otherwise)
+    SetBranch {
+        /// Repository name
+        repo: String,
+        /// Branch name, e.g. master
+        branch: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum AccountCommand {
+    /// Add or update a GitHub account
+    Add {
+        /// Name to refer to the account by, e.g. 'work'
+        name: String,
+        /// GitHub username
+        #[arg(long)]
+        username: String,
+        /// Commit email (defaults to the GitHub noreply address)
+        #[arg(long)]
+        email: Option<String>,
+        /// Environment variable holding the account's token (defaults to the gh login for the username)
+        #[arg(long)]
+        token_env: Option<String>,
+    },
+    /// List accounts and the repositories using them
+    List,
+    /// Remove an account
+    Remove {
+        /// Account name
+        name: String,
+    },
+    /// Use an account for a repository
+    Use {
+        /// Account name (omit with --clear to go back to the global logins)
+        #[arg(required_unless_present = "clear")]
+        name: Option<String>,
+        /// Repository name (defaults to configured default)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Stop using a specific account for the repository
+        #[arg(long, conflicts_with = "name")]
+        clear: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatsCommand {
+    /// Approximate tokens of every managed CLAUDE.md and each installed snippet's share
+    Tokens {
+        /// How many of the biggest context consumers to list
+        #[arg(short, long, default_value_t = 10)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExtractCommand {
+    /// Remove extracted files in ./.claude.local/snippets that were published or are stale
+    Gc {
+        /// Extracts older than this count as stale, e.g. 30d, 12w
+        #[arg(long, value_name = "AGE", default_value = "30d")]
+        older_than: String,
+        /// Directory the snippets were extracted into (default: ./.claude.local/snippets)
+        #[arg(long, value_name = "PATH")]
+        dir: Option<std::path::PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuditCommand {
+    /// Find snippets and sections duplicated in both user and project CLAUDE.md
+    Overlap,
 }
 
 #[derive(Subcommand)]
 enum RepoCommand {
-    /// Delete a snippet from the repository
+    /// Move a snippet to the repository's trash, or every snippet matching --id/--tag/--older-than
     Delete {
         /// Description or query to find the snippet to delete
+        #[arg(required_unless_present_any = ["id", "tag", "older_than"], conflicts_with_all = ["id", "tag", "older_than"])]
+        query: Option<String>,
+        /// Delete the snippet(s) whose ID starts with this prefix
+        #[arg(long, value_name = "PREFIX")]
+        id: Option<String>,
+        /// Delete snippets with this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Delete snippets created longer ago than this, e.g. 90d, 12w, 1y
+        #[arg(long, value_name = "AGE")]
+        older_than: Option<String>,
+    },
+    /// Bring a deleted snippet back from the trash
+    Restore {
+        /// Name or ID prefix of the deleted snippet
         query: String,
     },
+    /// List or empty the snippets deleted from the repository
+    Trash {
+        #[command(subcommand)]
+        trash_command: TrashCommand,
+    },
+    /// Mark a snippet deprecated; installing it warns and suggests the replacement
+    Deprecate {
+        /// Description or query to find the snippet
+        query: String,
+        /// ID (or a prefix of at least 4 characters) of the snippet to use instead
+        #[arg(long, value_name = "ID")]
+        replaced_by: Option<String>,
+    },
+    /// Archive a snippet: it stays in the repository but search and install skip it
+    Archive {
+        /// Description or query to find the snippet
+        query: String,
+    },
+    /// Make a deprecated or archived snippet active again
+    Reactivate {
+        /// Description or query to find the snippet
+        query: String,
+    },
+    /// Rename a snippet (its frontmatter name and its file)
+    Rename {
+        /// Description or query to find the snippet
+        query: String,
+        /// The new name
+        new_name: String,
+    },
+    /// Open a snippet in $EDITOR, or change its description and tags directly
+    Edit {
+        /// Description or query to find the snippet
+        query: String,
+        /// Replace the description ("" removes it)
+        #[arg(long)]
+        description: Option<String>,
+        /// Add a tag (repeatable)
+        #[arg(long, value_name = "TAG")]
+        add_tag: Vec<String>,
+        /// Remove a tag (repeatable)
+        #[arg(long, value_name = "TAG")]
+        remove_tag: Vec<String>,
+        /// Who the snippet may be shared with
+        #[arg(long, value_enum)]
+        visibility: Option<visibility::Visibility>,
+    },
     /// List snippets in the repository
-    List,
+    List {
+        /// Sort order
+        #[arg(long, value_enum, default_value = "created")]
+        sort: ListSort,
+        /// Only show snippets whose name, filename, description or tags contain this text
+        #[arg(long)]
+        filter: Option<String>,
+        /// Only show snippets by this author (any part of the name)
+        #[arg(long)]
+        author: Option<String>,
+        /// Show at most N snippets per page
+        #[arg(short, long)]
+        limit: Option<usize>,
+        /// Page to show when using --limit (starting at 1)
+        #[arg(long, default_value_t = 1, requires = "limit")]
+        page: usize,
+        /// One line per snippet: ID, created, size, name
+        #[arg(short, long)]
+        compact: bool,
+    },
     /// Open repository in browser
-    Open,
+    Open {
+        /// Print the repository's web URL instead, e.g. over SSH or in a container without a browser
+        #[arg(long)]
+        print_url: bool,
+    },
+    /// Show snippet counts and change history
+    Stats {
+        /// Per-author contributions, most edited snippets and activity by month
+        #[arg(long)]
+        contributions: bool,
+    },
+    /// Follow a GitHub rename or transfer and update the local remote
+    WebSync,
+    /// Turn an existing CLAUDE.md into snippets, one per section, in this repository (created if missing)
+    ImportClaudeMd {
+        /// The CLAUDE.md (or other instruction file) to split
+        path: std::path::PathBuf,
+        /// Let Claude Code decide how to split it instead of going by headings
+        #[arg(long)]
+        ai: bool,
+        /// Replace the sections in the file with managed blocks installing the new snippets
+        #[arg(long)]
+        rewrite: bool,
+    },
+    /// Follow a read-only repository (the community snippets by default) for search and install
+    Subscribe {
+        /// Git URL to clone (default: the community repository)
+        url: Option<String>,
+        /// Local name (default: 'community', or the last part of the URL)
+        #[arg(long = "as", value_name = "NAME")]
+        local_name: Option<String>,
+    },
+    /// List the followed read-only sources
+    Sources,
+    /// Stop following a source and remove its local copy
+    Unsubscribe {
+        /// Name the source is followed as
+        source: String,
+    },
+    /// Write every snippet to one markdown document, JSON array or .tar.gz archive
+    Export {
+        /// File to write, e.g. snippets.md, snippets.json or snippets.tar.gz
+        output: std::path::PathBuf,
+        /// Format (default: from the file extension)
+        #[arg(long, value_enum)]
+        format: Option<bundle::BundleFormat>,
+    },
+    /// Check snippets for malformed frontmatter, duplicate IDs, missing variants and secrets
+    Lint {
+        /// Non-interactive mode for the snippet repository's own CI: lints the current
+        /// directory unless --path is given, and exits 2 if the lint cannot run
+        #[arg(long)]
+        ci: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        format: lint::LintFormat,
+        /// Lint a checkout of a snippets repository instead of a configured one
+        #[arg(long, value_name = "DIR")]
+        path: Option<std::path::PathBuf>,
+    },
+    /// Add the snippets of a file written by 'repo export' to the repository
+    Import {
+        /// File to read (.md, .json or .tar.gz)
+        input: std::path::PathBuf,
+        /// Format (default: from the file extension)
+        #[arg(long, value_enum)]
+        format: Option<bundle::BundleFormat>,
+        /// Replace snippets whose ID is already in the repository
+        #[arg(long)]
+        overwrite: bool,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ListSort {
+    Name,
+    Created,
+    Updated,
+    Size,
+}
+
+struct ListOptions {
+    sort: ListSort,
+    filter: Option<String>,
+    author: Option<String>,
+    limit: Option<usize>,
+    page: usize,
+    compact: bool,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    if let Some(dir) = &cli.data_dir {
+        app_dirs::override_data_dir(dir);
+    }
+    
+    let verbosity = if cli.debug { cli.verbose.max(2) } else { cli.verbose };
+    let _log_guard = logging::init(logging::level(verbosity, cli.quiet), cli.log_file)?;
+    prompt::set_mode(cli.yes, cli.no_input);
+    
+    if cli.profile_perf {
+        perf::enable();
+    }
+    if cli.offline {
+        capabilities::force_offline();
+    }
+    if cli.no_sync {
+        github::disable_auto_sync();
+    }
+    
+    let started = std::time::Instant::now();
+    let result = run(cli).await;
+    perf::report(started.elapsed());
+    result
+}
 
+async fn run(cli: Cli) -> Result<()> {
+    if cli.command.needs_repository() {
+        github::ensure_repository().await?;
+    }
     match cli.command {
-        Commands::Publish { content, name, file } => {
-            publish::publish_snippet(content, name, file, cli.debug).await?;
+        Commands::Publish { dir: Some(dir), recursive, .. } => {
+            batch_publish::publish_dir(&dir, recursive).await?;
+        }
+        Commands::Publish { content, name, file, stdin, clipboard, edit, gist, attachments, .. } => {
+            let content = if stdin {
+                Some(publish::read_stdin_content()?)
+            } else if clipboard {
+                Some(publish::read_clipboard_content()?)
+            } else {
+                content
+            };
+            if edit {
+                scaffold::publish_with_editor(content, name).await?;
+            } else {
+                publish::publish_snippet(content, name, file, gist, attachments).await?;
+            }
+        }
+        Commands::New { template, name } => {
+            scaffold::new_snippet(template, name).await?;
+        }
+        Commands::Browse { repo } => {
+            browse::browse(&repo).await?;
+        }
+        Commands::Show { query, id, raw } => match id {
+            Some(id) => show::show_by_id(&id, raw)?,
+            None => show::show_snippet(&query.unwrap_or_default(), raw).await?,
+        },
+        Commands::Install { query, from_gist, from, collection, local, user, target, append_file, force, vars, variant, position, pin } => {
+            let vars = template::parse_var_args(&vars)?;
+            let position = placement::Placement::from_args(&position)?;
+            let destination = install::Destination { local, user, target, append_file, position, pin };
+            match (from_gist, collection, query, from) {
+                (Some(reference), _, _, _) => install::install_from_gist(&reference, destination, force, vars).await?,
+                (None, Some(name), _, _) => install::install_collection(&name, destination, force, vars).await?,
+                (None, None, Some(query), Some(repo)) => install::install_from_repo(&repo, &query, destination, force, vars).await?,
+                (None, None, Some(query), None) => install::install_snippet(query, destination, force, vars, variant).await?,
+                (None, None, None, _) => unreachable!("clap requires a query, --from-gist or --collection"),
+            }
+        }
+        Commands::Uninstall { queries, all, collection, local, user, target, append_file, purge } => {
+            let destination = install::Destination { local, user, target, append_file, ..Default::default() };
+            install::uninstall_snippet(queries, all, collection, destination, purge).await?;
         }
-        Commands::Install { query, local, user } => {
-            install::install_snippet(query, local, user).await?;
+        Commands::Init { name, language, collections, snippets, force } => {
+            init::init(init::InitOptions { name, language, collections, snippets, force }).await?;
         }
-        Commands::Uninstall { query, local, user } => {
-            install::uninstall_snippet(query, local, user).await?;
+        Commands::Suggest { limit, local, user, target, force } => {
+            suggest::suggest(install::Destination { local, user, target, ..Default::default() }, limit, force).await?;
         }
-        Commands::Search => {
-            search::search_snippets().await?;
+        Commands::Installed { local, user, target, append_file } => {
+            install::list_installed(install::Destination { local, user, target, append_file, ..Default::default() }).await?;
         }
-        Commands::Sync => {
-            github::sync_snippets().await?;
+        Commands::Update { local, user, target, append_file, include_pinned } => {
+            update::update(install::Destination { local, user, target, append_file, ..Default::default() }, include_pinned).await?;
+        }
+        Commands::Unpin { query, local, user, target, append_file } => {
+            update::unpin(&query, install::Destination { local, user, target, append_file, ..Default::default() }).await?;
+        }
+        Commands::Diff { query, local, user, target, append_file, side_by_side } => {
+            let layout = if side_by_side { diff::Layout::SideBySide } else { diff::Layout::Unified };
+            diff::diff(&query, install::Destination { local, user, target, append_file, ..Default::default() }, layout).await?;
+        }
+        Commands::Experiments => {
+            experiments::show()?;
+        }
+        Commands::Doctor { local, user, target, fix } => {
+            doctor::doctor(install::Destination { local, user, target, ..Default::default() }, fix)?;
+        }
+        Commands::Stats { stats_command: Some(StatsCommand::Tokens { limit }), .. } => {
+            tokens::show(limit)?;
+        }
+        Commands::Stats { stats_command: None, limit } => {
+            usage::show(limit)?;
+        }
+        Commands::Search { query, limit, ai } => {
+            match query {
+                Some(query) => search::search_snippets_by_query(query, limit, ai).await?,
+                None => search::search_snippets().await?,
+            }
         }
-        Commands::Pull => {
-            github::pull_snippets().await?;
+        Commands::Sync { status: true, .. } => {
+            sync_status::show()?;
         }
-        Commands::Extract { query } => {
-            extract::extract_snippet(query).await?;
+        Commands::Sync { message, squash, .. } => {
+            github::sync_snippets(github::SyncOptions { message, squash }).await?;
         }
-        Commands::Setup { repo } => {
-            github::setup_repository(repo).await?;
+        Commands::Pull { all } => {
+            github::pull_snippets(all).await?;
         }
-        Commands::Status => {
-            show_status().await?;
+        Commands::Extract { extract_command: Some(ExtractCommand::Gc { older_than, dir }), .. } => {
+            extract::collect_garbage(dir, delete::parse_age(&older_than)?)?;
         }
+        Commands::Extract { extract_command: None, query, from, local, no_ai, publish, repo, name, output, dir } => {
+            let query = query.expect("clap requires a query without a subcommand");
+            let options = extract::ExtractOptions { from, local, no_ai, publish, repo, name, output, dir };
+            extract::extract_snippet(query, options).await?;
+        }
+        Commands::Promote { query } => {
+            promote::promote_section(&query).await?;
+        }
+        Commands::Harvest { days, min_count } => {
+            harvest::harvest_snippets(days, min_count).await?;
+        }
+        Commands::Capture { duration, dir } => {
+            capture::capture_clipboard(delete::parse_age(&duration)?, dir).await?;
+        }
+        Commands::Watch { stop: true, .. } => {
+            watch::stop_daemon()?;
+        }
+        Commands::Watch { daemon: true, .. } => {
+            let args: Vec<String> = std::env::args().skip(1).filter(|arg| arg != "--daemon").collect();
+            watch::start_daemon(&args)?;
+        }
+        Commands::Watch { debounce, pull_every, .. } => {
+            let pull_every = delete::parse_age(&pull_every)?.to_std().map_err(|_| anyhow::anyhow!("--pull-every must be positive"))?;
+            watch::watch(watch::WatchOptions { debounce: std::time::Duration::from_secs(debounce), pull_every }).await?;
+        }
+        Commands::Exec { local, user, target, command } => {
+            exec::exec(&command, install::Destination { local, user, target, ..Default::default() }).await?;
+        }
+        Commands::Serve { port, bind, token } => {
+            serve::serve(port, &bind, token).await?;
+        }
+        Commands::Setup { repo, template, host, host_url, remote, org, team } => {
+            let host = hosts::HostOptions { kind: host, base_url: host_url, remote };
+            let org = org.map(|name| github::OrgOptions { name, team });
+            github::setup_repository(repo, template, host, org).await?;
+        }
+        Commands::Status { refresh } => {
+            show_status(refresh).await?;
+        }
+        Commands::Report { format, output } => {
+            report::generate(format, output)?;
+        }
+        Commands::Digest { since, output, file } => {
+            digest::generate(delete::parse_age(&since)?, output, file)?;
+        }
+        Commands::Audit { audit_command } => {
+            match audit_command {
+                AuditCommand::Overlap => {
+                    audit::audit_overlap()?;
+                }
+            }
+        }
+        Commands::State { state_command } => match state_command {
+            StateCommand::Export { archive, repos } => state::export(&archive, repos)?,
+            StateCommand::Import { archive } => state::import(&archive)?,
+            StateCommand::Migrate => app_dirs::migrate()?,
+        },
         Commands::Config { config_command } => {
             match config_command {
+                ConfigCommand::Set { key, value } => {
+                    let mut config = config::Config::load()?;
+                    config.set(&key, &value)?;
+                    if key.starts_with("claude.") {
+                        capabilities::clear_cache()?;
+                    }
+                    println!("✅ Set {} = {}", key, config.get(&key)?);
+                }
+                ConfigCommand::Get { key } => {
+                    println!("{}", config::Config::load()?.get(&key)?);
+                }
+                ConfigCommand::ListKeys => {
+                    let config = config::Config::load()?;
+                    let width = config::SETTINGS.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+                    for (key, description) in config::SETTINGS {
+                        println!("{:<width$}  {} [{}]", key, description, config.get(key)?, width = width);
+                    }
+                }
                 ConfigCommand::SetDefault { repo_name } => {
                     set_default_repo(repo_name).await?;
                 }
@@ -165,18 +1190,179 @@ async fn main() -> Result<()> {
                 ConfigCommand::SetInstallLocation { location } => {
                     set_install_location(location).await?;
                 }
+                ConfigCommand::SetClaude { key, value } => {
+                    let mut config = config::Config::load()?;
+                    config.set_claude_setting(&key, &value)?;
+                    capabilities::clear_cache()?;
+                    println!("✅ Set claude.{} = {}", key, value);
+                }
+                ConfigCommand::SetLimit { key, value } => {
+                    let mut config = config::Config::load()?;
+                    config.set_limit(&key, &value)?;
+                    println!("✅ Set limits.{} = {}", key, value);
+                }
+                ConfigCommand::SetLanguage { language } => {
+                    let mut config = config::Config::load()?;
+                    config.set_language(&language)?;
+                    if language.is_empty() {
+                        println!("✅ Cleared the snippet language; install uses the originals");
+                    } else {
+                        println!("✅ Install now prefers '{}' variants of snippets", language);
+                    }
+                }
+                ConfigCommand::SetVisibility { repo, level } => {
+                    let mut config = config::Config::load()?;
+                    config.set_repo_visibility(&repo, level)?;
+                    println!("✅ Repository '{}' is {}; snippets marked more private won't be pushed to it", repo, level.as_str());
+                }
+                ConfigCommand::SetBranch { repo, branch } => {
+                    let mut config = config::Config::load()?;
+                    config.set_repo_branch(&repo, branch.trim())?;
+                    println!("✅ Repository '{}' syncs branch '{}'", repo, branch.trim());
+                }
+                ConfigCommand::SetTarget { name, path } => {
+                    let mut config = config::Config::load()?;
+                    config.set_target(&name, &path)?;
+                    if path.is_empty() {
+                        println!("✅ Removed target '{}'", name);
+                    } else {
+                        println!("✅ Set target '{}' -> {}", name, path);
+                    }
+                }
+            }
+        }
+        Commands::Open { query, web, repo } => {
+            open_snippet(repo, query, web).await?;
+        }
+        Commands::Feedback { query, message, repo } => {
+            send_feedback(repo, query, message).await?;
+        }
+        Commands::Account { account_command } => {
+            match account_command {
+                AccountCommand::Add { name, username, email, token_env } => {
+                    accounts::add_account(&name, username, email, token_env)?;
+                }
+                AccountCommand::List => {
+                    accounts::list_accounts()?;
+                }
+                AccountCommand::Remove { name } => {
+                    accounts::remove_account(&name)?;
+                }
+                AccountCommand::Use { name, repo, clear: _ } => {
+                    let repo = resolve_repo_name(repo, false)?;
+                    accounts::use_account(name.as_deref(), &repo)?;
+                }
             }
         }
         Commands::Repo { name, default, repo_command } => {
             match repo_command {
-                RepoCommand::Delete { query } => {
-                    delete_snippet(name, default, query, cli.debug).await?;
+                RepoCommand::Delete { query: Some(query), .. } => {
+                    delete_snippet(name, default, query).await?;
+                }
+                RepoCommand::Delete { query: None, id, tag, older_than } => {
+                    let older_than = older_than.as_deref().map(delete::parse_age).transpose()?;
+                    let target_repo = resolve_repo_name(name, default)?;
+                    let repo_dir = existing_repo_dir(&target_repo)?;
+                    delete::delete_matching(&repo_dir, &target_repo, delete::DeleteFilter { id, tag, older_than }).await?;
+                }
+                RepoCommand::Restore { query } => {
+                    let target_repo = resolve_repo_name(name, default)?;
+                    trash::restore(&existing_repo_dir(&target_repo)?, &target_repo, &query).await?;
+                }
+                RepoCommand::Trash { trash_command } => {
+                    let target_repo = resolve_repo_name(name, default)?;
+                    let repo_dir = existing_repo_dir(&target_repo)?;
+                    match trash_command {
+                        TrashCommand::List => trash::list(&repo_dir, &target_repo)?,
+                        TrashCommand::Empty { older_than } => {
+                            let older_than = older_than.as_deref().map(delete::parse_age).transpose()?;
+                            trash::empty(&repo_dir, &target_repo, older_than).await?;
+                        }
+                    }
+                }
+                RepoCommand::Deprecate { query, replaced_by } => {
+                    let (target_repo, file) = find_repo_snippet(name, default, &query)?;
+                    status::set_status(&file, &target_repo, status::Status::Deprecated, replaced_by.as_deref()).await?;
+                }
+                RepoCommand::Archive { query } => {
+                    let (target_repo, file) = find_repo_snippet(name, default, &query)?;
+                    status::set_status(&file, &target_repo, status::Status::Archived, None).await?;
+                }
+                RepoCommand::Reactivate { query } => {
+                    let (target_repo, file) = find_repo_snippet(name, default, &query)?;
+                    status::set_status(&file, &target_repo, status::Status::Active, None).await?;
                 }
-                RepoCommand::List => {
-                    list_repo_snippets(name, default).await?;
+                RepoCommand::Rename { query, new_name } => {
+                    let (target_repo, file) = find_repo_snippet(name, default, &query)?;
+                    edit::rename_snippet(&file, &target_repo, &new_name).await?;
                 }
-                RepoCommand::Open => {
-                    open_repo_in_browser(name, default).await?;
+                RepoCommand::Edit { query, description, add_tag, remove_tag, visibility } => {
+                    let (target_repo, file) = find_repo_snippet(name, default, &query)?;
+                    let changes = edit::MetadataChanges { description, add_tags: add_tag, remove_tags: remove_tag, visibility };
+                    edit::edit_snippet(&file, &target_repo, changes).await?;
+                }
+                RepoCommand::List { sort, filter, author, limit, page, compact } => {
+                    let options = ListOptions { sort, filter, author, limit, page, compact };
+                    list_repo_snippets(name, default, options).await?;
+                }
+                RepoCommand::Open { print_url } => {
+                    open_repo_in_browser(name, default, print_url).await?;
+                }
+                RepoCommand::Stats { contributions } => {
+                    let target_repo = resolve_repo_name(name, default)?;
+                    let repo_dir = existing_repo_dir(&target_repo)?;
+                    stats::show_stats(&repo_dir, &target_repo, contributions)?;
+                }
+                RepoCommand::WebSync => {
+                    github::web_sync(&resolve_repo_name(name, default)?).await?;
+                }
+                RepoCommand::Subscribe { url, local_name } => {
+                    let url = url.unwrap_or_else(|| format!("https://github.com/{}", github::DEFAULT_REPO));
+                    let local_name = match (local_name, url.contains(github::DEFAULT_REPO)) {
+                        (Some(local_name), _) => local_name,
+                        (None, true) => subscriptions::COMMUNITY_NAME.to_string(),
+                        (None, false) => url.trim_end_matches('/').trim_end_matches(".git").rsplit(['/', ':']).next().unwrap_or_default().to_string(),
+                    };
+                    subscriptions::subscribe(&local_name, &url)?;
+                }
+                RepoCommand::Sources => {
+                    subscriptions::list_sources()?;
+                }
+                RepoCommand::Unsubscribe { source } => {
+                    subscriptions::unsubscribe(&source)?;
+                }
+                RepoCommand::Lint { ci, format, path } => {
+                    if ci {
+                        prompt::set_mode(false, true);
+                    }
+                    let outcome = match path.or_else(|| ci.then(|| std::path::PathBuf::from("."))) {
+                        Some(path) => Ok(path),
+                        None => resolve_repo_name(name, default).and_then(|repo| existing_repo_dir(&repo)),
+                    }
+                    .and_then(|repo_dir| lint::lint_repo(&repo_dir))
+                    .and_then(|(files, findings)| lint::report(files, &findings, format));
+                    match outcome {
+                        Ok(lint::EXIT_CLEAN) => {}
+                        Ok(code) => std::process::exit(code),
+                        Err(e) if ci => {
+                            eprintln!("Error: {:#}", e);
+                            std::process::exit(lint::EXIT_FAILED);
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                RepoCommand::Export { output, format } => {
+                    let target_repo = resolve_repo_name(name, default)?;
+                    let repo_dir = existing_repo_dir(&target_repo)?;
+                    bundle::export(&repo_dir, &target_repo, &output, format)?;
+                }
+                RepoCommand::ImportClaudeMd { path, ai, rewrite } => {
+                    bootstrap::import_claude_md(&path, &resolve_repo_name(name, default)?, ai, rewrite).await?;
+                }
+                RepoCommand::Import { input, format, overwrite } => {
+                    let target_repo = resolve_repo_name(name, default)?;
+                    let repo_dir = existing_repo_dir(&target_repo)?;
+                    bundle::import(&repo_dir, &target_repo, &input, format, overwrite).await?;
                 }
             }
         }
@@ -185,9 +1371,9 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn show_status() -> Result<()> {
+async fn show_status(refresh: bool) -> Result<()> {
     use std::fs;
-    use publish::{get_repos_dir, get_default_repo_dir};
+    use publish::get_repos_dir;
     
     println!("📊 Claude MD Snippets Status");
     println!("============================");
@@ -197,6 +1383,7 @@ async fn show_status() -> Result<()> {
     if !repos_dir.exists() {
         println!("❌ No repositories directory found at: {}", repos_dir.display());
         println!("💡 Run 'claude-md-snippets setup' to create your first repository");
+        show_capabilities(refresh)?;
         return Ok(());
     }
     
@@ -252,33 +1439,52 @@ async fn show_status() -> Result<()> {
     println!();
     println!("📍 Repositories directory: {}", repos_dir.display());
     
+    show_capabilities(refresh)?;
+    show_pending_changes()?;
+    
     Ok(())
 }
 
-fn count_snippets(repo_path: &std::path::Path) -> Result<usize> {
-    use std::fs;
+fn show_pending_changes() -> Result<()> {
+    let journal = journal::Journal::load()?;
+    if journal.pending.is_empty() {
+        return Ok(());
+    }
     
-    let snippets_dir = repo_path.join("snippets");
-    if !snippets_dir.exists() {
-        return Ok(0);
+    println!();
+    println!("📬 Changes queued while offline:");
+    for change in &journal.pending {
+        println!("  • [{}] {} ({})", change.repo, change.description, change.queued_at);
+    }
+    println!("  💡 Run 'claude-md-snippets sync' to push them");
+    Ok(())
+}
+
+fn show_capabilities(refresh: bool) -> Result<()> {
+    if refresh {
+        capabilities::clear_cache()?;
     }
     
-    let mut count = 0;
-    for entry in fs::read_dir(snippets_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            // Skip README.md and .gitignore type files
-            if let Some(filename) = path.file_stem().and_then(|n| n.to_str()) {
-                if !filename.eq_ignore_ascii_case("readme") {
-                    count += 1;
-                }
-            }
+    println!();
+    println!("🧰 Available tools:");
+    for tool in Tool::ALL {
+        if capabilities::has(tool) {
+            println!("  ✅ {}", tool.label());
+        } else {
+            println!("  ❌ {} - affects: {}", tool.label(), tool.affects());
+            println!("     💡 {}", tool.hint());
         }
     }
     
-    Ok(count)
+    Ok(())
+}
+
+fn count_snippets(repo_path: &std::path::Path) -> Result<usize> {
+    // Skip README.md files
+    Ok(scan::snippet_files(&repo_path.join("snippets"))
+        .iter()
+        .filter(|path| !path.file_stem().and_then(|n| n.to_str()).is_some_and(|stem| stem.eq_ignore_ascii_case("readme")))
+        .count())
 }
 
 async fn set_default_repo(repo_name: String) -> Result<()> {
@@ -352,9 +1558,38 @@ async fn show_config() -> Result<()> {
         }
     }
     
-    let config_path = publish::get_app_dir()?.join("config.json");
-    println!("📍 Config file: {}", config_path.display());
-    println!("📍 Default install location: {}", config.get_default_install_location());
+    println!("📍 Config file: {}", config::get_config_path()?.display());
+    println!("📍 Data directory: {}", publish::get_app_dir()?.display());
+    if let Some((project_path, project)) = project::ProjectConfig::find()? {
+        println!("📍 Project config: {} (overrides the values shown)", project_path.display());
+        if let Some(target) = &project.default_target {
+            println!("🎯 Project default target: {}", target);
+        }
+        for (id, rev) in &project.pins {
+            println!("📌 Pinned: {} @ {}", id, rev);
+        }
+        if !project.required.is_empty() {
+            println!("✅ Required by 'exec': {}", project.required.join(", "));
+        }
+    }
+    
+    println!();
+    println!("🔧 Settings (schema v{}):", config::SCHEMA_VERSION);
+    for (key, _) in config::SETTINGS {
+        let value = config.get(key)?;
+        println!("  {}: {}", key, if value.is_empty() { "(not set)" } else { value.as_str() });
+    }
+    
+    println!();
+    println!("🎯 Install targets (install --target <name>):");
+    for (name, path) in config::BUILTIN_TARGETS {
+        if !config.targets.contains_key(*name) {
+            println!("  {}: {} (built-in)", name, path);
+        }
+    }
+    for (name, path) in &config.targets {
+        println!("  {}: {}", name, path);
+    }
     
     Ok(())
 }
@@ -380,18 +1615,28 @@ async fn set_install_location(location: String) -> Result<()> {
     Ok(())
 }
 
-async fn delete_snippet(repo_name: Option<String>, use_default: bool, query: String, debug: bool) -> Result<()> {
+/// The directory of repository `repo_name`, which must exist.
+fn existing_repo_dir(repo_name: &str) -> Result<std::path::PathBuf> {
+    let repo_dir = publish::get_repos_dir()?.join(repo_name);
+    if !repo_dir.exists() {
+        anyhow::bail!("Repository '{}' not found at {}", repo_name, repo_dir.display());
+    }
+    Ok(repo_dir)
+}
+
+fn resolve_repo_name(repo_name: Option<String>, use_default: bool) -> Result<String> {
+    match repo_name {
+        Some(name) if !use_default => Ok(name),
+        _ => config::get_default_repo_name(),
+    }
+}
+
+async fn delete_snippet(repo_name: Option<String>, use_default: bool, query: String) -> Result<()> {
     use std::fs;
-    use std::process::Command;
-    use std::io::{self, Write};
     use publish::get_repos_dir;
     
     // Determine which repository to use
-    let target_repo = if use_default || repo_name.is_none() {
-        config::get_default_repo_name()?
-    } else {
-        repo_name.unwrap()
-    };
+    let target_repo = resolve_repo_name(repo_name, use_default)?;
     
     let repos_dir = get_repos_dir()?;
     let repo_dir = repos_dir.join(&target_repo);
@@ -407,12 +1652,13 @@ async fn delete_snippet(repo_name: Option<String>, use_default: bool, query: Str
     if !snippets_subdir.exists() {
         fs::create_dir_all(&snippets_subdir)?;
     }
-    let file_to_delete = find_snippet_file_intelligently(&query, &snippets_subdir, debug)?;
+    let file_to_delete = find_snippet_file_intelligently(&query, &snippets_subdir)?;
     
     // Read the file to show what will be deleted
     let content = fs::read_to_string(&file_to_delete)?;
-    let snippet_info = if let Ok(snippet) = publish::parse_markdown_frontmatter(&content) {
-        format!("'{}' (ID: {})", snippet.name, &snippet.id[..8])
+    let parsed = publish::parse_markdown_frontmatter(&content).ok();
+    let snippet_info = if let Some(snippet) = &parsed {
+        format!("'{}' (ID: {})", snippet.name, crate::ids::short(&snippet.id))
     } else {
         file_to_delete.file_name()
             .and_then(|n| n.to_str())
@@ -423,58 +1669,62 @@ async fn delete_snippet(repo_name: Option<String>, use_default: bool, query: Str
     // Confirm deletion
     println!("📄 Found snippet: {}", snippet_info);
     println!("📁 File: {}", file_to_delete.display());
-    print!("❓ Are you sure you want to delete this snippet? (y/N): ");
-    std::io::stdout().flush()?;
-    
-    let mut input = String::new();
-    std::io::stdin().read_line(&mut input)?;
-    let input = input.trim().to_lowercase();
-    
-    if input != "y" && input != "yes" {
+    if !prompt::confirm("❓ Are you sure you want to delete this snippet?", false)? {
         println!("❌ Deletion cancelled");
         return Ok(());
     }
     
-    // Delete the file
-    fs::remove_file(&file_to_delete)?;
+    // Move it to the trash, so a wrong match can be undone
+    trash::move_to_trash(&snippets_subdir, &file_to_delete, parsed.as_ref())?;
     println!("✅ Deleted snippet: {}", snippet_info);
+    println!("💡 It is in the repository's trash; 'claude-md-snippets repo restore {}' brings it back", query);
     
-    // Auto-sync with repository
-    println!("🔄 Syncing deletion with repository...");
-    match crate::github::sync_snippets().await {
-        Ok(()) => {
-            println!("✅ Successfully synced deletion to repository!");
-        }
-        Err(e) => {
-            println!("⚠️  Sync failed: {}", e);
-            println!("💡 You can manually sync later with 'claude-md-snippets sync'");
-        }
-    }
+    // Auto-sync with repository (queued when offline)
+    crate::github::sync_after_change(&format!("Delete snippet {}", snippet_info), crate::github::ChangeKind::Edit).await;
     
     Ok(())
 }
 
-fn find_snippet_file_intelligently(query: &str, repo_dir: &std::path::Path, debug: bool) -> Result<std::path::PathBuf> {
+/// The repository `repo --name/--default` points at, and its snippet file matching `query`.
+fn find_repo_snippet(repo_name: Option<String>, use_default: bool, query: &str) -> Result<(String, std::path::PathBuf)> {
+    let target_repo = resolve_repo_name(repo_name, use_default)?;
+    let snippets_subdir = publish::get_repos_dir()?.join(&target_repo).join("snippets");
+    if !snippets_subdir.exists() {
+        anyhow::bail!("No snippets found in repository '{}'", target_repo);
+    }
+    println!("🔍 Searching for snippet matching '{}' in repository '{}'...", query, target_repo);
+    let file = find_snippet_file_intelligently(query, &snippets_subdir)?;
+    Ok((target_repo, file))
+}
+
+fn find_snippet_file_intelligently(query: &str, repo_dir: &std::path::Path) -> Result<std::path::PathBuf> {
     use std::fs;
-    use std::process::Command;
+    
+    // An ID prefix names the file directly
+    let with_ids: Vec<(std::path::PathBuf, publish::Snippet)> = scan::snippet_files(repo_dir).into_iter()
+        .filter_map(|path| {
+            let snippet = publish::parse_markdown_frontmatter(&fs::read_to_string(&path).ok()?).ok()?;
+            Some((path, snippet))
+        })
+        .collect();
+    let snippets: Vec<publish::Snippet> = with_ids.iter().map(|(_, snippet)| snippet.clone()).collect();
+    if let Some(snippet) = ids::find_by_prefix(&snippets, query)? {
+        if let Some((path, _)) = with_ids.iter().find(|(_, s)| s.id == snippet.id) {
+            return Ok(path.clone());
+        }
+    }
     
     // First try simple filename matching
     let mut simple_matches = Vec::new();
-    for entry in fs::read_dir(repo_dir)? {
-        let entry = entry?;
-        let path = entry.path();
+    for path in scan::snippet_files(repo_dir) {
+        let filename = path.strip_prefix(repo_dir).unwrap_or(&path).display().to_string();
+        // Skip README and similar files
+        if filename.to_lowercase().contains("readme") {
+            continue;
+        }
         
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                // Skip README and similar files
-                if filename.to_lowercase().contains("readme") {
-                    continue;
-                }
-                
-                if filename.to_lowercase().contains(&query.to_lowercase()) {
-                    simple_matches.push(path);
-                }
-            }
+        if filename.to_lowercase().contains(&query.to_lowercase()) {
+            simple_matches.push(path);
         }
     }
     
@@ -487,35 +1737,29 @@ fn find_snippet_file_intelligently(query: &str, repo_dir: &std::path::Path, debu
     
     // Get list of all snippet files with content preview
     let mut file_list = String::new();
-    for entry in fs::read_dir(repo_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                // Skip README and similar files
-                if filename.to_lowercase().contains("readme") {
-                    continue;
-                }
-                
-                // Read and preview the file
-                let content = fs::read_to_string(&path).unwrap_or_default();
-                let preview = if let Ok(snippet) = publish::parse_markdown_frontmatter(&content) {
-                    format!("Name: {}\nContent preview:\n{}", 
-                        snippet.name,
-                        snippet.content.lines().take(5).collect::<Vec<_>>().join("\n")
-                    )
-                } else {
-                    content.lines().take(10).collect::<Vec<_>>().join("\n")
-                };
-                
-                file_list.push_str(&format!(
-                    "File: {}\n{}\n\n---\n\n",
-                    filename,
-                    preview
-                ));
-            }
+    for path in scan::snippet_files(repo_dir) {
+        let filename = path.strip_prefix(repo_dir).unwrap_or(&path).display().to_string();
+        // Skip README and similar files
+        if filename.to_lowercase().contains("readme") {
+            continue;
         }
+        
+        // Read and preview the file
+        let content = fs::read_to_string(&path).unwrap_or_default();
+        let preview = if let Ok(snippet) = publish::parse_markdown_frontmatter(&content) {
+            format!("Name: {}\nContent preview:\n{}", 
+                snippet.name,
+                snippet.content.lines().take(5).collect::<Vec<_>>().join("\n")
+            )
+        } else {
+            content.lines().take(10).collect::<Vec<_>>().join("\n")
+        };
+        
+        file_list.push_str(&format!(
+            "File: {}\n{}\n\n---\n\n",
+            filename,
+            preview
+        ));
     }
     
     if file_list.is_empty() {
@@ -529,60 +1773,28 @@ fn find_snippet_file_intelligently(query: &str, repo_dir: &std::path::Path, debu
         query, file_list
     );
     
-    if debug {
-        println!("🔧 Debug: Calling Claude Code CLI...");
-        println!("🔧 Debug: Command: claude --dangerously-skip-permissions --print <prompt>");
-        println!("🔧 Debug: Prompt length: {} characters", prompt.len());
-    }
-    
-    let output = std::process::Command::new("claude")
-        .arg("--dangerously-skip-permissions")
-        .arg("--print")
-        .arg(&prompt)
-        .output();
-    
-    let output = match output {
-        Ok(output) => {
-            if debug {
-                println!("🔧 Debug: Claude Code CLI returned with status: {}", output.status);
-                if !output.stderr.is_empty() {
-                    println!("🔧 Debug: stderr: {}", String::from_utf8_lossy(&output.stderr));
-                }
-            }
-            output
-        }
+    let response = if capabilities::has(Tool::Claude) {
+        claude_client::ClaudeClient::from_config()?.prompt(&prompt)
+    } else {
+        Err(anyhow::anyhow!("{} not detected", Tool::Claude.label()))
+    };
+    let suggested_filename = match response {
+        Ok(response) => response,
         Err(e) => {
-            println!("⚠️  Failed to execute Claude Code CLI: {}", e);
-            println!("💡 Falling back to simple matching");
-            // Fallback to simple matching
+            // Fallback to simple matching if Claude Code fails
+            println!("⚠️  Claude Code unavailable ({}), falling back to simple matching", e);
             if simple_matches.len() > 1 {
-                println!("⚠️  Multiple matches found:");
-                for (i, file) in simple_matches.iter().enumerate() {
-                    println!("  {}. {}", i + 1, file.display());
-                }
-                anyhow::bail!("Please be more specific with your query");
+                println!("🤔 Multiple snippets match '{}':", query);
+                return match prompt::choose("Which one?", &publish::file_candidates(&simple_matches))? {
+                    Some(i) => Ok(simple_matches[i].clone()),
+                    None => anyhow::bail!("No snippet selected"),
+                };
             } else if simple_matches.is_empty() {
                 anyhow::bail!("No snippet found matching '{}' in repository", query);
             }
             return Ok(simple_matches[0].clone());
         }
     };
-    
-    if !output.status.success() {
-        // Fallback to simple matching if Claude Code fails
-        if simple_matches.len() > 1 {
-            println!("⚠️  Claude Code unavailable. Multiple matches found:");
-            for (i, file) in simple_matches.iter().enumerate() {
-                println!("  {}. {}", i + 1, file.display());
-            }
-            anyhow::bail!("Please be more specific with your query");
-        } else if simple_matches.is_empty() {
-            anyhow::bail!("No snippet found matching '{}' in repository", query);
-        }
-        return Ok(simple_matches[0].clone());
-    }
-    
-    let suggested_filename = String::from_utf8_lossy(&output.stdout).trim().to_string();
     let suggested_path = repo_dir.join(&suggested_filename);
     
     if suggested_path.exists() {
@@ -592,16 +1804,12 @@ fn find_snippet_file_intelligently(query: &str, repo_dir: &std::path::Path, debu
     }
 }
 
-async fn list_repo_snippets(repo_name: Option<String>, use_default: bool) -> Result<()> {
+async fn list_repo_snippets(repo_name: Option<String>, use_default: bool, options: ListOptions) -> Result<()> {
     use std::fs;
     use publish::get_repos_dir;
     
     // Determine which repository to use
-    let target_repo = if use_default || repo_name.is_none() {
-        config::get_default_repo_name()?
-    } else {
-        repo_name.unwrap()
-    };
+    let target_repo = resolve_repo_name(repo_name, use_default)?;
     
     let repos_dir = get_repos_dir()?;
     let repo_dir = repos_dir.join(&target_repo);
@@ -622,42 +1830,71 @@ async fn list_repo_snippets(repo_name: Option<String>, use_default: bool) -> Res
         return Ok(());
     }
     
-    for entry in fs::read_dir(&snippets_subdir)? {
-        let entry = entry?;
-        let path = entry.path();
+    for path in scan::snippet_files(&snippets_subdir) {
+        let filename = path.strip_prefix(&snippets_subdir).unwrap_or(&path).display().to_string();
+        // Skip README and similar files
+        if filename.to_lowercase().contains("readme") {
+            continue;
+        }
         
-        if path.extension().and_then(|s| s.to_str()) == Some("md") {
-            if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                // Skip README and similar files
-                if filename.to_lowercase().contains("readme") {
-                    continue;
-                }
-                
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Ok(snippet) = publish::parse_markdown_frontmatter(&content) {
-                        snippets.push((filename.to_string(), snippet));
-                    } else {
-                        // File without frontmatter
-                        snippets.push((filename.to_string(), publish::Snippet {
-                            id: "unknown".to_string(),
-                            name: filename.replace(".md", "").replace("_", " "),
-                            content: content,
-                            created_at: "unknown".to_string(),
-                            description: None,
-                        }));
-                    }
-                }
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(snippet) = publish::parse_markdown_frontmatter(&content) {
+                snippets.push((filename.to_string(), snippet, modified));
+            } else {
+                // File without frontmatter
+                snippets.push((filename.to_string(), publish::Snippet {
+                    name: filename.replace(".md", "").replace("_", " "),
+                    content,
+                    created_at: "unknown".to_string(),
+                    ..Default::default()
+                }, modified));
             }
         }
     }
     
+    let total = snippets.len();
+    if let Some(filter) = &options.filter {
+        let filter = filter.to_lowercase();
+        snippets.retain(|(filename, snippet, _)| {
+            snippet.name.to_lowercase().contains(&filter)
+                || filename.to_lowercase().contains(&filter)
+                || snippet.description.as_deref().unwrap_or("").to_lowercase().contains(&filter)
+                || snippet.tags.iter().any(|tag| tag.contains(&filter))
+        });
+    }
+    if let Some(author) = &options.author {
+        snippets.retain(|(_, snippet, _)| attribution::by(snippet, author));
+    }
+    
     if snippets.is_empty() {
-        println!("  (no snippets found)");
+        if total > 0 {
+            println!("  (no snippets match the filter)");
+        } else {
+            println!("  (no snippets found)");
+        }
     } else {
-        // Sort by creation date (newest first)
-        snippets.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at));
+        match options.sort {
+            ListSort::Name => snippets.sort_by_key(|(_, snippet, _)| snippet.name.to_lowercase()),
+            // Newest first
+            ListSort::Created => snippets.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at)),
+            ListSort::Updated => snippets.sort_by_key(|(_, _, modified)| std::cmp::Reverse(*modified)),
+            // Largest first
+            ListSort::Size => snippets.sort_by_key(|(_, snippet, _)| std::cmp::Reverse(snippet.content.len())),
+        }
+        
+        let matched = snippets.len();
+        let limit = options.limit.unwrap_or(matched).max(1);
+        let page = options.page.max(1);
+        let start = (page - 1) * limit;
+        if start >= matched {
+            anyhow::bail!("Page {} is past the end ({} snippets, {} per page)", page, matched, limit);
+        }
+        let end = (start + limit).min(matched);
+        // Unique across the whole repository, not just this page
+        let short_ids = ids::ShortIds::new(snippets.iter().map(|(_, snippet, _)| snippet.id.as_str()));
         
-        for (filename, snippet) in snippets {
+        for (filename, snippet, _) in snippets.drain(start..end) {
             let created = if snippet.created_at != "unknown" {
                 chrono::DateTime::parse_from_rfc3339(&snippet.created_at)
                     .map(|dt| dt.format("%Y-%m-%d %H:%M").to_string())
@@ -665,15 +1902,37 @@ async fn list_repo_snippets(repo_name: Option<String>, use_default: bool) -> Res
             } else {
                 "unknown".to_string()
             };
+            let short_id = short_ids.padded(&snippet.id);
+            
+            if options.compact {
+                println!("  {}  {:<16}  {:>6}  {}", short_id, created, snippet.content.len(), snippet.name);
+                continue;
+            }
             
-            println!("  📄 {} ({})", snippet.name, &snippet.id[..8]);
+            println!("  📄 {} ({})", snippet.name, short_id.trim_end());
             println!("      File: {}", filename);
+            if !snippet.status.is_active() {
+                println!("      Status: {}", snippet.status.as_str());
+            }
             println!("      Created: {}", created);
             if let Some(desc) = &snippet.description {
                 println!("      Description: {}", desc);
             }
+            if !snippet.tags.is_empty() {
+                println!("      Tags: {}", snippet.tags.join(", "));
+            }
+            if let Some(author) = &snippet.author {
+                println!("      Author: {}", author);
+            }
             println!();
         }
+        
+        if end - start < total {
+            println!("📊 Showing {}-{} of {} matching ({} total)", start + 1, end, matched, total);
+            if end < matched {
+                println!("💡 Next page: --page {}", page + 1);
+            }
+        }
     }
     
     println!("📍 Repository directory: {}", repo_dir.display());
@@ -681,16 +1940,11 @@ async fn list_repo_snippets(repo_name: Option<String>, use_default: bool) -> Res
     Ok(())
 }
 
-async fn open_repo_in_browser(repo_name: Option<String>, use_default: bool) -> Result<()> {
-    use std::process::Command;
+async fn open_repo_in_browser(repo_name: Option<String>, use_default: bool, print_url: bool) -> Result<()> {
     use publish::get_repos_dir;
     
     // Determine which repository to use
-    let target_repo = if use_default || repo_name.is_none() {
-        config::get_default_repo_name()?
-    } else {
-        repo_name.unwrap()
-    };
+    let target_repo = resolve_repo_name(repo_name, use_default)?;
     
     let repos_dir = get_repos_dir()?;
     let repo_dir = repos_dir.join(&target_repo);
@@ -699,58 +1953,140 @@ async fn open_repo_in_browser(repo_name: Option<String>, use_default: bool) -> R
         anyhow::bail!("Repository '{}' not found at {}", target_repo, repo_dir.display());
     }
     
-    // Check if this is a git repository
-    let git_dir = repo_dir.join(".git");
-    if !git_dir.exists() {
-        anyhow::bail!("Repository '{}' is not a git repository. Initialize with git first.", target_repo);
+    let browser_url = origin_remote(&repo_dir, &target_repo)?.web_url();
+    if print_url {
+        println!("{}", browser_url);
+        return Ok(());
     }
     
-    // Get the remote URL
-    let output = Command::new("git")
-        .current_dir(&repo_dir)
-        .args(&["remote", "get-url", "origin"])
-        .output()?;
+    println!("🌐 Opening repository '{}' in browser...", target_repo);
+    println!("🔗 URL: {}", browser_url);
     
-    if !output.status.success() {
-        anyhow::bail!("No git remote 'origin' found for repository '{}'. Add a remote first.", target_repo);
+    if browser::open(&browser_url) {
+        println!("✅ Successfully opened repository in browser");
     }
     
-    let remote_url = String::from_utf8(output.stdout)?.trim().to_string();
+    Ok(())
+}
+
+async fn open_snippet(repo_name: Option<String>, query: String, web: bool) -> Result<()> {
+    use publish::get_repos_dir;
     
-    // Convert git URL to HTTPS URL if needed
-    let browser_url = if remote_url.starts_with("git@github.com:") {
-        remote_url.replace("git@github.com:", "https://github.com/")
-            .strip_suffix(".git").unwrap_or(&remote_url).to_string()
-    } else if remote_url.starts_with("https://github.com/") {
-        remote_url.strip_suffix(".git").unwrap_or(&remote_url).to_string()
-    } else {
-        remote_url
+    let target_repo = resolve_repo_name(repo_name, false)?;
+    let repo_dir = get_repos_dir()?.join(&target_repo);
+    let snippets_subdir = repo_dir.join("snippets");
+    
+    if !snippets_subdir.exists() {
+        anyhow::bail!("No snippets found in repository '{}'", target_repo);
+    }
+    
+    let snippet_file = find_snippet_file_intelligently(&query, &snippets_subdir)?;
+    
+    if !web {
+        println!("📝 Opening {} in {}...", snippet_file.display(), editor::editor_command());
+        editor::open_file(&snippet_file)?;
+        println!("💡 Run 'claude-md-snippets sync' to publish any changes");
+        return Ok(());
+    }
+    
+    let remote = origin_remote(&repo_dir, &target_repo)?;
+    let branch = github::sync_branch(&process::SystemRunner, &target_repo, &repo_dir, false);
+    let relative_path = snippet_file.strip_prefix(&repo_dir).unwrap_or(&snippet_file);
+    let Some(blob_url) = remote.blob_url(&branch, &relative_path.display().to_string()) else {
+        anyhow::bail!("Don't know how {} shows files; open {} instead", remote.host, remote.web_url());
     };
     
-    println!("🌐 Opening repository '{}' in browser...", target_repo);
-    println!("🔗 URL: {}", browser_url);
+    println!("🌐 Opening snippet in browser...");
+    println!("🔗 URL: {}", blob_url);
+    println!("💡 Unsynced changes won't appear until you run 'claude-md-snippets sync'");
     
-    // Open URL in default browser
-    let result = if cfg!(target_os = "macos") {
-        Command::new("open").arg(&browser_url).status()
-    } else if cfg!(target_os = "windows") {
-        Command::new("cmd").args(&["/c", "start", &browser_url]).status()
-    } else {
-        // Linux and other Unix-like systems
-        Command::new("xdg-open").arg(&browser_url).status()
+    if browser::open(&blob_url) {
+        println!("✅ Successfully opened snippet in browser");
+    }
+    
+    Ok(())
+}
+
+async fn send_feedback(repo_name: Option<String>, query: String, message: String) -> Result<()> {
+    use std::fs;
+    use std::process::Command;
+    use publish::get_repos_dir;
+    
+    let target_repo = resolve_repo_name(repo_name, false)?;
+    let repo_dir = get_repos_dir()?.join(&target_repo);
+    let snippets_subdir = repo_dir.join("snippets");
+    
+    if !snippets_subdir.exists() {
+        anyhow::bail!("No snippets found in repository '{}'", target_repo);
+    }
+    
+    let snippet_file = find_snippet_file_intelligently(&query, &snippets_subdir)?;
+    let relative_path = snippet_file.strip_prefix(&repo_dir).unwrap_or(&snippet_file).display().to_string();
+    let snippet = publish::parse_markdown_frontmatter(&fs::read_to_string(&snippet_file)?)?;
+    
+    // The last commit that touched the snippet identifies the version being discussed
+    let output = Command::new("git")
+        .current_dir(&repo_dir)
+        .args(["log", "-1", "--format=%h", "--", &relative_path])
+        .timed_output(Category::Git)?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let version = if version.is_empty() { "unsynced".to_string() } else { version };
+    
+    let title = format!("Feedback: {}", snippet.name);
+    let body = format!(
+        "**Snippet:** {}\n**ID:** {}\n**File:** `{}`\n**Version:** {}\n\n{}\n",
+        snippet.name, snippet.id, relative_path, version, message
+    );
+    
+    let browser_url = origin_remote(&repo_dir, &target_repo)?.web_url();
+    let Some((owner, repo)) = github::parse_github_remote(&browser_url) else {
+        anyhow::bail!("Feedback issues need a GitHub remote; '{}' uses {}", target_repo, browser_url);
     };
     
-    match result {
-        Ok(status) if status.success() => {
-            println!("✅ Successfully opened repository in browser");
-        }
-        Ok(_) => {
-            println!("⚠️  Failed to open browser. You can manually visit: {}", browser_url);
-        }
-        Err(e) => {
-            println!("⚠️  Failed to open browser ({}). You can manually visit: {}", e, browser_url);
-        }
+    println!("💬 Feedback on '{}' ({}) for {}/{}", snippet.name, version, owner, repo);
+    
+    if let Some(api) = github_api::GitHubClient::new(accounts::for_repo(&target_repo)?.as_ref()) {
+        capabilities::require(Tool::Network, "feedback")?;
+        let issue_url = api.create_issue(&owner, &repo, &title, &body).await?;
+        println!("✅ Opened issue: {}", issue_url);
+        return Ok(());
+    }
+    
+    // Without a token, let the browser's GitHub session file the prefilled issue
+    println!("ℹ️  No GitHub token found (set GITHUB_TOKEN or run 'gh auth login'); opening a prefilled issue form instead");
+    let issue_form = reqwest::Url::parse_with_params(
+        &format!("https://github.com/{}/{}/issues/new", owner, repo),
+        &[("title", title.as_str()), ("body", body.as_str())],
+    )?;
+    println!("🔗 URL: {}", issue_form);
+    if browser::open(issue_form.as_str()) {
+        println!("✅ Opened issue form in browser");
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// A repository's `origin` remote, parsed so its web URLs can be built for the host.
+fn origin_remote(repo_dir: &std::path::Path, target_repo: &str) -> Result<hosts::Remote> {
+    use std::process::Command;
+    
+    // Check if this is a git repository
+    let git_dir = repo_dir.join(".git");
+    if !git_dir.exists() {
+        anyhow::bail!("Repository '{}' is not a git repository. Initialize with git first.", target_repo);
+    }
+    
+    // Get the remote URL
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["remote", "get-url", "origin"])
+        .timed_output(Category::Git)?;
+    
+    if !output.status.success() {
+        anyhow::bail!("No git remote 'origin' found for repository '{}'. Add a remote first.", target_repo);
+    }
+    
+    let remote_url = String::from_utf8(output.stdout)?.trim().to_string();
+    hosts::Remote::parse(&remote_url)
+        .ok_or_else(|| anyhow::anyhow!("Remote '{}' of repository '{}' has no web page", remote_url, target_repo))
+}
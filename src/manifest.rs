@@ -0,0 +1,134 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use crate::lockfile::{FileLock, atomic_write};
+use crate::publish::get_app_dir;
+
+/// Record of every snippet installed into a CLAUDE.md (or other target) file.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Manifest {
+    #[serde(default)]
+    pub revision: u64,
+    #[serde(default)]
+    pub installs: Vec<InstallRecord>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct InstallRecord {
+    pub id: String, // short (8 char) ID used in the markers
+    pub name: String,
+    pub target: String,
+    pub content_hash: String,
+    pub installed_at: String,
+    /// Commit of the snippets repository this install is pinned to (`install --pin`); `update` leaves it alone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<String>,
+}
+
+impl Manifest {
+    pub fn load() -> Result<Self> {
+        let manifest_path = get_manifest_path()?;
+
+        if !manifest_path.exists() {
+            return Ok(Manifest::default());
+        }
+
+        read_manifest(&manifest_path)
+    }
+
+    /// Apply `change` to the latest on-disk manifest while holding its lock.
+    pub fn update<F>(change: F) -> Result<Manifest>
+    where
+        F: FnOnce(&mut Manifest) -> Result<()>,
+    {
+        let manifest_path = get_manifest_path()?;
+        let _lock = FileLock::acquire(&manifest_path)?;
+
+        let mut manifest = if manifest_path.exists() {
+            read_manifest(&manifest_path)?
+        } else {
+            Manifest::default()
+        };
+
+        change(&mut manifest)?;
+        manifest.revision += 1;
+        atomic_write(&manifest_path, &serde_json::to_string_pretty(&manifest)?)?;
+        Ok(manifest)
+    }
+
+    pub fn installs_in<'a>(&'a self, target: &'a Path) -> impl Iterator<Item = &'a InstallRecord> + 'a {
+        let target = target.display().to_string();
+        self.installs.iter().filter(move |record| record.target == target)
+    }
+
+    /// Record an installation, replacing any previous record of the same snippet in the same target.
+    pub fn record_install(id: &str, name: &str, target: &Path, content: &str) -> Result<()> {
+        let record = InstallRecord {
+            id: id.to_string(),
+            name: name.to_string(),
+            target: target.display().to_string(),
+            content_hash: content_hash(content),
+            installed_at: chrono::Utc::now().to_rfc3339(),
+            pinned: None,
+        };
+
+        Manifest::update(|manifest| {
+            manifest.installs.retain(|r| !(r.id == record.id && r.target == record.target));
+            manifest.installs.push(record);
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Pin the install of `id` in `target` to `commit`, or release it with `None`.
+    /// Returns whether there was such an install.
+    pub fn set_pin(id: &str, target: &Path, commit: Option<&str>) -> Result<bool> {
+        let target = target.display().to_string();
+        let mut found = false;
+        Manifest::update(|manifest| {
+            for record in manifest.installs.iter_mut().filter(|r| r.id == id && r.target == target) {
+                record.pinned = commit.map(str::to_string);
+                found = true;
+            }
+            Ok(())
+        })?;
+        Ok(found)
+    }
+
+    pub fn record_uninstall(id: &str, target: &Path) -> Result<()> {
+        let target = target.display().to_string();
+        Manifest::update(|manifest| {
+            manifest.installs.retain(|r| !(r.id == id && r.target == target));
+            Ok(())
+        })?;
+        Ok(())
+    }
+}
+
+/// Hash of the snippet content with whitespace and case normalized, so
+/// trivially reformatted copies of the same instructions compare equal.
+pub fn content_hash(content: &str) -> String {
+    let digest = Sha256::digest(normalize_content(content).as_bytes());
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn normalize_content(content: &str) -> String {
+    content
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn get_manifest_path() -> Result<std::path::PathBuf> {
+    let app_dir = get_app_dir()?;
+    Ok(app_dir.join("installed.json"))
+}
+
+fn read_manifest(manifest_path: &Path) -> Result<Manifest> {
+    let content = fs::read_to_string(manifest_path)?;
+    let manifest: Manifest = serde_json::from_str(&content)?;
+    Ok(manifest)
+}
@@ -0,0 +1,117 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SPANS: Mutex<Vec<Span>> = Mutex::new(Vec::new());
+
+/// Where time is spent, grouped the way users can act on it (cache, disable, go offline).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    FsScan,
+    Git,
+    Ai,
+    Probe,
+}
+
+impl Category {
+    fn label(self) -> &'static str {
+        match self {
+            Category::FsScan => "fs scan",
+            Category::Git => "git",
+            Category::Ai => "ai (claude)",
+            Category::Probe => "tool probes",
+        }
+    }
+}
+
+struct Span {
+    category: Category,
+    label: String,
+    elapsed: Duration,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Run `f`, recording its duration under `category` when `--profile-perf` is on.
+pub fn timed<T>(category: Category, label: &str, f: impl FnOnce() -> T) -> T {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return f();
+    }
+
+    let started = Instant::now();
+    let result = f();
+    let elapsed = started.elapsed();
+
+    if let Ok(mut spans) = SPANS.lock() {
+        spans.push(Span { category, label: label.to_string(), elapsed });
+    }
+    result
+}
+
+/// Print where the command's time went. No-op unless profiling is enabled.
+pub fn report(total: Duration) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Ok(spans) = SPANS.lock() else { return };
+
+    println!();
+    println!("⏱️  Performance profile");
+    println!("=======================");
+    println!("  {:<14} {:>9.3}s", "total", total.as_secs_f64());
+
+    let mut accounted = Duration::ZERO;
+    for category in [Category::FsScan, Category::Git, Category::Ai, Category::Probe] {
+        let matching: Vec<&Span> = spans.iter().filter(|s| s.category == category).collect();
+        let sum: Duration = matching.iter().map(|s| s.elapsed).sum();
+        accounted += sum;
+        println!("  {:<14} {:>9.3}s ({} calls)", category.label(), sum.as_secs_f64(), matching.len());
+    }
+    println!("  {:<14} {:>9.3}s", "other", total.saturating_sub(accounted).as_secs_f64());
+
+    let mut slowest: Vec<&Span> = spans.iter().collect();
+    slowest.sort_by_key(|s| std::cmp::Reverse(s.elapsed));
+    if !slowest.is_empty() {
+        println!();
+        println!("  Slowest operations:");
+        for span in slowest.iter().take(5) {
+            println!("    {:<32} {:>9.3}s  [{}]", span.label, span.elapsed.as_secs_f64(), span.category.label());
+        }
+    }
+
+    let ai_time: Duration = spans.iter().filter(|s| s.category == Category::Ai).map(|s| s.elapsed).sum();
+    if !total.is_zero() && ai_time.as_secs_f64() / total.as_secs_f64() > 0.5 {
+        println!();
+        println!("💡 Most time was spent waiting on Claude Code; 'search <query>' ranks snippets locally without it");
+    }
+}
+
+/// `Command::output` that records its duration, labelled with the program and subcommand.
+pub trait TimedCommand {
+    fn timed_output(&mut self, category: Category) -> std::io::Result<std::process::Output>;
+}
+
+impl TimedCommand for std::process::Command {
+    fn timed_output(&mut self, category: Category) -> std::io::Result<std::process::Output> {
+        let mut label = self.get_program().to_string_lossy().to_string();
+        if let Some(first_arg) = self.get_args().next() {
+            let first_arg = first_arg.to_string_lossy();
+            // Skip flags and long prompts so the label stays readable
+            if !first_arg.starts_with('-') && first_arg.len() <= 24 {
+                label = format!("{} {}", label, first_arg);
+            }
+        }
+        let args: Vec<String> = self.get_args().map(|arg| arg.to_string_lossy().to_string()).collect();
+        tracing::debug!("Running {} {}", self.get_program().to_string_lossy(), args.join(" "));
+        let output = timed(category, &label, || self.output())?;
+        tracing::debug!("{} exited with {}", label, output.status);
+        tracing::trace!("stdout: {}", String::from_utf8_lossy(&output.stdout).trim());
+        if !output.stderr.is_empty() {
+            tracing::trace!("stderr: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(output)
+    }
+}
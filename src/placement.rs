@@ -0,0 +1,152 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use crate::install::append_block;
+
+/// Heading of the section `--position alphabetical` keeps installed blocks in.
+pub const MANAGED_HEADING: &str = "## Installed Snippets";
+
+/// Where in the target file a newly installed block goes (`install --position`).
+#[derive(Default, Clone, PartialEq, Debug)]
+pub enum Placement {
+    /// After everything else (the default)
+    #[default]
+    End,
+    /// Before everything else
+    Top,
+    /// At the end of the section under this heading
+    AfterHeading(String),
+    /// In the managed "## Installed Snippets" section, sorted by name
+    Alphabetical,
+}
+
+impl Placement {
+    /// Parse `--position` values: `top`, `end`, `alphabetical` or
+    /// `after-heading <name>`.
+    pub fn from_args(args: &[String]) -> Result<Self> {
+        match args {
+            [] => Ok(Placement::End),
+            [place] if place == "end" => Ok(Placement::End),
+            [place] if place == "top" => Ok(Placement::Top),
+            [place] if place == "alphabetical" => Ok(Placement::Alphabetical),
+            [place] if place == "after-heading" => anyhow::bail!("--position after-heading needs the heading, e.g. --position after-heading \"Testing\""),
+            [place, heading] if place == "after-heading" => Ok(Placement::AfterHeading(heading.clone())),
+            _ => anyhow::bail!("Unknown --position '{}'; use top, end, alphabetical or after-heading <name>", args.join(" ")),
+        }
+    }
+
+    /// `existing` with `block` (a snippet with its markers, named `name`)
+    /// added where this placement says. `names` gives the names of the blocks
+    /// already installed, by ID, for sorting.
+    pub fn insert(&self, existing: &str, block: &str, name: &str, names: &HashMap<String, String>) -> Result<String> {
+        match self {
+            Placement::End => Ok(append_block(existing, block)),
+            Placement::Top => Ok(match existing.trim() {
+                "" => format!("{}\n", block),
+                _ => format!("{}\n\n{}", block, existing.trim_start()),
+            }),
+            Placement::AfterHeading(heading) => {
+                let lines: Vec<&str> = existing.lines().collect();
+                let Some((index, level)) = lines.iter().enumerate().find_map(|(i, line)| {
+                    heading_level(line).filter(|_| heading_text(line).eq_ignore_ascii_case(heading.trim())).map(|level| (i, level))
+                }) else {
+                    anyhow::bail!("No heading '{}' in the target file", heading);
+                };
+                let end = section_end(&lines, index, level);
+                Ok(splice(&lines, end, block))
+            }
+            Placement::Alphabetical => Ok(insert_sorted(existing, block, name, names)),
+        }
+    }
+}
+
+/// Put `block` into the managed section (creating it at the end of the file
+/// when missing) before the first block whose name sorts after `name`.
+fn insert_sorted(existing: &str, block: &str, name: &str, names: &HashMap<String, String>) -> String {
+    let lines: Vec<&str> = existing.lines().collect();
+    let Some(heading) = lines.iter().position(|line| line.trim() == MANAGED_HEADING) else {
+        return append_block(existing, &format!("{}\n\n{}", MANAGED_HEADING, block));
+    };
+    let end = section_end(&lines, heading, 2);
+
+    let key = name.to_lowercase();
+    let mut insert_at = end;
+    for (i, line) in lines.iter().enumerate().take(end).skip(heading + 1) {
+        let Some(id) = line.trim().strip_prefix("<!-- SNIPPET_START:").and_then(|rest| rest.strip_suffix(" -->")) else { continue };
+        let other = names.get(id.trim()).map(|name| name.to_lowercase()).unwrap_or_default();
+        if other > key {
+            insert_at = i;
+            break;
+        }
+    }
+    splice(&lines, insert_at, block)
+}
+
+/// `lines` with `block` inserted before line `at`, set off by blank lines.
+fn splice(lines: &[&str], at: usize, block: &str) -> String {
+    let before = lines[..at].join("\n");
+    let after = lines[at..].join("\n");
+    let mut out = before.trim_end().to_string();
+    if !out.is_empty() {
+        out.push_str("\n\n");
+    }
+    out.push_str(block);
+    if !after.trim().is_empty() {
+        out.push_str("\n\n");
+        out.push_str(after.trim_start_matches('\n'));
+    }
+    out.push('\n');
+    out
+}
+
+/// The line after the section starting at `start`: the next heading of
+/// `level` or higher outside code fences and snippet blocks, or the end.
+fn section_end(lines: &[&str], start: usize, level: usize) -> usize {
+    let mut in_fence = false;
+    let mut in_block = false;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") {
+            in_fence = !in_fence;
+        } else if trimmed.starts_with("<!-- SNIPPET_START:") {
+            in_block = true;
+        } else if trimmed.starts_with("<!-- SNIPPET_END:") {
+            in_block = false;
+        } else if !in_fence && !in_block && heading_level(line).is_some_and(|other| other <= level) {
+            return i;
+        }
+    }
+    lines.len()
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let hashes = line.chars().take_while(|&c| c == '#').count();
+    (hashes > 0 && hashes <= 6 && line[hashes..].starts_with(' ')).then_some(hashes)
+}
+
+fn heading_text(line: &str) -> &str {
+    line.trim_start_matches('#').trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placements() {
+        let existing = "# Project\n\nIntro\n\n## Testing\n\nRun cargo test\n\n## Style\n\nUse rustfmt\n";
+        let block = "<!-- SNIPPET_START:1a2b -->\nNew rule\n<!-- SNIPPET_END:1a2b -->";
+        let names = HashMap::new();
+
+        assert!(Placement::Top.insert(existing, block, "New", &names).unwrap().starts_with("<!-- SNIPPET_START:1a2b -->"));
+        let after = Placement::AfterHeading("testing".to_string()).insert(existing, block, "New", &names).unwrap();
+        assert!(after.contains("Run cargo test\n\n<!-- SNIPPET_START:1a2b -->\nNew rule\n<!-- SNIPPET_END:1a2b -->\n\n## Style"));
+        assert!(Placement::AfterHeading("Deploy".to_string()).insert(existing, block, "New", &names).is_err());
+
+        let managed = Placement::Alphabetical.insert(existing, "<!-- SNIPPET_START:9f8e -->\nZ\n<!-- SNIPPET_END:9f8e -->", "Zebra", &names).unwrap();
+        assert!(managed.starts_with(existing.trim_end()) && managed.contains("## Installed Snippets\n\n<!-- SNIPPET_START:9f8e -->"));
+        let names = HashMap::from([("9f8e".to_string(), "Zebra".to_string())]);
+        let sorted = Placement::Alphabetical.insert(&managed, block, "Alpha", &names).unwrap();
+        assert!(sorted.find("SNIPPET_START:1a2b").unwrap() < sorted.find("SNIPPET_START:9f8e").unwrap());
+        assert_eq!(Placement::from_args(&["after-heading".to_string(), "Testing".to_string()]).unwrap(), Placement::AfterHeading("Testing".to_string()));
+    }
+}
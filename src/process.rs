@@ -0,0 +1,84 @@
+use std::process::{Command, Output};
+use crate::perf::{Category, TimedCommand};
+
+/// Runs external commands (git, gh). Code that takes a runner instead of
+/// calling `Command::output` directly can be exercised against scripted
+/// results, or pointed at another backend, without the real binaries.
+pub trait ProcessRunner: Send + Sync {
+    fn output(&self, command: &mut Command, category: Category) -> std::io::Result<Output>;
+}
+
+/// Runs commands for real, recording their duration for `--profile-perf`.
+pub struct SystemRunner;
+
+impl ProcessRunner for SystemRunner {
+    fn output(&self, command: &mut Command, category: Category) -> std::io::Result<Output> {
+        command.timed_output(category)
+    }
+}
+
+/// `command.run_with(runner, category)`, the injectable counterpart of `timed_output`.
+pub trait RunWith {
+    fn run_with(&mut self, runner: &dyn ProcessRunner, category: Category) -> std::io::Result<Output>;
+}
+
+impl RunWith for Command {
+    fn run_with(&mut self, runner: &dyn ProcessRunner, category: Category) -> std::io::Result<Output> {
+        runner.output(self, category)
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+    use std::sync::Mutex;
+
+    /// Records every command and answers from scripted responses, matched by
+    /// command-line prefix (e.g. `"git rev-list"`). Unmatched commands succeed
+    /// with no output.
+    #[derive(Default)]
+    pub struct FakeRunner {
+        responses: Vec<(String, String, bool)>,
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl FakeRunner {
+        pub fn new() -> Self {
+            FakeRunner::default()
+        }
+
+        pub fn respond(mut self, prefix: &str, stdout: &str, success: bool) -> Self {
+            self.responses.push((prefix.to_string(), stdout.to_string(), success));
+            self
+        }
+
+        pub fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl ProcessRunner for FakeRunner {
+        fn output(&self, command: &mut Command, _category: Category) -> std::io::Result<Output> {
+            let line = std::iter::once(command.get_program())
+                .chain(command.get_args())
+                .map(|part| part.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            self.calls.lock().unwrap().push(line.clone());
+
+            let (stdout, success) = self
+                .responses
+                .iter()
+                .find(|(prefix, _, _)| line.starts_with(prefix.as_str()))
+                .map(|(_, stdout, success)| (stdout.clone(), *success))
+                .unwrap_or((String::new(), true));
+            Ok(Output {
+                status: ExitStatus::from_raw(if success { 0 } else { 1 << 8 }),
+                stdout: stdout.into_bytes(),
+                stderr: Vec::new(),
+            })
+        }
+    }
+}
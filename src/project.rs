@@ -0,0 +1,150 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::config::{Config, InstallLocation};
+use crate::perf::{Category, TimedCommand};
+use crate::publish::{self, Snippet};
+
+/// Project settings committed next to the code, found by walking up from the
+/// current directory. They are merged over the global `config.json`.
+pub const PROJECT_CONFIG_FILE: &str = ".claude-md-snippets.toml";
+
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub default_repo: Option<String>,
+    pub default_install_location: Option<InstallLocation>,
+    /// Target used when install/uninstall get no --local, --user or --target
+    pub default_target: Option<String>,
+    pub language: Option<String>,
+    /// Named targets, added to (and overriding) the global ones
+    #[serde(default)]
+    pub targets: BTreeMap<String, String>,
+    /// Snippet ID (or prefix) -> git revision of the snippets repository to install it from
+    #[serde(default)]
+    pub pins: BTreeMap<String, String>,
+    /// Snippet IDs (or prefixes) that `exec` makes sure are installed before running a command
+    #[serde(default)]
+    pub required: Vec<String>,
+}
+
+impl ProjectConfig {
+    /// The nearest project config above the current directory, with its path.
+    pub fn find() -> Result<Option<(PathBuf, ProjectConfig)>> {
+        let cwd = std::env::current_dir()?;
+        for dir in cwd.ancestors() {
+            let path = dir.join(PROJECT_CONFIG_FILE);
+            if path.is_file() {
+                let config = toml::from_str(&fs::read_to_string(&path)?)
+                    .with_context(|| format!("Invalid {}", path.display()))?;
+                return Ok(Some((path, config)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Override the global settings with the ones this project sets.
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(repo) = &self.default_repo {
+            config.default_repo = Some(repo.clone());
+        }
+        if let Some(location) = self.default_install_location {
+            config.install.location = location;
+        }
+        if let Some(language) = &self.language {
+            config.language = Some(language.clone());
+        }
+        for (name, path) in &self.targets {
+            config.targets.insert(name.clone(), path.clone());
+        }
+    }
+
+    /// The revision `id` is pinned to, if any.
+    pub fn pin_for(&self, id: &str) -> Option<&str> {
+        self.pins.iter()
+            .find(|(pinned, _)| !pinned.is_empty() && id.starts_with(pinned.as_str()))
+            .map(|(_, rev)| rev.as_str())
+    }
+}
+
+/// The snippet as it was at its pinned revision, or unchanged if this
+/// project doesn't pin it.
+pub fn pinned_version(snippet: &Snippet) -> Result<Snippet> {
+    let Some((path, project)) = ProjectConfig::find()? else {
+        return Ok(snippet.clone());
+    };
+    let Some(rev) = project.pin_for(&snippet.id) else {
+        return Ok(snippet.clone());
+    };
+
+    let repo_dir = publish::get_snippets_dir()?;
+    let pinned = snippet_at(&repo_dir, &snippet.id, rev)?.with_context(|| {
+        format!("'{}' doesn't exist at {} (pinned in {})", snippet.name, rev, path.display())
+    })?;
+    println!("📌 Using '{}' as of {} (pinned in {})", snippet.name, rev, PROJECT_CONFIG_FILE);
+    Ok(pinned)
+}
+
+/// Find the snippet with `id` in the repository's history at `rev`.
+fn snippet_at(repo_dir: &Path, id: &str, rev: &str) -> Result<Option<Snippet>> {
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["ls-tree", "--name-only", rev, "snippets/"])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("Unknown revision '{}' in {}", rev, repo_dir.display());
+    }
+
+    let short_id = crate::ids::short(id);
+    let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|file| file.ends_with(".md"))
+        .map(|file| file.to_string())
+        .collect();
+    // Filenames carry the short ID, so those are the likely candidates
+    files.sort_by_key(|file| !file.contains(short_id));
+
+    for file in files {
+        let output = Command::new("git")
+            .current_dir(repo_dir)
+            .args(["show", &format!("{}:{}", rev, file)])
+            .timed_output(Category::Git)?;
+        if let Ok(snippet) = publish::parse_markdown_frontmatter(&String::from_utf8_lossy(&output.stdout)) {
+            if snippet.id == id {
+                return Ok(Some(snippet));
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_config_overrides_global() {
+        let project: ProjectConfig = toml::from_str(r#"
+            default_repo = "team-snippets"
+            default_target = "agents"
+            required = ["5e6f7a8b"]
+
+            [targets]
+            rules = ".cursor/rules/team.md"
+
+            [pins]
+            1a2b3c4d = "v1.2"
+        "#).unwrap();
+
+        let mut config = Config { default_repo: Some("personal".to_string()), ..Config::default() };
+        project.apply(&mut config);
+        assert_eq!(config.get_default_repo(), Some("team-snippets"));
+        assert_eq!(config.get_target("rules"), Some(".cursor/rules/team.md"));
+        assert_eq!(project.pin_for("1a2b3c4d-5678"), Some("v1.2"));
+        assert_eq!(project.pin_for("ffffffff-0000"), None);
+        assert_eq!(project.required, ["5e6f7a8b"]);
+    }
+}
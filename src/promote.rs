@@ -0,0 +1,89 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use crate::audit;
+use crate::extract;
+use crate::manifest::Manifest;
+use crate::prompt;
+use crate::publish::{self, Snippet};
+use crate::store::FsStore;
+
+/// `promote`: publish a hand-written section of ./CLAUDE.md as a snippet and
+/// put it back between install markers, so the file keeps the same text but
+/// the section is now managed.
+pub async fn promote_section(query: &str) -> Result<()> {
+    let claude_md_path = Path::new("CLAUDE.md");
+    if !claude_md_path.is_file() {
+        anyhow::bail!("No CLAUDE.md in the current directory");
+    }
+    let content = fs::read_to_string(claude_md_path)?;
+
+    // Sections already between markers are managed, so only hand-written ones are candidates
+    let sections = audit::sections(&audit::strip_marked_blocks(&content));
+    let matched: Vec<&String> = sections.iter()
+        .filter(|section| extract::section_matches(section, query) && content.contains(section.as_str()))
+        .collect();
+
+    let section = match matched.as_slice() {
+        [] => {
+            println!("❌ No hand-written section of CLAUDE.md matches '{}'", query);
+            if !sections.is_empty() {
+                println!("💡 Sections found:");
+                for section in &sections {
+                    println!("  - {}", heading_of(section));
+                }
+            }
+            return Ok(());
+        }
+        [section] => *section,
+        several => {
+            println!("🤔 Several sections match '{}':", query);
+            let candidates: Vec<(String, String)> = several.iter()
+                .map(|s| (heading_of(s).to_string(), prompt::preview_lines(s.split_once('\n').map_or("", |(_, body)| body), 2)))
+                .collect();
+            match prompt::choose("Which one?", &candidates)? {
+                Some(i) => several[i],
+                None => {
+                    println!("❌ Promotion cancelled");
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    let name = heading_of(section).trim_start_matches('#').trim().to_string();
+    println!("📋 Section '{}' ({} lines):", name, section.lines().count());
+    println!("{}", prompt::preview_lines(section, 10));
+    if !prompt::confirm("Publish it as a snippet and manage it in CLAUDE.md?", true)? {
+        println!("❌ Promotion cancelled");
+        return Ok(());
+    }
+
+    let snippet = Snippet {
+        id: crate::ids::generate(),
+        name,
+        content: section.clone(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: Some("Promoted from a project CLAUDE.md".to_string()),
+        ..Default::default()
+    };
+    let snippet = crate::limits::check_size(&snippet)?;
+    let snippet = crate::attribution::fill(&snippet, &publish::get_default_repo_dir()?);
+    publish::save_snippet(&FsStore::default_repo()?, &snippet)?;
+    crate::index::refresh_index_quietly();
+
+    // Same text, now between markers and recorded like any other install
+    let short_id = crate::ids::short(&snippet.id);
+    let block = format!("<!-- SNIPPET_START:{} -->\n{}\n<!-- SNIPPET_END:{} -->", short_id, snippet.content.trim(), short_id);
+    fs::write(claude_md_path, content.replacen(section.as_str(), &block, 1))?;
+    let absolute_path = claude_md_path.canonicalize()?;
+    Manifest::record_install(short_id, &snippet.name, &absolute_path, snippet.content.trim())?;
+    println!("📝 {} now installs '{}' from the repository", absolute_path.display(), snippet.name);
+
+    crate::github::sync_after_change(&format!("Promote '{}' from a project CLAUDE.md", snippet.name), crate::github::ChangeKind::Publish).await;
+    Ok(())
+}
+
+fn heading_of(section: &str) -> &str {
+    section.lines().next().unwrap_or("").trim()
+}
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use std::io::{IsTerminal, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const INTERACTIVE: u8 = 0;
+const ASSUME_YES: u8 = 1;
+const NO_INPUT: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(INTERACTIVE);
+
+/// `--yes` answers every confirmation with yes; `--no-input` makes any
+/// question an error instead of waiting on stdin.
+pub fn set_mode(yes: bool, no_input: bool) {
+    let mode = if no_input { NO_INPUT } else if yes { ASSUME_YES } else { INTERACTIVE };
+    MODE.store(mode, Ordering::Relaxed);
+}
+
+/// Read one answer from stdin. Questions `--yes` can't answer (picking from a
+/// list, typing a name) are still asked on a terminal, and fail without one.
+fn read_answer(question: &str) -> Result<String> {
+    let reason = match MODE.load(Ordering::Relaxed) {
+        NO_INPUT => Some("running with --no-input"),
+        ASSUME_YES if !std::io::stdin().is_terminal() => Some("--yes only answers confirmations"),
+        _ => None,
+    };
+    if let Some(reason) = reason {
+        // End the half-printed question before the error goes to stderr
+        println!();
+        anyhow::bail!("Input needed: '{}' ({})", question, reason);
+    }
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+/// Ask a yes/no question about something the user asked for; Enter takes
+/// `default`. Always yes with `--yes`.
+pub fn confirm(question: &str, default: bool) -> Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    if MODE.load(Ordering::Relaxed) == ASSUME_YES {
+        println!("{} {}: y (--yes)", question, hint);
+        return Ok(true);
+    }
+    print!("{} {}: ", question, hint);
+    let answer = read_answer(question)?.to_lowercase();
+    Ok(match answer.as_str() {
+        "" => default,
+        answer => answer == "y" || answer == "yes",
+    })
+}
+
+/// Offer something optional the user didn't ask for. Declined without asking
+/// when running non-interactively.
+pub fn offer(question: &str) -> Result<bool> {
+    if MODE.load(Ordering::Relaxed) != INTERACTIVE {
+        return Ok(false);
+    }
+    confirm(question, false)
+}
+
+/// Ask for a line of text; Enter (or a non-interactive run) takes `default`.
+pub fn input(question: &str, default: Option<&str>) -> Result<String> {
+    let shown = match default {
+        Some(default) => format!("{} (default: {})", question, default),
+        None => question.to_string(),
+    };
+    if let (Some(default), true) = (default, MODE.load(Ordering::Relaxed) != INTERACTIVE) {
+        println!("{}: {}", shown, default);
+        return Ok(default.to_string());
+    }
+    print!("{}: ", shown);
+    let answer = read_answer(question)?;
+    Ok(if answer.is_empty() { default.unwrap_or("").to_string() } else { answer })
+}
+
+/// Ask the user to pick items from a numbered list of `count` entries.
+/// Accepts "1,3", "1 3" or "all"; an empty answer selects nothing.
+pub fn select(question: &str, count: usize) -> Result<Vec<usize>> {
+    print!("{} (e.g. 1,3 or 'all', Enter to skip): ", question);
+    parse_selection(&read_answer(question)?, count)
+}
+
+/// Ask the user to pick one of several candidates, shown numbered with a short
+/// preview under each. Returns `None` when they press Enter to cancel.
+pub fn choose(question: &str, candidates: &[(String, String)]) -> Result<Option<usize>> {
+    for (i, (label, preview)) in candidates.iter().enumerate() {
+        println!("  {}. {}", i + 1, label);
+        for line in preview.lines() {
+            println!("       {}", line);
+        }
+    }
+    loop {
+        print!("{} [1-{}, Enter to cancel]: ", question, candidates.len());
+        let input = read_answer(question)?;
+        if input.is_empty() {
+            return Ok(None);
+        }
+        match input.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= candidates.len() => return Ok(Some(number - 1)),
+            _ => println!("❌ Enter a number from 1 to {}", candidates.len()),
+        }
+    }
+}
+
+/// The first lines of `content` that say something, shortened for a chooser.
+pub fn preview_lines(content: &str, lines: usize) -> String {
+    content.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with("<!--"))
+        .take(lines)
+        .map(|line| match line.char_indices().nth(80) {
+            Some((cut, _)) => format!("{}…", &line[..cut]),
+            None => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parse a selection into zero-based indices, ignoring duplicates.
+pub fn parse_selection(input: &str, count: usize) -> Result<Vec<usize>> {
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+    if input.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+
+    let mut selected = Vec::new();
+    for part in input.split([',', ' ']).filter(|p| !p.is_empty()) {
+        let number: usize = part.parse().with_context(|| format!("Invalid selection '{}'", part))?;
+        if number == 0 || number > count {
+            anyhow::bail!("Selection {} is out of range (1-{})", number, count);
+        }
+        if !selected.contains(&(number - 1)) {
+            selected.push(number - 1);
+        }
+    }
+    Ok(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_selection() {
+        assert_eq!(parse_selection("", 3).unwrap(), Vec::<usize>::new());
+        assert_eq!(parse_selection("all", 3).unwrap(), vec![0, 1, 2]);
+        assert_eq!(parse_selection("3, 1 3", 3).unwrap(), vec![2, 0]);
+        assert!(parse_selection("4", 3).is_err());
+    }
+
+    #[test]
+    fn test_preview_lines() {
+        assert_eq!(preview_lines("\n# Title\n\n<!-- note -->\nBody\nMore", 2), "# Title\nBody");
+        assert_eq!(preview_lines(&"x".repeat(100), 1), format!("{}…", "x".repeat(80)));
+    }
+}
@@ -1,26 +1,67 @@
 use anyhow::Result;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
-use uuid::Uuid;
+use crate::template::TemplateVariable;
+use crate::visibility::Visibility;
+use crate::status::Status;
+use crate::store::{FsStore, SnippetStore};
 
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Snippet {
     pub id: String,
     pub name: String,
     pub content: String,
     pub created_at: String,
     pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<TemplateVariable>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Alternative versions by label, each a file relative to `snippets/`: translations keyed by
+    /// language code (`es` -> `variants/testing.es.md`) or phrasings to compare (`b`, `terse`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variants: BTreeMap<String, String>,
+    /// Who the snippet may be shared with; unrestricted when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+    /// Active, deprecated or archived; active snippets leave it out
+    #[serde(default, skip_serializing_if = "Status::is_active")]
+    pub status: Status,
+    /// ID of the snippet to use instead of this deprecated one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
+    /// Who wrote the snippet, from git or the GitHub login when published
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Web page of the repository the snippet was first published to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// SPDX identifier others may reuse the snippet under, e.g. MIT
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// IDs of snippets whose instructions contradict this one's; install warns
+    /// before putting both in the same file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts_with: Vec<String>,
 }
 
-pub async fn publish_snippet(content: Option<String>, custom_name: Option<String>, file: Option<String>, debug: bool) -> Result<()> {
+/// A snippet with just an ID, name and content, for tests to fill in the
+/// rest with struct update syntax.
+#[cfg(test)]
+pub fn test_snippet(id: &str, name: &str, content: &str) -> Snippet {
+    Snippet { id: id.to_string(), name: name.to_string(), content: content.to_string(), ..Default::default() }
+}
+
+pub async fn publish_snippet(content: Option<String>, custom_name: Option<String>, file: Option<String>, gist: bool, attachments: Vec<std::path::PathBuf>) -> Result<()> {
     // Determine content source and create snippet
     let snippet = if let Some(file_query) = file {
         // Load from extracted snippet file and preserve original metadata
-        load_snippet_from_local_file(&file_query, custom_name, debug)?
+        load_snippet_from_local_file(&file_query, custom_name)?
     } else if let Some(content_str) = content {
         // Create new snippet from content
-        let id = Uuid::new_v4().to_string();
+        let id = crate::ids::generate();
         let timestamp = chrono::Utc::now().to_rfc3339();
         let name = if let Some(name) = custom_name {
             name
@@ -33,44 +74,74 @@ pub async fn publish_snippet(content: Option<String>, custom_name: Option<String
             name,
             content: content_str,
             created_at: timestamp,
-            description: None,
+            ..Default::default()
         }
     } else {
         anyhow::bail!("Either content or --file must be provided");
     };
 
-    // Ensure directory structure exists (with snippets subdirectory)
-    let repo_dir = get_snippets_dir()?;
-    let snippets_dir = repo_dir.join("snippets");
-    fs::create_dir_all(&snippets_dir)?;
-    
-    let filename = format!("{}-{}.md", snippet.name.replace(' ', "-").to_lowercase(), &snippet.id[..8]);
-    let filepath = snippets_dir.join(filename);
+    if gist {
+        return crate::gist::publish_gist(&crate::limits::check_size(&snippet)?).await;
+    }
+    if !attachments.is_empty() {
+        // Copied first so the sync below commits them with the snippet
+        crate::assets::attach(&get_snippets_dir()?.join("snippets"), &snippet.id, &attachments)?;
+    }
+    save_and_sync_snippet(&snippet).await
+}
+
+/// Write `snippet` into the default repository, credited to whoever
+/// publishes it, and sync it to the remote.
+pub async fn save_and_sync_snippet(snippet: &Snippet) -> Result<()> {
+    let snippet = &crate::attribution::fill(&crate::limits::check_size(snippet)?, &get_default_repo_dir()?);
+    save_snippet(&FsStore::default_repo()?, snippet)?;
+    crate::index::refresh_index_quietly();
     
-    let markdown_content = create_markdown_with_frontmatter(&snippet)?;
-    fs::write(&filepath, markdown_content)?;
+    // Automatically sync with repository (queued when offline)
+    crate::github::sync_after_change(&format!("Publish snippet '{}'", snippet.name), crate::github::ChangeKind::Publish).await;
     
+    Ok(())
+}
+
+pub fn save_snippet(store: &dyn SnippetStore, snippet: &Snippet) -> Result<()> {
+    let location = store.save(snippet)?;
     println!("✅ Published snippet '{}' (ID: {})", snippet.name, snippet.id);
-    println!("📁 Saved to: {}", filepath.display());
+    println!("📁 Saved to: {}", location);
+    Ok(())
+}
+
+/// Read snippet content piped into the process, e.g. `sed -n 10,40p doc.md | ... publish --stdin`.
+pub fn read_stdin_content() -> Result<String> {
+    use std::io::{IsTerminal, Read};
     
-    // Automatically sync with repository
-    println!("🔄 Syncing with repository...");
-    match crate::github::sync_snippets().await {
-        Ok(()) => {
-            println!("✅ Successfully synced to repository!");
-        }
-        Err(e) => {
-            println!("⚠️  Sync failed: {}", e);
-            println!("💡 You can manually sync later with 'claude-md-snippets sync'");
-        }
+    let mut stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        anyhow::bail!("--stdin expects piped input, e.g. 'cat notes.md | claude-md-snippets publish --stdin'");
     }
     
-    Ok(())
+    let mut content = String::new();
+    stdin.read_to_string(&mut content)?;
+    non_empty_content(content, "stdin")
 }
 
-fn load_snippet_from_local_file(file_query: &str, custom_name: Option<String>, debug: bool) -> Result<Snippet> {
+/// Read snippet content from the system clipboard.
+pub fn read_clipboard_content() -> Result<String> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| anyhow::anyhow!("Could not access the clipboard: {}", e))?;
+    let content = clipboard.get_text()
+        .map_err(|e| anyhow::anyhow!("Could not read text from the clipboard: {}", e))?;
+    non_empty_content(content, "the clipboard")
+}
+
+fn non_empty_content(content: String, source: &str) -> Result<String> {
+    if content.trim().is_empty() {
+        anyhow::bail!("No content received from {}", source);
+    }
+    Ok(content)
+}
+
+fn load_snippet_from_local_file(file_query: &str, custom_name: Option<String>) -> Result<Snippet> {
     use std::path::Path;
-    use std::process::Command;
     
     // Look for snippet file in ./.claude.local/snippets/
     let local_snippets_dir = Path::new("./.claude.local/snippets");
@@ -107,16 +178,13 @@ fn load_snippet_from_local_file(file_query: &str, custom_name: Option<String>, d
             };
             
             return Ok(Snippet {
-                id: existing_snippet.id,
                 name: final_name,
-                content: existing_snippet.content,
-                created_at: existing_snippet.created_at,
-                description: existing_snippet.description,
+                ..existing_snippet
             });
         } else {
             // Fallback for files without frontmatter
             let name = get_name_from_file(file_path, &custom_name)?;
-            let id = Uuid::new_v4().to_string();
+            let id = crate::ids::generate();
             let timestamp = chrono::Utc::now().to_rfc3339();
             
             return Ok(Snippet {
@@ -124,14 +192,14 @@ fn load_snippet_from_local_file(file_query: &str, custom_name: Option<String>, d
                 name,
                 content,
                 created_at: timestamp,
-                description: None,
+                ..Default::default()
             });
         }
     }
     
     // Use Claude Code for intelligent matching
     println!("🤔 Using intelligent search to find matching snippet...");
-    let matched_file = find_file_with_claude_code(file_query, local_snippets_dir, debug)?;
+    let matched_file = find_file_with_claude_code(file_query, local_snippets_dir)?;
     
     let content = fs::read_to_string(&matched_file)?;
     
@@ -147,16 +215,13 @@ fn load_snippet_from_local_file(file_query: &str, custom_name: Option<String>, d
         };
         
         Ok(Snippet {
-            id: existing_snippet.id,
             name: final_name,
-            content: existing_snippet.content,
-            created_at: existing_snippet.created_at,
-            description: existing_snippet.description,
+            ..existing_snippet
         })
     } else {
         // Fallback: create new snippet if parsing fails
         let name = get_name_from_file(&matched_file, &custom_name)?;
-        let id = Uuid::new_v4().to_string();
+        let id = crate::ids::generate();
         let timestamp = chrono::Utc::now().to_rfc3339();
         
         Ok(Snippet {
@@ -164,14 +229,12 @@ fn load_snippet_from_local_file(file_query: &str, custom_name: Option<String>, d
             name,
             content,
             created_at: timestamp,
-            description: None,
+            ..Default::default()
         })
     }
 }
 
-fn find_file_with_claude_code(query: &str, snippets_dir: &Path, debug: bool) -> Result<std::path::PathBuf> {
-    use std::process::Command;
-    
+fn find_file_with_claude_code(query: &str, snippets_dir: &Path) -> Result<std::path::PathBuf> {
     // Get list of all files in the directory
     let mut file_list = String::new();
     for entry in fs::read_dir(snippets_dir)? {
@@ -208,32 +271,20 @@ fn find_file_with_claude_code(query: &str, snippets_dir: &Path, debug: bool) ->
         query, file_list
     );
     
-    if debug {
-        println!("🔧 Debug: Calling Claude Code CLI for file matching...");
-        println!("🔧 Debug: Command: claude --dangerously-skip-permissions --print <prompt>");
-        println!("🔧 Debug: Prompt length: {} characters", prompt.len());
-    }
-    
-    let output = Command::new("claude")
-        .arg("--dangerously-skip-permissions")
-        .arg("--print")
-        .arg(&prompt)
-        .output()?;
-    
-    if debug {
-        println!("🔧 Debug: Claude Code CLI returned with status: {}", output.status);
-        if !output.stderr.is_empty() {
-            println!("🔧 Debug: stderr: {}", String::from_utf8_lossy(&output.stderr));
-        }
-    }
-    
-    if !output.status.success() {
-        // Fallback to simple matching if Claude Code fails
-        println!("⚠️  Claude Code unavailable, falling back to simple matching");
+    if !crate::capabilities::has(crate::capabilities::Tool::Claude) {
+        crate::capabilities::note_unavailable(crate::capabilities::Tool::Claude, "AI file matching");
         return simple_fallback_match(query, snippets_dir);
     }
     
-    let suggested_filename = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let client = crate::claude_client::ClaudeClient::from_config()?;
+    let suggested_filename = match client.prompt(&prompt) {
+        Ok(response) => response,
+        Err(e) => {
+            // Fallback to simple matching if Claude Code fails
+            println!("⚠️  Claude Code unavailable ({}), falling back to simple matching", e);
+            return simple_fallback_match(query, snippets_dir);
+        }
+    };
     let suggested_path = snippets_dir.join(&suggested_filename);
     
     if suggested_path.exists() {
@@ -264,16 +315,26 @@ fn simple_fallback_match(query: &str, snippets_dir: &Path) -> Result<std::path::
     }
     
     if matches.len() > 1 {
-        println!("Multiple files found:");
-        for file in &matches {
-            println!("  - {}", file.display());
-        }
-        anyhow::bail!("Please be more specific with the file query.");
+        println!("🤔 Multiple files match '{}':", query);
+        return match crate::prompt::choose("Which one?", &file_candidates(&matches))? {
+            Some(i) => Ok(matches[i].clone()),
+            None => anyhow::bail!("No file selected"),
+        };
     }
     
     Ok(matches[0].clone())
 }
 
+/// Chooser entries for snippet files: the filename, previewed by its first lines.
+pub fn file_candidates(files: &[std::path::PathBuf]) -> Vec<(String, String)> {
+    files.iter().map(|file| {
+        let content = fs::read_to_string(file).unwrap_or_default();
+        let body = parse_markdown_frontmatter(&content).map(|s| s.content).unwrap_or(content);
+        let label = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        (label, crate::prompt::preview_lines(&body, 2))
+    }).collect()
+}
+
 fn get_name_from_file(file_path: &Path, custom_name: &Option<String>) -> Result<String> {
     let name = if let Some(custom) = custom_name {
         custom.clone()
@@ -294,7 +355,7 @@ fn get_name_from_file(file_path: &Path, custom_name: &Option<String>) -> Result<
     Ok(name)
 }
 
-fn generate_name_from_content(content: &str) -> String {
+pub fn generate_name_from_content(content: &str) -> String {
     // Extract first meaningful line or generate from keywords
     let lines: Vec<&str> = content.lines().collect();
     
@@ -323,53 +384,152 @@ fn generate_name_from_content(content: &str) -> String {
     format!("snippet-{}", chrono::Utc::now().format("%Y%m%d-%H%M"))
 }
 
-fn create_markdown_with_frontmatter(snippet: &Snippet) -> Result<String> {
-    // Create frontmatter
-    let frontmatter = format!(
-        "---\nid: {}\nname: {}\ncreated_at: {}\ndescription: {}\n---\n\n",
-        snippet.id,
-        snippet.name,
-        snippet.created_at,
-        snippet.description.as_deref().unwrap_or("null")
-    );
-    
-    // Combine frontmatter with content
-    let full_content = format!("{}{}", frontmatter, snippet.content);
-    
-    Ok(full_content)
+/// A snippet file's YAML frontmatter: every field of [`Snippet`] but its
+/// content, in the order files list them. Reading is lenient: unknown keys
+/// are ignored, numbers are read as text, and lists may also be written as
+/// comma-separated strings.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Frontmatter {
+    #[serde(deserialize_with = "text")]
+    id: String,
+    #[serde(deserialize_with = "text")]
+    name: String,
+    #[serde(deserialize_with = "text")]
+    created_at: String,
+    #[serde(deserialize_with = "optional_text")]
+    description: Option<String>,
+    #[serde(deserialize_with = "list", skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    #[serde(deserialize_with = "optional_text", skip_serializing_if = "Option::is_none")]
+    visibility: Option<String>,
+    #[serde(deserialize_with = "optional_text", skip_serializing_if = "Option::is_none")]
+    status: Option<String>,
+    #[serde(deserialize_with = "optional_text", skip_serializing_if = "Option::is_none")]
+    replaced_by: Option<String>,
+    #[serde(deserialize_with = "optional_text", skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(deserialize_with = "optional_text", skip_serializing_if = "Option::is_none")]
+    source_url: Option<String>,
+    #[serde(deserialize_with = "optional_text", skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+    #[serde(deserialize_with = "list", skip_serializing_if = "Vec::is_empty")]
+    conflicts_with: Vec<String>,
+    #[serde(deserialize_with = "or_default", skip_serializing_if = "Vec::is_empty")]
+    variables: Vec<TemplateVariable>,
+    #[serde(deserialize_with = "or_default", skip_serializing_if = "BTreeMap::is_empty")]
+    variants: BTreeMap<String, String>,
+}
+
+impl Frontmatter {
+    fn of(snippet: &Snippet) -> Self {
+        Frontmatter {
+            id: snippet.id.clone(),
+            name: snippet.name.clone(),
+            created_at: snippet.created_at.clone(),
+            description: snippet.description.clone(),
+            tags: snippet.tags.clone(),
+            visibility: snippet.visibility.map(|visibility| visibility.as_str().to_string()),
+            status: (!snippet.status.is_active()).then(|| snippet.status.as_str().to_string()),
+            replaced_by: snippet.replaced_by.clone(),
+            author: snippet.author.clone(),
+            source_url: snippet.source_url.clone(),
+            license: snippet.license.clone(),
+            conflicts_with: snippet.conflicts_with.clone(),
+            variables: snippet.variables.clone(),
+            variants: snippet.variants.clone(),
+        }
+    }
+
+    fn into_snippet(self, content: String) -> Snippet {
+        Snippet {
+            id: self.id,
+            name: self.name,
+            content,
+            created_at: self.created_at,
+            description: self.description,
+            variables: self.variables,
+            tags: self.tags.into_iter().map(|tag| tag.to_lowercase()).collect(),
+            variants: self.variants,
+            visibility: self.visibility.as_deref().map(Visibility::from_frontmatter),
+            status: self.status.as_deref().map(Status::from_frontmatter).unwrap_or_default(),
+            replaced_by: self.replaced_by,
+            author: self.author,
+            source_url: self.source_url,
+            license: self.license,
+            conflicts_with: self.conflicts_with,
+        }
+    }
+}
+
+pub fn create_markdown_with_frontmatter(snippet: &Snippet) -> Result<String> {
+    let frontmatter = serde_yaml::to_string(&Frontmatter::of(snippet))?;
+    Ok(format!("---\n{}---\n\n{}", frontmatter, snippet.content))
 }
 
 pub fn parse_markdown_frontmatter(content: &str) -> Result<Snippet> {
-    // Split frontmatter from content
-    let parts: Vec<&str> = content.splitn(3, "---").collect();
-    
-    if parts.len() < 3 {
+    let Some((frontmatter_yaml, markdown_content)) = split_frontmatter(content) else {
         anyhow::bail!("Invalid markdown format: missing frontmatter");
+    };
+    let frontmatter: Frontmatter = match frontmatter_yaml.trim() {
+        "" => Frontmatter::default(),
+        yaml => serde_yaml::from_str(yaml)?,
+    };
+    Ok(frontmatter.into_snippet(markdown_content.trim_start_matches(['\r', '\n']).to_string()))
+}
+
+/// The YAML between the file's opening `---` line and the next `---` line,
+/// and the markdown after it.
+fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    let rest = content.trim_start_matches('\u{feff}').strip_prefix("---")?;
+    let rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n'))?;
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return Some((&rest[..offset], &rest[offset + line.len()..]));
+        }
+        offset += line.len();
     }
-    
-    let frontmatter_yaml = parts[1].trim();
-    let markdown_content = parts[2].trim_start_matches('\n');
-    
-    // Parse YAML frontmatter
-    let frontmatter: serde_yaml::Value = serde_yaml::from_str(frontmatter_yaml)?;
-    
-    let snippet = Snippet {
-        id: frontmatter["id"].as_str().unwrap_or("").to_string(),
-        name: frontmatter["name"].as_str().unwrap_or("").to_string(),
-        created_at: frontmatter["created_at"].as_str().unwrap_or("").to_string(),
-        description: match frontmatter["description"].as_str() {
-            Some("null") | None => None,
-            Some(desc) => Some(desc.to_string()),
-        },
-        content: markdown_content.to_string(),
+    None
+}
+
+/// A scalar of any type as text; missing or null is empty.
+fn text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(optional_text(deserializer)?.unwrap_or_default())
+}
+
+fn optional_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::String(text) => Some(text),
+        serde_yaml::Value::Number(number) => Some(number.to_string()),
+        serde_yaml::Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    })
+}
+
+/// A YAML list (`[a, b]`) or a comma-separated string (`a, b`), trimmed.
+fn list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let items: Vec<String> = match serde_yaml::Value::deserialize(deserializer)? {
+        serde_yaml::Value::Sequence(items) => items.iter()
+            .filter_map(|item| item.as_str().map(|s| s.to_string()).or_else(|| item.as_i64().map(|n| n.to_string())))
+            .collect(),
+        serde_yaml::Value::String(list) => list.split(',').map(|s| s.to_string()).collect(),
+        _ => Vec::new(),
     };
-    
-    Ok(snippet)
+    Ok(items.into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect())
 }
 
+/// Null reads like a missing key.
+fn or_default<'de, D: Deserializer<'de>, T: Deserialize<'de> + Default>(deserializer: D) -> Result<T, D::Error> {
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// The data directory: repositories, install manifest, caches and logs.
 pub fn get_app_dir() -> Result<std::path::PathBuf> {
-    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
-    Ok(home.join(".claude-md-snippets"))
+    Ok(crate::app_dirs::AppDirs::current()?.data)
 }
 
 pub fn get_repos_dir() -> Result<std::path::PathBuf> {
@@ -377,6 +537,21 @@ pub fn get_repos_dir() -> Result<std::path::PathBuf> {
     Ok(app_dir.join("repos"))
 }
 
+/// Names of the local repositories, sorted.
+pub fn repo_names() -> Result<Vec<String>> {
+    let mut names: Vec<String> = match fs::read_dir(get_repos_dir()?) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .filter(|name| !name.starts_with('.'))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    names.sort();
+    Ok(names)
+}
+
 pub fn get_default_repo_dir() -> Result<std::path::PathBuf> {
     let repos_dir = get_repos_dir()?;
     let default_repo_name = crate::config::get_default_repo_name()?;
@@ -386,4 +561,31 @@ pub fn get_default_repo_dir() -> Result<std::path::PathBuf> {
 // Backward compatibility - use default repo
 pub fn get_snippets_dir() -> Result<std::path::PathBuf> {
     get_default_repo_dir()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frontmatter_round_trip() {
+        let mut snippet = parse_markdown_frontmatter("---\nid: 7Hq2mXbK\nname: x\n---\n\nBody\n").unwrap();
+        snippet.name = "Docker: \"compose\" --- it's v2".to_string();
+        snippet.description = Some("# not a comment: really".to_string());
+        snippet.tags = vec!["docker".to_string(), "ci: github".to_string()];
+        snippet.content = "Intro\n\n---\n\nAfter a rule\n".to_string();
+
+        let written = create_markdown_with_frontmatter(&snippet).unwrap();
+        let read = parse_markdown_frontmatter(&written).unwrap();
+        assert_eq!(read.name, snippet.name);
+        assert_eq!(read.description, snippet.description);
+        assert_eq!(read.tags, snippet.tags);
+        assert_eq!(read.content, snippet.content);
+        assert_eq!(create_markdown_with_frontmatter(&read).unwrap(), written);
+
+        // Hand-written files: comma-separated tags, numbers and null as text
+        let legacy = parse_markdown_frontmatter("---\nid: 12345678\nname: Tests\ndescription: null\ntags: Rust, CLI\n---\nRun them\n").unwrap();
+        assert_eq!((legacy.id.as_str(), legacy.description, legacy.tags), ("12345678", None, vec!["rust".to_string(), "cli".to_string()]));
+        assert!(parse_markdown_frontmatter("Title\n---\nid: x\n---\n").is_err());
+    }
+}
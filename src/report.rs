@@ -0,0 +1,266 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::config::Config;
+use crate::hosts::Remote;
+use crate::install::marked_blocks;
+use crate::journal::Journal;
+use crate::manifest::{InstallRecord, Manifest, content_hash};
+use crate::perf::{Category, TimedCommand};
+use crate::publish::{Snippet, get_repos_dir};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    #[value(alias = "md")]
+    Markdown,
+    Html,
+}
+
+/// How an installed snippet compares with its file and the repositories.
+#[derive(Debug, PartialEq)]
+enum InstallState {
+    UpToDate,
+    UpdateAvailable,
+    NotInRepository,
+    /// The manifest lists it, but its markers are gone from the file
+    MissingFromFile,
+}
+
+impl InstallState {
+    fn label(&self) -> &'static str {
+        match self {
+            InstallState::UpToDate => "up to date",
+            InstallState::UpdateAvailable => "update available",
+            InstallState::NotInRepository => "not in any repository",
+            InstallState::MissingFromFile => "missing from file",
+        }
+    }
+}
+
+/// `report`: everything the tool manages on this machine, as markdown or HTML
+/// for onboarding docs. Written to `output`, or printed.
+pub fn generate(format: ReportFormat, output: Option<PathBuf>) -> Result<()> {
+    let markdown = build_markdown()?;
+    let report = match format {
+        ReportFormat::Markdown => markdown,
+        ReportFormat::Html => to_html(&markdown),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(&path, report)?;
+            println!("✅ Report written to {}", path.display());
+        }
+        None => print!("{}", report),
+    }
+    Ok(())
+}
+
+fn build_markdown() -> Result<String> {
+    let config = Config::load()?;
+    let repos_dir = get_repos_dir()?;
+    let mut out = String::new();
+    out.push_str("# claude-md-snippets report\n\n");
+    out.push_str(&format!("Generated {} by claude-md-snippets-manager {}.\n\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M"), env!("CARGO_PKG_VERSION")));
+
+    // Repositories, with every snippet they hold for the install checks below
+    out.push_str("## Repositories\n\n");
+    let mut library: Vec<Snippet> = Vec::new();
+    let mut repos: Vec<PathBuf> = match fs::read_dir(&repos_dir) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()).collect(),
+        Err(_) => Vec::new(),
+    };
+    repos.sort();
+    if repos.is_empty() {
+        out.push_str("No repositories set up yet.\n\n");
+    } else {
+        out.push_str("| Repository | Snippets | Remote |\n|---|---|---|\n");
+        for repo in &repos {
+            let name = repo.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let snippets = crate::store::load_snippets_from(&repo.join("snippets")).unwrap_or_default();
+            let default = if config.get_default_repo() == Some(name.as_str()) { " (default)" } else { "" };
+            let remote = remote_link(repo).unwrap_or_else(|| "-".to_string());
+            out.push_str(&format!("| {}{} | {} | {} |\n", name, default, snippets.len(), remote));
+            library.extend(snippets);
+        }
+        out.push('\n');
+    }
+
+    // Installed snippets, per target file
+    out.push_str("## Installed snippets\n\n");
+    let manifest = Manifest::load()?;
+    let mut by_target: BTreeMap<&str, Vec<&InstallRecord>> = BTreeMap::new();
+    for record in &manifest.installs {
+        by_target.entry(&record.target).or_default().push(record);
+    }
+    if by_target.is_empty() {
+        out.push_str("Nothing installed yet.\n\n");
+    }
+    let mut updates = Vec::new();
+    for (target, records) in &by_target {
+        out.push_str(&format!("### `{}`\n\n", target));
+        let content = fs::read_to_string(target).ok();
+        if content.is_none() {
+            out.push_str("⚠️ This file no longer exists.\n\n");
+            continue;
+        }
+        let blocks = marked_blocks(content.as_deref().unwrap_or(""));
+        out.push_str("| Snippet | ID | Installed | Status |\n|---|---|---|---|\n");
+        for record in records {
+            let present = blocks.iter().any(|(id, _)| *id == record.id);
+            let state = install_state(record, present, &library);
+            if state == InstallState::UpdateAvailable {
+                updates.push(format!("{} in `{}`", record.name, target));
+            }
+            out.push_str(&format!("| {} | `{}` | {} | {} |\n",
+                record.name, record.id, record.installed_at.get(..10).unwrap_or(&record.installed_at), state.label()));
+        }
+        out.push('\n');
+    }
+
+    // Pending updates: newer repository versions and changes not pushed yet
+    out.push_str("## Pending updates\n\n");
+    let queued = Journal::load()?.pending;
+    if updates.is_empty() && queued.is_empty() {
+        out.push_str("Everything is up to date.\n");
+    }
+    for update in &updates {
+        out.push_str(&format!("- Newer version in the repository: {}\n", update));
+    }
+    for change in &queued {
+        out.push_str(&format!("- Not synced yet: [{}] {} ({})\n", change.repo, change.description, change.queued_at));
+    }
+
+    Ok(out)
+}
+
+fn install_state(record: &InstallRecord, present: bool, library: &[Snippet]) -> InstallState {
+    if !present {
+        return InstallState::MissingFromFile;
+    }
+    match library.iter().find(|snippet| snippet.id.starts_with(&record.id)) {
+        None => InstallState::NotInRepository,
+        Some(snippet) if content_hash(&snippet.content) == record.content_hash => InstallState::UpToDate,
+        Some(_) => InstallState::UpdateAvailable,
+    }
+}
+
+/// A markdown link to the repository's web page, when its remote is on a known host.
+fn remote_link(repo_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["remote", "get-url", "origin"])
+        .timed_output(Category::Git)
+        .ok()?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || url.is_empty() {
+        return None;
+    }
+    Some(match Remote::parse(&url) {
+        Some(remote) => format!("[{}]({})", remote.path, remote.web_url()),
+        None => url,
+    })
+}
+
+/// Convert report markdown (headings, tables, lists, paragraphs) into a
+/// standalone HTML page titled after its first heading.
+pub fn to_html(markdown: &str) -> String {
+    let title = markdown.lines().find_map(|line| line.strip_prefix("# ")).unwrap_or("claude-md-snippets report");
+    let mut body = String::new();
+    let mut in_table = false;
+    let mut in_list = false;
+
+    for line in markdown.lines() {
+        let is_row = line.starts_with('|');
+        if in_table && !is_row {
+            body.push_str("</table>\n");
+            in_table = false;
+        }
+        if in_list && !line.starts_with("- ") {
+            body.push_str("</ul>\n");
+            in_list = false;
+        }
+
+        if let Some(heading) = line.strip_prefix("### ") {
+            body.push_str(&format!("<h3>{}</h3>\n", inline_html(heading)));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            body.push_str(&format!("<h2>{}</h2>\n", inline_html(heading)));
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            body.push_str(&format!("<h1>{}</h1>\n", inline_html(heading)));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            if !in_list {
+                body.push_str("<ul>\n");
+                in_list = true;
+            }
+            body.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+        } else if is_row {
+            let cells: Vec<&str> = line.trim_matches('|').split('|').map(|c| c.trim()).collect();
+            if cells.iter().all(|c| c.chars().all(|ch| ch == '-')) {
+                continue;
+            }
+            let tag = if in_table { "td" } else { "th" };
+            if !in_table {
+                body.push_str("<table>\n");
+                in_table = true;
+            }
+            let row: String = cells.iter().map(|c| format!("<{tag}>{}</{tag}>", inline_html(c))).collect();
+            body.push_str(&format!("<tr>{}</tr>\n", row));
+        } else if !line.trim().is_empty() {
+            body.push_str(&format!("<p>{}</p>\n", inline_html(line)));
+        }
+    }
+    if in_table {
+        body.push_str("</table>\n");
+    }
+    if in_list {
+        body.push_str("</ul>\n");
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+        <style>body{{font-family:sans-serif;max-width:60em;margin:2em auto}}table{{border-collapse:collapse}}\
+        td,th{{border:1px solid #ccc;padding:.3em .6em;text-align:left}}code{{background:#f4f4f4}}</style>\n\
+        </head>\n<body>\n{}</body>\n</html>\n",
+        inline_html(title), body
+    )
+}
+
+/// Escape text and turn `code` spans and [text](url) links into HTML.
+fn inline_html(text: &str) -> String {
+    let escaped = text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let mut html = String::new();
+    let mut in_code = false;
+    for (i, part) in escaped.split('`').enumerate() {
+        if i > 0 {
+            html.push_str(if in_code { "</code>" } else { "<code>" });
+            in_code = !in_code;
+        }
+        html.push_str(part);
+    }
+
+    // Links are only ever whole cells: [text](url)
+    if let Some(inner) = html.strip_prefix('[').and_then(|rest| rest.strip_suffix(')')) {
+        if let Some((label, url)) = inner.split_once("](") {
+            return format!("<a href=\"{}\">{}</a>", url, label);
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_html() {
+        let html = to_html("# Report\n\n| Name | Remote |\n|---|---|\n| a <b> | [me/a](https://github.com/me/a) |\n\n- `x` updated\n");
+        assert!(html.contains("<h1>Report</h1>"));
+        assert!(html.contains("<tr><th>Name</th><th>Remote</th></tr>"));
+        assert!(html.contains("<tr><td>a &lt;b&gt;</td><td><a href=\"https://github.com/me/a\">me/a</a></td></tr>\n</table>"));
+        assert!(html.contains("<ul>\n<li><code>x</code> updated</li>\n</ul>"));
+    }
+}
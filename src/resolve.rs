@@ -0,0 +1,283 @@
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use crate::accounts::WithAccount;
+use crate::config::Account;
+use crate::diff::{self, Change};
+use crate::perf::Category;
+use crate::process::{ProcessRunner, RunWith};
+
+/// Part of a three-way merge of a snippet file.
+#[derive(Debug, PartialEq)]
+pub enum Chunk<'a> {
+    /// Lines both sides agree on, or that only one side changed
+    Resolved(Vec<&'a str>),
+    /// Lines both sides changed, differently
+    Conflict { base: Vec<&'a str>, local: Vec<&'a str>, remote: Vec<&'a str> },
+}
+
+/// Snippet files edited both here and on the fetched `origin/<branch>` since
+/// they last agreed. Any git failure just means nothing to resolve.
+pub fn overlapping_edits(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str) -> Vec<String> {
+    let remote = format!("origin/{}", branch);
+    let Some(base) = git_stdout(runner, repo_dir, &["merge-base", "HEAD", &remote]) else {
+        return Vec::new();
+    };
+    let changed = |args: &[&str]| git_stdout(runner, repo_dir, args)
+        .map(|out| out.lines().map(str::to_string).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let remote_changes = changed(&["diff", "--name-only", &base, &remote, "--", "snippets"]);
+    if remote_changes.is_empty() {
+        return Vec::new();
+    }
+    // Working tree against the base: committed but unpushed edits and uncommitted ones
+    let local_changes = changed(&["diff", "--name-only", &base, "--", "snippets"]);
+
+    local_changes.into_iter()
+        .filter(|path| remote_changes.contains(path))
+        .filter(|path| {
+            let theirs = git_stdout(runner, repo_dir, &["show", &format!("{}:{}", remote, path)]);
+            theirs.as_deref() != fs::read_to_string(repo_dir.join(path)).ok().as_deref()
+        })
+        .collect()
+}
+
+/// `sync` when `files` were edited on both sides: walk through each place
+/// the two versions disagree, let the user pick, then commit local work and
+/// merge the remote with those picks, so no conflict markers reach the files.
+pub fn merge_interactively(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str, account: Option<&Account>, files: &[String]) -> Result<()> {
+    let remote = format!("origin/{}", branch);
+    let base = git_stdout(runner, repo_dir, &["merge-base", "HEAD", &remote]).unwrap_or_default();
+    println!("🔀 {} snippet file(s) changed both here and on the remote", files.len());
+
+    let mut resolved = Vec::new();
+    for path in files {
+        let show = |rev: &str| git_stdout(runner, repo_dir, &["show", &format!("{}:{}", rev, path)]).unwrap_or_default();
+        let (base_text, remote_text) = (show(&base), show(&remote));
+        let local_text = fs::read_to_string(repo_dir.join(path)).unwrap_or_default();
+
+        let chunks = merge(&base_text, &local_text, &remote_text);
+        let conflicts = chunks.iter().filter(|chunk| matches!(chunk, Chunk::Conflict { .. })).count();
+        println!();
+        if conflicts == 0 {
+            println!("📄 {}: the edits don't overlap; merged automatically", path);
+        } else {
+            println!("📄 {}: {} conflicting change(s)", path, conflicts);
+        }
+        let mut choices = Vec::new();
+        for (number, chunk) in chunks.iter().filter(|chunk| matches!(chunk, Chunk::Conflict { .. })).enumerate() {
+            let Chunk::Conflict { base, local, remote } = chunk else { continue };
+            println!("── Change {} of {} — before either edit:", number + 1, conflicts);
+            for line in base {
+                println!("   {}", line);
+            }
+            println!("── Yours (left) and theirs (right):");
+            diff::print(&local.join("\n"), &remote.join("\n"), diff::Layout::SideBySide);
+            let both: Vec<&str> = local.iter().chain(remote).copied().collect();
+            let candidates = [
+                ("Keep yours".to_string(), local.join("\n")),
+                ("Take theirs (remote)".to_string(), remote.join("\n")),
+                ("Keep both, yours first".to_string(), both.join("\n")),
+            ];
+            let Some(choice) = crate::prompt::choose("Which version?", &candidates)? else {
+                anyhow::bail!("Sync stopped; nothing was pulled or committed");
+            };
+            choices.push(choice);
+        }
+        resolved.push((path.clone(), apply_choices(&chunks, &choices)));
+    }
+
+    // Commit local work, start the merge, then overwrite the files with the picks
+    git(runner, repo_dir, &["add", "-A"], account)?;
+    let message = crate::changeset::describe(runner, repo_dir);
+    let committed = git(runner, repo_dir, &["commit", "--quiet", "-m", &message], account)?;
+    // Exit status 1 with nothing staged just means the edits were already committed
+    if !committed.status.success() && !String::from_utf8_lossy(&committed.stdout).contains("nothing to commit") {
+        anyhow::bail!("git commit failed: {}", String::from_utf8_lossy(&committed.stderr).trim());
+    }
+    let merging = git(runner, repo_dir, &["merge", "--no-commit", "--no-ff", &remote], account)?;
+    if !repo_dir.join(".git").join("MERGE_HEAD").exists() {
+        anyhow::bail!("Could not start merging {}: {}", remote, String::from_utf8_lossy(&merging.stderr).trim());
+    }
+    finish_merge(runner, repo_dir, &resolved, account)?;
+    crate::index::refresh_index_quietly();
+    println!("✅ Merged remote changes with your picks");
+    Ok(())
+}
+
+/// Write the picked `resolved` files into the merge in progress and commit
+/// it. Any other path still conflicted (an asset, or a snippet the overlap
+/// check didn't catch) would be committed with conflict markers, so the
+/// merge is aborted instead.
+fn finish_merge(runner: &dyn ProcessRunner, repo_dir: &Path, resolved: &[(String, String)], account: Option<&Account>) -> Result<()> {
+    for (path, content) in resolved {
+        let file = repo_dir.join(path);
+        if content.trim().is_empty() {
+            // Deleted on one side and kept empty on the other
+            let _ = fs::remove_file(&file);
+        } else {
+            fs::write(&file, content)?;
+        }
+    }
+    let unresolved: Vec<String> = git_stdout(runner, repo_dir, &["diff", "--name-only", "--diff-filter=U"])
+        .unwrap_or_default()
+        .lines()
+        .filter(|path| !resolved.iter().any(|(resolved, _)| resolved == path))
+        .map(str::to_string)
+        .collect();
+    if !unresolved.is_empty() {
+        git(runner, repo_dir, &["merge", "--abort"], account)?;
+        anyhow::bail!("Sync stopped: {} also conflict and can't be merged here; resolve them with git in {}, then sync again",
+            unresolved.join(", "), repo_dir.display());
+    }
+    git(runner, repo_dir, &["add", "-A"], account)?;
+    let message = format!("Merge remote changes, resolving {} snippet file(s)", resolved.len());
+    let output = git(runner, repo_dir, &["commit", "--quiet", "-m", &message], account)?;
+    if !output.status.success() {
+        anyhow::bail!("git commit failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// Three-way merge of `local` and `remote`, both edited from `base`: changes
+/// only one side made are taken as they are, and changes both sides made
+/// differently are left as conflicts.
+pub fn merge<'a>(base: &'a str, local: &'a str, remote: &'a str) -> Vec<Chunk<'a>> {
+    let (base_lines, local_lines, remote_lines): (Vec<&str>, Vec<&str>, Vec<&str>) =
+        (base.lines().collect(), local.lines().collect(), remote.lines().collect());
+    let (to_local, to_remote) = (matches(base, local), matches(base, remote));
+
+    // Base lines both sides kept split the files into regions to compare
+    let mut anchors: Vec<(usize, usize, usize)> = (0..base_lines.len())
+        .filter_map(|i| Some((i, to_local[i]?, to_remote[i]?)))
+        .collect();
+    anchors.push((base_lines.len(), local_lines.len(), remote_lines.len()));
+
+    let mut chunks = Vec::new();
+    let (mut b, mut l, mut r) = (0, 0, 0);
+    for (next_b, next_l, next_r) in anchors {
+        let (base_part, local_part, remote_part) = (&base_lines[b..next_b], &local_lines[l..next_l], &remote_lines[r..next_r]);
+        if local_part == remote_part || remote_part == base_part {
+            push_resolved(&mut chunks, local_part);
+        } else if local_part == base_part {
+            push_resolved(&mut chunks, remote_part);
+        } else {
+            chunks.push(Chunk::Conflict { base: base_part.to_vec(), local: local_part.to_vec(), remote: remote_part.to_vec() });
+        }
+        if next_b < base_lines.len() {
+            push_resolved(&mut chunks, &base_lines[next_b..=next_b]);
+        }
+        (b, l, r) = (next_b + 1, next_l + 1, next_r + 1);
+    }
+    chunks
+}
+
+/// The merged file, taking choice `choices[n]` (0 yours, 1 theirs, 2 both)
+/// for the n-th conflict.
+fn apply_choices(chunks: &[Chunk], choices: &[usize]) -> String {
+    let mut choices = choices.iter();
+    let mut out: Vec<&str> = Vec::new();
+    for chunk in chunks {
+        match chunk {
+            Chunk::Resolved(lines) => out.extend(lines),
+            Chunk::Conflict { local, remote, .. } => match choices.next() {
+                Some(0) => out.extend(local),
+                Some(1) => out.extend(remote),
+                _ => out.extend(local.iter().chain(remote)),
+            },
+        }
+    }
+    if out.is_empty() { String::new() } else { format!("{}\n", out.join("\n")) }
+}
+
+fn push_resolved<'a>(chunks: &mut Vec<Chunk<'a>>, lines: &[&'a str]) {
+    if lines.is_empty() {
+        return;
+    }
+    match chunks.last_mut() {
+        Some(Chunk::Resolved(previous)) => previous.extend(lines),
+        _ => chunks.push(Chunk::Resolved(lines.to_vec())),
+    }
+}
+
+/// For each line of `from`, the line of `to` it was kept as, if any.
+fn matches(from: &str, to: &str) -> Vec<Option<usize>> {
+    let mut kept = vec![None; from.lines().count()];
+    let (mut i, mut j) = (0, 0);
+    for change in diff::lines(from, to) {
+        match change {
+            Change::Same(_) => {
+                kept[i] = Some(j);
+                i += 1;
+                j += 1;
+            }
+            Change::Removed(_) => i += 1,
+            Change::Added(_) => j += 1,
+        }
+    }
+    kept
+}
+
+fn git(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str], account: Option<&Account>) -> Result<Output> {
+    Ok(Command::new("git").current_dir(repo_dir).args(args).with_account(account).run_with(runner, Category::Git)?)
+}
+
+fn git_stdout(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").current_dir(repo_dir).args(args).run_with(runner, Category::Git).ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_three_way_merge() {
+        let base = "---\nname: Tests\n---\n\nRun tests\nUse cargo\n";
+        let local = "---\nname: Tests\n---\n\nRun all tests\nUse cargo\n";
+        let remote = "---\nname: Testing\n---\n\nRun unit tests\nUse cargo\n";
+        let chunks = merge(base, local, remote);
+        assert_eq!(chunks[0], Chunk::Resolved(vec!["---", "name: Testing", "---", ""]));
+        assert_eq!(chunks[1], Chunk::Conflict { base: vec!["Run tests"], local: vec!["Run all tests"], remote: vec!["Run unit tests"] });
+
+        assert_eq!(apply_choices(&chunks, &[1]), "---\nname: Testing\n---\n\nRun unit tests\nUse cargo\n");
+        assert_eq!(apply_choices(&chunks, &[2]), "---\nname: Testing\n---\n\nRun all tests\nRun unit tests\nUse cargo\n");
+    }
+
+    #[test]
+    fn test_unresolved_conflict_aborts_the_merge() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        let runner = &crate::process::SystemRunner;
+        let run = |args: &[&str]| git(runner, repo, args, None).unwrap();
+        run(&["init", "--quiet", "-b", "main"]);
+        run(&["config", "user.name", "Test"]);
+        run(&["config", "user.email", "test@example.com"]);
+        fs::create_dir_all(repo.join("snippets")).unwrap();
+        let write = |path: &str, text: &str| fs::write(repo.join(path), text).unwrap();
+        let commit = |message: &str| {
+            run(&["add", "-A"]);
+            run(&["commit", "--quiet", "-m", message]);
+        };
+        write("snippets/a.md", "base\n");
+        write("notes.txt", "base\n");
+        commit("base");
+        run(&["checkout", "--quiet", "-b", "theirs"]);
+        write("snippets/a.md", "theirs\n");
+        write("notes.txt", "theirs\n");
+        commit("theirs");
+        run(&["checkout", "--quiet", "main"]);
+        write("snippets/a.md", "ours\n");
+        write("notes.txt", "ours\n");
+        commit("ours");
+        run(&["merge", "--no-commit", "--no-ff", "theirs"]);
+
+        let resolved = [("snippets/a.md".to_string(), "picked\n".to_string())];
+        let error = finish_merge(runner, repo, &resolved, None).unwrap_err();
+        assert!(error.to_string().contains("notes.txt"));
+        assert!(!repo.join(".git/MERGE_HEAD").exists());
+        assert_eq!(fs::read_to_string(repo.join("notes.txt")).unwrap(), "ours\n");
+        assert_eq!(git_stdout(runner, repo, &["log", "-1", "--format=%s"]).unwrap(), "ours");
+    }
+}
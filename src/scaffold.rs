@@ -0,0 +1,191 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use crate::editor;
+use crate::publish::{self, Snippet};
+
+/// Starting structures offered by `new --template`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SnippetTemplate {
+    /// A short set of instructions
+    Basic,
+    /// How to run and write tests for a project
+    Testing,
+    /// Code style and conventions
+    Style,
+}
+
+impl SnippetTemplate {
+    fn default_name(self) -> &'static str {
+        match self {
+            SnippetTemplate::Basic => "New snippet",
+            SnippetTemplate::Testing => "Testing guidelines",
+            SnippetTemplate::Style => "Code style",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            SnippetTemplate::Basic => "What these instructions are for",
+            SnippetTemplate::Testing => "How to run and write tests",
+            SnippetTemplate::Style => "Code style and conventions",
+        }
+    }
+
+    fn body(self, name: &str) -> String {
+        let sections = match self {
+            SnippetTemplate::Basic => "\
+<!-- Describe when these instructions apply -->
+
+## Instructions
+
+-
+",
+            SnippetTemplate::Testing => "\
+<!-- Commands and conventions Claude should follow when touching tests -->
+
+## Running tests
+
+- Run the full suite with: ``
+- Run a single test with: ``
+
+## Writing tests
+
+-
+
+## Before committing
+
+-
+",
+            SnippetTemplate::Style => "\
+<!-- Conventions Claude should follow when writing code -->
+
+## Formatting
+
+-
+
+## Naming
+
+-
+
+## Patterns to avoid
+
+-
+",
+        };
+        format!("# {}\n\n{}", name, sections)
+    }
+}
+
+/// Open a pre-filled snippet skeleton in the editor and publish it once saved.
+pub async fn new_snippet(template: SnippetTemplate, name: Option<String>) -> Result<()> {
+    let name = name.unwrap_or_else(|| template.default_name().to_string());
+    let skeleton = Snippet {
+        id: crate::ids::generate(),
+        name: name.clone(),
+        content: template.body(&name),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: Some(template.description().to_string()),
+        ..Default::default()
+    };
+    edit_and_publish(skeleton).await
+}
+
+/// `publish --edit`: write the snippet in the editor, optionally starting from `content`.
+pub async fn publish_with_editor(content: Option<String>, name: Option<String>) -> Result<()> {
+    let name = name
+        .or_else(|| content.as_deref().map(publish::generate_name_from_content))
+        .unwrap_or_default();
+    let content = content.unwrap_or_else(|| "<!-- Write the snippet content below this line -->\n\n".to_string());
+    let skeleton = Snippet {
+        id: crate::ids::generate(),
+        name,
+        content,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: Some(String::new()),
+        ..Default::default()
+    };
+    edit_and_publish(skeleton).await
+}
+
+/// Open `skeleton` in the editor until it validates, then publish it. Leaving
+/// the file unchanged cancels.
+async fn edit_and_publish(skeleton: Snippet) -> Result<()> {
+    let mut initial = publish::create_markdown_with_frontmatter(&skeleton)?;
+    if skeleton.tags.is_empty() {
+        // Show the field so it's easy to fill in
+        initial = initial.replacen("\n---\n\n", "\ntags: []\n---\n\n", 1);
+    }
+
+    let mut text = initial.clone();
+    loop {
+        println!("📝 Opening {} in your editor...", editor::editor_command());
+        text = editor::edit_text(&text, "new-snippet")?;
+
+        if text.trim() == initial.trim() {
+            println!("❌ Snippet left unchanged, nothing published");
+            return Ok(());
+        }
+
+        match validate(&text, &skeleton) {
+            Ok(snippet) => return publish::save_and_sync_snippet(&snippet).await,
+            Err(e) => {
+                println!("❌ {}", e);
+                if !crate::prompt::confirm("Re-open the editor to fix it?", true)? {
+                    println!("❌ Nothing published");
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+fn validate(text: &str, skeleton: &Snippet) -> Result<Snippet> {
+    let parsed = publish::parse_markdown_frontmatter(text)
+        .map_err(|e| anyhow::anyhow!("Invalid frontmatter: {}", e))?;
+    if parsed.name.trim().is_empty() {
+        anyhow::bail!("The snippet needs a name in the frontmatter");
+    }
+    let content = strip_guidance(&parsed.content);
+    if content.trim().is_empty() {
+        anyhow::bail!("The snippet has no content");
+    }
+
+    // Keep the generated identity if the frontmatter fields were cleared
+    Ok(Snippet {
+        id: if parsed.id.is_empty() { skeleton.id.clone() } else { parsed.id },
+        created_at: if parsed.created_at.is_empty() { skeleton.created_at.clone() } else { parsed.created_at },
+        content,
+        ..parsed
+    })
+}
+
+/// Drop the template's `<!-- ... -->` guidance lines and any bullets left empty.
+fn strip_guidance(content: &str) -> String {
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !(trimmed.starts_with("<!--") && trimmed.ends_with("-->")) && trimmed != "-"
+        })
+        .collect();
+
+    let mut result = kept.join("\n");
+    while result.contains("\n\n\n") {
+        result = result.replace("\n\n\n", "\n\n");
+    }
+    format!("{}\n", result.trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_guidance() {
+        let body = SnippetTemplate::Basic.body("Demo");
+        assert_eq!(strip_guidance(&body), "# Demo\n\n## Instructions\n");
+
+        let filled = body.replace("-\n", "- Prefer small commits\n");
+        assert_eq!(strip_guidance(&filled), "# Demo\n\n## Instructions\n\n- Prefer small commits\n");
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use ignore::WalkBuilder;
+
+/// Per-repository ignore file for snippet folders, in `.gitignore` syntax.
+pub const IGNORE_FILE: &str = ".snippetsignore";
+
+/// Subfolders of `snippets/` holding attachments and translations rather than snippets.
+const RESERVED_DIRS: [&str; 2] = ["assets", "variants"];
+
+/// The snippet files under `snippets_dir`, sorted: markdown in any subfolder,
+/// skipping hidden files, attachments, translations and whatever the
+/// repository's `.gitignore`, `.ignore` or `.snippetsignore` exclude, so
+/// folders such as `drafts/` or `archive/` can stay out of results.
+///
+/// Symlinked files and folders are followed. A link back into a folder being
+/// walked is skipped with a warning, and a file reachable through several
+/// links is listed once.
+pub fn snippet_files(snippets_dir: &Path) -> Vec<PathBuf> {
+    if !snippets_dir.is_dir() {
+        return Vec::new();
+    }
+    let reserved: Vec<PathBuf> = RESERVED_DIRS.iter().map(|dir| snippets_dir.join(dir)).collect();
+
+    // Walk from the repository so its own ignore files apply, but not those
+    // of whatever directory (a dotfiles repo in $HOME, say) it lives in
+    let repo_dir = snippets_dir.parent().unwrap_or(snippets_dir).to_path_buf();
+    let root = snippets_dir.to_path_buf();
+    let mut walk = WalkBuilder::new(&repo_dir);
+    walk.parents(false)
+        .follow_links(true)
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .filter_entry(move |entry| {
+            entry.path() == repo_dir
+                || (entry.path().starts_with(&root) && !reserved.iter().any(|dir| entry.path() == dir))
+        });
+
+    let mut files: Vec<PathBuf> = walk
+        .build()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry.into_path()),
+            Err(err) => {
+                tracing::warn!("Skipping part of {}: {}", snippets_dir.display(), err);
+                None
+            }
+        })
+        .filter(|path| path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("md"))
+        .collect();
+    files.sort();
+
+    let mut seen = HashSet::new();
+    files.retain(|path| seen.insert(path.canonicalize().unwrap_or_else(|_| path.clone())));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_snippet_files() {
+        let home = tempfile::tempdir().unwrap();
+        // Ignore files above the repository don't apply
+        fs::write(home.path().join(".gitignore"), "*\n").unwrap();
+        let repo = home.path().join("repo");
+        let snippets_dir = repo.join("snippets");
+        for dir in ["drafts", "archive", "team", "assets/1234", "variants"] {
+            fs::create_dir_all(snippets_dir.join(dir)).unwrap();
+        }
+        for file in ["a.md", "notes.txt", ".hidden.md", "drafts/b.md", "archive/c.md", "team/d.md", "assets/1234/e.md", "variants/a.es.md"] {
+            fs::write(snippets_dir.join(file), "x").unwrap();
+        }
+        fs::write(repo.join(".gitignore"), "snippets/drafts/\n").unwrap();
+        fs::write(snippets_dir.join(IGNORE_FILE), "archive/\n").unwrap();
+
+        let found: Vec<PathBuf> = snippet_files(&snippets_dir)
+            .into_iter()
+            .map(|path| path.strip_prefix(&snippets_dir).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(found, vec![PathBuf::from("a.md"), PathBuf::from("team/d.md")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_folders() {
+        use std::os::unix::fs::symlink;
+
+        let repo = tempfile::tempdir().unwrap();
+        let shared = repo.path().join("shared");
+        let snippets_dir = repo.path().join("repo/snippets");
+        fs::create_dir_all(&shared).unwrap();
+        fs::create_dir_all(snippets_dir.join("go")).unwrap();
+        fs::write(shared.join("s.md"), "x").unwrap();
+        fs::write(snippets_dir.join("go/g.md"), "x").unwrap();
+        symlink(&shared, snippets_dir.join("shared")).unwrap();
+        symlink(&shared, snippets_dir.join("go/shared-again")).unwrap();
+        // A cycle back to the top must not be walked forever
+        symlink(&snippets_dir, snippets_dir.join("go/loop")).unwrap();
+
+        let found: Vec<PathBuf> = snippet_files(&snippets_dir)
+            .into_iter()
+            .map(|path| path.strip_prefix(&snippets_dir).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(found, vec![PathBuf::from("go/g.md"), PathBuf::from("go/shared-again/s.md")]);
+    }
+}
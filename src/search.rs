@@ -1,41 +1,85 @@
 use anyhow::Result;
-use std::fs;
 use std::process::{Command, Stdio};
 use std::io::Write;
-use crate::publish::{Snippet, get_snippets_dir};
+use crate::capabilities::{self, Tool};
+use crate::index::{SearchHit, SearchIndex};
+use crate::perf::{Category, timed};
+use crate::publish::Snippet;
+use crate::store::SnippetStore;
 
+/// Interactive search with fzf (or a numbered list without it) over the
+/// default and followed repositories; the picked snippets install together.
 pub async fn search_snippets() -> Result<()> {
-    // Load all available snippets
-    let snippets = load_snippets()?;
+    search_snippets_in(&crate::subscriptions::Followed::load()?).await
+}
+
+async fn search_snippets_in(store: &dyn SnippetStore) -> Result<()> {
+    let snippets = timed(Category::FsScan, "load snippets", || store.load_all())?;
     
     if snippets.is_empty() {
         println!("❌ No snippets found. Try publishing some first!");
         return Ok(());
     }
 
-    // Check if fzf is available
-    if !is_fzf_available() {
-        println!("❌ fzf is not installed. Please install it first:");
-        println!("   Ubuntu/Debian: sudo apt install fzf");
-        println!("   macOS: brew install fzf");
+    let chosen = match capabilities::has(Tool::Fzf) {
+        true => pick_with_fzf(&snippets)?,
+        false => {
+            capabilities::note_unavailable(Tool::Fzf, "Fuzzy search");
+            pick_from_list(&snippets)?
+        }
+    };
+    let Some(chosen) = chosen else {
+        println!("❌ Search cancelled");
+        return Ok(());
+    };
+    if chosen.is_empty() {
+        println!("❌ Nothing selected");
         return Ok(());
     }
 
-    // Create formatted list for fzf
-    let mut fzf_input = String::new();
-    for snippet in &snippets {
-        let preview = preview_content(&snippet.content, 50);
-        fzf_input.push_str(&format!("{}▪{}\n", snippet.name, preview.replace('\n', " │ ")));
+    for snippet in &chosen {
+        println!("\n📋 {} ({})", snippet.name, crate::ids::short(&snippet.id));
+        println!("{}", "─".repeat(50));
+        println!("{}", snippet.content.trim_end());
+        println!("{}", "─".repeat(50));
     }
+    println!();
+    let target = crate::install::resolve_target_path(false, false, None)?;
+    let question = match chosen.len() {
+        1 => format!("Install this snippet to {}?", target.display()),
+        n => format!("Install these {} snippets to {}?", n, target.display()),
+    };
+    if !crate::prompt::confirm(&question, true)? {
+        println!("❌ Installation cancelled");
+        return Ok(());
+    }
+    let chosen: Vec<Snippet> = chosen.into_iter().cloned().collect();
+    let installed = crate::install::install_batch(&chosen, &target, false, &Default::default()).await?;
+    if installed > 0 {
+        println!("✅ Installed {} of {} snippet(s)", installed, chosen.len());
+    }
+
+    Ok(())
+}
 
-    // Run fzf with preview
-    let mut fzf_cmd = Command::new("fzf")
-        .args(&[
+/// Let fzf pick snippets (Tab marks several); `None` when it was cancelled.
+fn pick_with_fzf(snippets: &[Snippet]) -> Result<Option<Vec<&Snippet>>> {
+    // The preview pane shows the whole snippet, as `show` prints it
+    let exe = std::env::current_exe()?;
+    let preview = format!("--preview={} show --id {{2}}", shell_quote(&exe.to_string_lossy()));
+    let mut fzf_cmd = Command::new("fzf");
+    // So the preview reads the same repositories under --data-dir
+    if let Some(dir) = crate::app_dirs::explicit_dir() {
+        fzf_cmd.env(crate::app_dirs::HOME_ENV, dir);
+    }
+    let mut fzf_cmd = fzf_cmd
+        .args([
+            "--multi",
             "--delimiter=▪",
             "--with-nth=1",
-            "--preview=echo {2}",
-            "--preview-window=down:3:wrap",
-            "--prompt=Select snippet: ",
+            &preview,
+            "--preview-window=right:60%:wrap",
+            "--prompt=Select snippets (Tab for several): ",
             "--height=50%",
             "--border",
         ])
@@ -43,94 +87,119 @@ pub async fn search_snippets() -> Result<()> {
         .stdout(Stdio::piped())
         .spawn()?;
 
-    // Write input to fzf
     if let Some(mut stdin) = fzf_cmd.stdin.take() {
-        stdin.write_all(fzf_input.as_bytes())?;
+        stdin.write_all(fzf_input(snippets).as_bytes())?;
     }
-
-    // Get the result
     let output = fzf_cmd.wait_with_output()?;
-
-    if output.status.success() {
-        let selection = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        
-        if !selection.is_empty() {
-            // Extract the snippet name (before ▪)
-            let snippet_name = selection.split('▪').next().unwrap_or("").trim();
-            
-            // Find the corresponding snippet
-            if let Some(snippet) = snippets.iter().find(|s| s.name == snippet_name) {
-                println!("\n📋 Selected snippet: {}", snippet.name);
-                println!("🔍 Full content:");
-                println!("{}", "─".repeat(50));
-                println!("{}", snippet.content);
-                println!("{}", "─".repeat(50));
-                
-                // Ask if user wants to install it
-                print!("\nInstall this snippet to CLAUDE.md? [Y/n]: ");
-                std::io::stdout().flush()?;
-                
-                let mut input = String::new();
-                std::io::stdin().read_line(&mut input)?;
-                let input = input.trim().to_lowercase();
-                
-                if input.is_empty() || input == "y" || input == "yes" {
-                    crate::install::install_to_claude_md(snippet, false, false).await?;
-                    println!("✅ Snippet installed successfully!");
-                } else {
-                    println!("❌ Installation cancelled");
-                }
-            }
-        }
-    } else {
-        println!("❌ Search cancelled");
+    if !output.status.success() {
+        return Ok(None);
     }
-
-    Ok(())
+    let selection = String::from_utf8_lossy(&output.stdout);
+    Ok(Some(selection.lines().filter_map(|line| selected(snippets, line)).collect()))
 }
 
-fn is_fzf_available() -> bool {
-    Command::new("fzf")
-        .arg("--version")
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+/// Without fzf: a numbered list to pick from.
+fn pick_from_list(snippets: &[Snippet]) -> Result<Option<Vec<&Snippet>>> {
+    for (i, snippet) in snippets.iter().enumerate() {
+        println!("  {}. {} ({})", i + 1, snippet.name, crate::ids::short(&snippet.id));
+    }
+    let chosen = crate::prompt::select("Install which snippets?", snippets.len())?;
+    Ok(Some(chosen.into_iter().map(|i| &snippets[i]).collect()))
 }
 
-fn load_snippets() -> Result<Vec<Snippet>> {
-    let snippets_dir = get_snippets_dir()?;
-    
-    if !snippets_dir.exists() {
-        return Ok(Vec::new());
+/// Non-interactive search: rank snippets with the local index, or ask Claude with `--ai`.
+pub async fn search_snippets_by_query(query: String, limit: usize, use_ai: bool) -> Result<()> {
+    if use_ai {
+        let snippets = timed(Category::FsScan, "load snippets", crate::store::load_snippets)?;
+        println!("🤔 Asking Claude Code for the best match for '{}'...", query);
+        match crate::install::find_best_match(&snippets, &query).await? {
+            Some(snippet) => println!("  📄 {} ({})", snippet.name, crate::ids::short(&snippet.id)),
+            None => println!("❌ No snippet matched '{}'", query),
+        }
+        return Ok(());
     }
     
-    let mut snippets = Vec::new();
+    let index = timed(Category::FsScan, "load search index", crate::index::load_index)?;
+    let hits = index.search(&query);
     
-    for entry in fs::read_dir(snippets_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        
-        if path.extension().and_then(|s| s.to_str()) == Some("json") {
-            if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(snippet) = serde_json::from_str::<Snippet>(&content) {
-                    snippets.push(snippet);
-                }
-            }
-        }
+    // Followed repositories aren't indexed on disk; they are searched as they are
+    let default_repo = crate::config::get_default_repo_name()?;
+    let repos_dir = crate::publish::get_repos_dir()?;
+    let subscribed: Vec<(String, SearchIndex)> = crate::config::Config::load()?.sources.into_keys()
+        .filter(|name| *name != default_repo)
+        .filter_map(|name| SearchIndex::build(&repos_dir.join(&name).join("snippets")).ok().map(|index| (name, index)))
+        .collect();
+    let subscribed_hits: Vec<(&str, Vec<SearchHit>)> = subscribed.iter()
+        .map(|(name, index)| (name.as_str(), index.search(&query)))
+        .filter(|(_, hits)| !hits.is_empty())
+        .collect();
+    
+    if hits.is_empty() && subscribed_hits.is_empty() {
+        println!("❌ No snippets matched '{}'", query);
+        println!("💡 Try different keywords, or use --ai to let Claude Code pick a match");
+        return Ok(());
     }
     
-    // Sort by creation date (newest first)
-    snippets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    if !hits.is_empty() {
+        println!("🔍 Results for '{}':", query);
+        print_hits(&hits, limit);
+    }
+    for (name, hits) in subscribed_hits {
+        println!("📚 Results from subscribed '{}':", name);
+        print_hits(&hits, limit);
+    }
     
-    Ok(snippets)
+    Ok(())
+}
+
+fn print_hits(hits: &[SearchHit], limit: usize) {
+    for (rank, hit) in hits.iter().take(limit).enumerate() {
+        let short_id = crate::ids::short(&hit.snippet.id);
+        println!("  {}. {} ({})  score {:.3}", rank + 1, hit.snippet.name, short_id, hit.score);
+        println!("      File: {}", hit.snippet.file);
+    }
+}
+
+/// One fzf line per snippet: "name (short ID)", then the ID the preview shows.
+fn fzf_input(snippets: &[Snippet]) -> String {
+    snippets.iter()
+        .map(|snippet| format!("{} ({})▪{}\n", snippet.name, crate::ids::short(&snippet.id), snippet.id))
+        .collect()
+}
+
+/// The snippet an fzf line picked, by the ID in it, since names can repeat.
+fn selected<'a>(snippets: &'a [Snippet], line: &str) -> Option<&'a Snippet> {
+    let id = line.split('▪').nth(1)?.trim();
+    snippets.iter().find(|snippet| snippet.id == id)
 }
 
-fn preview_content(content: &str, max_chars: usize) -> String {
-    let content = content.replace('\n', " ");
-    if content.len() > max_chars {
-        format!("{}...", &content[..max_chars])
-    } else {
-        content
+/// `text` as one word for the shell fzf runs its preview command with.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::FsStore;
+
+    #[test]
+    fn test_fzf_lines_from_markdown_snippets() {
+        let dir = tempfile::tempdir().unwrap();
+        let snippets_dir = dir.path().join("snippets");
+        std::fs::create_dir_all(snippets_dir.join("rust")).unwrap();
+        // Flat and filed in a category folder; old JSON snippets are not read
+        std::fs::write(snippets_dir.join("testing-1a2b3c4d.md"), "---\nid: 1a2b3c4d\nname: Testing\ncreated_at: '2024-01-01T00:00:00Z'\n---\n\nRun the tests\n").unwrap();
+        std::fs::write(snippets_dir.join("rust").join("clippy-5e6f7a8b.md"), "---\nid: 5e6f7a8b\nname: Clippy\ncreated_at: '2024-06-01T00:00:00Z'\n---\n\nDeny warnings — always\n").unwrap();
+        std::fs::write(snippets_dir.join("legacy.json"), r#"{"id":"9f8e7d6c","name":"Legacy","content":"x","created_at":"2023"}"#).unwrap();
+
+        let snippets = FsStore::new(snippets_dir).load_all().unwrap();
+        let input = fzf_input(&snippets);
+        assert_eq!(input, "Clippy (5e6f7a8b)▪5e6f7a8b\nTesting (1a2b3c4d)▪1a2b3c4d\n");
+
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!(selected(&snippets, line).map(|snippet| snippet.name.as_str()), Some("Testing"));
+        assert_eq!(shell_quote("/opt/it's/bin"), "'/opt/it'\\''s/bin'");
     }
 }
 
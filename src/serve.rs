@@ -0,0 +1,274 @@
+use anyhow::{Context, Result};
+use axum::extract::{Path as UrlPath, Query, Request, State};
+use axum::http::{StatusCode, header};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use crate::publish::{Snippet, get_repos_dir, repo_names};
+use crate::store::FsStore;
+
+/// Environment variable holding the API token, so it stays out of `ps` output.
+pub const TOKEN_ENV: &str = "CLAUDE_MD_SNIPPETS_TOKEN";
+const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+struct ServerState {
+    token: String,
+    /// Publishing switches the process-wide default repository, so one at a time
+    publishing: tokio::sync::Mutex<()>,
+}
+
+/// A snippet in listings and search results, without its content.
+#[derive(Serialize)]
+struct SnippetSummary {
+    repo: String,
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    tags: Vec<String>,
+    created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct SnippetDetail {
+    repo: String,
+    #[serde(flatten)]
+    snippet: Snippet,
+}
+
+#[derive(Deserialize)]
+struct ListQuery {
+    repo: Option<String>,
+    tag: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchQuery {
+    q: String,
+    repo: Option<String>,
+    limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct RepoQuery {
+    repo: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PublishRequest {
+    content: String,
+    name: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    repo: Option<String>,
+}
+
+/// An error answered as `{"error": "..."}` with its status code.
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(error: anyhow::Error) -> Self {
+        ApiError(StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+    }
+}
+
+type ApiResult<T> = std::result::Result<Json<T>, ApiError>;
+
+/// `serve`: answer REST requests over the local repositories, so a team can
+/// run a small internal snippet service on the same files and git history.
+/// Every request needs `Authorization: Bearer <token>`; without a token one is
+/// generated and printed.
+pub async fn serve(port: u16, bind: &str, token: Option<String>) -> Result<()> {
+    let token = match token.or_else(|| std::env::var(TOKEN_ENV).ok()).filter(|token| !token.is_empty()) {
+        Some(token) => token,
+        None => {
+            let token = Uuid::new_v4().simple().to_string();
+            println!("🔑 No token given; generated one for this run: {}", token);
+            println!("💡 Set {} (or pass --token) to keep it across restarts", TOKEN_ENV);
+            token
+        }
+    };
+    // Nobody answers prompts on a server: a question (e.g. during a sync) fails the request instead of hanging it
+    crate::prompt::set_mode(false, true);
+    let state = Arc::new(ServerState { token, publishing: tokio::sync::Mutex::new(()) });
+
+    let app = Router::new()
+        .route("/repos", get(list_repos))
+        .route("/snippets", get(list_snippets).post(publish))
+        .route("/snippets/search", get(search))
+        .route("/snippets/{id}", get(get_snippet))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .with_state(state);
+
+    let address = format!("{}:{}", bind, port);
+    let listener = tokio::net::TcpListener::bind(&address).await
+        .with_context(|| format!("Could not listen on {}", address))?;
+    println!("🌐 Serving snippets on http://{} (Ctrl-C to stop)", address);
+    println!("   GET  /repos");
+    println!("   GET  /snippets[?repo=&tag=]");
+    println!("   GET  /snippets/search?q=<query>[&repo=&limit=]");
+    println!("   GET  /snippets/<id>[?repo=]");
+    println!("   POST /snippets  {{\"content\", \"name\", \"description\", \"tags\", \"repo\"}}");
+    axum::serve(listener, app).await.context("The server stopped unexpectedly")?;
+    Ok(())
+}
+
+async fn authorize(State(state): State<Arc<ServerState>>, request: Request, next: Next) -> Response {
+    let given = request.headers().get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if !given.is_some_and(|given| tokens_match(given, &state.token)) {
+        tracing::info!("Rejected {} {}: missing or wrong token", request.method(), request.uri().path());
+        return ApiError(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token".to_string()).into_response();
+    }
+    tracing::info!("{} {}", request.method(), request.uri());
+    next.run(request).await
+}
+
+/// Compare without returning early, so timing doesn't reveal the token.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn list_repos() -> ApiResult<Vec<String>> {
+    Ok(Json(repo_names()?))
+}
+
+async fn list_snippets(Query(query): Query<ListQuery>) -> ApiResult<Vec<SnippetSummary>> {
+    let summaries = load(query.repo.as_deref())?
+        .into_iter()
+        .flat_map(|(repo, snippets)| snippets.into_iter().map(move |snippet| (repo.clone(), snippet)))
+        .filter(|(_, snippet)| query.tag.as_ref().is_none_or(|tag| snippet.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))))
+        .map(|(repo, snippet)| summary(repo, &snippet, None))
+        .collect();
+    Ok(Json(summaries))
+}
+
+async fn search(Query(query): Query<SearchQuery>) -> ApiResult<Vec<SnippetSummary>> {
+    let mut results: Vec<SnippetSummary> = Vec::new();
+    for (repo, snippets) in load(query.repo.as_deref())? {
+        for (score, snippet) in crate::install::rank_matches(&snippets, &query.q) {
+            results.push(summary(repo.clone(), snippet, Some(score)));
+        }
+    }
+    results.sort_by_key(|result| std::cmp::Reverse(result.score));
+    results.truncate(query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT));
+    Ok(Json(results))
+}
+
+/// One snippet by ID, or by an ID prefix of at least four characters.
+async fn get_snippet(UrlPath(id): UrlPath<String>, Query(query): Query<RepoQuery>) -> ApiResult<SnippetDetail> {
+    let mut found: Vec<SnippetDetail> = Vec::new();
+    for (repo, snippets) in load(query.repo.as_deref())? {
+        for snippet in snippets {
+            if snippet.id == id || (id.len() >= 4 && snippet.id.starts_with(&id)) {
+                found.push(SnippetDetail { repo: repo.clone(), snippet });
+            }
+        }
+    }
+    match found.len() {
+        0 => Err(ApiError(StatusCode::NOT_FOUND, format!("No snippet with ID '{}'", id))),
+        1 => Ok(Json(found.remove(0))),
+        n => Err(ApiError(StatusCode::CONFLICT, format!("'{}' matches {} snippets; use a longer ID", id, n))),
+    }
+}
+
+/// Save a new snippet into a repository (the default one unless `repo` is
+/// given) and sync it, just like `publish`.
+async fn publish(State(state): State<Arc<ServerState>>, Json(request): Json<PublishRequest>) -> std::result::Result<(StatusCode, Json<SnippetDetail>), ApiError> {
+    if request.content.trim().is_empty() {
+        return Err(ApiError(StatusCode::BAD_REQUEST, "'content' must not be empty".to_string()));
+    }
+    let repo = match request.repo {
+        Some(repo) => repo,
+        None => crate::config::get_default_repo_name()?,
+    };
+    let repo_dir = existing_repo_dir(&repo)?;
+
+    let snippet = Snippet {
+        id: crate::ids::generate(),
+        name: request.name.unwrap_or_else(|| crate::publish::generate_name_from_content(&request.content)),
+        content: request.content,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: request.description,
+        tags: request.tags,
+        ..Default::default()
+    };
+    // Nobody is at the terminal to offer a summary to, so oversized snippets are refused
+    let over = crate::limits::exceeded(&snippet.content, &crate::config::Config::load()?.limits);
+    if !over.is_empty() {
+        return Err(ApiError(StatusCode::PAYLOAD_TOO_LARGE, format!("Snippet is too large: {}", over.join(", "))));
+    }
+
+    let _publishing = state.publishing.lock().await;
+    let snippet = crate::attribution::fill(&snippet, &repo_dir);
+    crate::publish::save_snippet(&FsStore::new(repo_dir.join("snippets")), &snippet)?;
+    crate::config::override_default_repo(&repo);
+    crate::index::refresh_index_quietly();
+    crate::github::sync_after_change(&format!("Publish snippet '{}'", snippet.name), crate::github::ChangeKind::Publish).await;
+    Ok((StatusCode::CREATED, Json(SnippetDetail { repo, snippet })))
+}
+
+fn summary(repo: String, snippet: &Snippet, score: Option<i64>) -> SnippetSummary {
+    SnippetSummary {
+        repo,
+        id: snippet.id.clone(),
+        name: snippet.name.clone(),
+        description: snippet.description.clone(),
+        tags: snippet.tags.clone(),
+        created_at: snippet.created_at.clone(),
+        score,
+    }
+}
+
+/// Snippets of `repo`, or of every repository, by repository name.
+fn load(repo: Option<&str>) -> std::result::Result<Vec<(String, Vec<Snippet>)>, ApiError> {
+    let repos = match repo {
+        Some(repo) => {
+            existing_repo_dir(repo)?;
+            vec![repo.to_string()]
+        }
+        None => repo_names()?,
+    };
+    let mut loaded = Vec::new();
+    for repo in repos {
+        let snippets = crate::store::load_snippets_from(&get_repos_dir()?.join(&repo).join("snippets"))?;
+        loaded.push((repo, snippets));
+    }
+    Ok(loaded)
+}
+
+fn existing_repo_dir(repo: &str) -> std::result::Result<std::path::PathBuf, ApiError> {
+    let dir = get_repos_dir()?.join(repo);
+    // Only plain names, so a request can't reach outside the repositories folder
+    if repo.is_empty() || repo.contains(['/', '\\']) || repo.starts_with('.') || !dir.is_dir() {
+        return Err(ApiError(StatusCode::NOT_FOUND, format!("No repository named '{}'", repo)));
+    }
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("s3cret-token", "s3cret-token"));
+        assert!(!tokens_match("s3cret-tokex", "s3cret-token"));
+        assert!(!tokens_match("s3cret", "s3cret-token"));
+        assert!(!tokens_match("", "s3cret-token"));
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::Result;
+use std::io::IsTerminal;
+use crate::publish::{self, Snippet};
+use crate::store::SnippetStore;
+
+/// `show`: print one snippet of the default repository, its metadata first.
+/// The content is rendered as formatted markdown on a terminal; `raw` prints
+/// the snippet file as stored instead, for piping.
+pub async fn show_snippet(query: &str, raw: bool) -> Result<()> {
+    let snippets = crate::store::load_snippets()?;
+    if snippets.is_empty() {
+        anyhow::bail!("No snippets found. Try publishing some first!");
+    }
+
+    // An ID prefix picks the snippet directly (see find_best_match)
+    let Some(snippet) = crate::install::find_best_match(&snippets, query).await? else {
+        anyhow::bail!("No snippet found for '{}'", query);
+    };
+
+    print(&snippet, &snippets, raw)
+}
+
+/// `show --id`: the snippet with this ID (or unique ID prefix) in the default
+/// or a followed repository, without any fuzzy or Claude matching. Search
+/// uses it for its fzf preview.
+pub fn show_by_id(id: &str, raw: bool) -> Result<()> {
+    let snippets = crate::subscriptions::Followed::load()?.load_all()?;
+    match crate::ids::find_by_prefix(&snippets, id)? {
+        Some(snippet) => print(snippet, &snippets, raw),
+        None => anyhow::bail!("No snippet with ID '{}'", id),
+    }
+}
+
+fn print(snippet: &Snippet, snippets: &[Snippet], raw: bool) -> Result<()> {
+    if raw {
+        print!("{}", publish::create_markdown_with_frontmatter(snippet)?);
+        return Ok(());
+    }
+
+    let short_ids = crate::ids::ShortIds::new(snippets.iter().map(|snippet| snippet.id.as_str()));
+    print_metadata(snippet, short_ids.get(&snippet.id));
+    println!("{}", "─".repeat(50));
+    if std::io::stdout().is_terminal() {
+        termimad::MadSkin::default().print_text(&snippet.content);
+    } else {
+        println!("{}", snippet.content.trim_end());
+    }
+    Ok(())
+}
+
+fn print_metadata(snippet: &Snippet, short_id: &str) {
+    println!("📄 {}", snippet.name);
+    if short_id == snippet.id {
+        println!("   🆔 {}", snippet.id);
+    } else {
+        println!("   🆔 {} ({})", short_id, snippet.id);
+    }
+    println!("   📅 {}", snippet.created_at.get(..10).unwrap_or(&snippet.created_at));
+    if let Some(description) = &snippet.description {
+        println!("   📝 {}", description);
+    }
+    if !snippet.tags.is_empty() {
+        println!("   🏷️  {}", snippet.tags.join(", "));
+    }
+    if let Some(author) = &snippet.author {
+        println!("   👤 {}", author);
+    }
+    if let Some(source_url) = &snippet.source_url {
+        println!("   🔗 {}", source_url);
+    }
+    if let Some(license) = &snippet.license {
+        println!("   ⚖️  {}", license);
+    }
+    if let Some(visibility) = snippet.visibility {
+        println!("   🔒 {}", visibility.as_str());
+    }
+    if !snippet.status.is_active() {
+        match &snippet.replaced_by {
+            Some(id) => println!("   ⚠️  {}, replaced by {}", snippet.status.as_str(), crate::ids::short(id)),
+            None => println!("   ⚠️  {}", snippet.status.as_str()),
+        }
+    }
+    if !snippet.conflicts_with.is_empty() {
+        let ids: Vec<&str> = snippet.conflicts_with.iter().map(|id| crate::ids::short(id)).collect();
+        println!("   ⚔️  Conflicts with: {}", ids.join(", "));
+    }
+    if !snippet.variables.is_empty() {
+        let names: Vec<&str> = snippet.variables.iter().map(|variable| variable.name.as_str()).collect();
+        println!("   🔧 Variables: {}", names.join(", "));
+    }
+    if !snippet.variants.is_empty() {
+        let languages: Vec<&str> = snippet.variants.keys().map(|language| language.as_str()).collect();
+        println!("   🌐 Translations: {}", languages.join(", "));
+    }
+    let tokens = crate::limits::estimate_tokens(&snippet.content);
+    println!("   📏 {} bytes, ~{} tokens", snippet.content.len(), tokens);
+}
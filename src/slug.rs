@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+
+// Long enough to stay recognisable, short enough for Windows path limits
+const MAX_SLUG_LEN: usize = 60;
+const WINDOWS_RESERVED: &[&str] = &[
+    "con", "prn", "aux", "nul",
+    "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9",
+    "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// A filename stem for `name` that is safe on every OS: transliterated to
+/// ASCII ("Café Größe" -> "cafe-groesse"), lowercase, with every other run of
+/// characters collapsed into `separator`, capped in length and never a
+/// Windows device name.
+pub fn slugify(name: &str, separator: char) -> String {
+    let ascii = deunicode::deunicode(&german_umlauts(name));
+
+    let mut slug = String::new();
+    let mut pending_separator = false;
+    for c in ascii.chars() {
+        if c.is_ascii_alphanumeric() {
+            if pending_separator && !slug.is_empty() {
+                slug.push(separator);
+            }
+            pending_separator = false;
+            slug.push(c.to_ascii_lowercase());
+        } else {
+            pending_separator = true;
+        }
+    }
+
+    if slug.len() > MAX_SLUG_LEN {
+        // Cut at a word boundary when there is one reasonably close
+        let cut = slug[..MAX_SLUG_LEN]
+            .rfind(separator)
+            .filter(|&pos| pos > MAX_SLUG_LEN / 2)
+            .unwrap_or(MAX_SLUG_LEN);
+        slug.truncate(cut);
+    }
+
+    if slug.is_empty() {
+        slug = "snippet".to_string();
+    }
+    if WINDOWS_RESERVED.contains(&slug.as_str()) {
+        slug.push(separator);
+        slug.push_str("snippet");
+    }
+    slug
+}
+
+/// German umlauts read better as digraphs than with the dots dropped.
+fn german_umlauts(name: &str) -> String {
+    name.replace('ä', "ae").replace('ö', "oe").replace('ü', "ue")
+        .replace('Ä', "Ae").replace('Ö', "Oe").replace('Ü', "Ue")
+}
+
+/// `<slug>-<id8>.md` for a snippet in `dir`. If a different snippet already
+/// owns that filename (say, two IDs sharing a prefix), a numeric suffix keeps
+/// them apart; the snippet's own existing file is reused.
+pub fn snippet_filename(dir: &Path, name: &str, id: &str) -> String {
+    let stem = format!("{}-{}", slugify(name, '-'), crate::ids::short(id));
+    let mut candidate = format!("{}.md", stem);
+    let mut counter = 2;
+    while let Ok(existing) = fs::read_to_string(dir.join(&candidate)) {
+        let same_snippet = crate::publish::parse_markdown_frontmatter(&existing)
+            .map(|snippet| snippet.id == id)
+            .unwrap_or(false);
+        if same_snippet {
+            break;
+        }
+        candidate = format!("{}-{}.md", stem, counter);
+        counter += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("Running GUI Applications", '-'), "running-gui-applications");
+        assert_eq!(slugify("Café Größe: naïve/überall", '-'), "cafe-groesse-naive-ueberall");
+        assert_eq!(slugify("日本語のルール", '-'), "ri-ben-yu-noruru");
+        assert_eq!(slugify("🚀", '-'), "rocket");
+        assert_eq!(slugify("!!!", '-'), "snippet");
+        assert_eq!(slugify("CON", '-'), "con-snippet");
+        assert_eq!(slugify("a/b\\c", '_'), "a_b_c");
+        assert_eq!(slugify("Test/Path\\Name", '-'), "test-path-name");
+        assert_eq!(slugify("Special-Characters!@#", '_'), "special_characters");
+        assert_eq!(slugify("C:\\Windows: a ../../escape", '-'), "c-windows-a-escape");
+        assert_eq!(slugify("Rust 🦀 tips", '-'), "rust-crab-tips");
+
+        let long = slugify(&"word ".repeat(40), '-');
+        assert!(long.len() <= MAX_SLUG_LEN && long.ends_with("word"));
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use crate::capabilities::{self, Tool};
+use crate::perf::{Category, TimedCommand};
+use crate::publish::{self, Snippet};
+use crate::store::{FsStore, SnippetStore};
+
+/// Repository holding `<template>/*.md` starter snippets, used unless `templates_source` is configured.
+pub const DEFAULT_TEMPLATES_SOURCE: &str = "https://github.com/claude-md-snippets/starter-templates";
+
+/// Curated starter sets for `setup --template`.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StarterTemplate {
+    Rust,
+    Web,
+    Data,
+}
+
+impl StarterTemplate {
+    fn dir_name(self) -> &'static str {
+        match self {
+            StarterTemplate::Rust => "rust",
+            StarterTemplate::Web => "web",
+            StarterTemplate::Data => "data",
+        }
+    }
+
+    /// A small offline fallback used when the templates source can't be fetched.
+    fn bundled(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            StarterTemplate::Rust => &[
+                ("Rust build and test", "\
+# Rust build and test
+
+- Run `cargo fmt` before committing
+- Treat clippy warnings as errors: `cargo clippy --all-targets -- -D warnings`
+- Run `cargo test` and make sure it passes before finishing a change
+"),
+                ("Rust error handling", "\
+# Rust error handling
+
+- Use `anyhow::Result` in binaries and `thiserror` error enums in libraries
+- Add context to fallible calls with `.context(...)` / `.with_context(...)`
+- Don't use `unwrap()` or `expect()` outside of tests and truly unreachable cases
+"),
+            ],
+            StarterTemplate::Web => &[
+                ("Frontend workflow", "\
+# Frontend workflow
+
+- Check `package.json` scripts before running tools directly
+- Run the linter and type checker after changes
+- Use the package manager that matches the lockfile (npm, pnpm or yarn)
+"),
+                ("Accessibility basics", "\
+# Accessibility basics
+
+- Every interactive element must be reachable and usable with the keyboard
+- Images need meaningful `alt` text (empty `alt` for decorative images)
+- Prefer semantic HTML elements over `div`s with click handlers
+"),
+            ],
+            StarterTemplate::Data => &[
+                ("Python data workflow", "\
+# Python data workflow
+
+- Use the project's virtual environment; never install packages globally
+- Keep notebooks free of large outputs before committing
+- Move reusable analysis code from notebooks into modules
+"),
+                ("SQL safety", "\
+# SQL safety
+
+- Never run UPDATE, DELETE or DROP statements without explicit confirmation
+- Add a LIMIT to exploratory queries
+- Use parameterized queries; never build SQL with string formatting
+"),
+            ],
+        }
+    }
+}
+
+/// Copy the starter snippets for `template` into a freshly set up repository
+/// and commit them. Returns the number of snippets added.
+pub fn seed_starter_snippets(repo_dir: &Path, template: StarterTemplate, source: &str) -> Result<usize> {
+    println!("🌱 Seeding '{}' starter snippets...", template.dir_name());
+
+    let snippets = match fetch_starters(template, source) {
+        Ok(snippets) if !snippets.is_empty() => snippets,
+        Ok(_) => {
+            println!("⚠️  No '{}' templates found in {}; using the bundled starter set", template.dir_name(), source);
+            bundled_starters(template)
+        }
+        Err(e) => {
+            println!("⚠️  Could not fetch templates from {} ({}); using the bundled starter set", source, e);
+            bundled_starters(template)
+        }
+    };
+
+    let snippets_dir = repo_dir.join("snippets");
+    fs::create_dir_all(&snippets_dir)?;
+    let existing: Vec<String> = crate::store::load_snippets_from(&snippets_dir)?
+        .into_iter()
+        .map(|snippet| snippet.name.to_lowercase())
+        .collect();
+
+    let mut added = 0;
+    for snippet in snippets {
+        if existing.contains(&snippet.name.to_lowercase()) {
+            continue;
+        }
+        let filename = crate::slug::snippet_filename(&snippets_dir, &snippet.name, &snippet.id);
+        fs::write(snippets_dir.join(filename), publish::create_markdown_with_frontmatter(&snippet)?)?;
+        println!("  + {}", snippet.name);
+        added += 1;
+    }
+
+    if added > 0 {
+        Command::new("git")
+            .current_dir(repo_dir)
+            .args(["add", "snippets"])
+            .timed_output(Category::Git)?;
+        Command::new("git")
+            .current_dir(repo_dir)
+            .args(["commit", "-m", &format!("Add {} starter snippets", template.dir_name())])
+            .timed_output(Category::Git)?;
+    }
+
+    println!("✅ Added {} starter snippets", added);
+    Ok(added)
+}
+
+fn fetch_starters(template: StarterTemplate, source: &str) -> Result<Vec<Snippet>> {
+    let is_remote = (source.contains("://") && !source.starts_with("file://")) || source.starts_with("git@");
+    if is_remote {
+        capabilities::require(Tool::Network, "fetching starter templates")?;
+    }
+
+    let checkout = std::env::temp_dir().join(format!("claude-md-snippets-starters-{}", std::process::id()));
+    if checkout.exists() {
+        fs::remove_dir_all(&checkout)?;
+    }
+
+    let output = Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", source])
+        .arg(&checkout)
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    // A throwaway checkout, so no point caching what was parsed
+    let result = FsStore::new(checkout.join(template.dir_name())).load_all();
+    fs::remove_dir_all(&checkout).ok();
+
+    // Fresh identities, so the starters become the user's own snippets
+    Ok(result?.into_iter().map(|snippet| Snippet {
+        id: crate::ids::generate(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        ..snippet
+    }).collect())
+}
+
+fn bundled_starters(template: StarterTemplate) -> Vec<Snippet> {
+    template.bundled().iter().map(|(name, content)| Snippet {
+        id: crate::ids::generate(),
+        name: name.to_string(),
+        content: content.to_string(),
+        created_at: chrono::Utc::now().to_rfc3339(),
+        description: Some(format!("{} starter snippet", template.dir_name())),
+        tags: vec![template.dir_name().to_string()],
+        ..Default::default()
+    }).collect()
+}
@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use crate::app_dirs::AppDirs;
+use crate::publish::get_app_dir;
+
+/// Files under the app directory that make up this machine's state. Caches
+/// (search index, detected tools) and logs are rebuilt, so they stay out.
+const STATE_FILES: [&str; 5] = ["config.json", "installed.json", "pending-sync.json", "experiments.json", "usage.json"];
+/// Archive entry listing every other entry with its checksum.
+const MANIFEST_ENTRY: &str = "state-manifest.json";
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveManifest {
+    format: u32,
+    created_at: String,
+    /// claude-md-snippets-manager version that wrote the archive
+    version: String,
+    files: Vec<ArchivedFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchivedFile {
+    /// Path relative to the app directory
+    path: String,
+    sha256: String,
+}
+
+/// `state export`: bundle the config, install manifest and unsynced changes
+/// (plus every repository, `.git` included, with `repos`) into a `.tar.gz`.
+pub fn export(archive: &Path, repos: bool) -> Result<()> {
+    let app_dir = get_app_dir()?;
+    let mut files: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+    for name in STATE_FILES {
+        if let Ok(bytes) = fs::read(local_path(name)?) {
+            files.insert(name.to_string(), bytes);
+        }
+    }
+    if repos {
+        collect_repos(&app_dir, &mut files)?;
+    }
+    if files.is_empty() {
+        anyhow::bail!("Nothing to export: {} holds no configuration yet", app_dir.display());
+    }
+
+    let manifest = ArchiveManifest {
+        format: FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        files: files.iter().map(|(path, bytes)| ArchivedFile { path: path.clone(), sha256: sha256_hex(bytes) }).collect(),
+    };
+
+    let file = File::create(archive).with_context(|| format!("Failed to create {}", archive.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    append(&mut builder, MANIFEST_ENTRY, serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+    for (path, bytes) in &files {
+        append(&mut builder, path, bytes)?;
+    }
+    builder.into_inner()?.finish()?;
+
+    println!("✅ Exported {} file(s) to {}", files.len(), archive.display());
+    if repos {
+        println!("📦 Includes {} repository(ies)", repo_names(&files).len());
+    } else {
+        println!("💡 Repositories are not included; add --repos to bundle them too");
+    }
+    Ok(())
+}
+
+/// `state import`: check every file in `archive` against its checksum, then
+/// replace the local state (and repositories it contains) with it.
+pub fn import(archive: &Path) -> Result<()> {
+    let (manifest, files) = read_archive(archive)?;
+    println!("📦 Archive from {} (version {}), {} file(s)",
+        manifest.created_at.get(..10).unwrap_or(&manifest.created_at), manifest.version, files.len());
+
+    let app_dir = get_app_dir()?;
+    let state: Vec<&str> = STATE_FILES.iter().copied().filter(|name| files.contains_key(*name)).collect();
+    let repos = repo_names(&files);
+    for name in &state {
+        println!("  • {}", name);
+    }
+    for repo in &repos {
+        let note = if app_dir.join("repos").join(repo).exists() { " (replaces the local copy)" } else { "" };
+        println!("  • repository '{}'{}", repo, note);
+    }
+    if !crate::prompt::confirm("Replace the local state with the archive?", false)? {
+        println!("❌ Import cancelled");
+        return Ok(());
+    }
+
+    for repo in &repos {
+        let dir = app_dir.join("repos").join(repo);
+        if dir.exists() {
+            fs::remove_dir_all(&dir).with_context(|| format!("Failed to remove {}", dir.display()))?;
+        }
+    }
+    for (path, bytes) in &files {
+        let target = local_path(path)?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&target, bytes).with_context(|| format!("Failed to write {}", target.display()))?;
+    }
+
+    if !repos.is_empty() {
+        crate::index::refresh_index_quietly();
+    }
+    println!("✅ Imported {} file(s) into {}", files.len(), app_dir.display());
+    Ok(())
+}
+
+/// Where an archived file goes: config.json to the config directory,
+/// everything else under the data directory.
+fn local_path(path: &str) -> Result<PathBuf> {
+    let dirs = AppDirs::current()?;
+    Ok(match path {
+        "config.json" => dirs.config,
+        _ => dirs.data,
+    }.join(path))
+}
+
+/// Every file of every repository, keyed by its path under the app directory.
+/// Symlinked files are stored as their contents; symlinked folders are skipped.
+fn collect_repos(app_dir: &Path, files: &mut BTreeMap<String, Vec<u8>>) -> Result<()> {
+    let repos_dir = app_dir.join("repos");
+    if !repos_dir.is_dir() {
+        return Ok(());
+    }
+    for entry in ignore::WalkBuilder::new(&repos_dir).standard_filters(false).build() {
+        let entry = entry?;
+        if !entry.path().is_file() {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(app_dir)?;
+        let key = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        files.insert(key, fs::read(entry.path())?);
+    }
+    Ok(())
+}
+
+/// Names of the repositories with files in `files`.
+fn repo_names(files: &BTreeMap<String, Vec<u8>>) -> Vec<&str> {
+    let mut names: Vec<&str> = files.keys().filter_map(|path| path.strip_prefix("repos/")?.split('/').next()).collect();
+    names.dedup();
+    names
+}
+
+fn append<W: std::io::Write>(builder: &mut tar::Builder<W>, path: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp() as u64);
+    builder.append_data(&mut header, path, bytes)?;
+    Ok(())
+}
+
+/// Read the whole archive and verify it: the manifest must list exactly the
+/// files present, each with a matching checksum and a path inside the app
+/// directory. Nothing is written until this passes.
+fn read_archive(archive: &Path) -> Result<(ArchiveManifest, BTreeMap<String, Vec<u8>>)> {
+    let file = File::open(archive).with_context(|| format!("Failed to open {}", archive.display()))?;
+    let mut tar = tar::Archive::new(GzDecoder::new(file));
+    let mut manifest = None;
+    let mut files = BTreeMap::new();
+    for entry in tar.entries()? {
+        let mut entry = entry.context("Not a state archive (expected a .tar.gz from 'state export')")?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        if path == MANIFEST_ENTRY {
+            manifest = Some(serde_json::from_slice::<ArchiveManifest>(&bytes).context("Invalid state manifest")?);
+        } else {
+            files.insert(path, bytes);
+        }
+    }
+
+    let manifest = manifest.context("Not a state archive: it has no state-manifest.json")?;
+    verify(&manifest, &files)?;
+    Ok((manifest, files))
+}
+
+fn verify(manifest: &ArchiveManifest, files: &BTreeMap<String, Vec<u8>>) -> Result<()> {
+    if manifest.format > FORMAT_VERSION {
+        anyhow::bail!("The archive was written by a newer version ({}); upgrade to import it", manifest.version);
+    }
+    for listed in &manifest.files {
+        if !is_safe_path(&listed.path) {
+            anyhow::bail!("Refusing unsafe path in archive: {}", listed.path);
+        }
+        let bytes = files.get(&listed.path).with_context(|| format!("Archive is incomplete: {} is missing", listed.path))?;
+        if sha256_hex(bytes) != listed.sha256 {
+            anyhow::bail!("Archive is corrupted: checksum mismatch for {}", listed.path);
+        }
+    }
+    if let Some(extra) = files.keys().find(|path| !manifest.files.iter().any(|listed| &listed.path == *path)) {
+        anyhow::bail!("Archive has a file its manifest doesn't list: {}", extra);
+    }
+    Ok(())
+}
+
+/// One of the state files, or a file inside `repos/<name>/`.
+fn is_safe_path(path: &str) -> bool {
+    let path = Path::new(path);
+    let normal = path.components().all(|component| matches!(component, Component::Normal(_)));
+    normal && (STATE_FILES.iter().any(|name| path == Path::new(name)) || (path.starts_with("repos") && path.components().count() > 2))
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_archive() {
+        let mut files = BTreeMap::new();
+        files.insert("config.json".to_string(), b"{}".to_vec());
+        files.insert("repos/team/snippets/a.md".to_string(), b"rule".to_vec());
+        let mut manifest = ArchiveManifest {
+            format: FORMAT_VERSION,
+            created_at: String::new(),
+            version: String::new(),
+            files: files.iter().map(|(path, bytes)| ArchivedFile { path: path.clone(), sha256: sha256_hex(bytes) }).collect(),
+        };
+        assert!(verify(&manifest, &files).is_ok());
+
+        files.insert("repos/team/snippets/a.md".to_string(), b"tampered".to_vec());
+        assert!(verify(&manifest, &files).is_err());
+
+        manifest.files.push(ArchivedFile { path: "../.bashrc".to_string(), sha256: String::new() });
+        files.insert("repos/team/snippets/a.md".to_string(), b"rule".to_vec());
+        assert!(verify(&manifest, &files).unwrap_err().to_string().contains("unsafe path"));
+        assert!(!is_safe_path("repos/team"));
+        assert!(!is_safe_path("logs/x.log"));
+    }
+}
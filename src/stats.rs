@@ -0,0 +1,182 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Command;
+use crate::perf::{Category, TimedCommand};
+
+/// One commit touching `snippets/`, as read from `git log`.
+#[derive(Debug, PartialEq)]
+struct Commit {
+    author: String,
+    /// ISO 8601 author date
+    date: String,
+    files: Vec<String>,
+}
+
+/// `repo stats`: size of the library, plus with `contributions` who maintains
+/// it, which snippets change most and how activity is spread over time.
+pub fn show_stats(repo_dir: &Path, repo_name: &str, contributions: bool) -> Result<()> {
+    let snippets = crate::store::load_snippets_from(&repo_dir.join("snippets"))?;
+    let lines: usize = snippets.iter().map(|s| s.content.lines().count()).sum();
+
+    println!("📊 Repository '{}'", repo_name);
+    println!("================================");
+    println!("📝 Snippets: {} ({} lines)", snippets.len(), lines);
+
+    if !repo_dir.join(".git").exists() {
+        println!("ℹ️  Not a git repository, so there is no history to summarize");
+        return Ok(());
+    }
+    let commits = snippet_history(repo_dir)?;
+    println!("🔀 Commits touching snippets: {}", commits.len());
+    if let Some(latest) = commits.first() {
+        println!("🕒 Last change: {} by {}", &latest.date[..latest.date.len().min(10)], latest.author);
+    }
+
+    if !contributions {
+        println!("💡 Add --contributions for per-author activity");
+        return Ok(());
+    }
+    if commits.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!("👥 Contributors:");
+    for author in by_author(&commits) {
+        println!("  {:<24} {:>4} commit(s), {:>3} snippet(s) touched, {} → {}",
+            author.name, author.commits, author.snippets, author.first, author.last);
+    }
+
+    // Name files by their current snippet where one still exists
+    let names: HashMap<String, String> = crate::scan::snippet_files(&repo_dir.join("snippets"))
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            let snippet = crate::publish::parse_markdown_frontmatter(&content).ok()?;
+            let file = path.strip_prefix(repo_dir).ok()?.to_string_lossy().into_owned();
+            Some((file, snippet.name))
+        })
+        .collect();
+
+    println!();
+    println!("✏️  Most edited snippets:");
+    for (file, edits) in most_edited(&commits).into_iter().take(10) {
+        let label = names.get(&file).cloned().unwrap_or_else(|| format!("{} (deleted or renamed)", file));
+        println!("  {:>4}  {}", edits, label);
+    }
+
+    println!();
+    println!("📅 Activity by month:");
+    let months = by_month(&commits);
+    let busiest = months.values().copied().max().unwrap_or(1);
+    for (month, count) in months.iter().rev().take(12).rev() {
+        let bar = "█".repeat((count * 30).div_ceil(busiest));
+        println!("  {}  {:<30} {}", month, bar, count);
+    }
+
+    Ok(())
+}
+
+fn snippet_history(repo_dir: &Path) -> Result<Vec<Commit>> {
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["log", "--format=%x1e%an%x1f%aI", "--name-only", "--", "snippets/"])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        // A repository without commits yet has no history
+        return Ok(Vec::new());
+    }
+    Ok(parse_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git log --format=%x1e%an%x1f%aI --name-only`, newest first.
+fn parse_log(log: &str) -> Vec<Commit> {
+    log.split('\x1e')
+        .filter_map(|entry| {
+            let mut lines = entry.lines();
+            let (author, date) = lines.next()?.split_once('\x1f')?;
+            let files = lines
+                .map(|line| line.trim())
+                .filter(|line| line.ends_with(".md"))
+                .map(|line| line.to_string())
+                .collect();
+            Some(Commit { author: author.to_string(), date: date.to_string(), files })
+        })
+        .collect()
+}
+
+struct AuthorStats {
+    name: String,
+    commits: usize,
+    snippets: usize,
+    first: String,
+    last: String,
+}
+
+/// Authors by number of commits, most active first.
+fn by_author(commits: &[Commit]) -> Vec<AuthorStats> {
+    let mut grouped: BTreeMap<&str, Vec<&Commit>> = BTreeMap::new();
+    for commit in commits {
+        grouped.entry(&commit.author).or_default().push(commit);
+    }
+
+    let mut authors: Vec<AuthorStats> = grouped.into_iter().map(|(name, commits)| {
+        let mut files: Vec<&String> = commits.iter().flat_map(|c| &c.files).collect();
+        files.sort();
+        files.dedup();
+        let mut dates: Vec<&str> = commits.iter().map(|c| c.date.get(..10).unwrap_or(&c.date)).collect();
+        dates.sort();
+        AuthorStats {
+            name: name.to_string(),
+            commits: commits.len(),
+            snippets: files.len(),
+            first: dates.first().unwrap_or(&"").to_string(),
+            last: dates.last().unwrap_or(&"").to_string(),
+        }
+    }).collect();
+    authors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    authors
+}
+
+/// Files by the number of commits that changed them, most edited first.
+fn most_edited(commits: &[Commit]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for file in commits.iter().flat_map(|c| &c.files) {
+        *counts.entry(file).or_default() += 1;
+    }
+    let mut files: Vec<(String, usize)> = counts.into_iter().map(|(file, n)| (file.to_string(), n)).collect();
+    files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    files
+}
+
+/// Commits per `YYYY-MM`, oldest month first.
+fn by_month(commits: &[Commit]) -> BTreeMap<String, usize> {
+    let mut months = BTreeMap::new();
+    for commit in commits {
+        *months.entry(commit.date.get(..7).unwrap_or(&commit.date).to_string()).or_default() += 1;
+    }
+    months
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contribution_summary() {
+        let log = "\x1eAda\x1f2024-03-02T10:00:00+00:00\n\nsnippets/a.md\nsnippets/b.md\n\
+                   \x1eBob\x1f2024-02-10T09:00:00+00:00\n\nsnippets/a.md\n\
+                   \x1eAda\x1f2024-02-01T08:00:00+00:00\n\nsnippets/a.md\ncollection.yaml\n";
+        let commits = parse_log(log);
+        assert_eq!(commits.len(), 3);
+        assert_eq!(commits[2].files, ["snippets/a.md"]);
+
+        let authors = by_author(&commits);
+        assert_eq!((authors[0].name.as_str(), authors[0].commits, authors[0].snippets), ("Ada", 2, 2));
+        assert_eq!((authors[0].first.as_str(), authors[0].last.as_str()), ("2024-02-01", "2024-03-02"));
+
+        assert_eq!(most_edited(&commits)[0], ("snippets/a.md".to_string(), 3));
+        assert_eq!(by_month(&commits).into_iter().collect::<Vec<_>>(), [("2024-02".to_string(), 2), ("2024-03".to_string(), 1)]);
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use crate::github::ChangeKind;
+use crate::publish::{self, Snippet};
+
+/// Where a snippet is in its life (its `status:` frontmatter field).
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    #[default]
+    Active,
+    /// Still installable, with a warning pointing at its replacement
+    Deprecated,
+    /// Kept for history; hidden from search and install
+    Archived,
+}
+
+impl Status {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Active => "active",
+            Status::Deprecated => "deprecated",
+            Status::Archived => "archived",
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self == Status::Active
+    }
+
+    /// Read a frontmatter value; anything unrecognised counts as active.
+    pub fn from_frontmatter(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "deprecated" => Status::Deprecated,
+            "archived" => Status::Archived,
+            "active" => Status::Active,
+            other => {
+                tracing::warn!("Unknown status '{}', treating the snippet as active", other);
+                Status::Active
+            }
+        }
+    }
+}
+
+/// Drop archived snippets, returning how many were hidden.
+pub fn hide_archived(snippets: &mut Vec<Snippet>) -> usize {
+    let before = snippets.len();
+    snippets.retain(|snippet| snippet.status != Status::Archived);
+    before - snippets.len()
+}
+
+/// Warn before installing a deprecated snippet, suggesting its replacement
+/// when the default repository has it.
+pub fn warn_if_deprecated(snippet: &Snippet) {
+    if snippet.status != Status::Deprecated {
+        return;
+    }
+    let replacement = snippet.replaced_by.as_ref().and_then(|id| {
+        crate::store::load_snippets().ok()?.into_iter().find(|other| &other.id == id)
+    });
+    match replacement {
+        Some(replacement) => {
+            println!("⚠️  '{}' is deprecated in favour of '{}' ({})", snippet.name, replacement.name, crate::ids::short(&replacement.id));
+            println!("💡 Install the replacement instead: claude-md-snippets install \"{}\"", replacement.name);
+        }
+        None => println!("⚠️  '{}' is deprecated", snippet.name),
+    }
+}
+
+/// `repo deprecate` / `repo archive` / `repo reactivate`: set the status of
+/// the snippet in `file`. `replaced_by` is an ID (or a prefix of at least four
+/// characters) of another snippet in the same repository.
+pub async fn set_status(file: &Path, repo_name: &str, status: Status, replaced_by: Option<&str>) -> Result<()> {
+    let mut snippet = publish::parse_markdown_frontmatter(&fs::read_to_string(file)?)?;
+    let replaced_by = match replaced_by {
+        Some(prefix) => Some(replacement_id(repo_name, &snippet, prefix)?),
+        // Archiving keeps a recorded replacement; reactivating drops it
+        None if status == Status::Active => None,
+        None => snippet.replaced_by.clone(),
+    };
+    if snippet.status == status && snippet.replaced_by == replaced_by {
+        println!("ℹ️  '{}' is already {}", snippet.name, status.as_str());
+        return Ok(());
+    }
+
+    snippet.status = status;
+    snippet.replaced_by = replaced_by;
+    fs::write(file, publish::create_markdown_with_frontmatter(&snippet)?)?;
+    match status {
+        Status::Active => println!("✅ '{}' is active again", snippet.name),
+        Status::Deprecated => println!("⚠️  '{}' is now deprecated; installing it shows a warning", snippet.name),
+        Status::Archived => println!("📦 '{}' is archived; search and install no longer show it", snippet.name),
+    }
+
+    let verb = match status {
+        Status::Active => "Reactivate",
+        Status::Deprecated => "Deprecate",
+        Status::Archived => "Archive",
+    };
+    crate::edit::finish(repo_name, &format!("{} snippet '{}' (ID: {})", verb, snippet.name, crate::ids::short(&snippet.id)), ChangeKind::Edit).await;
+    Ok(())
+}
+
+fn replacement_id(repo_name: &str, snippet: &Snippet, prefix: &str) -> Result<String> {
+    if prefix.len() < 4 {
+        anyhow::bail!("--replaced-by needs at least 4 characters of the replacement's ID");
+    }
+    let snippets = crate::store::load_snippets_from(&publish::get_repos_dir()?.join(repo_name).join("snippets"))?;
+    let matching: Vec<&Snippet> = snippets.iter().filter(|other| other.id.starts_with(prefix)).collect();
+    match matching.as_slice() {
+        [replacement] if replacement.id == snippet.id => anyhow::bail!("A snippet can't replace itself"),
+        [replacement] => {
+            println!("🔁 Replacement: '{}' ({})", replacement.name, crate::ids::short(&replacement.id));
+            Ok(replacement.id.clone())
+        }
+        [] => anyhow::bail!("No snippet in '{}' has an ID starting with '{}'", repo_name, prefix),
+        _ => anyhow::bail!("'{}' matches {} snippets in '{}'; use a longer ID", prefix, matching.len(), repo_name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_frontmatter() {
+        let text = "---\nid: 1a2b3c4d\nname: Old rule\nstatus: deprecated\nreplaced_by: 5e6f7a8b\n---\n\nBody\n";
+        let mut snippet = publish::parse_markdown_frontmatter(text).unwrap();
+        assert_eq!(snippet.status, Status::Deprecated);
+        assert_eq!(snippet.replaced_by.as_deref(), Some("5e6f7a8b"));
+        assert_eq!(publish::parse_markdown_frontmatter(&publish::create_markdown_with_frontmatter(&snippet).unwrap()).unwrap().status, Status::Deprecated);
+
+        snippet.status = Status::Archived;
+        let mut snippets = vec![snippet];
+        assert_eq!(hide_archived(&mut snippets), 1);
+        assert!(snippets.is_empty());
+        assert_eq!(Status::from_frontmatter("retired"), Status::Active);
+    }
+}
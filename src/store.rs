@@ -0,0 +1,210 @@
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use crate::publish::{self, Snippet};
+use crate::{scan, slug};
+
+/// Every snippet in the default repository, newest first.
+pub fn load_snippets() -> Result<Vec<Snippet>> {
+    FsStore::default_repo()?.load_all()
+}
+
+/// Every snippet in `snippets_dir`, newest first.
+pub fn load_snippets_from(snippets_dir: &Path) -> Result<Vec<Snippet>> {
+    FsStore::cached(snippets_dir.to_path_buf())?.load_all()
+}
+
+/// `load_snippets` off the async runtime's worker threads.
+pub async fn load_snippets_async() -> Result<Vec<Snippet>> {
+    tokio::task::spawn_blocking(load_snippets).await?
+}
+
+/// Where snippets are kept. Publish and install go through this rather than
+/// reading the repository directory themselves, so tests (and other
+/// backends) can swap in a different store.
+pub trait SnippetStore {
+    /// Every snippet, newest first.
+    fn load_all(&self) -> Result<Vec<Snippet>>;
+    /// Add or replace `snippet`, returning where it was written.
+    fn save(&self, snippet: &Snippet) -> Result<String>;
+}
+
+/// Markdown files with YAML frontmatter in a repository's `snippets/` directory.
+pub struct FsStore {
+    snippets_dir: PathBuf,
+    /// Parsed snippets from the last load, reused for files that haven't changed since
+    cache_file: Option<PathBuf>,
+}
+
+/// A snippet file as it was when last parsed.
+#[derive(Serialize, Deserialize, Clone)]
+struct CachedFile {
+    modified: u128,
+    len: u64,
+    /// `None` for files that aren't valid snippets, so they aren't parsed again either
+    snippet: Option<Snippet>,
+}
+
+impl FsStore {
+    pub fn new(snippets_dir: PathBuf) -> Self {
+        FsStore { snippets_dir, cache_file: None }
+    }
+
+    /// A store that keeps parsed snippets in a cache file under the app
+    /// directory, so loading hundreds of them only parses what changed.
+    pub fn cached(snippets_dir: PathBuf) -> Result<Self> {
+        // Keyed by version too, so snippets parsed by an older release aren't reused
+        let key = crate::manifest::content_hash(&format!("{}\n{}", env!("CARGO_PKG_VERSION"), snippets_dir.display()));
+        let cache_file = publish::get_app_dir()?.join("cache").join(format!("snippets-{}.json", &key[..16]));
+        Ok(FsStore { snippets_dir, cache_file: Some(cache_file) })
+    }
+
+    /// The store for the default repository.
+    pub fn default_repo() -> Result<Self> {
+        FsStore::cached(publish::get_snippets_dir()?.join("snippets"))
+    }
+
+    /// The file currently holding the snippet with `id`, in any subfolder.
+    fn find_file(&self, id: &str) -> Option<PathBuf> {
+        scan::snippet_files(&self.snippets_dir).into_iter().find(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .and_then(|content| publish::parse_markdown_frontmatter(&content).ok())
+                .is_some_and(|snippet| snippet.id == id)
+        })
+    }
+}
+
+impl SnippetStore for FsStore {
+    fn load_all(&self) -> Result<Vec<Snippet>> {
+        let cache: HashMap<String, CachedFile> = self.cache_file.as_ref()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        // Files are parsed in parallel; unchanged ones come straight from the cache
+        let files: Vec<(String, CachedFile, bool)> = scan::snippet_files(&self.snippets_dir)
+            .par_iter()
+            .filter_map(|path| {
+                let metadata = fs::metadata(path).ok()?;
+                let modified = metadata.modified().ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .map(|since| since.as_nanos())
+                    .unwrap_or(0);
+                let key = path.display().to_string();
+                if let Some(cached) = cache.get(&key).filter(|cached| cached.modified == modified && cached.len == metadata.len()) {
+                    return Some((key, cached.clone(), false));
+                }
+                let snippet = fs::read_to_string(path).ok()
+                    .and_then(|content| publish::parse_markdown_frontmatter(&content).ok());
+                Some((key, CachedFile { modified, len: metadata.len(), snippet }, true))
+            })
+            .collect();
+
+        if let Some(cache_file) = &self.cache_file {
+            if files.len() != cache.len() || files.iter().any(|(_, _, parsed)| *parsed) {
+                let fresh: HashMap<&str, &CachedFile> = files.iter().map(|(key, file, _)| (key.as_str(), file)).collect();
+                // A cache that can't be written only costs the next load its speed
+                if let Err(e) = crate::lockfile::atomic_write(cache_file, &serde_json::to_string(&fresh)?) {
+                    tracing::debug!("Could not write snippet cache {}: {}", cache_file.display(), e);
+                }
+            }
+        }
+
+        let mut snippets: Vec<Snippet> = files.into_iter().filter_map(|(_, file, _)| file.snippet).collect();
+        snippets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(snippets)
+    }
+
+    fn save(&self, snippet: &Snippet) -> Result<String> {
+        // A snippet being replaced stays in the category folder it was filed in
+        let existing = self.find_file(&snippet.id);
+        let dir = existing.as_deref().and_then(|path| path.parent()).unwrap_or(&self.snippets_dir).to_path_buf();
+        fs::create_dir_all(&dir)?;
+        let filepath = dir.join(slug::snippet_filename(&dir, &snippet.name, &snippet.id));
+        fs::write(&filepath, publish::create_markdown_with_frontmatter(snippet)?)?;
+        if let Some(old) = existing.filter(|old| *old != filepath) {
+            fs::remove_file(old)?;
+        }
+        Ok(filepath.display().to_string())
+    }
+}
+
+/// Snippets held in memory, for tests that shouldn't touch the disk.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryStore {
+    snippets: std::sync::Mutex<Vec<Snippet>>,
+}
+
+#[cfg(test)]
+impl SnippetStore for MemoryStore {
+    fn load_all(&self) -> Result<Vec<Snippet>> {
+        let mut snippets = self.snippets.lock().unwrap().clone();
+        snippets.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(snippets)
+    }
+
+    fn save(&self, snippet: &Snippet) -> Result<String> {
+        let mut snippets = self.snippets.lock().unwrap();
+        snippets.retain(|existing| existing.id != snippet.id);
+        snippets.push(snippet.clone());
+        Ok(format!("memory:{}", snippet.id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet(id: &str, name: &str, created_at: &str) -> Snippet {
+        Snippet { created_at: created_at.to_string(), ..publish::test_snippet(id, name, &format!("# {}\n", name)) }
+    }
+
+    #[test]
+    fn test_fs_store_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FsStore::new(dir.path().join("snippets"));
+        store.save(&snippet("11111111-a", "Older", "2024-01-01T00:00:00Z")).unwrap();
+        store.save(&snippet("22222222-b", "Newer", "2024-06-01T00:00:00Z")).unwrap();
+
+        let names: Vec<String> = store.load_all().unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["Newer", "Older"]);
+    }
+
+    #[test]
+    fn test_cached_load_picks_up_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let snippets_dir = dir.path().join("snippets");
+        let cache_file = dir.path().join("cache.json");
+        let store = FsStore { snippets_dir: snippets_dir.clone(), cache_file: Some(cache_file.clone()) };
+        store.save(&snippet("11111111-a", "First", "2024-01-01T00:00:00Z")).unwrap();
+        assert_eq!(store.load_all().unwrap().len(), 1);
+        assert!(cache_file.exists());
+
+        // A cached entry for a file that's gone must not come back
+        let written = store.save(&snippet("11111111-a", "Renamed", "2024-01-01T00:00:00Z")).unwrap();
+        store.save(&snippet("22222222-b", "Second", "2024-06-01T00:00:00Z")).unwrap();
+        let names: Vec<String> = store.load_all().unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["Second", "Renamed"]);
+
+        fs::remove_file(written).unwrap();
+        let names: Vec<String> = store.load_all().unwrap().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, ["Second"]);
+    }
+
+    #[test]
+    fn test_publish_into_memory_store_replaces_by_id() {
+        let store = MemoryStore::default();
+        publish::save_snippet(&store, &snippet("11111111-a", "Draft", "2024-01-01T00:00:00Z")).unwrap();
+        publish::save_snippet(&store, &snippet("11111111-a", "Final", "2024-01-01T00:00:00Z")).unwrap();
+
+        let snippets = store.load_all().unwrap();
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].name, "Final");
+    }
+}
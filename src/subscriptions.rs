@@ -0,0 +1,229 @@
+use anyhow::Result;
+use std::process::Command;
+use crate::capabilities::{self, Tool};
+use crate::config::{Config, Source};
+use crate::perf::{Category, TimedCommand};
+use crate::publish::{Snippet, get_repos_dir};
+use crate::store::{FsStore, SnippetStore};
+
+/// Local name of the community repository (`github::DEFAULT_REPO`) when followed.
+pub const COMMUNITY_NAME: &str = "community";
+
+/// First run: offer to follow the community repository next to the new
+/// personal one, so search and install have content before anything is
+/// published. Failing to clone only costs a warning.
+pub fn offer_community() -> Result<()> {
+    let url = format!("https://github.com/{}", crate::github::DEFAULT_REPO);
+    if !capabilities::reachable(&url) {
+        return Ok(());
+    }
+    if !crate::prompt::confirm(&format!("Also follow the community snippets ({}, read-only)?", url), true)? {
+        println!("💡 Follow them later with 'claude-md-snippets repo subscribe'");
+        return Ok(());
+    }
+    if let Err(e) = subscribe(COMMUNITY_NAME, &url) {
+        println!("⚠️  Could not follow the community snippets: {}", e);
+    }
+    Ok(())
+}
+
+/// `repo subscribe`: register `url` as the read-only source `name` and clone
+/// it. Pull keeps it up to date; publish and sync never write to it.
+pub fn subscribe(name: &str, url: &str) -> Result<()> {
+    capabilities::require(Tool::Git, "repo subscribe")?;
+    if !std::path::Path::new(url).exists() && !capabilities::reachable(url) {
+        anyhow::bail!("repo subscribe can't reach {}. {}", url, Tool::Network.hint());
+    }
+    let repos_dir = get_repos_dir()?;
+    if repos_dir.join(name).exists() {
+        anyhow::bail!("A repository named '{}' already exists", name);
+    }
+    clone(name, url)?;
+    Config::update(|config| {
+        config.sources.insert(name.to_string(), Source { url: url.to_string() });
+        Ok(())
+    })?;
+
+    let count = crate::store::load_snippets_from(&repos_dir.join(name).join("snippets"))?.len();
+    println!("✅ Following '{}' ({} snippets); search and install include it", name, count);
+    Ok(())
+}
+
+fn clone(name: &str, url: &str) -> Result<()> {
+    let repos_dir = get_repos_dir()?;
+    std::fs::create_dir_all(&repos_dir)?;
+    println!("📥 Cloning {}...", url);
+    let output = Command::new("git")
+        .current_dir(&repos_dir)
+        .args(["clone", "--quiet", url, name])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("git clone failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// `repo unsubscribe`: stop following the source `name` and remove its clone.
+pub fn unsubscribe(name: &str) -> Result<()> {
+    if !Config::load()?.is_subscribed(name) {
+        anyhow::bail!("'{}' is not a followed source; 'repo sources' lists them", name);
+    }
+    if !crate::prompt::confirm(&format!("Stop following '{}' and remove its local copy?", name), true)? {
+        println!("❌ Cancelled");
+        return Ok(());
+    }
+    Config::update(|config| {
+        config.sources.remove(name);
+        Ok(())
+    })?;
+    let dir = get_repos_dir()?.join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir)?;
+    }
+    println!("✅ No longer following '{}'", name);
+    Ok(())
+}
+
+/// `repo sources`: the followed read-only repositories, where they come
+/// from and how many snippets each has here.
+pub fn list_sources() -> Result<()> {
+    let config = Config::load()?;
+    if config.sources.is_empty() {
+        println!("ℹ️  No sources followed; add one with 'claude-md-snippets repo subscribe [<url>] [--as <name>]'");
+        return Ok(());
+    }
+    let repos_dir = get_repos_dir()?;
+    println!("📡 Sources (read-only; search and install include them, publish never writes to them):");
+    for (name, source) in &config.sources {
+        let url = if source.url.is_empty() { "(URL recorded on the next pull)" } else { &source.url };
+        let snippets_dir = repos_dir.join(name).join("snippets");
+        match snippets_dir.parent().is_some_and(|dir| dir.exists()) {
+            true => {
+                let count = crate::store::load_snippets_from(&snippets_dir).map(|snippets| snippets.len()).unwrap_or(0);
+                println!("  • {} — {} ({} snippets)", name, url, count);
+            }
+            false => println!("  • {} — {} (not on this machine; 'pull --all' clones it)", name, url),
+        }
+    }
+    Ok(())
+}
+
+/// Fail for a read-only repository, before anything is committed to it.
+pub fn ensure_writable(repo_name: &str) -> Result<()> {
+    if Config::load()?.is_subscribed(repo_name) {
+        anyhow::bail!("'{}' is a read-only subscription; make one of your own repositories the default with 'claude-md-snippets config set-default <name>'", repo_name);
+    }
+    Ok(())
+}
+
+/// `pull`: fast-forward every followed source. With `clone_missing`
+/// (`pull --all`), sources registered but not on this machine are cloned.
+pub fn pull_sources(clone_missing: bool) -> Result<()> {
+    let repos_dir = get_repos_dir()?;
+    for (name, source) in Config::load()?.sources {
+        let dir = repos_dir.join(&name);
+        if !dir.exists() {
+            match (clone_missing, source.url.is_empty()) {
+                (true, false) => match clone(&name, &source.url) {
+                    Ok(()) => println!("✅ Cloned source '{}'", name),
+                    Err(e) => println!("⚠️  Could not clone '{}': {}", name, e),
+                },
+                (true, true) => println!("⚠️  Source '{}' is missing and has no URL; follow it again with 'repo subscribe <url> --as {}'", name, name),
+                (false, _) => println!("💡 Source '{}' is not on this machine; 'pull --all' clones it", name),
+            }
+            continue;
+        }
+        let output = Command::new("git")
+            .current_dir(&dir)
+            .args(["pull", "--ff-only", "--quiet"])
+            .timed_output(Category::Git)?;
+        if output.status.success() {
+            println!("✅ Updated source '{}'", name);
+        } else {
+            println!("⚠️  Could not update '{}': {}", name, String::from_utf8_lossy(&output.stderr).trim());
+        }
+        if source.url.is_empty() {
+            record_url(&name, &dir)?;
+        }
+    }
+    Ok(())
+}
+
+/// Remember where a source followed before sources kept URLs comes from.
+fn record_url(name: &str, dir: &std::path::Path) -> Result<()> {
+    let output = Command::new("git").current_dir(dir).args(["remote", "get-url", "origin"]).timed_output(Category::Git)?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !url.is_empty() {
+        Config::update(|config| {
+            if let Some(source) = config.sources.get_mut(name) {
+                source.url = url;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+/// The default repository plus every followed one. Snippets found in both
+/// come from the default repository; saving always goes to it.
+pub struct Followed {
+    own: FsStore,
+    subscribed: Vec<FsStore>,
+}
+
+impl Followed {
+    pub fn load() -> Result<Self> {
+        let config = Config::load()?;
+        let default = crate::config::get_default_repo_name()?;
+        let repos_dir = get_repos_dir()?;
+        let subscribed = config.sources.keys()
+            .filter(|name| **name != default)
+            .map(|name| FsStore::cached(repos_dir.join(name).join("snippets")))
+            .collect::<Result<_>>()?;
+        Ok(Followed { own: FsStore::default_repo()?, subscribed })
+    }
+}
+
+impl SnippetStore for Followed {
+    fn load_all(&self) -> Result<Vec<Snippet>> {
+        let mut snippets = self.own.load_all()?;
+        for store in &self.subscribed {
+            for snippet in store.load_all()? {
+                if !snippets.iter().any(|own| own.id == snippet.id) {
+                    snippets.push(snippet);
+                }
+            }
+        }
+        Ok(snippets)
+    }
+
+    fn save(&self, snippet: &Snippet) -> Result<String> {
+        self.own.save(snippet)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_followed_prefers_own_snippets() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |repo: &str, id: &str, name: &str| {
+            let snippets_dir = dir.path().join(repo);
+            std::fs::create_dir_all(&snippets_dir).unwrap();
+            std::fs::write(snippets_dir.join(format!("{}.md", id)), format!("---\nid: {}\nname: {}\n---\n\nBody\n", id, name)).unwrap();
+        };
+        write("own", "1a2b3c4d", "Mine");
+        write("community", "1a2b3c4d", "Theirs");
+        write("community", "5e6f7a8b", "Shared");
+
+        let followed = Followed {
+            own: FsStore::new(dir.path().join("own")),
+            subscribed: vec![FsStore::new(dir.path().join("community"))],
+        };
+        let mut names: Vec<String> = followed.load_all().unwrap().into_iter().map(|snippet| snippet.name).collect();
+        names.sort();
+        assert_eq!(names, ["Mine", "Shared"]);
+    }
+}
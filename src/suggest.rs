@@ -0,0 +1,175 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use crate::install::Destination;
+use crate::publish::Snippet;
+
+/// Files whose presence says what language a project is written in, and the
+/// keywords that snippets about it carry in their tags or names. The first
+/// keyword names the language.
+const LANGUAGES: &[(&str, &[&str])] = &[
+    ("Cargo.toml", &["rust", "cargo"]),
+    ("package.json", &["javascript", "node", "npm"]),
+    ("tsconfig.json", &["typescript"]),
+    ("deno.json", &["deno", "typescript"]),
+    ("pyproject.toml", &["python"]),
+    ("requirements.txt", &["python", "pip"]),
+    ("setup.py", &["python"]),
+    ("Pipfile", &["python", "pipenv"]),
+    ("go.mod", &["go", "golang"]),
+    ("Gemfile", &["ruby"]),
+    ("pom.xml", &["java", "maven"]),
+    ("build.gradle", &["java", "gradle"]),
+    ("build.gradle.kts", &["kotlin", "gradle"]),
+    ("composer.json", &["php"]),
+    ("mix.exs", &["elixir"]),
+    ("Package.swift", &["swift"]),
+    ("CMakeLists.txt", &["c++", "cpp", "cmake"]),
+];
+
+/// Files that say which tools a project uses, like [`LANGUAGES`].
+const TOOLS: &[(&str, &[&str])] = &[
+    ("Makefile", &["make"]),
+    ("Dockerfile", &["docker"]),
+    ("docker-compose.yml", &["docker", "compose"]),
+    ("compose.yaml", &["docker", "compose"]),
+    (".github/workflows", &["ci", "github-actions"]),
+    (".gitlab-ci.yml", &["ci", "gitlab"]),
+    ("terraform.tf", &["terraform"]),
+    ("main.tf", &["terraform"]),
+];
+
+/// Frameworks and tools worth a keyword of their own, found among package.json dependencies.
+const JS_FRAMEWORKS: &[&str] = &["typescript", "react", "vue", "svelte", "angular", "next", "express", "jest", "vitest", "tailwindcss"];
+
+/// `suggest`: detect what the current project is built with and offer the
+/// snippets tagged or named for it, best matches first, to install together.
+pub async fn suggest(destination: Destination, limit: usize, force: bool) -> Result<()> {
+    let project_dir = std::env::current_dir()?;
+    let detected = detect(&project_dir);
+    if detected.is_empty() {
+        println!("🤷 Couldn't tell what {} is built with (no Cargo.toml, package.json, pyproject.toml, Dockerfile, ...)", project_dir.display());
+        return Ok(());
+    }
+    let keywords: BTreeSet<&str> = detected.values().flatten().map(String::as_str).collect();
+    println!("🔍 Detected: {}", detected.keys().cloned().collect::<Vec<_>>().join(", "));
+
+    let target_path = destination.resolve()?;
+    let installed: BTreeSet<String> = match fs::read_to_string(&target_path) {
+        Ok(content) => crate::install::marked_blocks(&content).into_iter().map(|(id, _)| id).collect(),
+        Err(_) => BTreeSet::new(),
+    };
+    let snippets = crate::store::load_snippets()?;
+    let mut ranked: Vec<(usize, &Snippet)> = snippets.iter()
+        .filter(|snippet| snippet.status.is_active() && !installed.contains(crate::ids::short(&snippet.id)))
+        .map(|snippet| (score(snippet, &keywords), snippet))
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    ranked.truncate(limit);
+    if ranked.is_empty() {
+        println!("ℹ️  No snippets (not already installed) are tagged or named for {}", keywords.into_iter().collect::<Vec<_>>().join(", "));
+        return Ok(());
+    }
+
+    println!("💡 Suggested for this project:");
+    for (i, (_, snippet)) in ranked.iter().enumerate() {
+        let tags = match snippet.tags.is_empty() {
+            true => String::new(),
+            false => format!(" [{}]", snippet.tags.join(", ")),
+        };
+        println!("  {}. {} ({}){}", i + 1, snippet.name, crate::ids::short(&snippet.id), tags);
+    }
+    let chosen = crate::prompt::select(&format!("Install which into {}?", target_path.display()), ranked.len())?;
+    if chosen.is_empty() {
+        println!("❌ Nothing installed");
+        return Ok(());
+    }
+
+    let mut count = 0;
+    for i in &chosen {
+        let snippet = ranked[*i].1;
+        println!("📦 {}", snippet.name);
+        if crate::install::install_confirmed(snippet, &target_path, force, &HashMap::new(), &destination.position).await? {
+            count += 1;
+        }
+    }
+    println!("✅ Installed {} of {} suggested snippet(s)", count, chosen.len());
+    Ok(())
+}
+
+/// The language of the project in `dir`, from the first marker file found.
+pub fn language(dir: &Path) -> Option<&'static str> {
+    LANGUAGES.iter().find(|(marker, _)| dir.join(marker).exists()).map(|(_, keywords)| keywords[0])
+}
+
+/// The markers found in `dir`, each with its keywords.
+pub fn detect(dir: &Path) -> BTreeMap<String, Vec<String>> {
+    let mut detected: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (marker, keywords) in LANGUAGES.iter().chain(TOOLS) {
+        if dir.join(marker).exists() {
+            detected.insert(marker.to_string(), keywords.iter().map(|keyword| keyword.to_string()).collect());
+        }
+    }
+    if let Some(keywords) = detected.get_mut("package.json") {
+        let manifest = fs::read_to_string(dir.join("package.json")).unwrap_or_default();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap_or_default();
+        for section in ["dependencies", "devDependencies"] {
+            let Some(dependencies) = manifest[section].as_object() else { continue };
+            for framework in JS_FRAMEWORKS {
+                if dependencies.contains_key(*framework) && !keywords.iter().any(|keyword| keyword == framework) {
+                    keywords.push(framework.to_string());
+                }
+            }
+        }
+    }
+    detected
+}
+
+/// How well `snippet` fits the detected keywords: a tag counts most, then a
+/// word of the name, then a mention in the description.
+pub fn score(snippet: &Snippet, keywords: &BTreeSet<&str>) -> usize {
+    let name_words: Vec<String> = snippet.name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric() && c != '+')
+        .map(str::to_string)
+        .collect();
+    let description = snippet.description.as_deref().unwrap_or_default().to_lowercase();
+    keywords.iter()
+        .map(|keyword| {
+            if snippet.tags.iter().any(|tag| tag == keyword) {
+                3
+            } else if name_words.iter().any(|word| word == keyword) {
+                2
+            } else if description.split(|c: char| !c.is_alphanumeric() && c != '+').any(|word| word == *keyword) {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_and_score() {
+        let dir = std::env::temp_dir().join(format!("suggest-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(dir.join("package.json"), r#"{"devDependencies": {"react": "^18", "typescript": "^5"}}"#).unwrap();
+        let detected = detect(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(detected.keys().collect::<Vec<_>>(), ["Cargo.toml", "package.json"]);
+        assert!(detected["package.json"].contains(&"react".to_string()));
+        assert!(detected["package.json"].contains(&"typescript".to_string()));
+
+        let parse = |text: &str| crate::publish::parse_markdown_frontmatter(text).unwrap();
+        let keywords: BTreeSet<&str> = ["rust", "cargo"].into();
+        assert_eq!(score(&parse("---\nid: AbCdEfGh\nname: Testing\ntags: [rust]\n---\n\nx\n"), &keywords), 3);
+        assert_eq!(score(&parse("---\nid: AbCdEfGh\nname: Rust errors\n---\n\nx\n"), &keywords), 2);
+        assert_eq!(score(&parse("---\nid: AbCdEfGh\nname: Trusty\n---\n\nx\n"), &keywords), 0);
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+use crate::accounts::{self, WithAccount};
+use crate::capabilities::{self, Tool};
+use crate::journal::Journal;
+use crate::perf::Category;
+use crate::process::{ProcessRunner, RunWith, SystemRunner};
+
+/// What fetching from `origin` said about it.
+#[derive(Debug, PartialEq)]
+enum RemoteState {
+    /// No `origin` configured
+    NoRemote,
+    Reachable,
+    /// Reachable, but without the branch yet (nothing pushed)
+    Empty,
+    /// The host answered that the repository doesn't exist (or isn't visible to you)
+    NotFound,
+    /// Credentials were refused
+    Denied,
+    /// Anything else, with git's first line of explanation
+    Unreachable(String),
+    /// Not contacted (offline); counts are as of the last fetch
+    NotChecked,
+}
+
+/// `sync --status`: for every repository, whether its remote is there, how
+/// far it is ahead of or behind it, what isn't committed or pushed yet, and
+/// when it last synced, with a hint for whatever would make a push fail.
+pub fn show() -> Result<()> {
+    capabilities::require(Tool::Git, "sync --status")?;
+    let runner = &SystemRunner;
+    let repos_dir = crate::publish::get_repos_dir()?;
+    let repos: Vec<String> = crate::publish::repo_names()?.into_iter()
+        .filter(|name| repos_dir.join(name).join(".git").exists())
+        .collect();
+    if repos.is_empty() {
+        println!("ℹ️  No git repositories in {}", repos_dir.display());
+        return Ok(());
+    }
+    let config = crate::config::Config::load()?;
+    let journal = Journal::load()?;
+
+    println!("📊 Sync status");
+    for name in &repos {
+        let repo_dir = repos_dir.join(name);
+        let online = crate::github::remote_reachable(runner, &repo_dir);
+        let branch = crate::github::sync_branch(runner, name, &repo_dir, online);
+        let remote_url = git_line(runner, &repo_dir, &["remote", "get-url", "origin"]);
+
+        println!();
+        let role = if config.is_subscribed(name) { ", read-only source" } else { "" };
+        println!("📁 {} (branch {}{})", name, branch, role);
+        let state = match (&remote_url, online) {
+            (None, _) => RemoteState::NoRemote,
+            (Some(_), false) => RemoteState::NotChecked,
+            (Some(_), true) => fetch(runner, name, &repo_dir, &branch),
+        };
+        if let Some(url) = &remote_url {
+            println!("   🔗 {}", url);
+        }
+        println!("   {}", describe(&state));
+
+        if let Some((ahead, behind)) = ahead_behind(runner, &repo_dir, &branch) {
+            println!("   ⬆️  {} ahead, ⬇️  {} behind origin/{}", ahead, behind, branch);
+            if ahead > 0 && behind > 0 {
+                println!("   💡 Both sides have new commits; 'sync' merges them, asking about snippets edited on both");
+            }
+        }
+        let uncommitted = git_lines(runner, &repo_dir, &["status", "--porcelain"]);
+        if !uncommitted.is_empty() {
+            println!("   ✏️  {} uncommitted file(s)", uncommitted.len());
+        }
+        let queued = journal.pending_for(name).len();
+        if queued > 0 {
+            println!("   📬 {} change(s) queued for the next sync", queued);
+        }
+        match journal.last_synced.get(name).and_then(|time| chrono::DateTime::parse_from_rfc3339(time).ok()) {
+            Some(time) => println!("   🕒 Last synced {}", time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")),
+            None => println!("   🕒 Not synced from here yet"),
+        }
+        if let Some(hint) = hint(&state, name) {
+            println!("   💡 {}", hint);
+        }
+    }
+    Ok(())
+}
+
+/// Fetch `branch` to learn whether the remote is there and to count against
+/// what it has now.
+fn fetch(runner: &dyn ProcessRunner, repo_name: &str, repo_dir: &Path, branch: &str) -> RemoteState {
+    let account = accounts::for_repo(repo_name).ok().flatten();
+    let output = Command::new("git")
+        .current_dir(repo_dir)
+        .args(["fetch", "--quiet", "origin", branch])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .with_account(account.as_ref())
+        .run_with(runner, Category::Git);
+    match output {
+        Ok(output) if output.status.success() => RemoteState::Reachable,
+        Ok(output) => classify(&String::from_utf8_lossy(&output.stderr)),
+        Err(e) => RemoteState::Unreachable(e.to_string()),
+    }
+}
+
+/// What a failed `git fetch` means, from its error output.
+fn classify(stderr: &str) -> RemoteState {
+    let lower = stderr.to_lowercase();
+    if lower.contains("couldn't find remote ref") {
+        RemoteState::Empty
+    } else if lower.contains("repository not found") || lower.contains("does not appear to be a git repository") || lower.contains("does not exist") {
+        RemoteState::NotFound
+    } else if lower.contains("authentication failed") || lower.contains("permission denied") || lower.contains("could not read username") {
+        RemoteState::Denied
+    } else {
+        let reason = stderr.lines().map(str::trim).find(|line| !line.is_empty()).unwrap_or("unknown error");
+        RemoteState::Unreachable(reason.trim_start_matches("fatal: ").to_string())
+    }
+}
+
+fn describe(state: &RemoteState) -> String {
+    match state {
+        RemoteState::NoRemote => "❌ No remote: changes stay on this machine".to_string(),
+        RemoteState::Reachable => "✅ Remote reachable".to_string(),
+        RemoteState::Empty => "✅ Remote reachable, nothing pushed to it yet".to_string(),
+        RemoteState::NotFound => "❌ Remote repository not found".to_string(),
+        RemoteState::Denied => "❌ Remote refused your credentials".to_string(),
+        RemoteState::Unreachable(reason) => format!("⚠️  Remote unreachable: {}", reason),
+        RemoteState::NotChecked => "📴 Offline: remote not checked, counts are as of the last fetch".to_string(),
+    }
+}
+
+fn hint(state: &RemoteState, repo_name: &str) -> Option<String> {
+    match state {
+        RemoteState::NoRemote => Some("'sync' offers to put it on GitHub".to_string()),
+        RemoteState::NotFound => Some(format!("If it was renamed or transferred, run 'claude-md-snippets repo --name {} web-sync'", repo_name)),
+        RemoteState::Denied => Some(format!("Check your login with 'gh auth status', or pick another identity with 'account use <name> --repo {}'", repo_name)),
+        _ => None,
+    }
+}
+
+fn ahead_behind(runner: &dyn ProcessRunner, repo_dir: &Path, branch: &str) -> Option<(usize, usize)> {
+    let counts = git_line(runner, repo_dir, &["rev-list", "--left-right", "--count", &format!("{0}...origin/{0}", branch)])?;
+    let mut numbers = counts.split_whitespace().filter_map(|n| n.parse().ok());
+    Some((numbers.next()?, numbers.next()?))
+}
+
+fn git_lines(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str]) -> Vec<String> {
+    Command::new("git")
+        .current_dir(repo_dir)
+        .args(args)
+        .run_with(runner, Category::Git)
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn git_line(runner: &dyn ProcessRunner, repo_dir: &Path, args: &[&str]) -> Option<String> {
+    git_lines(runner, repo_dir, args).into_iter().next().filter(|line| !line.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_fetch_errors() {
+        assert_eq!(classify("fatal: couldn't find remote ref main\n"), RemoteState::Empty);
+        assert_eq!(classify("remote: Repository not found.\nfatal: repository 'https://github.com/me/x/' not found\n"), RemoteState::NotFound);
+        assert_eq!(classify("fatal: Authentication failed for 'https://github.com/me/x/'\n"), RemoteState::Denied);
+        assert_eq!(
+            classify("fatal: unable to access 'https://github.com/me/x/': Could not resolve host: github.com\n"),
+            RemoteState::Unreachable("unable to access 'https://github.com/me/x/': Could not resolve host: github.com".to_string())
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::publish::Snippet;
+
+/// A `{{NAME}}` placeholder declared under `variables:` in a snippet's frontmatter.
+///
+/// Accepts either a bare name (`- PROJECT_NAME`) or a mapping with an optional
+/// description and default value.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(from = "VariableSpec")]
+pub struct TemplateVariable {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VariableSpec {
+    Name(String),
+    Full {
+        name: String,
+        description: Option<String>,
+        default: Option<String>,
+    },
+}
+
+impl From<VariableSpec> for TemplateVariable {
+    fn from(spec: VariableSpec) -> Self {
+        match spec {
+            VariableSpec::Name(name) => TemplateVariable { name, description: None, default: None },
+            VariableSpec::Full { name, description, default } => TemplateVariable { name, description, default },
+        }
+    }
+}
+
+/// Parse `--var key=value` arguments.
+pub fn parse_var_args(args: &[String]) -> Result<HashMap<String, String>> {
+    let mut values = HashMap::new();
+    for arg in args {
+        let Some((key, value)) = arg.split_once('=') else {
+            anyhow::bail!("Invalid --var '{}': expected key=value", arg);
+        };
+        values.insert(key.trim().to_string(), value.to_string());
+    }
+    Ok(values)
+}
+
+/// Replace each declared `{{NAME}}` placeholder with its value. Undeclared
+/// placeholders are left untouched, since they may be literal content.
+/// Placeholders are found in one pass over `content`, so a value that
+/// itself contains `{{OTHER}}` is inserted as is.
+pub fn render(content: &str, values: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let value = after.find("}}").and_then(|end| values.get(after[..end].trim()).map(|value| (end, value)));
+        match value {
+            Some((end, value)) => {
+                rendered.push_str(value);
+                rest = &after[end + 2..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Return a copy of `snippet` with its variables filled in from `provided`,
+/// prompting for any that are missing.
+pub fn render_snippet(snippet: &Snippet, provided: &HashMap<String, String>) -> Result<Snippet> {
+    if snippet.variables.is_empty() {
+        return Ok(snippet.clone());
+    }
+
+    let unknown: Vec<&String> = provided.keys()
+        .filter(|key| !snippet.variables.iter().any(|v| &v.name == *key))
+        .collect();
+    if !unknown.is_empty() {
+        println!("⚠️  Ignoring values for undeclared variables: {}",
+            unknown.iter().map(|k| k.as_str()).collect::<Vec<_>>().join(", "));
+    }
+
+    let mut values = HashMap::new();
+    for variable in &snippet.variables {
+        let value = match provided.get(&variable.name) {
+            Some(value) => value.clone(),
+            None => prompt_for(variable)?,
+        };
+        values.insert(variable.name.clone(), value);
+    }
+
+    Ok(Snippet {
+        content: render(&snippet.content, &values),
+        ..snippet.clone()
+    })
+}
+
+fn prompt_for(variable: &TemplateVariable) -> Result<String> {
+    loop {
+        let question = match &variable.description {
+            Some(desc) => format!("🔧 {} ({})", variable.name, desc),
+            None => format!("🔧 {}", variable.name),
+        };
+        let input = crate::prompt::input(&question, variable.default.as_deref())?;
+        if !input.is_empty() {
+            return Ok(input);
+        }
+        println!("❌ A value is required for {}", variable.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_replaces_declared_placeholders_only() {
+        let mut values = HashMap::new();
+        values.insert("PROJECT_NAME".to_string(), "acme".to_string());
+        let rendered = render("Project {{PROJECT_NAME}} / {{ PROJECT_NAME }} / {{OTHER}}", &values);
+        assert_eq!(rendered, "Project acme / acme / {{OTHER}}");
+    }
+
+    #[test]
+    fn test_render_does_not_rescan_values() {
+        let mut values = HashMap::new();
+        values.insert("GREETING".to_string(), "hello {{NAME}}".to_string());
+        values.insert("NAME".to_string(), "world".to_string());
+        assert_eq!(render("{{GREETING}}, {{ NAME }}", &values), "hello {{NAME}}, world");
+        assert_eq!(render("{{{NAME}}} {{NAME", &values), "{world} {{NAME");
+    }
+
+    #[test]
+    fn test_variable_spec_forms() {
+        let vars: Vec<TemplateVariable> = serde_yaml::from_str(
+            "- LANGUAGE\n- name: PROJECT_NAME\n  description: Project name\n  default: app\n",
+        ).unwrap();
+        assert_eq!(vars[0].name, "LANGUAGE");
+        assert!(vars[0].default.is_none());
+        assert_eq!(vars[1].default.as_deref(), Some("app"));
+    }
+}
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+use crate::limits::estimate_tokens;
+use crate::manifest::Manifest;
+
+/// Approximate token counts of one target file.
+#[derive(Debug, PartialEq)]
+struct FileTokens {
+    total: usize,
+    /// (short ID, tokens) of each snippet block, largest first
+    snippets: Vec<(String, usize)>,
+}
+
+/// `stats tokens`: how much of Claude's context every managed CLAUDE.md (or
+/// other target) takes up, each installed snippet's share of it, and the
+/// snippets costing the most across all of them.
+pub fn show(limit: usize) -> Result<()> {
+    let manifest = Manifest::load()?;
+    let mut targets: Vec<&str> = manifest.installs.iter().map(|record| record.target.as_str()).collect();
+    targets.sort();
+    targets.dedup();
+    if targets.is_empty() {
+        println!("ℹ️  No installs recorded on this machine yet");
+        return Ok(());
+    }
+
+    let name_of = |id: &str| manifest.installs.iter()
+        .find(|record| record.id == id)
+        .map(|record| record.name.clone())
+        .unwrap_or_else(|| id.to_string());
+
+    println!("📏 Approximate context size of managed files (~4 characters per token):");
+    let mut files = Vec::new();
+    for target in targets {
+        let Ok(content) = fs::read_to_string(target) else {
+            println!();
+            println!("  ⚠️  {} no longer exists; skipped", target);
+            continue;
+        };
+        let tokens = count(&content);
+        let from_snippets: usize = tokens.snippets.iter().map(|(_, n)| n).sum();
+        println!();
+        println!("  {:>7}  {} ({}% from {} snippet(s))", format!("~{}", tokens.total), target, percent(from_snippets, tokens.total), tokens.snippets.len());
+        for (id, n) in &tokens.snippets {
+            println!("  {:>7}    {:>3}%  {} ({})", format!("~{}", n), percent(*n, tokens.total), name_of(id), id);
+        }
+        files.push((target, tokens));
+    }
+
+    let total: usize = files.iter().map(|(_, tokens)| tokens.total).sum();
+    let mut consumers: HashMap<&str, (usize, usize)> = HashMap::new();
+    for (id, n) in files.iter().flat_map(|(_, tokens)| &tokens.snippets) {
+        let entry = consumers.entry(id).or_default();
+        entry.0 += n;
+        entry.1 += 1;
+    }
+    let mut consumers: Vec<(&str, (usize, usize))> = consumers.into_iter().collect();
+    consumers.sort_by(|a, b| b.1.0.cmp(&a.1.0).then_with(|| a.0.cmp(b.0)));
+
+    println!();
+    println!("🧮 Total: ~{} tokens across {} file(s)", total, files.len());
+    if consumers.is_empty() {
+        return Ok(());
+    }
+    println!();
+    println!("🐘 Biggest context consumers:");
+    for (rank, (id, (n, copies))) in consumers.iter().take(limit).enumerate() {
+        println!("  {:>2}. {:>7}  {} ({}), in {} file(s)", rank + 1, format!("~{}", n), name_of(id), id, copies);
+    }
+    println!("💡 Trim one with 'repo edit', or remove it where it isn't needed with 'uninstall'");
+    Ok(())
+}
+
+fn count(content: &str) -> FileTokens {
+    let mut snippets: Vec<(String, usize)> = crate::install::marked_blocks(content).into_iter()
+        .map(|(id, body)| (id, estimate_tokens(&body)))
+        .collect();
+    snippets.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    FileTokens { total: estimate_tokens(content), snippets }
+}
+
+fn percent(part: usize, whole: usize) -> usize {
+    (part * 100).checked_div(whole).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_snippet_shares() {
+        let content = "# Project\n\n<!-- SNIPPET_START:1a2b -->\nRun the tests\n<!-- SNIPPET_END:1a2b -->\n\n<!-- SNIPPET_START:9f8e -->\nUse spaces, not tabs, everywhere in this repository\n<!-- SNIPPET_END:9f8e -->\n";
+        let tokens = count(content);
+        assert_eq!(tokens.total, estimate_tokens(content));
+        assert_eq!(tokens.snippets.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(), ["9f8e", "1a2b"]);
+        assert_eq!(tokens.snippets[1].1, estimate_tokens("Run the tests"));
+        assert_eq!(percent(1, 0), 0);
+    }
+}
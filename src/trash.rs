@@ -0,0 +1,231 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::assets;
+use crate::github::ChangeKind;
+use crate::publish::{self, Snippet};
+
+/// Folder at the top of a repository (beside `snippets/`, so nothing reads
+/// it as a snippet) holding deleted snippets until the trash is emptied. It
+/// is committed like everything else, so a deletion can be undone anywhere.
+const TRASH_DIR: &str = ".trash";
+/// Each deletion goes to a folder named after when it happened.
+const BATCH_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// A deleted snippet file, still in the trash.
+pub struct Trashed {
+    /// Where it is now
+    path: PathBuf,
+    /// Where it was, relative to `snippets/`
+    relative: PathBuf,
+    deleted_at: chrono::NaiveDateTime,
+    snippet: Option<Snippet>,
+}
+
+impl Trashed {
+    fn label(&self) -> String {
+        match &self.snippet {
+            Some(snippet) => format!("{} ({})", snippet.name, crate::ids::short(&snippet.id)),
+            None => self.relative.display().to_string(),
+        }
+    }
+}
+
+/// Move the snippet file `file` (and its attachments) from `snippets_dir`
+/// into the repository's trash instead of deleting it.
+pub fn move_to_trash(snippets_dir: &Path, file: &Path, snippet: Option<&Snippet>) -> Result<()> {
+    let batch = trash_dir(snippets_dir).join(chrono::Local::now().format(BATCH_FORMAT).to_string());
+    let relative = file.strip_prefix(snippets_dir).unwrap_or(file.file_name().map(Path::new).unwrap_or(file));
+    let target = batch.join(relative);
+    fs::create_dir_all(target.parent().unwrap_or(&batch))?;
+    fs::rename(file, &target).with_context(|| format!("Could not move {} to the trash", file.display()))?;
+    if let Some(snippet) = snippet {
+        let assets_dir = assets::assets_dir(snippets_dir, &snippet.id);
+        if assets_dir.exists() {
+            let trashed_assets = assets::assets_dir(&batch, &snippet.id);
+            fs::create_dir_all(trashed_assets.parent().unwrap_or(&batch))?;
+            fs::rename(&assets_dir, &trashed_assets)?;
+        }
+    }
+    Ok(())
+}
+
+/// `repo trash list`: what can still be restored, newest deletions first.
+pub fn list(repo_dir: &Path, repo_name: &str) -> Result<()> {
+    let trashed = trashed(&repo_dir.join("snippets"))?;
+    if trashed.is_empty() {
+        println!("🗑️  The trash of '{}' is empty", repo_name);
+        return Ok(());
+    }
+    println!("🗑️  {} deleted snippet(s) in '{}':", trashed.len(), repo_name);
+    for item in &trashed {
+        println!("  • {} — deleted {}", item.label(), item.deleted_at.format("%Y-%m-%d %H:%M"));
+    }
+    println!("💡 Bring one back with 'claude-md-snippets repo restore <name or ID>'");
+    Ok(())
+}
+
+/// `repo restore`: put the deleted snippet matching `query` (by ID prefix or
+/// name) back where it was, with its attachments.
+pub async fn restore(repo_dir: &Path, repo_name: &str, query: &str) -> Result<()> {
+    let snippets_dir = repo_dir.join("snippets");
+    let trashed = trashed(&snippets_dir)?;
+    let query_lower = query.to_lowercase();
+    let matching: Vec<&Trashed> = trashed.iter()
+        .filter(|item| match &item.snippet {
+            Some(snippet) => crate::ids::matches_prefix(&snippet.id, query) || snippet.name.to_lowercase().contains(&query_lower),
+            None => item.relative.to_string_lossy().to_lowercase().contains(&query_lower),
+        })
+        .collect();
+    let item = match matching.as_slice() {
+        [] => anyhow::bail!("Nothing in the trash of '{}' matches '{}'; 'repo trash list' shows what's there", repo_name, query),
+        [item] => *item,
+        _ => {
+            let candidates: Vec<(String, String)> = matching.iter()
+                .map(|item| (item.label(), format!("deleted {}", item.deleted_at.format("%Y-%m-%d %H:%M"))))
+                .collect();
+            let Some(choice) = crate::prompt::choose("Several deleted snippets match; which one?", &candidates)? else {
+                println!("❌ Restore cancelled");
+                return Ok(());
+            };
+            matching[choice]
+        }
+    };
+
+    let target = snippets_dir.join(&item.relative);
+    if target.exists() {
+        anyhow::bail!("{} exists again; rename or delete it before restoring", target.display());
+    }
+    fs::create_dir_all(target.parent().unwrap_or(&snippets_dir))?;
+    fs::rename(&item.path, &target)?;
+    if let Some(snippet) = &item.snippet {
+        let batch = batch_of(&snippets_dir, &item.path);
+        let trashed_assets = assets::assets_dir(&batch, &snippet.id);
+        if trashed_assets.exists() {
+            let assets_dir = assets::assets_dir(&snippets_dir, &snippet.id);
+            fs::create_dir_all(assets_dir.parent().unwrap_or(&snippets_dir))?;
+            fs::rename(&trashed_assets, &assets_dir)?;
+        }
+    }
+    remove_empty_dirs(&trash_dir(&snippets_dir));
+    println!("♻️  Restored {} to {}", item.label(), target.display());
+
+    crate::edit::finish(repo_name, &format!("Restore snippet {}", item.label()), ChangeKind::Edit).await;
+    Ok(())
+}
+
+/// `repo trash empty`: delete what's in the trash for good, everything or
+/// only what was deleted more than `older_than` ago.
+pub async fn empty(repo_dir: &Path, repo_name: &str, older_than: Option<chrono::Duration>) -> Result<()> {
+    let snippets_dir = repo_dir.join("snippets");
+    let now = chrono::Local::now().naive_local();
+    let doomed: Vec<Trashed> = trashed(&snippets_dir)?.into_iter()
+        .filter(|item| older_than.is_none_or(|age| now.signed_duration_since(item.deleted_at) > age))
+        .collect();
+    if doomed.is_empty() {
+        println!("🗑️  Nothing to remove from the trash of '{}'", repo_name);
+        return Ok(());
+    }
+    for item in &doomed {
+        println!("  • {}", item.label());
+    }
+    if !crate::prompt::confirm(&format!("❓ Permanently delete these {} snippet(s)? This can't be undone", doomed.len()), false)? {
+        println!("❌ Cancelled");
+        return Ok(());
+    }
+    let batches: std::collections::BTreeSet<PathBuf> = doomed.iter().map(|item| batch_of(&snippets_dir, &item.path)).collect();
+    for batch in batches {
+        fs::remove_dir_all(&batch)?;
+    }
+    remove_empty_dirs(&trash_dir(&snippets_dir));
+    println!("✅ Removed {} snippet(s) from the trash", doomed.len());
+
+    crate::edit::finish(repo_name, &format!("Empty trash ({} snippet(s))", doomed.len()), ChangeKind::Edit).await;
+    Ok(())
+}
+
+fn trash_dir(snippets_dir: &Path) -> PathBuf {
+    snippets_dir.parent().unwrap_or(snippets_dir).join(TRASH_DIR)
+}
+
+/// The deletion folder `path` (somewhere inside the trash) belongs to.
+fn batch_of(snippets_dir: &Path, path: &Path) -> PathBuf {
+    let trash = trash_dir(snippets_dir);
+    match path.strip_prefix(&trash).ok().and_then(|relative| relative.components().next()) {
+        Some(batch) => trash.join(batch),
+        None => trash,
+    }
+}
+
+/// Every snippet file in the trash, newest deletions first.
+fn trashed(snippets_dir: &Path) -> Result<Vec<Trashed>> {
+    let Ok(batches) = fs::read_dir(trash_dir(snippets_dir)) else {
+        return Ok(Vec::new());
+    };
+    let mut items = Vec::new();
+    for batch in batches.filter_map(|entry| entry.ok()) {
+        let name = batch.file_name().to_string_lossy().into_owned();
+        let Ok(deleted_at) = chrono::NaiveDateTime::parse_from_str(&name, BATCH_FORMAT) else { continue };
+        let batch_dir = batch.path();
+        for path in markdown_files(&batch_dir) {
+            let Ok(relative) = path.strip_prefix(&batch_dir).map(Path::to_path_buf) else { continue };
+            if relative.starts_with("assets") {
+                continue;
+            }
+            let snippet = fs::read_to_string(&path).ok().and_then(|content| publish::parse_markdown_frontmatter(&content).ok());
+            items.push(Trashed { path, relative, deleted_at, snippet });
+        }
+    }
+    items.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at).then(a.relative.cmp(&b.relative)));
+    Ok(items)
+}
+
+fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(dir) else { return files };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            files.extend(markdown_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "md") {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Drop folders left empty by a restore, the trash itself included.
+fn remove_empty_dirs(dir: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        if entry.path().is_dir() {
+            remove_empty_dirs(&entry.path());
+        }
+    }
+    // Fails, as intended, while anything is left inside
+    let _ = fs::remove_dir(dir);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trash_and_list() {
+        let repo = std::env::temp_dir().join(format!("trash-test-{}", std::process::id()));
+        let snippets_dir = repo.join("snippets");
+        fs::create_dir_all(snippets_dir.join("rust")).unwrap();
+        let file = snippets_dir.join("rust").join("tests-7Hq2mXbK.md");
+        fs::write(&file, "---\nid: 7Hq2mXbK\nname: Tests\n---\n\nRun the tests\n").unwrap();
+        let snippet = publish::parse_markdown_frontmatter(&fs::read_to_string(&file).unwrap()).unwrap();
+
+        move_to_trash(&snippets_dir, &file, Some(&snippet)).unwrap();
+        assert!(!file.exists());
+        let items = trashed(&snippets_dir).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].relative, Path::new("rust/tests-7Hq2mXbK.md"));
+        assert_eq!(items[0].label(), "Tests (7Hq2mXbK)");
+        assert_eq!(batch_of(&snippets_dir, &items[0].path).parent(), Some(repo.join(TRASH_DIR).as_path()));
+        fs::remove_dir_all(&repo).unwrap();
+    }
+}
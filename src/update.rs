@@ -0,0 +1,131 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use crate::install::{self, Destination};
+use crate::manifest::{InstallRecord, Manifest, content_hash};
+use crate::perf::{Category, TimedCommand};
+use crate::store::SnippetStore;
+
+/// `install --pin`: keep the install of `id` in `target` at the repository's
+/// current commit until `unpin`.
+pub fn pin(id: &str, target: &Path) -> Result<()> {
+    let commit = current_commit()?;
+    let target = target.canonicalize().unwrap_or(target.to_path_buf());
+    Manifest::set_pin(crate::ids::short(id), &target, Some(&commit))?;
+    println!("📌 Pinned at {}; 'update' leaves it alone until 'unpin'", commit);
+    Ok(())
+}
+
+/// `update`: bring every snippet installed in the target up to the current
+/// repository version. Pinned installs are skipped unless `include_pinned`,
+/// and blocks edited by hand are never overwritten.
+pub async fn update(destination: Destination, include_pinned: bool) -> Result<()> {
+    let target_path = destination.resolve()?;
+    let Ok(content) = fs::read_to_string(&target_path) else {
+        anyhow::bail!("{} not found", target_path.display());
+    };
+    let absolute_path = target_path.canonicalize().unwrap_or(target_path.clone());
+    let blocks = install::marked_blocks(&content);
+    let snippets = crate::subscriptions::Followed::load()?.load_all()?;
+    let manifest = Manifest::load()?;
+
+    let mut updates = Vec::new();
+    let mut skipped = 0;
+    for record in manifest.installs_in(&absolute_path) {
+        let Some((_, body)) = blocks.iter().find(|(id, _)| *id == record.id) else { continue };
+        let Some(snippet) = snippets.iter().find(|s| s.id.starts_with(&record.id)) else { continue };
+        if content_hash(snippet.content.trim()) == record.content_hash {
+            continue;
+        }
+        match &record.pinned {
+            Some(commit) if !include_pinned => {
+                println!("📌 {} is pinned at {}; skipped (--include-pinned updates it too)", record.name, commit);
+                skipped += 1;
+                continue;
+            }
+            _ => {}
+        }
+        if content_hash(body) != record.content_hash {
+            println!("✏️  {} was edited in place; skipped so your changes stay (see 'diff \"{}\"')", record.name, record.name);
+            skipped += 1;
+            continue;
+        }
+        updates.push((record, snippet, body));
+    }
+
+    if updates.is_empty() {
+        match skipped {
+            0 => println!("✅ Everything in {} is up to date", target_path.display()),
+            _ => println!("ℹ️  Nothing else to update in {}", target_path.display()),
+        }
+        return Ok(());
+    }
+    println!("🆕 Newer versions in the repository:");
+    for (record, snippet, body) in &updates {
+        println!();
+        println!("  • {} ({})", snippet.name, record.id);
+        crate::diff::print(body, snippet.content.trim(), crate::diff::Layout::Unified);
+    }
+    println!();
+    if !crate::prompt::confirm(&format!("Update {} snippet(s) in {}?", updates.len(), absolute_path.display()), true)? {
+        println!("❌ Update cancelled");
+        return Ok(());
+    }
+
+    let mut updated = 0;
+    for (record, snippet, _) in updates {
+        println!("📦 {}", snippet.name);
+        if install::install_confirmed(snippet, &target_path, true, &HashMap::new(), &Default::default()).await? {
+            updated += 1;
+            // A pinned install updated on purpose stays pinned, at the new version
+            if record.pinned.is_some() {
+                pin(&record.id, &absolute_path)?;
+            }
+        }
+    }
+    println!("✅ Updated {} snippet(s)", updated);
+    Ok(())
+}
+
+/// `unpin`: let `update` change the install matching `query` again.
+pub async fn unpin(query: &str, destination: Destination) -> Result<()> {
+    let target_path = destination.resolve()?;
+    let absolute_path = target_path.canonicalize().unwrap_or(target_path.clone());
+    let manifest = Manifest::load()?;
+    let record = find_record(&manifest, &absolute_path, query).await?;
+    if record.pinned.is_none() {
+        println!("ℹ️  {} is not pinned in {}", record.name, target_path.display());
+        return Ok(());
+    }
+    Manifest::set_pin(&record.id, &absolute_path, None)?;
+    println!("✅ Unpinned {}; 'update' will bring it up to date", record.name);
+    Ok(())
+}
+
+/// The install in `target` an installed ID, exact name or search query refers to.
+async fn find_record<'a>(manifest: &'a Manifest, target: &'a Path, query: &str) -> Result<&'a InstallRecord> {
+    let by_id_or_name = manifest.installs_in(target)
+        .find(|record| (query.len() >= 4 && record.id.starts_with(query)) || record.name.eq_ignore_ascii_case(query));
+    if let Some(record) = by_id_or_name {
+        return Ok(record);
+    }
+    let Some(snippet) = install::find_best_match(&crate::store::load_snippets()?, query).await? else {
+        anyhow::bail!("No snippet matches '{}'", query);
+    };
+    manifest.installs_in(target)
+        .find(|record| record.id == crate::ids::short(&snippet.id))
+        .ok_or_else(|| anyhow::anyhow!("'{}' is not installed in {}", snippet.name, target.display()))
+}
+
+fn current_commit() -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(crate::publish::get_snippets_dir()?)
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .timed_output(Category::Git)?;
+    if !output.status.success() {
+        anyhow::bail!("--pin needs the snippets repository to have at least one commit");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
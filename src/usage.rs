@@ -0,0 +1,153 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::lockfile::{FileLock, atomic_write};
+use crate::publish::{Snippet, get_app_dir, get_repos_dir, repo_names};
+
+/// Every install on this machine, oldest first, kept after uninstalls so
+/// `stats` can tell popular snippets from ones nobody uses.
+#[derive(Serialize, Deserialize, Default)]
+pub struct UsageLog {
+    #[serde(default)]
+    pub installs: Vec<UsageEvent>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct UsageEvent {
+    pub installed_at: String,
+    pub target: String,
+    /// Repository the snippet was installed from, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    pub id: String,
+    pub name: String,
+}
+
+impl UsageLog {
+    pub fn load() -> Result<Self> {
+        let path = get_usage_path()?;
+        if !path.exists() {
+            return Ok(UsageLog::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&path)?)?)
+    }
+
+    /// Note that `snippet` was just installed into `target`.
+    pub fn record(target: &Path, snippet: &Snippet) -> Result<()> {
+        let path = get_usage_path()?;
+        let _lock = FileLock::acquire(&path)?;
+        let mut log = UsageLog::load()?;
+        log.installs.push(UsageEvent {
+            installed_at: chrono::Utc::now().to_rfc3339(),
+            target: target.display().to_string(),
+            repo: crate::config::get_default_repo_name().ok(),
+            id: snippet.id.clone(),
+            name: snippet.name.clone(),
+        });
+        atomic_write(&path, &serde_json::to_string_pretty(&log)?)
+    }
+
+    /// Drop the installs of snippet `id` (the short ID of the markers) into
+    /// `target`, for `uninstall --purge`. Returns how many were removed.
+    pub fn forget(id: &str, target: &Path) -> Result<usize> {
+        let path = get_usage_path()?;
+        let _lock = FileLock::acquire(&path)?;
+        let mut log = UsageLog::load()?;
+        let target = target.display().to_string();
+        let before = log.installs.len();
+        log.installs.retain(|event| !(event.target == target && crate::ids::short(&event.id) == id));
+        let removed = before - log.installs.len();
+        if removed > 0 {
+            atomic_write(&path, &serde_json::to_string_pretty(&log)?)?;
+        }
+        Ok(removed)
+    }
+
+    /// Snippets by number of installs, most installed first (ties by name).
+    fn most_installed(&self) -> Vec<(&UsageEvent, usize)> {
+        let mut counts: HashMap<&str, (&UsageEvent, usize)> = HashMap::new();
+        for event in &self.installs {
+            let entry = counts.entry(&event.id).or_insert((event, 0));
+            // The latest event carries the current name
+            *entry = (event, entry.1 + 1);
+        }
+        let mut ranked: Vec<(&UsageEvent, usize)> = counts.into_values().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+        ranked
+    }
+
+    /// Snippets of a repository that were never installed on this machine.
+    fn never_used<'a>(&self, snippets: &'a [Snippet]) -> Vec<&'a Snippet> {
+        snippets
+            .iter()
+            .filter(|snippet| !self.installs.iter().any(|event| event.id == snippet.id))
+            .collect()
+    }
+}
+
+/// `stats`: the most installed snippets, the latest installs, and per
+/// repository the snippets never installed here, as candidates for pruning.
+pub fn show(limit: usize) -> Result<()> {
+    let log = UsageLog::load()?;
+    if log.installs.is_empty() {
+        println!("ℹ️  No installs recorded yet; 'install' records each one");
+    } else {
+        println!("🏆 Most installed:");
+        for (event, count) in log.most_installed().into_iter().take(limit) {
+            let repo = event.repo.as_deref().map(|repo| format!(", {}", repo)).unwrap_or_default();
+            println!("  {:>4}  {} ({}{})", count, event.name, crate::ids::short(&event.id), repo);
+        }
+        println!();
+        println!("🕒 Recently installed:");
+        for event in log.installs.iter().rev().take(limit) {
+            println!("  {}  {} → {}", event.installed_at.get(..10).unwrap_or(&event.installed_at), event.name, event.target);
+        }
+    }
+
+    for repo in repo_names()? {
+        let snippets = crate::store::load_snippets_from(&get_repos_dir()?.join(&repo).join("snippets"))?;
+        if snippets.is_empty() {
+            continue;
+        }
+        let unused = log.never_used(&snippets);
+        println!();
+        if unused.is_empty() {
+            println!("✅ Every snippet in '{}' has been installed", repo);
+            continue;
+        }
+        println!("💤 Never installed from '{}' ({} of {}):", repo, unused.len(), snippets.len());
+        for snippet in unused {
+            println!("  • {} ({}, created {})", snippet.name, crate::ids::short(&snippet.id), snippet.created_at.get(..10).unwrap_or(&snippet.created_at));
+        }
+    }
+    Ok(())
+}
+
+fn get_usage_path() -> Result<std::path::PathBuf> {
+    Ok(get_app_dir()?.join("usage.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_summary() {
+        let event = |id: &str, name: &str| UsageEvent {
+            installed_at: String::new(),
+            target: "/work/app/CLAUDE.md".to_string(),
+            repo: None,
+            id: id.to_string(),
+            name: name.to_string(),
+        };
+        let log = UsageLog { installs: vec![event("1a2b", "Tests"), event("5e6f", "Formatting"), event("1a2b", "Testing")] };
+
+        let ranked: Vec<(&str, usize)> = log.most_installed().into_iter().map(|(event, count)| (event.name.as_str(), count)).collect();
+        assert_eq!(ranked, vec![("Testing", 2), ("Formatting", 1)]);
+
+        let snippets = vec![crate::publish::test_snippet("1a2b", "1a2b", ""), crate::publish::test_snippet("9f8e", "9f8e", "")];
+        assert_eq!(log.never_used(&snippets).iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), ["9f8e"]);
+    }
+}
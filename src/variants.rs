@@ -0,0 +1,90 @@
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::publish::{self, Snippet};
+
+/// The variant for `language`: an exact match first ("pt-br"), then one for
+/// the base language ("pt"). Codes are compared case-insensitively, with
+/// locale forms like `es_ES.UTF-8` read as "es-es".
+pub fn pick<'a>(variants: &'a BTreeMap<String, String>, language: &str) -> Option<(&'a str, &'a str)> {
+    let wanted = normalize(language);
+    let base = wanted.split('-').next().unwrap_or(&wanted).to_string();
+    let find = |code: &str| {
+        variants
+            .iter()
+            .find(|(lang, _)| normalize(lang) == code)
+            .map(|(lang, file)| (lang.as_str(), file.as_str()))
+    };
+    find(&wanted).or_else(|| find(&base))
+}
+
+fn normalize(language: &str) -> String {
+    let language = language.split('.').next().unwrap_or(language);
+    language.trim().replace('_', "-").to_lowercase()
+}
+
+/// `snippet` with its content swapped for the variant in the configured
+/// language, if it has one, and that variant's label. Otherwise (or if the
+/// variant file is missing) the original is used.
+pub fn localize(snippet: &Snippet, snippets_dir: &Path, language: Option<&str>) -> Result<(Snippet, Option<String>)> {
+    let Some((lang, file)) = language.and_then(|language| pick(&snippet.variants, language)) else {
+        return Ok((snippet.clone(), None));
+    };
+
+    let path = snippets_dir.join(file);
+    let Some(content) = read_variant(&path) else {
+        println!("⚠️  Variant '{}' not found at {}, using the original", lang, path.display());
+        return Ok((snippet.clone(), None));
+    };
+    println!("🌐 Using the '{}' variant", lang);
+    Ok((Snippet { content, ..snippet.clone() }, Some(lang.to_string())))
+}
+
+/// `snippet` as its variant labelled `label` (e.g. `install --variant b`),
+/// compared case-insensitively.
+pub fn select(snippet: &Snippet, snippets_dir: &Path, label: &str) -> Result<Snippet> {
+    if label.eq_ignore_ascii_case(crate::experiments::ORIGINAL) {
+        return Ok(snippet.clone());
+    }
+    let Some((name, file)) = snippet.variants.iter().find(|(name, _)| name.eq_ignore_ascii_case(label)) else {
+        let labels: Vec<&str> = snippet.variants.keys().map(|name| name.as_str()).collect();
+        if labels.is_empty() {
+            anyhow::bail!("'{}' has no variants", snippet.name);
+        }
+        anyhow::bail!("'{}' has no variant '{}'; it has: {}, {}", snippet.name, label, crate::experiments::ORIGINAL, labels.join(", "));
+    };
+    let path = snippets_dir.join(file);
+    let content = read_variant(&path)
+        .ok_or_else(|| anyhow::anyhow!("Variant '{}' of '{}' not found at {}", name, snippet.name, path.display()))?;
+    println!("🧪 Using the '{}' variant", name);
+    Ok(Snippet { content, ..snippet.clone() })
+}
+
+/// A variant file's content. Variant files may be plain markdown or carry
+/// their own frontmatter.
+fn read_variant(path: &Path) -> Option<String> {
+    let text = fs::read_to_string(path).ok()?;
+    Some(match publish::parse_markdown_frontmatter(&text) {
+        Ok(variant) => variant.content,
+        Err(_) => text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_variant() {
+        let variants = BTreeMap::from([
+            ("es".to_string(), "variants/a.es.md".to_string()),
+            ("pt-BR".to_string(), "variants/a.pt-br.md".to_string()),
+        ]);
+        assert_eq!(pick(&variants, "es").unwrap().0, "es");
+        assert_eq!(pick(&variants, "es_MX.UTF-8").unwrap().0, "es");
+        assert_eq!(pick(&variants, "pt_BR").unwrap().0, "pt-BR");
+        assert!(pick(&variants, "pt").is_none());
+        assert!(pick(&variants, "de").is_none());
+    }
+}
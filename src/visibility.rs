@@ -0,0 +1,98 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use crate::publish::Snippet;
+
+/// Who a snippet may be shared with (its `visibility:` frontmatter field), and
+/// who a repository is shared with. Ordered from most to least restricted.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Only ever in your own private repositories
+    Private,
+    /// Fine for repositories shared with your team
+    Team,
+    /// Fine anywhere
+    Public,
+}
+
+impl Visibility {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Team => "team",
+            Visibility::Public => "public",
+        }
+    }
+
+    /// Read a frontmatter value. Anything unrecognised counts as private, so a
+    /// typo never lets a snippet out.
+    pub fn from_frontmatter(value: &str) -> Self {
+        match value.trim().to_lowercase().as_str() {
+            "public" => Visibility::Public,
+            "team" => Visibility::Team,
+            "private" => Visibility::Private,
+            other => {
+                tracing::warn!("Unknown visibility '{}', treating the snippet as private", other);
+                Visibility::Private
+            }
+        }
+    }
+}
+
+/// Snippets under `snippets_dir` too private for a repository shared at `level`.
+/// Snippets without a `visibility:` field go anywhere.
+pub fn too_private(snippets_dir: &Path, level: Visibility) -> Vec<(PathBuf, Snippet)> {
+    crate::scan::snippet_files(snippets_dir)
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            let snippet = crate::publish::parse_markdown_frontmatter(&content).ok()?;
+            Some((path, snippet))
+        })
+        .filter(|(_, snippet)| snippet.visibility.is_some_and(|visibility| visibility < level))
+        .collect()
+}
+
+/// Refuse to push `repo_name` while it holds snippets more private than the
+/// repository's configured visibility (`public` unless set otherwise).
+pub fn check_before_push(repo_name: &str, repo_dir: &Path) -> Result<()> {
+    let level = crate::config::Config::load()?.get_repo_visibility(repo_name);
+    let blocked = too_private(&repo_dir.join("snippets"), level);
+    if blocked.is_empty() {
+        return Ok(());
+    }
+
+    println!("🔒 Repository '{}' is {}, but these snippets are not:", repo_name, level.as_str());
+    for (path, snippet) in &blocked {
+        let visibility = snippet.visibility.map(Visibility::as_str).unwrap_or("public");
+        println!("  • {} ({}) - {}", snippet.name, visibility, path.strip_prefix(repo_dir).unwrap_or(path).display());
+    }
+    println!("💡 Move them to a private repository, or change their visibility with 'claude-md-snippets repo edit <query> --visibility <level>'");
+    println!("💡 If the repository itself is private, record that with 'claude-md-snippets config set-visibility {} private'", repo_name);
+    anyhow::bail!("Refusing to push {} snippet(s) to '{}'", blocked.len(), repo_name);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_too_private() {
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path().join("snippets");
+        fs::create_dir(&dir).unwrap();
+        for (file, visibility) in [("a.md", Some("private")), ("b.md", Some("team")), ("c.md", None), ("d.md", Some("secret"))] {
+            let field = visibility.map(|v| format!("visibility: {}\n", v)).unwrap_or_default();
+            fs::write(dir.join(file), format!("---\nid: {}\nname: {}\n{}---\n\nBody\n", file, file, field)).unwrap();
+        }
+
+        let names = |level| -> Vec<String> {
+            too_private(&dir, level).into_iter().map(|(_, snippet)| snippet.name).collect()
+        };
+        assert_eq!(names(Visibility::Public), vec!["a.md", "b.md", "d.md"]);
+        assert_eq!(names(Visibility::Team), vec!["a.md", "d.md"]);
+        assert!(names(Visibility::Private).is_empty());
+    }
+}
@@ -0,0 +1,186 @@
+use anyhow::{Context, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use crate::publish::{get_app_dir, get_repos_dir};
+
+/// How `watch` runs: how long edits settle before a sync, and how often the
+/// remotes are pulled while nothing changes locally.
+pub struct WatchOptions {
+    pub debounce: Duration,
+    pub pull_every: Duration,
+}
+
+/// `watch`: sync every repository whose files change (edits made straight in
+/// an editor included), once the changes have settled, and pull all of them
+/// periodically. Runs until interrupted.
+pub async fn watch(options: WatchOptions) -> Result<()> {
+    let repos_dir = get_repos_dir()?;
+    if !repos_dir.is_dir() {
+        anyhow::bail!("No repositories to watch in {}", repos_dir.display());
+    }
+
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx).context("Could not start watching files")?;
+    watcher.watch(&repos_dir, RecursiveMode::Recursive)
+        .with_context(|| format!("Could not watch {}", repos_dir.display()))?;
+    println!("👀 Watching {} (sync {}s after changes settle, pull every {} min); Ctrl-C to stop",
+        repos_dir.display(), options.debounce.as_secs(), options.pull_every.as_secs() / 60);
+
+    let mut pending: BTreeSet<String> = BTreeSet::new();
+    let mut last_pull = Instant::now();
+    loop {
+        // Wait for edits to settle, or until the next pull is due
+        let timeout = if pending.is_empty() { options.pull_every.saturating_sub(last_pull.elapsed()) } else { options.debounce };
+        match rx.recv_timeout(timeout) {
+            // Reads (git status, our own scans) open files too; only changes count
+            Ok(Ok(event)) if !matches!(event.kind, EventKind::Access(_)) => {
+                pending.extend(changed_repos(&repos_dir, event.paths.iter().map(|path| path.as_path())));
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::warn!("File watching error: {}", e),
+            Err(RecvTimeoutError::Timeout) if !pending.is_empty() => {
+                for repo in std::mem::take(&mut pending) {
+                    println!("✏️  {} changed at {}", repo, chrono::Local::now().format("%H:%M:%S"));
+                    crate::config::override_default_repo(&repo);
+                    crate::index::refresh_index_quietly();
+                    crate::github::sync_after_change(&format!("Update snippets in '{}'", repo), crate::github::ChangeKind::Watched).await;
+                }
+                // Whatever the sync itself wrote (pulled files) is not a new edit
+                while rx.try_recv().is_ok() {}
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                pull_all(&repos_dir).await;
+                while rx.try_recv().is_ok() {}
+                last_pull = Instant::now();
+            }
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("File watching stopped unexpectedly"),
+        }
+    }
+}
+
+/// Pull every repository, skipping quietly those whose remote is offline.
+async fn pull_all(repos_dir: &Path) {
+    let Ok(entries) = fs::read_dir(repos_dir) else { return };
+    let mut repos: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join(".git").exists())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    repos.sort();
+    for repo in repos {
+        if !crate::github::remote_reachable(&crate::process::SystemRunner, &repos_dir.join(&repo)) {
+            tracing::info!("'{}' is offline; skipping its periodic pull", repo);
+            continue;
+        }
+        crate::config::override_default_repo(&repo);
+        if let Err(e) = crate::github::pull_snippets(false).await {
+            println!("⚠️  Could not pull '{}': {}", repo, e);
+        }
+    }
+}
+
+/// Repositories (by directory name) with a changed file among `paths`,
+/// ignoring git's own files and editor swap, backup and temporary files.
+fn changed_repos<'a>(repos_dir: &Path, paths: impl Iterator<Item = &'a Path>) -> BTreeSet<String> {
+    paths
+        .filter_map(|path| path.strip_prefix(repos_dir).ok())
+        .filter(|relative| {
+            let hidden = relative.components().any(|component| {
+                matches!(component, Component::Normal(name) if name.to_string_lossy().starts_with('.'))
+            });
+            let name = relative.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+            let scratch = name.ends_with('~') || name.ends_with(".swp") || name.ends_with(".tmp");
+            relative.components().count() > 1 && !hidden && !scratch
+        })
+        .filter_map(|relative| match relative.components().next() {
+            Some(Component::Normal(repo)) => Some(repo.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `watch --daemon`: start the watcher in the background, logging to
+/// `<app dir>/logs/watch.log`, and remember its process ID for `watch --stop`.
+pub fn start_daemon(args: &[String]) -> Result<()> {
+    let pid_file = pid_file()?;
+    if let Some(pid) = running_pid(&pid_file) {
+        println!("ℹ️  The watcher is already running (PID {})", pid);
+        return Ok(());
+    }
+
+    let logs_dir = get_app_dir()?.join("logs");
+    fs::create_dir_all(&logs_dir)?;
+    let log_path = logs_dir.join("watch.log");
+    let log = fs::OpenOptions::new().create(true).append(true).open(&log_path)?;
+    let child = std::process::Command::new(std::env::current_exe()?)
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log)
+        .spawn()
+        .context("Could not start the watcher")?;
+    fs::write(&pid_file, child.id().to_string())?;
+
+    println!("✅ Watching in the background (PID {})", child.id());
+    println!("📄 Log: {}", log_path.display());
+    println!("💡 Stop it with 'claude-md-snippets watch --stop'");
+    Ok(())
+}
+
+/// `watch --stop`: end the background watcher.
+pub fn stop_daemon() -> Result<()> {
+    let pid_file = pid_file()?;
+    let Some(pid) = running_pid(&pid_file) else {
+        println!("ℹ️  No background watcher is running");
+        let _ = fs::remove_file(&pid_file);
+        return Ok(());
+    };
+    let status = std::process::Command::new("kill").arg(pid.to_string()).status()
+        .context("Could not run 'kill'")?;
+    if !status.success() {
+        anyhow::bail!("Could not stop the watcher (PID {})", pid);
+    }
+    fs::remove_file(&pid_file)?;
+    println!("✅ Stopped the background watcher (PID {})", pid);
+    Ok(())
+}
+
+fn pid_file() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("watch.pid"))
+}
+
+/// The recorded watcher's PID, if that process is still alive.
+fn running_pid(pid_file: &Path) -> Option<u32> {
+    let pid: u32 = fs::read_to_string(pid_file).ok()?.trim().parse().ok()?;
+    let alive = std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success());
+    alive.then_some(pid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changed_repos() {
+        let repos_dir = Path::new("/home/me/.claude-md-snippets/repos");
+        let paths: Vec<PathBuf> = [
+            "work/snippets/a.md",
+            "work/snippets/.a.md.swp",
+            "personal/.git/index",
+            "personal/snippets/b.md~",
+            "team/snippets/go/c.md",
+            "stray.txt",
+        ].iter().map(|path| repos_dir.join(path)).collect();
+
+        let repos: Vec<String> = changed_repos(repos_dir, paths.iter().map(|path| path.as_path())).into_iter().collect();
+        assert_eq!(repos, ["team", "work"]);
+    }
+}
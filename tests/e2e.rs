@@ -0,0 +1,168 @@
+//! End-to-end tests driving the real binary against a fake git remote and fake
+//! `claude`/`gh`. Run with `cargo test --features e2e`.
+
+#![cfg(feature = "e2e")]
+
+mod support;
+
+use support::Harness;
+
+fn publish(h: &Harness, content: &str, name: &str) {
+    h.run_ok(&["publish", content, "--name", name], "");
+}
+
+/// ID of the only snippet published so far, read from its frontmatter.
+fn only_snippet_id(h: &Harness) -> String {
+    let snippets_dir = h.repo_dir().join("snippets");
+    let entries: Vec<_> = std::fs::read_dir(&snippets_dir)
+        .expect("snippets dir")
+        .map(|entry| entry.expect("dir entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    assert_eq!(entries.len(), 1, "expected exactly one snippet in {}", snippets_dir.display());
+    let content = std::fs::read_to_string(&entries[0]).expect("read snippet");
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("id: "))
+        .expect("id in frontmatter")
+        .trim_matches(['"', '\''])
+        .to_string()
+}
+
+#[test]
+fn publish_sync_install_uninstall_round_trip() {
+    let h = Harness::new();
+    h.init_remote();
+
+    publish(&h, "# Testing rules\n\n- Run the full test suite before committing", "Testing rules");
+    let id = only_snippet_id(&h);
+
+    h.git(&h.repo_dir(), &["remote", "add", "origin", h.remote.to_str().unwrap()]);
+    h.run_ok(&["sync"], "");
+    let pushed = h.git(&h.remote, &["ls-tree", "-r", "--name-only", "main"]);
+    assert!(pushed.contains("snippets/testing-rules"), "snippet not pushed: {}", pushed);
+
+    h.run_ok(&["install", "testing rules", "--local"], "y\n");
+    let installed = h.read_project_file("CLAUDE.md");
+    assert!(installed.contains(&format!("<!-- SNIPPET_START:{} -->", &id[..8])));
+    assert!(installed.contains("Run the full test suite before committing"));
+
+    let listed = h.run_ok(&["installed", "--local"], "");
+    assert!(listed.contains("Testing rules"), "installed list: {}", listed);
+
+    h.run_ok(&["uninstall", &id[..8], "--local"], "y\n");
+    let remaining = h.read_project_file("CLAUDE.md");
+    assert!(!remaining.contains("SNIPPET_START"), "markers left behind: {}", remaining);
+    assert!(!remaining.contains("Run the full test suite"));
+}
+
+#[test]
+fn pull_brings_in_snippets_pushed_from_another_machine() {
+    let h = Harness::new();
+    h.init_remote();
+    publish(&h, "Prefer small, focused commits", "Commit style");
+    h.git(&h.repo_dir(), &["remote", "add", "origin", h.remote.to_str().unwrap()]);
+    h.run_ok(&["sync"], "");
+
+    // A second clone adds a snippet and pushes it
+    let other = h.project.join("other-machine");
+    h.git(&h.project, &["clone", "--quiet", h.remote.to_str().unwrap(), other.to_str().unwrap()]);
+    std::fs::write(
+        other.join("snippets/lint-rules-12345678.md"),
+        "---\nid: 12345678-0000-0000-0000-000000000000\nname: Lint rules\ncreated_at: 2024-01-01T00:00:00Z\n---\n\nTreat warnings as errors\n",
+    )
+    .unwrap();
+    h.git(&other, &["add", "-A"]);
+    h.git(&other, &["commit", "--quiet", "-m", "Add lint rules"]);
+    h.git(&other, &["push", "--quiet", "origin", "main"]);
+
+    h.run_ok(&["pull"], "");
+    assert!(h.repo_dir().join("snippets/lint-rules-12345678.md").exists());
+}
+
+#[test]
+fn install_uses_the_snippet_claude_picks() {
+    let h = Harness::new();
+    publish(&h, "Use tabs for indentation", "Indentation");
+    let id = only_snippet_id(&h);
+    publish(&h, "Write docs for every public function", "Documentation");
+
+    // The query matches neither snippet textually, so only the AI answer can pick it
+    h.set_claude_response(&id);
+    h.run_ok(&["install", "whitespace conventions", "--local"], "y\n");
+
+    assert!(h.claude_prompts().contains("whitespace conventions"));
+    let installed = h.read_project_file("CLAUDE.md");
+    assert!(installed.contains("Use tabs for indentation"), "installed: {}", installed);
+    assert!(!installed.contains("Write docs"));
+}
+
+#[test]
+fn install_falls_back_to_fuzzy_matching_when_claude_fails() {
+    let h = Harness::new();
+    publish(&h, "Use tabs for indentation", "Indentation");
+
+    let output = h.run_ok(&["install", "indentation", "--local"], "y\n");
+    assert!(output.contains("using fuzzy matching"), "output: {}", output);
+    assert!(h.read_project_file("CLAUDE.md").contains("Use tabs for indentation"));
+}
+
+#[test]
+fn setup_creates_and_pushes_to_the_github_repo() {
+    let h = Harness::new();
+    h.run_ok(&["setup", "--repo", "mine"], "");
+
+    assert!(h.gh_calls().contains("repo create mine --private"));
+    let remote = h.remotes_dir.join("mine.git");
+    assert_eq!(h.git(&remote, &["log", "--format=%s", "-1", "main"]), "Initial commit");
+
+    // Later syncs push to the same (rewritten) remote
+    publish(&h, "Keep functions short", "Function size");
+    let pushed = h.git(&remote, &["ls-tree", "-r", "--name-only", "main"]);
+    assert!(pushed.contains("snippets/function-size"), "snippet not pushed: {}", pushed);
+}
+
+#[test]
+fn offline_changes_are_queued_and_replayed_by_sync() {
+    let h = Harness::new();
+    h.init_remote();
+    publish(&h, "Prefer small, focused commits", "Commit style");
+    h.git(&h.repo_dir(), &["remote", "add", "origin", h.remote.to_str().unwrap()]);
+    h.run_ok(&["sync"], "");
+
+    let output = h.run_ok(&["publish", "Treat warnings as errors", "--name", "Lint rules", "--offline"], "");
+    assert!(output.contains("queued"), "output: {}", output);
+    assert!(!h.git(&h.remote, &["ls-tree", "-r", "--name-only", "main"]).contains("lint-rules"));
+    assert!(h.run_ok(&["status"], "").contains("Publish snippet 'Lint rules'"));
+
+    let output = h.run_ok(&["sync"], "");
+    assert!(output.contains("Replaying 1 change(s)"), "output: {}", output);
+    assert!(h.git(&h.remote, &["ls-tree", "-r", "--name-only", "main"]).contains("lint-rules"));
+    assert!(!h.run_ok(&["status"], "").contains("queued while offline"));
+}
+
+#[test]
+fn attached_assets_are_installed_and_cleaned_up() {
+    let h = Harness::new();
+    let diagram = h.project.join("arch.png");
+    std::fs::write(&diagram, b"\x89PNG fake image").unwrap();
+
+    h.run_ok(
+        &["publish", "See the architecture diagram: ![arch](assets/arch.png)", "--name", "Architecture", "--attach", diagram.to_str().unwrap()],
+        "",
+    );
+    let id = only_snippet_id(&h);
+    assert!(h.repo_dir().join("snippets/assets").join(&id).join("arch.png").exists());
+
+    let docs = h.project.join("docs");
+    std::fs::create_dir_all(&docs).unwrap();
+    let target = docs.join("AGENTS.md");
+    h.run_ok(&["install", "architecture", "--target", target.to_str().unwrap()], "y\n");
+    let installed_asset = docs.join("snippet-assets").join(&id[..8]).join("arch.png");
+    assert_eq!(std::fs::read(&installed_asset).unwrap(), b"\x89PNG fake image");
+    let content = std::fs::read_to_string(&target).unwrap();
+    assert!(content.contains(&format!("(snippet-assets/{}/arch.png)", &id[..8])), "content: {}", content);
+
+    h.run_ok(&["uninstall", &id[..8], "--target", target.to_str().unwrap()], "y\n");
+    assert!(!docs.join("snippet-assets").exists());
+}
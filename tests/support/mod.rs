@@ -0,0 +1,197 @@
+//! Test support for the end-to-end CLI tests.
+//!
+//! A [`Harness`] gives each test an isolated world: a temporary `HOME` with its
+//! own global git config, a project directory to run commands from, a local bare
+//! git remote, and scriptable fake `claude` and `gh` binaries first on `PATH`.
+//! Nothing touches the real home directory, the network or the real CLIs.
+
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use tempfile::TempDir;
+
+/// GitHub user the fake `gh` reports as logged in.
+pub const FAKE_GH_USER: &str = "tester";
+
+pub struct Harness {
+    root: TempDir,
+    pub home: PathBuf,
+    pub project: PathBuf,
+    /// Bare repository standing in for the GitHub remote.
+    pub remote: PathBuf,
+    /// Where the fake `gh repo create` puts repositories; `https://github.com/tester/*`
+    /// is rewritten to point here.
+    pub remotes_dir: PathBuf,
+    bin_dir: PathBuf,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        let root = tempfile::tempdir().expect("create temp dir");
+        let home = root.path().join("home");
+        let project = root.path().join("project");
+        let remotes_dir = root.path().join("remotes");
+        let bin_dir = root.path().join("bin");
+        for dir in [&home, &project, &remotes_dir, &bin_dir] {
+            fs::create_dir_all(dir).expect("create harness dir");
+        }
+
+        fs::write(
+            home.join(".gitconfig"),
+            format!(
+                "[user]\n\tname = Test User\n\temail = test@example.com\n\
+                 [init]\n\tdefaultBranch = main\n\
+                 [url \"{}/\"]\n\tinsteadOf = https://github.com/{}/\n",
+                remotes_dir.display(),
+                FAKE_GH_USER
+            ),
+        )
+        .expect("write gitconfig");
+
+        let harness = Harness {
+            remote: remotes_dir.join("origin.git"),
+            root,
+            home,
+            project,
+            remotes_dir,
+            bin_dir,
+        };
+        harness.write_fake_claude();
+        harness.write_fake_gh();
+        harness
+    }
+
+    fn state_dir(&self) -> &Path {
+        self.root.path()
+    }
+
+    fn write_script(&self, name: &str, body: &str) {
+        let path = self.bin_dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{}", body)).expect("write fake binary");
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod fake binary");
+    }
+
+    /// `claude --version` always works; prompts are logged and answered with the
+    /// scripted response, or fail if none is set.
+    fn write_fake_claude(&self) {
+        let state = self.state_dir().display();
+        self.write_script(
+            "claude",
+            &format!(
+                "if [ \"$1\" = \"--version\" ]; then echo \"1.0.0 (fake claude)\"; exit 0; fi\n\
+                 for arg; do last=\"$arg\"; done\n\
+                 printf '%s\\n' \"$last\" >> \"{state}/claude.log\"\n\
+                 if [ -f \"{state}/claude-response\" ]; then cat \"{state}/claude-response\"; exit 0; fi\n\
+                 echo \"fake claude: no response scripted\" >&2\n\
+                 exit 1\n"
+            ),
+        );
+    }
+
+    /// Just enough of `gh` for setup: a logged-in user, and `repo create`
+    /// making a bare repository under `remotes_dir`.
+    fn write_fake_gh(&self) {
+        let state = self.state_dir().display();
+        let remotes = self.remotes_dir.display();
+        self.write_script(
+            "gh",
+            &format!(
+                "echo \"$*\" >> \"{state}/gh.log\"\n\
+                 case \"$1 $2\" in\n\
+                 \"--version \"*) echo \"gh version 2.0.0 (fake)\" ;;\n\
+                 \"api user\")\n\
+                 \tif [ \"$3\" = \"--jq\" ]; then echo {user}; else echo '{{\"login\":\"{user}\",\"email\":null}}'; fi ;;\n\
+                 \"repo create\")\n\
+                 \tname=\"${{3##*/}}\"\n\
+                 \tgit init --bare --quiet \"{remotes}/$name.git\" ;;\n\
+                 *) echo \"fake gh: unsupported: $*\" >&2; exit 1 ;;\n\
+                 esac\n",
+                user = FAKE_GH_USER
+            ),
+        );
+    }
+
+    /// Make the fake `claude` answer every prompt with `response`.
+    pub fn set_claude_response(&self, response: &str) {
+        fs::write(self.state_dir().join("claude-response"), response).expect("write claude response");
+    }
+
+    /// Prompts the fake `claude` received, in order.
+    pub fn claude_prompts(&self) -> String {
+        fs::read_to_string(self.state_dir().join("claude.log")).unwrap_or_default()
+    }
+
+    /// Commands the fake `gh` received, one per line.
+    pub fn gh_calls(&self) -> String {
+        fs::read_to_string(self.state_dir().join("gh.log")).unwrap_or_default()
+    }
+
+    /// The default snippet repository inside the temporary home.
+    pub fn repo_dir(&self) -> PathBuf {
+        self.home.join(".local/share/claude-md-snippets/repos/default")
+    }
+
+    /// The CLI, run from the project directory inside the isolated environment.
+    pub fn cmd(&self, args: &[&str]) -> Command {
+        let path = format!(
+            "{}:{}",
+            self.bin_dir.display(),
+            std::env::var("PATH").unwrap_or_default()
+        );
+        let mut command = Command::cargo_bin("claude-md-snippets-manager").expect("find CLI binary");
+        command
+            .args(args)
+            .current_dir(&self.project)
+            .env_clear()
+            .env("PATH", path)
+            .env("HOME", &self.home)
+            .env("GIT_CONFIG_GLOBAL", self.home.join(".gitconfig"))
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("EDITOR", "true");
+        command
+    }
+
+    /// Run the CLI feeding `stdin`, without checking the exit status.
+    pub fn run(&self, args: &[&str], stdin: &str) -> Output {
+        self.cmd(args).write_stdin(stdin).output().expect("run CLI")
+    }
+
+    /// Run the CLI and return its stdout, failing the test if it exits non-zero.
+    pub fn run_ok(&self, args: &[&str], stdin: &str) -> String {
+        let assert = self.cmd(args).write_stdin(stdin).assert().success();
+        String::from_utf8_lossy(&assert.get_output().stdout).to_string()
+    }
+
+    /// Create the bare remote at `self.remote`.
+    pub fn init_remote(&self) {
+        self.git(&self.remotes_dir, &["init", "--bare", "--quiet", "origin.git"]);
+    }
+
+    /// Run git in `dir` with the harness environment, returning trimmed stdout.
+    pub fn git(&self, dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("HOME", &self.home)
+            .env("GIT_CONFIG_GLOBAL", self.home.join(".gitconfig"))
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .output()
+            .expect("run git");
+        assert!(
+            output.status.success(),
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    pub fn read_project_file(&self, name: &str) -> String {
+        fs::read_to_string(self.project.join(name)).unwrap_or_default()
+    }
+}